@@ -15,6 +15,10 @@ pub mod search_actors;
 pub mod search_actors_typeahead;
 pub mod status;
 
+fn _default_adult_content_pref_enabled() -> bool {
+    false
+}
+
 #[jacquard_derive::lexicon]
 #[derive(
     serde::Serialize,
@@ -28,6 +32,9 @@ pub mod status;
 )]
 #[serde(rename_all = "camelCase")]
 pub struct AdultContentPref<'a> {
+    ///Defaults to `false`
+    #[serde(default = "_default_adult_content_pref_enabled")]
+    #[builder(default = false)]
     pub enabled: bool,
 }
 
@@ -353,6 +360,10 @@ pub struct MutedWordsPref<'a> {
     pub items: Vec<crate::app_bsky::actor::MutedWord<'a>>,
 }
 
+fn _default_nux_completed() -> bool {
+    false
+}
+
 /// A new user experiences (NUX) storage object
 #[jacquard_derive::lexicon]
 #[derive(
@@ -367,6 +378,9 @@ pub struct MutedWordsPref<'a> {
 )]
 #[serde(rename_all = "camelCase")]
 pub struct Nux<'a> {
+    ///Defaults to `false`
+    #[serde(default = "_default_nux_completed")]
+    #[builder(default = false)]
     pub completed: bool,
     /// Arbitrary data for the NUX. The structure is defined by the NUX itself. Limited to 300 characters.
     #[serde(skip_serializing_if = "std::option::Option::is_none")]
@@ -70,4 +70,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetBookmarksRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetBookmarks<'de>;
     type Response = GetBookmarksResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetBookmarks<'a> {
+    type Item = <crate::app_bsky::bookmark::BookmarkView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetBookmarksOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetBookmarksOutput<'static>) -> Vec<Self::Item> {
+        output.bookmarks
+    }
 }
\ No newline at end of file
@@ -70,6 +70,10 @@ pub enum ViewUnionRecord<'a> {
     StarterPackViewBasic(Box<crate::app_bsky::graph::StarterPackViewBasic<'a>>),
 }
 
+fn _default_view_blocked_blocked() -> bool {
+    true
+}
+
 #[jacquard_derive::lexicon]
 #[derive(
     serde::Serialize,
@@ -85,11 +89,18 @@ pub enum ViewUnionRecord<'a> {
 pub struct ViewBlocked<'a> {
     #[serde(borrow)]
     pub author: crate::app_bsky::feed::BlockedAuthor<'a>,
+    ///Defaults to `true`
+    #[serde(default = "_default_view_blocked_blocked")]
+    #[builder(default = true)]
     pub blocked: bool,
     #[serde(borrow)]
     pub uri: jacquard_common::types::string::AtUri<'a>,
 }
 
+fn _default_view_detached_detached() -> bool {
+    true
+}
+
 #[jacquard_derive::lexicon]
 #[derive(
     serde::Serialize,
@@ -103,11 +114,18 @@ pub struct ViewBlocked<'a> {
 )]
 #[serde(rename_all = "camelCase")]
 pub struct ViewDetached<'a> {
+    ///Defaults to `true`
+    #[serde(default = "_default_view_detached_detached")]
+    #[builder(default = true)]
     pub detached: bool,
     #[serde(borrow)]
     pub uri: jacquard_common::types::string::AtUri<'a>,
 }
 
+fn _default_view_not_found_not_found() -> bool {
+    true
+}
+
 #[jacquard_derive::lexicon]
 #[derive(
     serde::Serialize,
@@ -121,6 +139,9 @@ pub struct ViewDetached<'a> {
 )]
 #[serde(rename_all = "camelCase")]
 pub struct ViewNotFound<'a> {
+    ///Defaults to `true`
+    #[serde(default = "_default_view_not_found_not_found")]
+    #[builder(default = true)]
     pub not_found: bool,
     #[serde(borrow)]
     pub uri: jacquard_common::types::string::AtUri<'a>,
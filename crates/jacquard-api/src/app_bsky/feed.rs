@@ -51,6 +51,10 @@ pub struct BlockedAuthor<'a> {
     pub viewer: Option<crate::app_bsky::actor::ViewerState<'a>>,
 }
 
+fn _default_blocked_post_blocked() -> bool {
+    true
+}
+
 #[jacquard_derive::lexicon]
 #[derive(
     serde::Serialize,
@@ -66,6 +70,9 @@ pub struct BlockedAuthor<'a> {
 pub struct BlockedPost<'a> {
     #[serde(borrow)]
     pub author: crate::app_bsky::feed::BlockedAuthor<'a>,
+    ///Defaults to `true`
+    #[serde(default = "_default_blocked_post_blocked")]
+    #[builder(default = true)]
     pub blocked: bool,
     #[serde(borrow)]
     pub uri: jacquard_common::types::string::AtUri<'a>,
@@ -445,6 +452,10 @@ impl std::fmt::Display for InteractionShare {
     }
 }
 
+fn _default_not_found_post_not_found() -> bool {
+    true
+}
+
 #[jacquard_derive::lexicon]
 #[derive(
     serde::Serialize,
@@ -458,6 +469,9 @@ impl std::fmt::Display for InteractionShare {
 )]
 #[serde(rename_all = "camelCase")]
 pub struct NotFoundPost<'a> {
+    ///Defaults to `true`
+    #[serde(default = "_default_not_found_post_not_found")]
+    #[builder(default = true)]
     pub not_found: bool,
     #[serde(borrow)]
     pub uri: jacquard_common::types::string::AtUri<'a>,
@@ -72,4 +72,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetActorFeedsRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetActorFeeds<'de>;
     type Response = GetActorFeedsResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetActorFeeds<'a> {
+    type Item = <crate::app_bsky::feed::GeneratorView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetActorFeedsOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetActorFeedsOutput<'static>) -> Vec<Self::Item> {
+        output.feeds
+    }
 }
\ No newline at end of file
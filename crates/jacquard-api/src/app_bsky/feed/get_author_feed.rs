@@ -123,4 +123,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetAuthorFeedRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetAuthorFeed<'de>;
     type Response = GetAuthorFeedResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetAuthorFeed<'a> {
+    type Item = <crate::app_bsky::feed::FeedViewPost<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetAuthorFeedOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetAuthorFeedOutput<'static>) -> Vec<Self::Item> {
+        output.feed
+    }
 }
\ No newline at end of file
@@ -110,4 +110,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetFeedSkeletonRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetFeedSkeleton<'de>;
     type Response = GetFeedSkeletonResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetFeedSkeleton<'a> {
+    type Item = <crate::app_bsky::feed::SkeletonFeedPost<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetFeedSkeletonOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetFeedSkeletonOutput<'static>) -> Vec<Self::Item> {
+        output.feed
+    }
 }
\ No newline at end of file
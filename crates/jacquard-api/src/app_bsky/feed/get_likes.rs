@@ -99,4 +99,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetLikesRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetLikes<'de>;
     type Response = GetLikesResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetLikes<'a> {
+    type Item = <jacquard_common::types::value::Data<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetLikesOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetLikesOutput<'static>) -> Vec<Self::Item> {
+        output.likes
+    }
 }
\ No newline at end of file
@@ -106,4 +106,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetListFeedRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetListFeed<'de>;
     type Response = GetListFeedResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetListFeed<'a> {
+    type Item = <crate::app_bsky::feed::FeedViewPost<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetListFeedOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetListFeedOutput<'static>) -> Vec<Self::Item> {
+        output.feed
+    }
 }
\ No newline at end of file
@@ -80,4 +80,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetQuotesRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetQuotes<'de>;
     type Response = GetQuotesResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetQuotes<'a> {
+    type Item = <crate::app_bsky::feed::PostView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetQuotesOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetQuotesOutput<'static>) -> Vec<Self::Item> {
+        output.posts
+    }
 }
\ No newline at end of file
@@ -70,4 +70,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetSuggestedFeedsRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetSuggestedFeeds<'de>;
     type Response = GetSuggestedFeedsResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetSuggestedFeeds<'a> {
+    type Item = <crate::app_bsky::feed::GeneratorView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetSuggestedFeedsOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetSuggestedFeedsOutput<'static>) -> Vec<Self::Item> {
+        output.feeds
+    }
 }
\ No newline at end of file
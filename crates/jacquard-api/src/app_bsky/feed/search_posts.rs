@@ -141,4 +141,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for SearchPostsRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = SearchPosts<'de>;
     type Response = SearchPostsResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for SearchPosts<'a> {
+    type Item = <crate::app_bsky::feed::PostView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o SearchPostsOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: SearchPostsOutput<'static>) -> Vec<Self::Item> {
+        output.posts
+    }
 }
\ No newline at end of file
@@ -288,6 +288,10 @@ impl std::fmt::Display for Modlist {
     }
 }
 
+fn _default_not_found_actor_not_found() -> bool {
+    true
+}
+
 /// indicates that a handle or DID could not be resolved
 #[jacquard_derive::lexicon]
 #[derive(
@@ -304,6 +308,9 @@ impl std::fmt::Display for Modlist {
 pub struct NotFoundActor<'a> {
     #[serde(borrow)]
     pub actor: jacquard_common::types::ident::AtIdentifier<'a>,
+    ///Defaults to `true`
+    #[serde(default = "_default_not_found_actor_not_found")]
+    #[builder(default = true)]
     pub not_found: bool,
 }
 
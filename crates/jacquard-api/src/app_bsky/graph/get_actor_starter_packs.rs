@@ -72,4 +72,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetActorStarterPacksRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetActorStarterPacks<'de>;
     type Response = GetActorStarterPacksResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetActorStarterPacks<'a> {
+    type Item = <crate::app_bsky::graph::StarterPackViewBasic<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetActorStarterPacksOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetActorStarterPacksOutput<'static>) -> Vec<Self::Item> {
+        output.starter_packs
+    }
 }
\ No newline at end of file
@@ -74,4 +74,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetFollowersRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetFollowers<'de>;
     type Response = GetFollowersResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetFollowers<'a> {
+    type Item = <crate::app_bsky::actor::ProfileView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetFollowersOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetFollowersOutput<'static>) -> Vec<Self::Item> {
+        output.followers
+    }
 }
\ No newline at end of file
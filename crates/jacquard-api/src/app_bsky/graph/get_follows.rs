@@ -74,4 +74,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetFollowsRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetFollows<'de>;
     type Response = GetFollowsResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetFollows<'a> {
+    type Item = <crate::app_bsky::actor::ProfileView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetFollowsOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetFollowsOutput<'static>) -> Vec<Self::Item> {
+        output.follows
+    }
 }
\ No newline at end of file
@@ -74,4 +74,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetKnownFollowersRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetKnownFollowers<'de>;
     type Response = GetKnownFollowersResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetKnownFollowers<'a> {
+    type Item = <crate::app_bsky::actor::ProfileView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetKnownFollowersOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetKnownFollowersOutput<'static>) -> Vec<Self::Item> {
+        output.followers
+    }
 }
\ No newline at end of file
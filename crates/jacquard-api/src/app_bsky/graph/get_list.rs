@@ -74,4 +74,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetListRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetList<'de>;
     type Response = GetListResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetList<'a> {
+    type Item = <crate::app_bsky::graph::ListItemView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetListOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetListOutput<'static>) -> Vec<Self::Item> {
+        output.items
+    }
 }
\ No newline at end of file
@@ -70,4 +70,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetListMutesRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetListMutes<'de>;
     type Response = GetListMutesResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetListMutes<'a> {
+    type Item = <crate::app_bsky::graph::ListView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetListMutesOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetListMutesOutput<'static>) -> Vec<Self::Item> {
+        output.lists
+    }
 }
\ No newline at end of file
@@ -75,4 +75,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetListsRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetLists<'de>;
     type Response = GetListsResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetLists<'a> {
+    type Item = <crate::app_bsky::graph::ListView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetListsOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetListsOutput<'static>) -> Vec<Self::Item> {
+        output.lists
+    }
 }
\ No newline at end of file
@@ -97,4 +97,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetListsWithMembershipRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetListsWithMembership<'de>;
     type Response = GetListsWithMembershipResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetListsWithMembership<'a> {
+    type Item = <jacquard_common::types::value::Data<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetListsWithMembershipOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetListsWithMembershipOutput<'static>) -> Vec<Self::Item> {
+        output.lists_with_membership
+    }
 }
\ No newline at end of file
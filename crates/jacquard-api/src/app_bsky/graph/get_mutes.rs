@@ -70,4 +70,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetMutesRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetMutes<'de>;
     type Response = GetMutesResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetMutes<'a> {
+    type Item = <crate::app_bsky::actor::ProfileView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetMutesOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetMutesOutput<'static>) -> Vec<Self::Item> {
+        output.mutes
+    }
 }
\ No newline at end of file
@@ -74,6 +74,29 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetStarterPacksWithMembershipReques
     type Response = GetStarterPacksWithMembershipResponse;
 }
 
+impl<'a> jacquard_common::xrpc::Paginated for GetStarterPacksWithMembership<'a> {
+    type Item = <jacquard_common::types::value::Data<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetStarterPacksWithMembershipOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetStarterPacksWithMembershipOutput<'static>) -> Vec<Self::Item> {
+        output.starter_packs_with_membership
+    }
+}
+
 /// A starter pack and an optional list item indicating membership of a target user to that starter pack.
 #[jacquard_derive::lexicon]
 #[derive(
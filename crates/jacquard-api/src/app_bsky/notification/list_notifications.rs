@@ -83,6 +83,29 @@ impl jacquard_common::xrpc::XrpcEndpoint for ListNotificationsRequest {
     type Response = ListNotificationsResponse;
 }
 
+impl<'a> jacquard_common::xrpc::Paginated for ListNotifications<'a> {
+    type Item = <jacquard_common::types::value::Data<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o ListNotificationsOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: ListNotificationsOutput<'static>) -> Vec<Self::Item> {
+        output.notifications
+    }
+}
+
 #[jacquard_derive::lexicon]
 #[derive(
     serde::Serialize,
@@ -74,4 +74,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetPopularFeedGeneratorsRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetPopularFeedGenerators<'de>;
     type Response = GetPopularFeedGeneratorsResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetPopularFeedGenerators<'a> {
+    type Item = <crate::app_bsky::feed::GeneratorView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetPopularFeedGeneratorsOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetPopularFeedGeneratorsOutput<'static>) -> Vec<Self::Item> {
+        output.feeds
+    }
 }
\ No newline at end of file
@@ -115,4 +115,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for SearchActorsSkeletonRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = SearchActorsSkeleton<'de>;
     type Response = SearchActorsSkeletonResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for SearchActorsSkeleton<'a> {
+    type Item = <crate::app_bsky::unspecced::SkeletonSearchActor<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o SearchActorsSkeletonOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: SearchActorsSkeletonOutput<'static>) -> Vec<Self::Item> {
+        output.actors
+    }
 }
\ No newline at end of file
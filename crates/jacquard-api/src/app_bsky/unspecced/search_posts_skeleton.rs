@@ -144,4 +144,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for SearchPostsSkeletonRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = SearchPostsSkeleton<'de>;
     type Response = SearchPostsSkeletonResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for SearchPostsSkeleton<'a> {
+    type Item = <crate::app_bsky::unspecced::SkeletonSearchPost<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o SearchPostsSkeletonOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: SearchPostsSkeletonOutput<'static>) -> Vec<Self::Item> {
+        output.posts
+    }
 }
\ No newline at end of file
@@ -113,4 +113,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for SearchStarterPacksSkeletonRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = SearchStarterPacksSkeleton<'de>;
     type Response = SearchStarterPacksSkeletonResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for SearchStarterPacksSkeleton<'a> {
+    type Item = <crate::app_bsky::unspecced::SkeletonSearchStarterPack<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o SearchStarterPacksSkeletonOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: SearchStarterPacksSkeletonOutput<'static>) -> Vec<Self::Item> {
+        output.starter_packs
+    }
 }
\ No newline at end of file
@@ -10,6 +10,10 @@ pub mod key;
 pub mod player;
 pub mod verification;
 
+fn _default_sync_status_synced_with_at_repo() -> bool {
+    false
+}
+
 /// The sync status for a record used to help sync between your ATProto record and local record.
 #[jacquard_derive::lexicon]
 #[derive(
@@ -30,6 +34,9 @@ pub struct SyncStatus<'a> {
     #[builder(into)]
     pub hash: jacquard_common::CowStr<'a>,
     /// A flag to know if it has been synced with the AT repo. Used mostly client side to filter what records need syncing
+    ///Defaults to `false`
+    #[serde(default = "_default_sync_status_synced_with_at_repo")]
+    #[builder(default = false)]
     pub synced_with_at_repo: bool,
     pub updated_at: jacquard_common::types::string::Datetime,
 }
\ No newline at end of file
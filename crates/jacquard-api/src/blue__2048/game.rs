@@ -5,6 +5,18 @@
 // This file was automatically generated from Lexicon schemas.
 // Any manual changes will be overwritten on the next regeneration.
 
+fn _default_game_completed() -> bool {
+    false
+}
+
+fn _default_game_current_score() -> i64 {
+    0i64
+}
+
+fn _default_game_won() -> bool {
+    false
+}
+
 /// A declaration of an instance of a at://2048 game
 #[jacquard_derive::lexicon]
 #[derive(
@@ -20,9 +32,15 @@
 #[serde(rename_all = "camelCase")]
 pub struct Game<'a> {
     /// The player no longer has any moves left
+    ///Defaults to `false`
+    #[serde(default = "_default_game_completed")]
+    #[builder(default = false)]
     pub completed: bool,
     pub created_at: jacquard_common::types::string::Datetime,
     /// The game's current score
+    ///Defaults to `0`
+    #[serde(default = "_default_game_current_score")]
+    #[builder(default = 0i64)]
     pub current_score: i64,
     /// This is the recording of the game. Like chess notation, but for 2048
     #[serde(borrow)]
@@ -32,6 +50,9 @@ pub struct Game<'a> {
     #[serde(borrow)]
     pub sync_status: crate::blue__2048::SyncStatus<'a>,
     /// The player has found a 2048 tile (they have won)
+    ///Defaults to `false`
+    #[serde(default = "_default_game_won")]
+    #[builder(default = false)]
     pub won: bool,
 }
 
@@ -5,6 +5,10 @@
 // This file was automatically generated from Lexicon schemas.
 // Any manual changes will be overwritten on the next regeneration.
 
+fn _default_profile_solo_play() -> bool {
+    false
+}
+
 /// A declaration of a at://2048 player's profile
 #[jacquard_derive::lexicon]
 #[derive(
@@ -21,6 +25,9 @@
 pub struct Profile<'a> {
     pub created_at: jacquard_common::types::string::Datetime,
     /// Does not want to show up anywhere. Keep stats to your PDS.
+    ///Defaults to `false`
+    #[serde(default = "_default_profile_solo_play")]
+    #[builder(default = false)]
     pub solo_play: bool,
     /// The sync status of this record with the users AT Protocol repo.
     #[serde(borrow)]
@@ -5,6 +5,34 @@
 // This file was automatically generated from Lexicon schemas.
 // Any manual changes will be overwritten on the next regeneration.
 
+fn _default_stats_average_score() -> i64 {
+    0i64
+}
+
+fn _default_stats_games_played() -> i64 {
+    0i64
+}
+
+fn _default_stats_highest_number_block() -> i64 {
+    0i64
+}
+
+fn _default_stats_highest_score() -> i64 {
+    0i64
+}
+
+fn _default_stats_least_moves_to_find_twenty_forty_eight() -> i64 {
+    0i64
+}
+
+fn _default_stats_times_twenty_forty_eight_been_found() -> i64 {
+    0i64
+}
+
+fn _default_stats_total_score() -> i64 {
+    0i64
+}
+
 /// A declaration of a at://2048 player's stats over the course of their playtime
 #[jacquard_derive::lexicon]
 #[derive(
@@ -20,22 +48,43 @@
 #[serde(rename_all = "camelCase")]
 pub struct Stats<'a> {
     /// Average score across all games
+    ///Defaults to `0`
+    #[serde(default = "_default_stats_average_score")]
+    #[builder(default = 0i64)]
     pub average_score: i64,
     pub created_at: jacquard_common::types::string::Datetime,
     /// Total numbers of games the user has played
+    ///Defaults to `0`
+    #[serde(default = "_default_stats_games_played")]
+    #[builder(default = 0i64)]
     pub games_played: i64,
     /// The highest number block the player has fround. example 128, 256, etc
+    ///Defaults to `0`
+    #[serde(default = "_default_stats_highest_number_block")]
+    #[builder(default = 0i64)]
     pub highest_number_block: i64,
     /// The highest score the user has gotten in a game
+    ///Defaults to `0`
+    #[serde(default = "_default_stats_highest_score")]
+    #[builder(default = 0i64)]
     pub highest_score: i64,
     /// The smallest number of moves to get the 2048 block
+    ///Defaults to `0`
+    #[serde(default = "_default_stats_least_moves_to_find_twenty_forty_eight")]
+    #[builder(default = 0i64)]
     pub least_moves_to_find_twenty_forty_eight: i64,
     /// The sync status of this record with the users AT Protocol repo.
     #[serde(borrow)]
     pub sync_status: crate::blue__2048::SyncStatus<'a>,
     /// Times the 2048 block has been found also count as wins
+    ///Defaults to `0`
+    #[serde(default = "_default_stats_times_twenty_forty_eight_been_found")]
+    #[builder(default = 0i64)]
     pub times_twenty_forty_eight_been_found: i64,
     /// Total score across all games
+    ///Defaults to `0`
+    #[serde(default = "_default_stats_total_score")]
+    #[builder(default = 0i64)]
     pub total_score: i64,
 }
 
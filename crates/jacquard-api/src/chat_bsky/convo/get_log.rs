@@ -102,4 +102,25 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetLogRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetLog<'de>;
     type Response = GetLogResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetLog<'a> {
+    type Item = <GetLogOutputLogsItem<'a> as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetLogOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetLogOutput<'static>) -> Vec<Self::Item> {
+        output.logs
+    }
 }
\ No newline at end of file
@@ -92,4 +92,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetMessagesRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetMessages<'de>;
     type Response = GetMessagesResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetMessages<'a> {
+    type Item = <GetMessagesOutputMessagesItem<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetMessagesOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetMessagesOutput<'static>) -> Vec<Self::Item> {
+        output.messages
+    }
 }
\ No newline at end of file
@@ -78,4 +78,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for ListConvosRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = ListConvos<'de>;
     type Response = ListConvosResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for ListConvos<'a> {
+    type Item = <crate::chat_bsky::convo::ConvoView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o ListConvosOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: ListConvosOutput<'static>) -> Vec<Self::Item> {
+        output.convos
+    }
 }
\ No newline at end of file
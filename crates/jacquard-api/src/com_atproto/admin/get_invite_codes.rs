@@ -75,4 +75,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetInviteCodesRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetInviteCodes<'de>;
     type Response = GetInviteCodesResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetInviteCodes<'a> {
+    type Item = <crate::com_atproto::server::InviteCode<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetInviteCodesOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetInviteCodesOutput<'static>) -> Vec<Self::Item> {
+        output.codes
+    }
 }
\ No newline at end of file
@@ -74,4 +74,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for SearchAccountsRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = SearchAccounts<'de>;
     type Response = SearchAccountsResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for SearchAccounts<'a> {
+    type Item = <crate::com_atproto::admin::AccountView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o SearchAccountsOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: SearchAccountsOutput<'static>) -> Vec<Self::Item> {
+        output.accounts
+    }
 }
\ No newline at end of file
@@ -75,4 +75,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for QueryLabelsRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = QueryLabels<'de>;
     type Response = QueryLabelsResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for QueryLabels<'a> {
+    type Item = <crate::com_atproto::label::Label<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o QueryLabelsOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: QueryLabelsOutput<'static>) -> Vec<Self::Item> {
+        output.labels
+    }
 }
\ No newline at end of file
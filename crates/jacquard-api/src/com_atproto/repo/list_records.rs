@@ -78,6 +78,29 @@ impl jacquard_common::xrpc::XrpcEndpoint for ListRecordsRequest {
     type Response = ListRecordsResponse;
 }
 
+impl<'a> jacquard_common::xrpc::Paginated for ListRecords<'a> {
+    type Item = <jacquard_common::types::value::Data<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o ListRecordsOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: ListRecordsOutput<'static>) -> Vec<Self::Item> {
+        output.records
+    }
+}
+
 #[jacquard_derive::lexicon]
 #[derive(
     serde::Serialize,
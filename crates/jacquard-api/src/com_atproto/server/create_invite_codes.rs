@@ -25,6 +25,10 @@ pub struct AccountCodes<'a> {
     pub codes: Vec<jacquard_common::CowStr<'a>>,
 }
 
+fn _default_create_invite_codes_code_count() -> i64 {
+    1i64
+}
+
 #[jacquard_derive::lexicon]
 #[derive(
     serde::Serialize,
@@ -39,6 +43,9 @@ pub struct AccountCodes<'a> {
 #[serde(rename_all = "camelCase")]
 #[builder(start_fn = new)]
 pub struct CreateInviteCodes<'a> {
+    ///Defaults to `1`
+    #[serde(default = "_default_create_invite_codes_code_count")]
+    #[builder(default = 1i64)]
     pub code_count: i64,
     #[serde(skip_serializing_if = "std::option::Option::is_none")]
     #[builder(into)]
@@ -135,4 +135,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for ListBlobsRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = ListBlobs<'de>;
     type Response = ListBlobsResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for ListBlobs<'a> {
+    type Item = <jacquard_common::types::string::Cid<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o ListBlobsOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: ListBlobsOutput<'static>) -> Vec<Self::Item> {
+        output.cids
+    }
 }
\ No newline at end of file
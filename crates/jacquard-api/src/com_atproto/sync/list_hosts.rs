@@ -97,4 +97,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for ListHostsRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = ListHosts<'de>;
     type Response = ListHostsResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for ListHosts<'a> {
+    type Item = <jacquard_common::types::value::Data<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o ListHostsOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: ListHostsOutput<'static>) -> Vec<Self::Item> {
+        output.hosts
+    }
 }
\ No newline at end of file
@@ -72,6 +72,29 @@ impl jacquard_common::xrpc::XrpcEndpoint for ListReposRequest {
     type Response = ListReposResponse;
 }
 
+impl<'a> jacquard_common::xrpc::Paginated for ListRepos<'a> {
+    type Item = <jacquard_common::types::value::Data<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o ListReposOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: ListReposOutput<'static>) -> Vec<Self::Item> {
+        output.repos
+    }
+}
+
 #[jacquard_derive::lexicon]
 #[derive(
     serde::Serialize,
@@ -73,4 +73,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetActorBookmarksRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetActorBookmarks<'de>;
     type Response = GetActorBookmarksResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetActorBookmarks<'a> {
+    type Item = <crate::community_lexicon::bookmarks::bookmark::Bookmark<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetActorBookmarksOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetActorBookmarksOutput<'static>) -> Vec<Self::Item> {
+        output.bookmarks
+    }
 }
\ No newline at end of file
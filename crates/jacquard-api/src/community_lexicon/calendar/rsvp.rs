@@ -41,6 +41,10 @@ impl std::fmt::Display for Interested {
     }
 }
 
+fn _default_rsvp_status() -> jacquard_common::CowStr<'static> {
+    jacquard_common::CowStr::new_static("community.lexicon.calendar.rsvp#going")
+}
+
 /// An RSVP for an event.
 #[jacquard_derive::lexicon]
 #[derive(
@@ -55,8 +59,15 @@ impl std::fmt::Display for Interested {
 )]
 #[serde(rename_all = "camelCase")]
 pub struct Rsvp<'a> {
+    ///Defaults to `"community.lexicon.calendar.rsvp#going"`
     #[serde(borrow)]
     #[builder(into)]
+    #[serde(default = "_default_rsvp_status")]
+    #[builder(
+        default = jacquard_common::CowStr::new_static(
+            "community.lexicon.calendar.rsvp#going"
+        )
+    )]
     pub status: jacquard_common::CowStr<'a>,
     #[serde(borrow)]
     pub subject: crate::com_atproto::repo::strong_ref::StrongRef<'a>,
@@ -74,4 +74,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for SearchActorsRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = SearchActors<'de>;
     type Response = SearchActorsResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for SearchActors<'a> {
+    type Item = <crate::fm_teal::alpha::actor::MiniProfileView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o SearchActorsOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: SearchActorsOutput<'static>) -> Vec<Self::Item> {
+        output.actors
+    }
 }
\ No newline at end of file
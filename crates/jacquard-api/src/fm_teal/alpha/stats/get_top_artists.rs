@@ -76,4 +76,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetTopArtistsRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetTopArtists<'de>;
     type Response = GetTopArtistsResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetTopArtists<'a> {
+    type Item = <crate::fm_teal::alpha::stats::ArtistView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetTopArtistsOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetTopArtistsOutput<'static>) -> Vec<Self::Item> {
+        output.artists
+    }
 }
\ No newline at end of file
@@ -78,4 +78,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetUserTopArtistsRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetUserTopArtists<'de>;
     type Response = GetUserTopArtistsResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetUserTopArtists<'a> {
+    type Item = <crate::fm_teal::alpha::stats::ArtistView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetUserTopArtistsOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetUserTopArtistsOutput<'static>) -> Vec<Self::Item> {
+        output.artists
+    }
 }
\ No newline at end of file
@@ -78,4 +78,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetUserTopReleasesRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetUserTopReleases<'de>;
     type Response = GetUserTopReleasesResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetUserTopReleases<'a> {
+    type Item = <crate::fm_teal::alpha::stats::ReleaseView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetUserTopReleasesOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetUserTopReleasesOutput<'static>) -> Vec<Self::Item> {
+        output.releases
+    }
 }
\ No newline at end of file
@@ -112,4 +112,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for ListWebhooksRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = ListWebhooks<'de>;
     type Response = ListWebhooksResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for ListWebhooks<'a> {
+    type Item = <crate::place_stream::server::Webhook<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o ListWebhooksOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: ListWebhooksOutput<'static>) -> Vec<Self::Item> {
+        output.webhooks
+    }
 }
\ No newline at end of file
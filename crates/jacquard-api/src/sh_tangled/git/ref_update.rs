@@ -184,6 +184,10 @@ impl jacquard_common::types::collection::Collection for RefUpdateRecord {
     type Record = RefUpdateRecord;
 }
 
+fn _default_meta_is_default_ref() -> bool {
+    false
+}
+
 #[jacquard_derive::lexicon]
 #[derive(
     serde::Serialize,
@@ -199,6 +203,9 @@ impl jacquard_common::types::collection::Collection for RefUpdateRecord {
 pub struct Meta<'a> {
     #[serde(borrow)]
     pub commit_count: crate::sh_tangled::git::ref_update::CommitCountBreakdown<'a>,
+    ///Defaults to `false`
+    #[serde(default = "_default_meta_is_default_ref")]
+    #[builder(default = false)]
     pub is_default_ref: bool,
     #[serde(skip_serializing_if = "std::option::Option::is_none")]
     #[builder(into)]
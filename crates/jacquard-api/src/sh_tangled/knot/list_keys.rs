@@ -108,6 +108,29 @@ impl jacquard_common::xrpc::XrpcEndpoint for ListKeysRequest {
     type Response = ListKeysResponse;
 }
 
+impl<'a> jacquard_common::xrpc::Paginated for ListKeys<'a> {
+    type Item = <jacquard_common::types::value::Data<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o ListKeysOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: ListKeysOutput<'static>) -> Vec<Self::Item> {
+        output.keys
+    }
+}
+
 #[jacquard_derive::lexicon]
 #[derive(
     serde::Serialize,
@@ -8,6 +8,10 @@
 pub mod closed;
 pub mod open;
 
+fn _default_state_state() -> jacquard_common::CowStr<'static> {
+    jacquard_common::CowStr::new_static("sh.tangled.repo.issue.state.open")
+}
+
 #[jacquard_derive::lexicon]
 #[derive(
     serde::Serialize,
@@ -24,8 +28,13 @@ pub struct State<'a> {
     #[serde(borrow)]
     pub issue: jacquard_common::types::string::AtUri<'a>,
     /// state of the issue
+    ///Defaults to `"sh.tangled.repo.issue.state.open"`
     #[serde(borrow)]
     #[builder(into)]
+    #[serde(default = "_default_state_state")]
+    #[builder(
+        default = jacquard_common::CowStr::new_static("sh.tangled.repo.issue.state.open")
+    )]
     pub state: jacquard_common::CowStr<'a>,
 }
 
@@ -9,6 +9,10 @@ pub mod closed;
 pub mod merged;
 pub mod open;
 
+fn _default_status_status() -> jacquard_common::CowStr<'static> {
+    jacquard_common::CowStr::new_static("sh.tangled.repo.pull.status.open")
+}
+
 #[jacquard_derive::lexicon]
 #[derive(
     serde::Serialize,
@@ -25,8 +29,13 @@ pub struct Status<'a> {
     #[serde(borrow)]
     pub pull: jacquard_common::types::string::AtUri<'a>,
     /// status of the pull request
+    ///Defaults to `"sh.tangled.repo.pull.status.open"`
     #[serde(borrow)]
     #[builder(into)]
+    #[serde(default = "_default_status_status")]
+    #[builder(
+        default = jacquard_common::CowStr::new_static("sh.tangled.repo.pull.status.open")
+    )]
     pub status: jacquard_common::CowStr<'a>,
 }
 
@@ -78,4 +78,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for SearchClipsRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = SearchClips<'de>;
     type Response = SearchClipsResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for SearchClips<'a> {
+    type Item = <crate::social_clippr::feed::ClipView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o SearchClipsOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: SearchClipsOutput<'static>) -> Vec<Self::Item> {
+        output.clips
+    }
 }
\ No newline at end of file
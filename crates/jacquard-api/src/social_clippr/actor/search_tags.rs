@@ -78,4 +78,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for SearchTagsRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = SearchTags<'de>;
     type Response = SearchTagsResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for SearchTags<'a> {
+    type Item = <crate::social_clippr::feed::TagView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o SearchTagsOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: SearchTagsOutput<'static>) -> Vec<Self::Item> {
+        output.tags
+    }
 }
\ No newline at end of file
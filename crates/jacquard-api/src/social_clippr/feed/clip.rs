@@ -5,6 +5,10 @@
 // This file was automatically generated from Lexicon schemas.
 // Any manual changes will be overwritten on the next regeneration.
 
+fn _default_clip_unlisted() -> bool {
+    false
+}
+
 /// Record containing a bookmarked item, or 'clip'.
 #[jacquard_derive::lexicon]
 #[derive(
@@ -44,6 +48,9 @@ pub struct Clip<'a> {
     #[builder(into)]
     pub title: jacquard_common::CowStr<'a>,
     /// Whether the bookmark can be used for feed indexing and aggregation
+    ///Defaults to `false`
+    #[serde(default = "_default_clip_unlisted")]
+    #[builder(default = false)]
     pub unlisted: bool,
     /// Whether the bookmark has been read by the user
     #[serde(skip_serializing_if = "std::option::Option::is_none")]
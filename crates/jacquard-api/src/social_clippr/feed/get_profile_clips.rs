@@ -77,4 +77,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetProfileClipsRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetProfileClips<'de>;
     type Response = GetProfileClipsResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetProfileClips<'a> {
+    type Item = <crate::social_clippr::feed::ClipView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetProfileClipsOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetProfileClipsOutput<'static>) -> Vec<Self::Item> {
+        output.feed
+    }
 }
\ No newline at end of file
@@ -72,4 +72,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetProfileTagsRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetProfileTags<'de>;
     type Response = GetProfileTagsResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetProfileTags<'a> {
+    type Item = <crate::social_clippr::feed::TagView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetProfileTagsOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetProfileTagsOutput<'static>) -> Vec<Self::Item> {
+        output.feed
+    }
 }
\ No newline at end of file
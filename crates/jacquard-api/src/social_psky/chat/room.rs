@@ -107,6 +107,10 @@ impl jacquard_common::types::collection::Collection for RoomRecord {
     type Record = RoomRecord;
 }
 
+fn _default_modlist_ref_active() -> bool {
+    false
+}
+
 #[jacquard_derive::lexicon]
 #[derive(
     serde::Serialize,
@@ -120,6 +124,9 @@ impl jacquard_common::types::collection::Collection for RoomRecord {
 )]
 #[serde(rename_all = "camelCase")]
 pub struct ModlistRef<'a> {
+    ///Defaults to `false`
+    #[serde(default = "_default_modlist_ref_active")]
+    #[builder(default = false)]
     pub active: bool,
     #[serde(borrow)]
     pub users: Vec<jacquard_common::types::string::Did<'a>>,
@@ -133,4 +133,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for QueryEventsRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = QueryEvents<'de>;
     type Response = QueryEventsResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for QueryEvents<'a> {
+    type Item = <crate::tools_ozone::moderation::ModEventView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o QueryEventsOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: QueryEventsOutput<'static>) -> Vec<Self::Item> {
+        output.events
+    }
 }
\ No newline at end of file
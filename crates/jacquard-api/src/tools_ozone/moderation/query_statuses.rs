@@ -170,4 +170,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for QueryStatusesRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = QueryStatuses<'de>;
     type Response = QueryStatusesResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for QueryStatuses<'a> {
+    type Item = <crate::tools_ozone::moderation::SubjectStatusView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o QueryStatusesOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: QueryStatusesOutput<'static>) -> Vec<Self::Item> {
+        output.subject_statuses
+    }
 }
\ No newline at end of file
@@ -78,4 +78,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for SearchReposRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = SearchRepos<'de>;
     type Response = SearchReposResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for SearchRepos<'a> {
+    type Item = <crate::tools_ozone::moderation::RepoView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o SearchReposOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: SearchReposOutput<'static>) -> Vec<Self::Item> {
+        output.repos
+    }
 }
\ No newline at end of file
@@ -110,4 +110,25 @@ impl jacquard_common::xrpc::XrpcEndpoint for GetValuesRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = GetValues<'de>;
     type Response = GetValuesResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for GetValues<'a> {
+    type Item = <jacquard_common::CowStr<'a> as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o GetValuesOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: GetValuesOutput<'static>) -> Vec<Self::Item> {
+        output.values
+    }
 }
\ No newline at end of file
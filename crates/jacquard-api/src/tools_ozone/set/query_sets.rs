@@ -84,4 +84,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for QuerySetsRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = QuerySets<'de>;
     type Response = QuerySetsResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for QuerySets<'a> {
+    type Item = <crate::tools_ozone::set::SetView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o QuerySetsOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: QuerySetsOutput<'static>) -> Vec<Self::Item> {
+        output.sets
+    }
 }
\ No newline at end of file
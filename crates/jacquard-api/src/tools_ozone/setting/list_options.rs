@@ -82,4 +82,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for ListOptionsRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = ListOptions<'de>;
     type Response = ListOptionsResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for ListOptions<'a> {
+    type Item = <crate::tools_ozone::setting::Option<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o ListOptionsOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: ListOptionsOutput<'static>) -> Vec<Self::Item> {
+        output.options
+    }
 }
\ No newline at end of file
@@ -79,4 +79,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for ListMembersRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = ListMembers<'de>;
     type Response = ListMembersResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for ListMembers<'a> {
+    type Item = <crate::tools_ozone::team::Member<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o ListMembersOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: ListMembersOutput<'static>) -> Vec<Self::Item> {
+        output.members
+    }
 }
\ No newline at end of file
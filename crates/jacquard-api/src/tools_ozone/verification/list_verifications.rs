@@ -87,4 +87,27 @@ impl jacquard_common::xrpc::XrpcEndpoint for ListVerificationsRequest {
     const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
     type Request<'de> = ListVerifications<'de>;
     type Response = ListVerificationsResponse;
+}
+
+impl<'a> jacquard_common::xrpc::Paginated for ListVerifications<'a> {
+    type Item = <crate::tools_ozone::verification::VerificationView<
+        'a,
+    > as jacquard_common::IntoStatic>::Output;
+    fn cursor(&self) -> std::option::Option<&str> {
+        self.cursor.as_deref()
+    }
+    fn set_cursor(
+        &mut self,
+        cursor: std::option::Option<jacquard_common::CowStr<'static>>,
+    ) {
+        self.cursor = cursor;
+    }
+    fn next_cursor<'o>(
+        output: &'o ListVerificationsOutput<'static>,
+    ) -> std::option::Option<&'o str> {
+        output.cursor.as_deref()
+    }
+    fn items(output: ListVerificationsOutput<'static>) -> Vec<Self::Item> {
+        output.verifications
+    }
 }
\ No newline at end of file
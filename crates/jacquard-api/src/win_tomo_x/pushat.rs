@@ -9,6 +9,10 @@ pub mod allow;
 pub mod push_notify;
 
 pub type DeviceList<'a> = Vec<crate::win_tomo_x::pushat::DeviceListItem<'a>>;
+fn _default_device_list_item_current() -> bool {
+    false
+}
+
 #[jacquard_derive::lexicon]
 #[derive(
     serde::Serialize,
@@ -22,6 +26,9 @@ pub type DeviceList<'a> = Vec<crate::win_tomo_x::pushat::DeviceListItem<'a>>;
 )]
 #[serde(rename_all = "camelCase")]
 pub struct DeviceListItem<'a> {
+    ///Defaults to `false`
+    #[serde(default = "_default_device_list_item_current")]
+    #[builder(default = false)]
     pub current: bool,
     pub id: jacquard_common::types::string::Tid,
     #[serde(borrow)]
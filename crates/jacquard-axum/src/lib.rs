@@ -39,15 +39,27 @@
 //! - **Query**: Deserializes from query string parameters
 //! - **Procedure**: Deserializes from request body (supports custom encodings via `decode_body`)
 //!
+//! Procedures that also take query parameters alongside their body (e.g. a blob upload with
+//! a filename in the query string) can use [`ExtractXrpcWithQuery`], which decodes the body
+//! as above and a second type from the query string.
+//!
 //! Deserialization errors return a 400 Bad Request with a JSON error body matching
 //! the XRPC error format.
 //!
+//! Subscription endpoints (WebSocket event streams like `com.atproto.sync.subscribeRepos`)
+//! are served with the `subscription` module, behind the `subscriptions` feature.
+//!
 //! The extractor deserializes to borrowed types first, then converts to `'static` via
 //! [`IntoStatic`], avoiding the DeserializeOwned requirement of the Json axum extractor and similar.
 
 pub mod did_web;
 #[cfg(feature = "service-auth")]
 pub mod service_auth;
+#[cfg(feature = "subscriptions")]
+pub mod subscription;
+
+#[cfg(feature = "service-auth")]
+pub use service_auth::AuthedXrpc;
 
 use axum::{
     Json, Router,
@@ -60,6 +72,7 @@ use jacquard::{
     IntoStatic,
     xrpc::{XrpcEndpoint, XrpcError, XrpcMethod, XrpcRequest},
 };
+use serde::Deserialize;
 use serde_json::json;
 
 /// Axum extractor for XRPC requests
@@ -131,6 +144,105 @@ where
     }
 }
 
+/// Query string parameters decoded by [`ExtractXrpcWithQuery`]
+///
+/// Mirrors [`XrpcEndpoint::Request`]: `Params<'a>` may borrow from the query string, and
+/// is converted with [`IntoStatic`] once decoded, so the parameter type doesn't need to be
+/// `DeserializeOwned`. Implement it on the `'static` form of the type.
+///
+/// ```
+/// use jacquard::CowStr;
+/// use jacquard_axum::XrpcQueryParams;
+/// use jacquard_derive::IntoStatic;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize, IntoStatic)]
+/// struct UploadParams<'a> {
+///     #[serde(borrow)]
+///     filename: Option<CowStr<'a>>,
+/// }
+///
+/// impl XrpcQueryParams for UploadParams<'static> {
+///     type Params<'a> = UploadParams<'a>;
+/// }
+/// ```
+pub trait XrpcQueryParams {
+    /// The parameters, borrowing from the query string
+    type Params<'a>: Deserialize<'a> + IntoStatic<Output = Self::Params<'static>>;
+}
+
+/// Axum extractor for XRPC procedures that also carry query parameters
+///
+/// The endpoint request is extracted exactly as [`ExtractXrpc`] does, and `Q` is
+/// deserialized separately from the query string, borrowing first and then converted to
+/// `'static` the same way. For query endpoints, both come from the query string.
+///
+/// ```no_run
+/// # use jacquard::api::com_atproto::repo::upload_blob::UploadBlobRequest;
+/// # use jacquard::CowStr;
+/// # use jacquard_derive::IntoStatic;
+/// use jacquard_axum::{ExtractXrpcWithQuery, XrpcQueryParams};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize, IntoStatic)]
+/// struct UploadParams<'a> {
+///     #[serde(borrow)]
+///     filename: Option<CowStr<'a>>,
+/// }
+///
+/// impl XrpcQueryParams for UploadParams<'static> {
+///     type Params<'a> = UploadParams<'a>;
+/// }
+///
+/// async fn upload_blob(
+///     ExtractXrpcWithQuery(blob, params): ExtractXrpcWithQuery<
+///         UploadBlobRequest,
+///         UploadParams<'static>,
+///     >,
+/// ) {
+///     // blob is the decoded body, params.filename came from `?filename=...`
+/// #   let _ = (blob, params.filename);
+/// }
+/// ```
+pub struct ExtractXrpcWithQuery<E: XrpcEndpoint, Q: XrpcQueryParams>(
+    pub E::Request<'static>,
+    pub Q::Params<'static>,
+);
+
+impl<S, R, Q> FromRequest<S> for ExtractXrpcWithQuery<R, Q>
+where
+    S: Send + Sync,
+    R: XrpcEndpoint,
+    for<'a> R::Request<'a>: IntoStatic<Output = R::Request<'static>>,
+    Q: XrpcQueryParams,
+    for<'a> Q::Params<'a>: Send,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let params = decode_query_params::<Q>(req.uri().query().unwrap_or(""))?;
+        let ExtractXrpc(request) = ExtractXrpc::<R>::from_request(req, state).await?;
+        Ok(ExtractXrpcWithQuery(request, params))
+    }
+}
+
+/// Decode `Q` borrowing from `query`, then take ownership
+///
+/// Kept out of `from_request` so the borrowed parameters never live across an await.
+fn decode_query_params<Q: XrpcQueryParams>(query: &str) -> Result<Q::Params<'static>, Response> {
+    let params = serde_html_form::from_str::<Q::Params<'_>>(query).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "InvalidRequest",
+                "message": format!("failed to decode query parameters: {}", e)
+            })),
+        )
+            .into_response()
+    })?;
+    Ok(params.into_static())
+}
+
 /// Conversion trait to turn an XrpcEndpoint and a handler into an axum Router
 pub trait IntoRouter {
     fn into_router<T, S, U>(handler: U) -> Router<S>
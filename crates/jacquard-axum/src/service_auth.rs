@@ -37,13 +37,15 @@
 //! }
 //! ```
 
+use crate::ExtractXrpc;
 use axum::{
     Json,
-    extract::FromRequestParts,
+    extract::{FromRequest, FromRequestParts, Request},
     http::{HeaderValue, StatusCode, header, request::Parts},
     middleware::Next,
     response::{IntoResponse, Response},
 };
+use jacquard::xrpc::{XrpcEndpoint, XrpcRequest};
 use jacquard_common::{
     CowStr, IntoStatic,
     service_auth::{self, PublicKey},
@@ -343,76 +345,158 @@ where
         state: &S,
     ) -> impl std::future::Future<Output = Result<Self, Self::Rejection>> + Send {
         async move {
-            // Extract Authorization header
-            let auth_header = parts
-                .headers
-                .get(header::AUTHORIZATION)
-                .ok_or(ServiceAuthError::MissingAuthHeader)?;
-
-            // Parse Bearer token
-            let auth_str = auth_header
-                .to_str()
-                .map_err(|_| ServiceAuthError::InvalidAuthHeader)?;
-
-            let token = auth_str
-                .strip_prefix("Bearer ")
-                .ok_or(ServiceAuthError::InvalidAuthHeader)?;
-
-            // Parse JWT
-            let parsed = service_auth::parse_jwt(token)?;
-
-            // Get claims for DID resolution
-            let claims = parsed.claims();
-
-            // Resolve DID to get signing key (do this before checking claims)
-            let did_doc = state
-                .resolver()
-                .resolve_did_doc(&claims.iss)
-                .await
-                .map_err(|e| ServiceAuthError::DidResolutionFailed {
-                    did: claims.iss.clone().into_static(),
-                    source: Box::new(e),
-                })?;
-
-            // Parse the DID document response to get verification methods
-            let doc = did_doc
-                .parse()
-                .map_err(|e| ServiceAuthError::DidResolutionFailed {
-                    did: claims.iss.clone().into_static(),
-                    source: Box::new(e),
-                })?;
-
-            // Extract signing key from DID document
-            let verification_methods = doc
-                .verification_method
-                .as_deref()
-                .ok_or_else(|| ServiceAuthError::NoSigningKey(claims.iss.clone().into_static()))?;
-
-            let signing_key = extract_signing_key(verification_methods)
-                .ok_or_else(|| ServiceAuthError::NoSigningKey(claims.iss.clone().into_static()))?;
-
-            // Verify signature FIRST - if this fails, nothing else matters
-            service_auth::verify_signature(&parsed, &signing_key)?;
-
-            // Now validate claims (audience, expiration, etc.)
-            claims.validate(state.service_did())?;
+            let auth = verify_service_auth(parts, state).await?;
 
             // Check method binding if required
-            if state.require_lxm() && claims.lxm.is_none() {
+            if state.require_lxm() && auth.lxm.is_none() {
                 return Err(ServiceAuthError::MethodBindingRequired);
             }
 
-            // All checks passed - return verified auth
-            Ok(ExtractServiceAuth(VerifiedServiceAuth {
-                did: claims.iss.clone().into_static(),
-                aud: claims.aud.clone().into_static(),
-                lxm: claims.lxm.as_ref().map(|l| l.clone().into_static()),
-                jti: claims.jti.as_ref().map(|j| j.clone().into_static()),
-            }))
+            Ok(ExtractServiceAuth(auth))
         }
     }
 }
 
+/// Verify the service auth JWT in the request's Authorization header.
+///
+/// Checks the signature against the issuer's DID document and the audience and expiry
+/// claims. Method binding is left to the caller.
+async fn verify_service_auth<S>(
+    parts: &Parts,
+    state: &S,
+) -> Result<VerifiedServiceAuth<'static>, ServiceAuthError>
+where
+    S: ServiceAuth + Send + Sync,
+    S::Resolver: Send + Sync,
+{
+    // Extract Authorization header
+    let auth_header = parts
+        .headers
+        .get(header::AUTHORIZATION)
+        .ok_or(ServiceAuthError::MissingAuthHeader)?;
+
+    // Parse Bearer token
+    let auth_str = auth_header
+        .to_str()
+        .map_err(|_| ServiceAuthError::InvalidAuthHeader)?;
+
+    let token = auth_str
+        .strip_prefix("Bearer ")
+        .ok_or(ServiceAuthError::InvalidAuthHeader)?;
+
+    // Parse JWT
+    let parsed = service_auth::parse_jwt(token)?;
+
+    // Get claims for DID resolution
+    let claims = parsed.claims();
+
+    // Resolve DID to get signing key (do this before checking claims)
+    let did_doc = state
+        .resolver()
+        .resolve_did_doc(&claims.iss)
+        .await
+        .map_err(|e| ServiceAuthError::DidResolutionFailed {
+            did: claims.iss.clone().into_static(),
+            source: Box::new(e),
+        })?;
+
+    // Parse the DID document response to get verification methods
+    let doc = did_doc
+        .parse()
+        .map_err(|e| ServiceAuthError::DidResolutionFailed {
+            did: claims.iss.clone().into_static(),
+            source: Box::new(e),
+        })?;
+
+    // Extract signing key from DID document
+    let verification_methods = doc
+        .verification_method
+        .as_deref()
+        .ok_or_else(|| ServiceAuthError::NoSigningKey(claims.iss.clone().into_static()))?;
+
+    let signing_key = extract_signing_key(verification_methods)
+        .ok_or_else(|| ServiceAuthError::NoSigningKey(claims.iss.clone().into_static()))?;
+
+    // Verify signature FIRST - if this fails, nothing else matters
+    service_auth::verify_signature(&parsed, &signing_key)?;
+
+    // Now validate claims (audience, expiration, etc.)
+    claims.validate(state.service_did())?;
+
+    // All checks passed - return verified auth
+    Ok(VerifiedServiceAuth {
+        did: claims.iss.clone().into_static(),
+        aud: claims.aud.clone().into_static(),
+        lxm: claims.lxm.as_ref().map(|l| l.clone().into_static()),
+        jti: claims.jti.as_ref().map(|j| j.clone().into_static()),
+    })
+}
+
+/// Axum extractor for XRPC requests that must carry service auth.
+///
+/// Verifies the service auth JWT like [`ExtractServiceAuth`], then extracts the request
+/// like [`ExtractXrpc`]. A token bound to a method (`lxm`) must be bound to this endpoint's
+/// NSID; a token without one is only accepted if the config doesn't require method binding.
+/// Missing or invalid tokens are rejected with a 401 and an XRPC error body.
+///
+/// # Example
+///
+/// ```no_run
+/// use axum::Router;
+/// use jacquard::api::com_atproto::identity::resolve_handle::ResolveHandleRequest;
+/// use jacquard_axum::{AuthedXrpc, IntoRouter, service_auth::ServiceAuthConfig};
+/// use jacquard_identity::JacquardResolver;
+///
+/// async fn resolve_handle(
+///     AuthedXrpc(auth, req): AuthedXrpc<ResolveHandleRequest>,
+/// ) -> String {
+///     format!("{} requested {}", auth.did(), req.handle)
+/// }
+///
+/// # fn app(config: ServiceAuthConfig<JacquardResolver>) -> Router {
+/// ResolveHandleRequest::into_router(resolve_handle).with_state(config)
+/// # }
+/// ```
+pub struct AuthedXrpc<E: XrpcEndpoint>(pub VerifiedServiceAuth<'static>, pub E::Request<'static>);
+
+impl<S, R> FromRequest<S> for AuthedXrpc<R>
+where
+    S: ServiceAuth + Send + Sync,
+    S::Resolver: Send + Sync,
+    R: XrpcEndpoint,
+    for<'a> R::Request<'a>: IntoStatic<Output = R::Request<'static>>,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let (parts, body) = req.into_parts();
+        let auth = verify_service_auth(&parts, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+
+        let nsid = Nsid::raw(<R::Request<'static> as XrpcRequest>::NSID);
+        match auth.lxm() {
+            Some(lxm) if lxm.as_str() != nsid.as_str() => {
+                return Err(ServiceAuthError::JwtError(
+                    service_auth::ServiceAuthError::MethodMismatch {
+                        expected: nsid,
+                        actual: Some(lxm.clone()),
+                    },
+                )
+                .into_response());
+            }
+            None if state.require_lxm() => {
+                return Err(ServiceAuthError::MethodBindingRequired.into_response());
+            }
+            _ => {}
+        }
+
+        let req = Request::from_parts(parts, body);
+        let ExtractXrpc(request) = ExtractXrpc::<R>::from_request(req, state).await?;
+        Ok(AuthedXrpc(auth, request))
+    }
+}
+
 /// Extract the signing key from a DID document's verification methods.
 ///
 /// This looks for a key with type "atproto" or the first available key
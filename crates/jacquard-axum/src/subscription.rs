@@ -0,0 +1,243 @@
+//! WebSocket support for XRPC subscription endpoints
+//!
+//! [`SubscriptionUpgrade`] extracts a subscription's query parameters and upgrades the
+//! connection; the upgraded socket is handed to your callback as a [`SubscriptionSink`],
+//! which frames outgoing events the way the firehose does: a DAG-CBOR header
+//! (`{op, t}`) followed by the DAG-CBOR event body, in a single binary message.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use axum::{Router, response::Response, routing::get};
+//! use jacquard::api::com_atproto::sync::subscribe_repos::SubscribeReposEndpoint;
+//! use jacquard_axum::subscription::SubscriptionUpgrade;
+//! use jacquard_common::xrpc::SubscriptionEndpoint;
+//!
+//! async fn subscribe_repos(upgrade: SubscriptionUpgrade<SubscribeReposEndpoint>) -> Response {
+//!     // Replay from the requested cursor, if any, then follow live events
+//!     let from = upgrade.cursor().unwrap_or(0);
+//!     upgrade.on_upgrade(move |mut sink| async move {
+//!         // sink.send_frame("#commit", &commit).await ...
+//! #       let _ = (&mut sink, from);
+//!     })
+//! }
+//!
+//! let app: Router = Router::new().route(SubscribeReposEndpoint::PATH, get(subscribe_repos));
+//! ```
+
+use std::collections::TryReserveError;
+use std::marker::PhantomData;
+
+use axum::{
+    Json,
+    extract::{
+        FromRequestParts,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    http::{StatusCode, request::Parts},
+    response::{IntoResponse, Response},
+};
+use jacquard::IntoStatic;
+use jacquard_common::xrpc::{MessageEncoding, SubscriptionEndpoint};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Errors from sending on a [`SubscriptionSink`]
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+pub enum SubscriptionError {
+    /// An event header or body couldn't be encoded as DAG-CBOR
+    #[error("failed to encode DAG-CBOR frame: {0}")]
+    #[diagnostic(code(jacquard_axum::subscription::cbor))]
+    Cbor(#[from] serde_ipld_dagcbor::EncodeError<TryReserveError>),
+
+    /// An event body couldn't be encoded as JSON
+    #[error("failed to encode JSON frame: {0}")]
+    #[diagnostic(code(jacquard_axum::subscription::json))]
+    Json(#[from] serde_json::Error),
+
+    /// The WebSocket connection failed or was closed by the client
+    #[error("websocket error: {0}")]
+    #[diagnostic(code(jacquard_axum::subscription::websocket))]
+    WebSocket(#[from] axum::Error),
+}
+
+/// Frame header, encoded ahead of every event body
+#[derive(Serialize)]
+struct FrameHeader<'a> {
+    op: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    t: Option<&'a str>,
+}
+
+/// Body of an error frame
+#[derive(Serialize)]
+struct ErrorFrameBody<'a> {
+    error: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<&'a str>,
+}
+
+/// Encode a message frame (`op: 1`) with event type `t`, e.g. `#commit`
+///
+/// The result is the DAG-CBOR header followed directly by the DAG-CBOR body, as read by
+/// [`parse_event_header`](jacquard_common::xrpc::subscription::parse_event_header).
+pub fn encode_frame<T: Serialize>(t: &str, body: &T) -> Result<Vec<u8>, SubscriptionError> {
+    let mut frame = serde_ipld_dagcbor::to_vec(&FrameHeader { op: 1, t: Some(t) })?;
+    frame.extend(serde_ipld_dagcbor::to_vec(body)?);
+    Ok(frame)
+}
+
+/// Encode an error frame (`op: -1`), such as `FutureCursor` or `ConsumerTooSlow`
+pub fn encode_error_frame(
+    error: &str,
+    message: Option<&str>,
+) -> Result<Vec<u8>, SubscriptionError> {
+    let mut frame = serde_ipld_dagcbor::to_vec(&FrameHeader { op: -1, t: None })?;
+    frame.extend(serde_ipld_dagcbor::to_vec(&ErrorFrameBody {
+        error,
+        message,
+    })?);
+    Ok(frame)
+}
+
+/// Axum extractor for XRPC subscription endpoints
+///
+/// Deserializes the endpoint's parameters from the query string (borrowed, then converted
+/// with [`IntoStatic`], as in [`ExtractXrpc`](crate::ExtractXrpc)) and prepares the
+/// WebSocket upgrade. Bad parameters are rejected with a 400 and an XRPC error body before
+/// the upgrade happens.
+pub struct SubscriptionUpgrade<E: SubscriptionEndpoint> {
+    /// The subscription parameters
+    pub params: E::Params<'static>,
+    cursor: Option<i64>,
+    upgrade: WebSocketUpgrade,
+}
+
+#[derive(Deserialize)]
+struct CursorParam {
+    cursor: Option<i64>,
+}
+
+fn invalid_request(message: String) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({
+            "error": "InvalidRequest",
+            "message": message
+        })),
+    )
+        .into_response()
+}
+
+/// Decode the endpoint parameters and the cursor from a query string
+fn decode_query<E>(query: &str) -> Result<(E::Params<'static>, Option<i64>), String>
+where
+    E: SubscriptionEndpoint,
+    for<'a> E::Params<'a>: IntoStatic<Output = E::Params<'static>>,
+{
+    let params = serde_html_form::from_str::<E::Params<'_>>(query)
+        .map_err(|e| format!("failed to decode request: {e}"))?
+        .into_static();
+    let cursor = serde_html_form::from_str::<CursorParam>(query)
+        .map_err(|e| format!("invalid cursor: {e}"))?
+        .cursor;
+    Ok((params, cursor))
+}
+
+impl<S, E> FromRequestParts<S> for SubscriptionUpgrade<E>
+where
+    S: Send + Sync,
+    E: SubscriptionEndpoint,
+    for<'a> E::Params<'a>: IntoStatic<Output = E::Params<'static>>,
+    for<'a> E::Params<'a>: Send,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        // Decoded synchronously, so nothing borrowed from `parts` is held across the await
+        let (params, cursor) =
+            decode_query::<E>(parts.uri.query().unwrap_or("")).map_err(invalid_request)?;
+        let upgrade = WebSocketUpgrade::from_request_parts(parts, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        Ok(Self {
+            params,
+            cursor,
+            upgrade,
+        })
+    }
+}
+
+impl<E: SubscriptionEndpoint> SubscriptionUpgrade<E> {
+    /// The `cursor` query parameter, if the client asked to backfill from a sequence number
+    ///
+    /// Replay events after this sequence number before switching to live events. If the
+    /// cursor is ahead of the stream, send a `FutureCursor` error frame and close.
+    pub fn cursor(&self) -> Option<i64> {
+        self.cursor
+    }
+
+    /// Complete the upgrade, running `callback` with a sink for the connection
+    pub fn on_upgrade<F, Fut>(self, callback: F) -> Response
+    where
+        F: FnOnce(SubscriptionSink<E>) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.upgrade.on_upgrade(move |socket| {
+            callback(SubscriptionSink {
+                socket,
+                _endpoint: PhantomData,
+            })
+        })
+    }
+}
+
+/// Outgoing side of an upgraded subscription connection
+///
+/// DAG-CBOR endpoints get one binary message per event, framed as header then body. JSON
+/// endpoints get the body alone as a text message.
+pub struct SubscriptionSink<E: SubscriptionEndpoint> {
+    socket: WebSocket,
+    _endpoint: PhantomData<fn() -> E>,
+}
+
+impl<E: SubscriptionEndpoint> SubscriptionSink<E> {
+    /// Send one event of type `t` (e.g. `#commit`) with the given body
+    pub async fn send_frame<T: Serialize>(
+        &mut self,
+        t: &str,
+        body: &T,
+    ) -> Result<(), SubscriptionError> {
+        let message = match E::ENCODING {
+            MessageEncoding::DagCbor => Message::Binary(encode_frame(t, body)?.into()),
+            MessageEncoding::Json => Message::Text(serde_json::to_string(body)?.into()),
+        };
+        Ok(self.socket.send(message).await?)
+    }
+
+    /// Send an error frame, then close the connection
+    pub async fn send_error(
+        mut self,
+        error: &str,
+        message: Option<&str>,
+    ) -> Result<(), SubscriptionError> {
+        let frame = match E::ENCODING {
+            MessageEncoding::DagCbor => Message::Binary(encode_error_frame(error, message)?.into()),
+            MessageEncoding::Json => {
+                Message::Text(serde_json::to_string(&ErrorFrameBody { error, message })?.into())
+            }
+        };
+        self.socket.send(frame).await?;
+        Ok(self.socket.send(Message::Close(None)).await?)
+    }
+
+    /// Close the connection
+    pub async fn close(mut self) -> Result<(), SubscriptionError> {
+        Ok(self.socket.send(Message::Close(None)).await?)
+    }
+
+    /// Access the underlying socket, e.g. to read client messages
+    pub fn socket(&mut self) -> &mut WebSocket {
+        &mut self.socket
+    }
+}
@@ -1,6 +1,7 @@
 use axum::{Json, Router, response::IntoResponse};
 use axum_test::TestServer;
-use jacquard_axum::{ExtractXrpc, IntoRouter};
+use jacquard_axum::{ExtractXrpc, ExtractXrpcWithQuery, IntoRouter, XrpcQueryParams};
+use jacquard_common::CowStr;
 use jacquard_common::types::string::Did;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -142,3 +143,110 @@ async fn test_multiple_params_with_encoded_did() {
     println!("Parsed DID: {}", body.did.as_str());
     assert_eq!(body.did.as_str(), "did:web:example.com");
 }
+
+// Mock procedure endpoint that also takes query parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TestProcedureRequest<'a> {
+    #[serde(borrow)]
+    did: Did<'a>,
+}
+
+impl jacquard::IntoStatic for TestProcedureRequest<'_> {
+    type Output = TestProcedureRequest<'static>;
+
+    fn into_static(self) -> Self::Output {
+        TestProcedureRequest {
+            did: self.did.into_static(),
+        }
+    }
+}
+
+impl jacquard::xrpc::XrpcRequest for TestProcedureRequest<'_> {
+    const NSID: &'static str = "com.example.test.procedure";
+    const METHOD: jacquard::xrpc::XrpcMethod =
+        jacquard::xrpc::XrpcMethod::Procedure("application/json");
+    type Response = TestQueryResponse<'static>;
+}
+
+impl jacquard::xrpc::XrpcEndpoint for TestProcedureRequest<'_> {
+    const PATH: &'static str = "/xrpc/com.example.test.procedure";
+    const METHOD: jacquard::xrpc::XrpcMethod =
+        jacquard::xrpc::XrpcMethod::Procedure("application/json");
+    type Request<'a> = TestProcedureRequest<'a>;
+    type Response = TestQueryResponse<'static>;
+}
+
+#[derive(Debug, Deserialize)]
+struct TestProcedureParams<'a> {
+    #[serde(borrow)]
+    name: CowStr<'a>,
+    #[serde(default)]
+    dry_run: bool,
+}
+
+impl jacquard::IntoStatic for TestProcedureParams<'_> {
+    type Output = TestProcedureParams<'static>;
+
+    fn into_static(self) -> Self::Output {
+        TestProcedureParams {
+            name: self.name.into_static(),
+            dry_run: self.dry_run,
+        }
+    }
+}
+
+impl XrpcQueryParams for TestProcedureParams<'static> {
+    type Params<'a> = TestProcedureParams<'a>;
+}
+
+async fn test_procedure_handler(
+    ExtractXrpcWithQuery(req, params): ExtractXrpcWithQuery<
+        TestProcedureRequest<'_>,
+        TestProcedureParams<'static>,
+    >,
+) -> impl IntoResponse {
+    let mut extra_data = BTreeMap::new();
+    extra_data.insert("name".to_string(), params.name.as_str().into());
+    extra_data.insert("dryRun".to_string(), params.dry_run.into());
+    Json(TestQueryResponse {
+        did: req.did,
+        extra_data,
+    })
+}
+
+#[tokio::test]
+async fn test_procedure_with_query_params() {
+    let app = Router::new().merge(TestProcedureRequest::into_router(test_procedure_handler));
+
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .post("/xrpc/com.example.test.procedure?name=avatar.png&dry_run=true")
+        .json(&serde_json::json!({ "did": "did:plc:123abc" }))
+        .await;
+
+    response.assert_status_ok();
+
+    let text = response.text();
+    let body: TestQueryResponse = serde_json::from_str(&text).unwrap();
+    assert_eq!(body.did.as_str(), "did:plc:123abc");
+    assert_eq!(body.extra_data["name"], "avatar.png");
+    assert_eq!(body.extra_data["dryRun"], true);
+}
+
+#[tokio::test]
+async fn test_procedure_with_bad_query_params() {
+    let app = Router::new().merge(TestProcedureRequest::into_router(test_procedure_handler));
+
+    let server = TestServer::new(app).unwrap();
+
+    // `name` is required
+    let response = server
+        .post("/xrpc/com.example.test.procedure?dry_run=true")
+        .json(&serde_json::json!({ "did": "did:plc:123abc" }))
+        .await;
+
+    response.assert_status_bad_request();
+    let body: serde_json::Value = serde_json::from_str(&response.text()).unwrap();
+    assert_eq!(body["error"], "InvalidRequest");
+}
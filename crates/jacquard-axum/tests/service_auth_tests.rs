@@ -9,9 +9,11 @@ use axum::{
 use base64::Engine;
 use base64::engine::general_purpose::URL_SAFE_NO_PAD;
 use bytes::Bytes;
+use jacquard::api::com_atproto::identity::resolve_handle::ResolveHandleRequest;
 use jacquard_axum::service_auth::{
     ExtractServiceAuth, ServiceAuthConfig, VerifiedServiceAuth, service_auth_middleware,
 };
+use jacquard_axum::{AuthedXrpc, IntoRouter};
 use jacquard_common::{
     CowStr, IntoStatic,
     service_auth::JwtHeader,
@@ -541,3 +543,84 @@ async fn test_invalid_signature() {
     // Should fail due to invalid signature
     assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
 }
+
+async fn authed_resolve_handle(AuthedXrpc(auth, req): AuthedXrpc<ResolveHandleRequest>) -> String {
+    format!("{} asked for {}", auth.did(), req.handle)
+}
+
+/// Send `token` (if any) to an `AuthedXrpc` route, returning the status and body
+async fn authed_xrpc_request(token: Option<String>) -> (StatusCode, String) {
+    let signing_key = k256::ecdsa::SigningKey::from_slice(&[7; 32]).unwrap();
+    let did_doc = create_test_did_doc("did:plc:test123", signing_key.verifying_key());
+    let config = ServiceAuthConfig::new(
+        Did::new_static("did:web:feedgen.example.com").unwrap(),
+        MockResolver::new(did_doc),
+    );
+    let app = ResolveHandleRequest::into_router(authed_resolve_handle).with_state(config);
+
+    let mut request =
+        Request::builder().uri("/xrpc/com.atproto.identity.resolveHandle?handle=alice.example.com");
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let response = app
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, String::from_utf8(body_bytes.to_vec()).unwrap())
+}
+
+fn authed_xrpc_token(lxm: Option<&str>) -> String {
+    let signing_key = k256::ecdsa::SigningKey::from_slice(&[7; 32]).unwrap();
+    create_test_jwt(
+        "did:plc:test123",
+        "did:web:feedgen.example.com",
+        chrono::Utc::now().timestamp() + 300,
+        lxm,
+        &signing_key,
+    )
+}
+
+#[tokio::test]
+async fn test_authed_xrpc_accepts_token_bound_to_endpoint() {
+    let token = authed_xrpc_token(Some("com.atproto.identity.resolveHandle"));
+
+    let (status, body) = authed_xrpc_request(Some(token)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "did:plc:test123 asked for alice.example.com");
+}
+
+#[tokio::test]
+async fn test_authed_xrpc_rejects_token_for_other_method() {
+    let token = authed_xrpc_token(Some("app.bsky.feed.getFeedSkeleton"));
+
+    let (status, body) = authed_xrpc_request(Some(token)).await;
+
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(body["error"], "AuthenticationRequired");
+    assert!(
+        body["message"]
+            .as_str()
+            .unwrap()
+            .contains("method mismatch")
+    );
+}
+
+#[tokio::test]
+async fn test_authed_xrpc_rejects_unbound_and_missing_tokens() {
+    let (status, body) = authed_xrpc_request(Some(authed_xrpc_token(None))).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(body["error"], "AuthenticationRequired");
+
+    let (status, body) = authed_xrpc_request(None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(body["error"], "AuthMissing");
+}
@@ -0,0 +1,83 @@
+//! Compare `serde_json` and `simd-json` on a feed-shaped response body.
+//!
+//! `simd-json` is an opt-in feature, so run with
+//! `cargo bench -p jacquard-common --features simd-json --bench json_backends`.
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use jacquard_common::types::value::Data;
+use std::hint::black_box;
+
+/// Build something shaped like an `app.bsky.feed.getTimeline` page
+fn feed_response(posts: usize) -> Vec<u8> {
+    let feed: Vec<_> = (0..posts)
+        .map(|i| {
+            serde_json::json!({
+                "post": {
+                    "uri": format!("at://did:plc:z72i7hdynmk6r22z27h6tvur/app.bsky.feed.post/3l{i:011}"),
+                    "cid": "bafyreihtzsyfxt4qbc3sbf6ooxagwyuxphwuwxhrxwaesh5ozrr7uqzdna",
+                    "author": {
+                        "did": "did:plc:z72i7hdynmk6r22z27h6tvur",
+                        "handle": "bsky.app",
+                        "displayName": "Bluesky",
+                        "avatar": "https://cdn.bsky.app/img/avatar/plain/did:plc:z72i7hdynmk6r22z27h6tvur/bafkreihagr2cmvl2jt4mgx3sppwe2it3fwolkrbtjrhcnwjk4jdijhsoze@jpeg",
+                        "labels": [],
+                        "createdAt": "2023-04-12T04:53:57.057Z"
+                    },
+                    "record": {
+                        "$type": "app.bsky.feed.post",
+                        "createdAt": "2024-10-16T18:02:11.000Z",
+                        "langs": ["en"],
+                        "text": format!("Post number {i} with some text, a link to https://example.com/{i} and a \"quoted\" \u{1F98B} emoji"),
+                        "facets": [{
+                            "index": { "byteStart": 38, "byteEnd": 61 },
+                            "features": [{
+                                "$type": "app.bsky.richtext.facet#link",
+                                "uri": format!("https://example.com/{i}")
+                            }]
+                        }]
+                    },
+                    "replyCount": i % 7,
+                    "repostCount": i % 13,
+                    "likeCount": i * 3,
+                    "quoteCount": 0,
+                    "indexedAt": "2024-10-16T18:02:11.482Z",
+                    "labels": []
+                }
+            })
+        })
+        .collect();
+    serde_json::to_vec(&serde_json::json!({ "cursor": "1729101731482::bafyrei", "feed": feed }))
+        .unwrap()
+}
+
+fn bench_backends(c: &mut Criterion) {
+    let body = feed_response(50);
+
+    let mut group = c.benchmark_group("feed_to_data");
+    group.throughput(Throughput::Bytes(body.len() as u64));
+
+    group.bench_function("serde_json", |b| {
+        b.iter(|| {
+            let data: Data<'_> = serde_json::from_slice(black_box(&body)).unwrap();
+            black_box(data);
+        })
+    });
+
+    let mut scratch = Vec::with_capacity(body.len());
+    group.bench_function("simd_json", |b| {
+        b.iter(|| {
+            // simd-json parses in place, so each iteration needs a fresh copy of the body.
+            // `Response::parse_mut` takes the body instead, but only with the opt-in
+            // `simd-json` feature; without it responses go through serde_json.
+            scratch.clear();
+            scratch.extend_from_slice(black_box(&body));
+            let data = Data::from_json_mut(&mut scratch).unwrap();
+            black_box(data);
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_backends);
+criterion_main!(benches);
@@ -21,6 +21,7 @@ pub struct ClientError {
     url: Option<SmolStr>,
     details: Option<SmolStr>,
     location: Option<SmolStr>,
+    headers: Option<http::HeaderMap>,
 }
 
 /// Error categories for client operations
@@ -96,6 +97,7 @@ impl ClientError {
             url: None,
             details: None,
             location: None,
+            headers: None,
         }
     }
 
@@ -109,6 +111,11 @@ impl ClientError {
         self.source.as_ref()
     }
 
+    /// Get the [`TransportError`] behind a transport error, e.g. [`TransportError::Timeout`]
+    pub fn transport_error(&self) -> Option<&TransportError> {
+        self.source.as_deref()?.downcast_ref()
+    }
+
     /// Get the context string if present
     pub fn context(&self) -> Option<&str> {
         self.context.as_ref().map(|s| s.as_str())
@@ -129,6 +136,14 @@ impl ClientError {
         self.location.as_ref().map(|s| s.as_str())
     }
 
+    /// Get the response headers if present
+    ///
+    /// Set for errors built from an HTTP response, such as a 429 carrying `ratelimit-*`
+    /// headers.
+    pub fn headers(&self) -> Option<&http::HeaderMap> {
+        self.headers.as_ref()
+    }
+
     /// Add help text to this error
     pub fn with_help(mut self, help: impl Into<SmolStr>) -> Self {
         self.help = Some(help.into());
@@ -159,6 +174,12 @@ impl ClientError {
         self
     }
 
+    /// Attach the headers of the response this error came from
+    pub fn with_headers(mut self, headers: http::HeaderMap) -> Self {
+        self.headers = Some(headers);
+        self
+    }
+
     // Constructors for each kind
 
     /// Create a transport error
@@ -211,25 +232,27 @@ pub type XrpcResult<T> = std::result::Result<T, ClientError>;
 // ============================================================================
 
 /// Transport-level errors that occur during HTTP communication
-// #[deprecated(since = "0.8.0", note = "Use ClientError::transport() instead")]
-// #[derive(Debug, thiserror::Error, miette::Diagnostic)]
-// pub enum TransportError {
-//     /// Failed to establish connection to server
-//     #[error("Connection error: {0}")]
-//     Connect(String),
-
-//     /// Request timed out
-//     #[error("Request timeout")]
-//     Timeout,
-
-//     /// Request construction failed (malformed URI, headers, etc.)
-//     #[error("Invalid request: {0}")]
-//     InvalidRequest(String),
-
-//     /// Other transport error
-//     #[error("Transport error: {0}")]
-//     Other(Box<dyn std::error::Error + Send + Sync>),
-// }
+///
+/// Carried as the source of a [`ClientErrorKind::Transport`] error; get it back with
+/// [`ClientError::transport_error`].
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+pub enum TransportError {
+    /// Failed to establish connection to server
+    #[error("Connection error: {0}")]
+    Connect(String),
+
+    /// Request timed out
+    #[error("Request timeout")]
+    Timeout,
+
+    /// Request construction failed (malformed URI, headers, etc.)
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
+    /// Other transport error
+    #[error("Transport error: {0}")]
+    Other(Box<dyn std::error::Error + Send + Sync>),
+}
 
 /// Response deserialization errors
 ///
@@ -244,6 +267,14 @@ pub enum DecodeError {
         #[source]
         serde_json::Error,
     ),
+    /// SIMD JSON deserialization failed
+    #[cfg(feature = "simd-json")]
+    #[error("Failed to deserialize JSON (simd-json): {0}")]
+    SimdJson(
+        #[from]
+        #[source]
+        simd_json::Error,
+    ),
     /// CBOR deserialization failed (local I/O)
     #[error("Failed to deserialize CBOR: {0}")]
     CborLocal(
@@ -278,6 +309,21 @@ pub enum DecodeError {
     #[cfg(feature = "websocket")]
     #[error("Unknown event type: {0}")]
     UnknownEventType(smol_str::SmolStr),
+
+    /// The server sent an error frame (`op: -1`) instead of a message
+    ///
+    /// Servers close the connection after sending one.
+    #[cfg(feature = "websocket")]
+    #[error(
+        "Subscription error: {error}{}",
+        .message.as_deref().map(|m| format!(": {m}")).unwrap_or_default()
+    )]
+    ErrorFrame {
+        /// Error name from the subscription's lexicon, e.g. `FutureCursor`
+        error: smol_str::SmolStr,
+        /// Human-readable detail, if the server sent any
+        message: Option<String>,
+    },
 }
 
 /// HTTP error response (non-200 status codes outside of XRPC error handling)
@@ -343,12 +389,11 @@ impl crate::IntoStatic for AuthError {
 // Conversions from old to new
 // ============================================================================
 
-#[allow(deprecated)]
-// impl From<TransportError> for ClientError {
-//     fn from(e: TransportError) -> Self {
-//         Self::transport(e)
-//     }
-// }
+impl From<TransportError> for ClientError {
+    fn from(e: TransportError) -> Self {
+        Self::transport(e)
+    }
+}
 
 impl From<DecodeError> for ClientError {
     fn from(e: DecodeError) -> Self {
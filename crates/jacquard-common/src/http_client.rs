@@ -17,6 +17,13 @@ pub trait HttpClient {
     ) -> impl Future<Output = core::result::Result<http::Response<Vec<u8>>, Self::Error>>;
 }
 
+/// Final URL of a response, after any redirects the client followed.
+///
+/// Clients that can report it attach this to [`http::Response::extensions`]; the `reqwest`
+/// client does. Compare it with the request URI to tell whether a redirect happened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseUrl(pub url::Url);
+
 #[cfg(feature = "streaming")]
 use crate::stream::{ByteStream, StreamError};
 
@@ -80,6 +87,8 @@ impl HttpClient for reqwest::Client {
             builder = builder.header(name.as_str(), value.as_bytes());
         }
 
+        builder = builder.extension(ResponseUrl(resp.url().clone()));
+
         // Read body
         let body = resp.bytes().await?.to_vec();
 
@@ -129,6 +129,14 @@ impl<T: IntoStatic> IntoStatic for Vec<T> {
     }
 }
 
+impl<T: IntoStatic, const N: usize> IntoStatic for [T; N] {
+    type Output = [T::Output; N];
+
+    fn into_static(self) -> Self::Output {
+        self.map(|v| v.into_static())
+    }
+}
+
 impl<T: IntoStatic + Clone> IntoStatic for Arc<T> {
     type Output = Arc<T::Output>;
 
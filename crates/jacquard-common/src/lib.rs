@@ -199,6 +199,9 @@ pub use cowstr::CowStr;
 pub use into_static::IntoStatic;
 pub use smol_str;
 pub use url;
+#[cfg(feature = "inventory")]
+#[doc(hidden)]
+pub use inventory;
 
 /// A copy-on-write immutable string type that uses [`smol_str::SmolStr`] for
 /// the "owned" variant.
@@ -211,6 +214,12 @@ pub mod error;
 /// HTTP client abstraction used by jacquard crates.
 pub mod http_client;
 pub mod macros;
+/// Client-side pacing from rate limit response headers.
+#[cfg(feature = "ratelimit")]
+pub mod ratelimit;
+/// Exponential backoff with jitter for retrying fallible async operations.
+#[cfg(feature = "retry")]
+pub mod retry;
 /// Service authentication JWT parsing and verification.
 #[cfg(feature = "service-auth")]
 pub mod service_auth;
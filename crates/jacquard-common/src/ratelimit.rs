@@ -0,0 +1,490 @@
+//! Client-side pacing from `ratelimit-*` response headers.
+//!
+//! PDSes and app views report rate limit state on every response. [`RateLimitedClient`] wraps
+//! any [`XrpcClient`] and remembers, per rate limit bucket, when a limit has run out; the next
+//! call that draws on that bucket waits until the reported reset time instead of going out and
+//! coming back as a 429. Responses naming a `ratelimit-policy` share a bucket with every method
+//! reporting the same policy; otherwise each NSID is its own bucket.
+//!
+//! Only calls made through [`XrpcClient::send`] and [`XrpcClient::send_with_opts`] are paced.
+//! A 429 can still get through (limits shared across methods, other clients on the same IP),
+//! so pair this with `retry::retry_with_backoff` (the `retry` feature) when that matters.
+//!
+//! ```no_run
+//! # async fn example<C>(agent: C)
+//! # where
+//! #     C: jacquard_common::xrpc::XrpcClient + Send + Sync,
+//! # {
+//! use jacquard_common::ratelimit::RateLimitedClient;
+//!
+//! let client = RateLimitedClient::new(agent);
+//! // client.send(request).await? waits out exhausted limits before sending
+//! # }
+//! ```
+
+use crate::error::XrpcResult;
+use crate::http_client::HttpClient;
+use crate::xrpc::{CallOptions, XrpcClient, XrpcRequest, XrpcResponse};
+use http::HeaderMap;
+use n0_future::time::{Duration, SystemTime};
+use smol_str::SmolStr;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use url::Url;
+
+/// `ratelimit-limit` response header
+pub const RATELIMIT_LIMIT: &str = "ratelimit-limit";
+/// `ratelimit-remaining` response header
+pub const RATELIMIT_REMAINING: &str = "ratelimit-remaining";
+/// `ratelimit-reset` response header
+pub const RATELIMIT_RESET: &str = "ratelimit-reset";
+/// `ratelimit-policy` response header
+pub const RATELIMIT_POLICY: &str = "ratelimit-policy";
+
+/// Rate limit state reported by a response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Requests allowed per window, if reported
+    pub limit: Option<u64>,
+    /// Requests left in the current window
+    pub remaining: u64,
+    /// When the current window resets
+    pub reset: SystemTime,
+    /// The `ratelimit-policy` the state applies to, if named
+    pub policy: Option<SmolStr>,
+}
+
+impl RateLimit {
+    /// Read rate limit state from response headers
+    ///
+    /// Returns `None` unless both `ratelimit-remaining` and `ratelimit-reset` are present and
+    /// numeric. atproto services send the reset as a Unix timestamp in seconds; small values
+    /// are treated as seconds from now, as in the IETF `RateLimit` header draft. A reset too
+    /// far in the future to represent is treated as no reset at all.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let number =
+            |name: &str| -> Option<u64> { headers.get(name)?.to_str().ok()?.trim().parse().ok() };
+        let remaining = number(RATELIMIT_REMAINING)?;
+        let reset = number(RATELIMIT_RESET)?;
+        // Anything before 2001 can't be a real timestamp
+        let reset = if reset >= 1_000_000_000 {
+            SystemTime::UNIX_EPOCH.checked_add(Duration::from_secs(reset))?
+        } else {
+            SystemTime::now() + Duration::from_secs(reset)
+        };
+        let policy = headers
+            .get(RATELIMIT_POLICY)
+            .and_then(|v| v.to_str().ok())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(SmolStr::new);
+        Some(Self {
+            limit: number(RATELIMIT_LIMIT),
+            remaining,
+            reset,
+            policy,
+        })
+    }
+
+    /// Whether no requests are left in the current window
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining == 0
+    }
+}
+
+#[derive(Debug, Default)]
+struct LimitState {
+    /// Exhausted limits reported without a policy, private to one method
+    by_nsid: HashMap<&'static str, SystemTime>,
+    /// Exhausted limits named by a `ratelimit-policy`, shared by every method reporting it
+    by_policy: HashMap<SmolStr, SystemTime>,
+    /// Policy last reported for each method
+    policies: HashMap<&'static str, SmolStr>,
+}
+
+impl LimitState {
+    /// When the limit `nsid` draws on resets, if it is exhausted
+    fn blocked_until(&self, nsid: &str) -> Option<SystemTime> {
+        match self.policies.get(nsid) {
+            Some(policy) => self.by_policy.get(policy).copied(),
+            None => self.by_nsid.get(nsid).copied(),
+        }
+    }
+
+    fn unblock(&mut self, nsid: &str) {
+        match self.policies.get(nsid) {
+            Some(policy) => self.by_policy.remove(policy),
+            None => self.by_nsid.remove(nsid),
+        };
+    }
+}
+
+/// [`XrpcClient`] wrapper that waits out exhausted rate limits
+///
+/// After each response, including error responses such as a 429, the `ratelimit-*` headers
+/// are recorded for the bucket the request draws on: the `ratelimit-policy` it reported, or
+/// its NSID when there is none. When `ratelimit-remaining` reaches zero, later calls drawing
+/// on the same bucket sleep until `ratelimit-reset` before being sent. State is shared
+/// between clones.
+#[derive(Debug, Clone)]
+pub struct RateLimitedClient<C> {
+    inner: C,
+    state: Arc<Mutex<LimitState>>,
+}
+
+impl<C> RateLimitedClient<C> {
+    /// Wrap a client
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            state: Arc::new(Mutex::new(LimitState::default())),
+        }
+    }
+
+    /// The wrapped client
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Unwrap, discarding the recorded rate limit state
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+
+    /// How long a call to `nsid` would currently wait, if at all
+    pub fn delay_for(&self, nsid: &str) -> Option<Duration> {
+        let mut state = self.state.lock().unwrap();
+        let until = state.blocked_until(nsid)?;
+        match until.duration_since(SystemTime::now()) {
+            Ok(delay) if !delay.is_zero() => Some(delay),
+            _ => {
+                state.unblock(nsid);
+                None
+            }
+        }
+    }
+
+    /// Record the rate limit state from a response to `nsid`
+    pub fn observe(&self, nsid: &'static str, headers: &HeaderMap) {
+        let Some(limit) = RateLimit::from_headers(headers) else {
+            return;
+        };
+        let mut state = self.state.lock().unwrap();
+        match limit.policy.clone() {
+            Some(policy) => state.policies.insert(nsid, policy),
+            None => state.policies.remove(nsid),
+        };
+        if limit.is_exhausted() {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                nsid,
+                policy = ?limit.policy,
+                "rate limit exhausted, pacing later requests"
+            );
+            match limit.policy {
+                Some(policy) => state.by_policy.insert(policy, limit.reset),
+                None => state.by_nsid.insert(nsid, limit.reset),
+            };
+        } else {
+            state.unblock(nsid);
+        }
+    }
+
+    /// Record the headers of a response or, for errors such as a 429, of the error response
+    fn observe_result<R>(&self, nsid: &'static str, result: &XrpcResult<XrpcResponse<R>>)
+    where
+        R: XrpcRequest,
+    {
+        let headers = match result {
+            Ok(response) => Some(response.headers()),
+            Err(e) => e.headers(),
+        };
+        if let Some(headers) = headers {
+            self.observe(nsid, headers);
+        }
+    }
+
+    async fn wait_for(&self, nsid: &str) {
+        if let Some(delay) = self.delay_for(nsid) {
+            n0_future::time::sleep(delay).await;
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<C: HttpClient + Sync> HttpClient for RateLimitedClient<C> {
+    type Error = C::Error;
+
+    fn send_http(
+        &self,
+        request: http::Request<Vec<u8>>,
+    ) -> impl Future<Output = core::result::Result<http::Response<Vec<u8>>, Self::Error>> + Send
+    {
+        self.inner.send_http(request)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl<C: HttpClient> HttpClient for RateLimitedClient<C> {
+    type Error = C::Error;
+
+    fn send_http(
+        &self,
+        request: http::Request<Vec<u8>>,
+    ) -> impl Future<Output = core::result::Result<http::Response<Vec<u8>>, Self::Error>> {
+        self.inner.send_http(request)
+    }
+}
+
+impl<C: XrpcClient + Sync> XrpcClient for RateLimitedClient<C> {
+    fn base_uri(&self) -> impl Future<Output = Url> {
+        self.inner.base_uri()
+    }
+
+    fn opts(&self) -> impl Future<Output = CallOptions<'_>> {
+        self.inner.opts()
+    }
+
+    async fn send<R>(&self, request: R) -> XrpcResult<XrpcResponse<R>>
+    where
+        R: XrpcRequest + Send + Sync,
+        <R as XrpcRequest>::Response: Send + Sync,
+    {
+        self.wait_for(R::NSID).await;
+        let result = self.inner.send(request).await;
+        self.observe_result::<R>(R::NSID, &result);
+        result
+    }
+
+    async fn send_with_opts<R>(
+        &self,
+        request: R,
+        opts: CallOptions<'_>,
+    ) -> XrpcResult<XrpcResponse<R>>
+    where
+        R: XrpcRequest + Send + Sync,
+        <R as XrpcRequest>::Response: Send + Sync,
+    {
+        self.wait_for(R::NSID).await;
+        let result = self.inner.send_with_opts(request, opts).await;
+        self.observe_result::<R>(R::NSID, &result);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+
+    fn headers(remaining: &str, reset: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(RATELIMIT_LIMIT, HeaderValue::from_static("100"));
+        headers.insert(
+            RATELIMIT_REMAINING,
+            HeaderValue::from_str(remaining).unwrap(),
+        );
+        headers.insert(RATELIMIT_RESET, HeaderValue::from_str(reset).unwrap());
+        headers
+    }
+
+    fn epoch_secs(time: SystemTime) -> u64 {
+        time.duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    #[test]
+    fn parses_headers() {
+        let limit = RateLimit::from_headers(&headers("7", "1700000000")).unwrap();
+        assert_eq!(limit.limit, Some(100));
+        assert_eq!(limit.remaining, 7);
+        assert_eq!(epoch_secs(limit.reset), 1_700_000_000);
+        assert!(!limit.is_exhausted());
+
+        // Small values are seconds from now
+        let limit = RateLimit::from_headers(&headers("0", "30")).unwrap();
+        assert!(limit.is_exhausted());
+        let delay = limit.reset.duration_since(SystemTime::now()).unwrap();
+        assert!(delay > Duration::from_secs(25) && delay <= Duration::from_secs(30));
+
+        assert_eq!(RateLimit::from_headers(&HeaderMap::new()), None);
+        assert_eq!(RateLimit::from_headers(&headers("soon", "30")), None);
+        // A reset past what SystemTime can hold is ignored rather than overflowing
+        assert_eq!(
+            RateLimit::from_headers(&headers("0", &u64::MAX.to_string())),
+            None
+        );
+    }
+
+    #[test]
+    fn paces_per_nsid() {
+        let client = RateLimitedClient::new(());
+        let reset = (epoch_secs(SystemTime::now()) + 60).to_string();
+
+        client.observe("com.example.a", &headers("3", &reset));
+        assert_eq!(client.delay_for("com.example.a"), None);
+
+        client.observe("com.example.a", &headers("0", &reset));
+        let delay = client.delay_for("com.example.a").unwrap();
+        assert!(delay > Duration::from_secs(50));
+        assert_eq!(client.delay_for("com.example.b"), None);
+
+        // A reset in the past clears the state
+        client.observe("com.example.a", &headers("0", "1700000000"));
+        assert_eq!(client.delay_for("com.example.a"), None);
+
+        // Responses without headers leave the state alone
+        client.observe("com.example.b", &headers("0", &reset));
+        client.observe("com.example.b", &HeaderMap::new());
+        assert!(client.delay_for("com.example.b").is_some());
+    }
+
+    #[test]
+    fn paces_per_policy() {
+        let client = RateLimitedClient::new(());
+        let reset = (epoch_secs(SystemTime::now()) + 60).to_string();
+        let with_policy = |remaining: &str, policy: &'static str| {
+            let mut headers = headers(remaining, &reset);
+            headers.insert(RATELIMIT_POLICY, HeaderValue::from_static(policy));
+            headers
+        };
+
+        // Both methods report the same account-wide policy
+        client.observe("com.example.a", &with_policy("5", "3000;w=300"));
+        client.observe("com.example.b", &with_policy("0", "3000;w=300"));
+        assert!(client.delay_for("com.example.a").is_some());
+        assert!(client.delay_for("com.example.b").is_some());
+
+        // A method on a different policy is unaffected
+        client.observe("com.example.c", &with_policy("5", "100;w=60"));
+        assert_eq!(client.delay_for("com.example.c"), None);
+
+        // Fresh quota on the shared policy unblocks every method on it
+        client.observe("com.example.a", &with_policy("2999", "3000;w=300"));
+        assert_eq!(client.delay_for("com.example.b"), None);
+    }
+
+    mod client {
+        use super::*;
+        use crate::xrpc::{GenericError, XrpcExt, XrpcMethod, XrpcResp};
+        use serde::{Deserialize, Serialize};
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        #[derive(Serialize, Deserialize)]
+        struct Ping;
+
+        struct PingResponse;
+
+        impl XrpcResp for PingResponse {
+            const NSID: &'static str = "com.example.ping";
+            const ENCODING: &'static str = "application/json";
+            type Output<'de> = ();
+            type Err<'de> = GenericError<'de>;
+        }
+
+        impl XrpcRequest for Ping {
+            const NSID: &'static str = "com.example.ping";
+            const METHOD: XrpcMethod = XrpcMethod::Query;
+            type Response = PingResponse;
+        }
+
+        /// Answers with `status`, reporting the quota as used up and resetting five seconds
+        /// after each request
+        struct Exhausted {
+            status: u16,
+            calls: AtomicU64,
+        }
+
+        impl Exhausted {
+            fn new(status: u16) -> RateLimitedClient<Self> {
+                RateLimitedClient::new(Self {
+                    status,
+                    calls: AtomicU64::new(0),
+                })
+            }
+        }
+
+        impl HttpClient for Exhausted {
+            type Error = std::convert::Infallible;
+
+            async fn send_http(
+                &self,
+                _request: http::Request<Vec<u8>>,
+            ) -> Result<http::Response<Vec<u8>>, Self::Error> {
+                self.calls.fetch_add(1, Ordering::SeqCst);
+                let body = if self.status == 200 {
+                    b"null".to_vec()
+                } else {
+                    br#"{"error":"RateLimitExceeded","message":"Rate Limit Exceeded"}"#.to_vec()
+                };
+                Ok(http::Response::builder()
+                    .status(self.status)
+                    .header(RATELIMIT_REMAINING, "0")
+                    .header(RATELIMIT_RESET, "5")
+                    .body(body)
+                    .unwrap())
+            }
+        }
+
+        impl XrpcClient for Exhausted {
+            async fn base_uri(&self) -> Url {
+                Url::parse("https://pds.example.com").unwrap()
+            }
+
+            async fn send<R>(&self, request: R) -> XrpcResult<XrpcResponse<R>>
+            where
+                R: XrpcRequest + Send + Sync,
+                <R as XrpcRequest>::Response: Send + Sync,
+            {
+                self.xrpc(self.base_uri().await).send(&request).await
+            }
+
+            async fn send_with_opts<R>(
+                &self,
+                request: R,
+                _opts: CallOptions<'_>,
+            ) -> XrpcResult<XrpcResponse<R>>
+            where
+                R: XrpcRequest + Send + Sync,
+                <R as XrpcRequest>::Response: Send + Sync,
+            {
+                self.send(request).await
+            }
+        }
+
+        #[tokio::test(start_paused = true)]
+        async fn send_waits_for_reset() {
+            let client = Exhausted::new(200);
+            let start = n0_future::time::Instant::now();
+            client.send(Ping).await.unwrap();
+            assert!(start.elapsed() < Duration::from_secs(1));
+
+            // The first response used up the quota, so the second call sleeps until the reset
+            client.send(Ping).await.unwrap();
+            assert!(start.elapsed() >= Duration::from_secs(4));
+            assert_eq!(client.inner().calls.load(Ordering::SeqCst), 2);
+        }
+
+        #[tokio::test(start_paused = true)]
+        async fn send_waits_for_reset_after_429() {
+            let client = Exhausted::new(429);
+            let start = n0_future::time::Instant::now();
+            let Err(err) = client.send(Ping).await else {
+                panic!("a 429 should be an error");
+            };
+            assert!(matches!(
+                err.kind(),
+                crate::error::ClientErrorKind::Http { status } if status.as_u16() == 429
+            ));
+            assert!(start.elapsed() < Duration::from_secs(1));
+
+            // The 429's headers are recorded even though the call failed
+            assert!(client.delay_for(Ping::NSID).is_some());
+            assert!(client.send(Ping).await.is_err());
+            assert!(start.elapsed() >= Duration::from_secs(4));
+            assert_eq!(client.inner().calls.load(Ordering::SeqCst), 2);
+        }
+    }
+}
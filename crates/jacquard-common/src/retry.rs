@@ -0,0 +1,312 @@
+//! Transport-agnostic exponential backoff with jitter.
+//!
+//! [`retry_with_backoff`] re-runs an async operation until it succeeds, returns an error the
+//! caller classifies as permanent, or runs out of attempts. Delays double from
+//! [`RetryConfig::base_delay`] up to [`RetryConfig::max_delay`], with optional random jitter so
+//! many clients retrying at once don't synchronize.
+//!
+//! ```
+//! # async fn example() {
+//! use jacquard_common::retry::{RetryConfig, retry_with_backoff};
+//! use std::time::Duration;
+//!
+//! let config = RetryConfig {
+//!     max_attempts: 3,
+//!     base_delay: Duration::from_millis(1),
+//!     ..Default::default()
+//! };
+//! let mut calls = 0;
+//! let result: Result<u32, &str> = retry_with_backoff(
+//!     &config,
+//!     || {
+//!         calls += 1;
+//!         let n = calls;
+//!         async move { if n < 3 { Err("busy") } else { Ok(n) } }
+//!     },
+//!     |e| *e == "busy",
+//! )
+//! .await;
+//! assert_eq!(result, Ok(3));
+//! # }
+//! ```
+//!
+//! For XRPC calls, [`RetryPolicy`] layers HTTP status handling and `Retry-After` on top of a
+//! [`RetryConfig`]; install one with [`XrpcCall::retry`](crate::xrpc::XrpcCall::retry).
+
+use crate::xrpc::XrpcMethod;
+use http::{HeaderMap, StatusCode};
+use std::future::Future;
+use std::time::Duration;
+
+/// Backoff parameters for [`retry_with_backoff`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryConfig {
+    /// Total number of attempts, including the first. `0` and `1` both mean "no retries".
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles on each subsequent retry
+    pub base_delay: Duration,
+    /// Upper bound on any single delay
+    pub max_delay: Duration,
+    /// Fraction of each delay (`0.0..=1.0`) that is randomized
+    ///
+    /// `0.0` gives fixed delays, `1.0` gives "full jitter" (anywhere from zero to the
+    /// computed delay). Values outside the range are clamped.
+    pub jitter: f64,
+}
+
+impl Default for RetryConfig {
+    /// 3 attempts, 200ms base delay, 10s cap, 50% jitter
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(10),
+            jitter: 0.5,
+        }
+    }
+}
+
+impl RetryConfig {
+    /// A config that never retries
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Delay to wait after the given failed attempt (1-based), before jitter
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.base_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+
+    /// Delay to wait after the given failed attempt (1-based), with jitter applied
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        let delay = self.backoff(attempt);
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        if jitter == 0.0 {
+            return delay;
+        }
+        let scale = 1.0 - jitter * rand::random::<f64>();
+        delay.mul_f64(scale)
+    }
+}
+
+/// When and how an [`XrpcCall`](crate::xrpc::XrpcCall) retries a failed request
+///
+/// Only responses with one of [`statuses`](Self::statuses) are retried. Queries are
+/// idempotent and retried by default; procedures are not unless
+/// [`retry_procedures`](Self::retry_procedures) is set, since a procedure the server
+/// actually applied before failing would be applied twice.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Attempt count, delays, and jitter
+    pub backoff: RetryConfig,
+    /// Response statuses worth retrying
+    pub statuses: Vec<StatusCode>,
+    /// Whether procedures (POST) are retried as well as queries
+    pub retry_procedures: bool,
+}
+
+impl Default for RetryPolicy {
+    /// Default backoff, retrying 429, 502, 503, and 504 on queries only
+    fn default() -> Self {
+        Self {
+            backoff: RetryConfig::default(),
+            statuses: vec![
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+            retry_procedures: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Default policy with the given backoff
+    pub fn new(backoff: RetryConfig) -> Self {
+        Self {
+            backoff,
+            ..Default::default()
+        }
+    }
+
+    /// Replace the set of retryable statuses
+    pub fn statuses(mut self, statuses: impl IntoIterator<Item = StatusCode>) -> Self {
+        self.statuses = statuses.into_iter().collect();
+        self
+    }
+
+    /// Opt in to retrying procedures
+    pub fn retry_procedures(mut self, retry: bool) -> Self {
+        self.retry_procedures = retry;
+        self
+    }
+
+    /// Whether calls with this method are retried at all
+    pub fn applies_to(&self, method: XrpcMethod) -> bool {
+        match method {
+            XrpcMethod::Query => true,
+            XrpcMethod::Procedure(_) => self.retry_procedures,
+        }
+    }
+
+    /// Whether a response with this status is retried
+    pub fn is_retryable(&self, status: StatusCode) -> bool {
+        self.statuses.contains(&status)
+    }
+
+    /// Delay before retrying after the given failed attempt (1-based)
+    ///
+    /// A `Retry-After` header on the failed response wins over the computed backoff, capped
+    /// at [`RetryConfig::max_delay`].
+    pub fn delay_for(&self, attempt: u32, headers: &HeaderMap) -> Duration {
+        match retry_after(headers) {
+            Some(delay) => delay.min(self.backoff.max_delay),
+            None => self.backoff.delay_for(attempt),
+        }
+    }
+}
+
+/// Read a `Retry-After` header, in either delay-seconds or HTTP-date form
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers
+        .get(http::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    // A date in the past means "now"
+    Some(
+        (at.with_timezone(&chrono::Utc) - chrono::Utc::now())
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// Run `operation` until it succeeds, fails permanently, or exhausts `config.max_attempts`
+///
+/// `is_retryable` decides whether an error is transient. The first non-retryable error, or
+/// the error from the last attempt, is returned as-is.
+pub async fn retry_with_backoff<T, E, F, Fut>(
+    config: &RetryConfig,
+    mut operation: F,
+    is_retryable: impl Fn(&E) -> bool,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let max_attempts = config.max_attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        match operation().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < max_attempts && is_retryable(&e) => {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(attempt, max_attempts, "retrying after transient error");
+                n0_future::time::sleep(config.delay_for(attempt)).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn fast(max_attempts: u32) -> RetryConfig {
+        RetryConfig {
+            max_attempts,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(2),
+            jitter: 0.0,
+        }
+    }
+
+    #[test]
+    fn backoff_doubles_and_caps() {
+        let config = RetryConfig {
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(500),
+            jitter: 0.0,
+            ..Default::default()
+        };
+        assert_eq!(config.backoff(1), Duration::from_millis(100));
+        assert_eq!(config.backoff(2), Duration::from_millis(200));
+        assert_eq!(config.backoff(3), Duration::from_millis(400));
+        assert_eq!(config.backoff(4), Duration::from_millis(500));
+        assert_eq!(config.backoff(64), Duration::from_millis(500));
+        assert_eq!(config.delay_for(2), Duration::from_millis(200));
+    }
+
+    #[test]
+    fn jitter_stays_within_bounds() {
+        let config = RetryConfig {
+            base_delay: Duration::from_millis(100),
+            jitter: 1.0,
+            ..Default::default()
+        };
+        for _ in 0..100 {
+            assert!(config.delay_for(1) <= Duration::from_millis(100));
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_until_success() {
+        let calls = Cell::new(0);
+        let result: Result<u32, &str> = retry_with_backoff(
+            &fast(5),
+            || {
+                calls.set(calls.get() + 1);
+                let n = calls.get();
+                async move { if n < 3 { Err("transient") } else { Ok(n) } }
+            },
+            |_| true,
+        )
+        .await;
+        assert_eq!(result, Ok(3));
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[tokio::test]
+    async fn stops_on_permanent_error_and_after_max_attempts() {
+        let calls = Cell::new(0);
+        let result: Result<(), &str> = retry_with_backoff(
+            &fast(5),
+            || {
+                calls.set(calls.get() + 1);
+                async { Err("permanent") }
+            },
+            |e| *e != "permanent",
+        )
+        .await;
+        assert_eq!(result, Err("permanent"));
+        assert_eq!(calls.get(), 1);
+
+        calls.set(0);
+        let result: Result<(), &str> = retry_with_backoff(
+            &fast(4),
+            || {
+                calls.set(calls.get() + 1);
+                async { Err("transient") }
+            },
+            |_| true,
+        )
+        .await;
+        assert_eq!(result, Err("transient"));
+        assert_eq!(calls.get(), 4);
+    }
+}
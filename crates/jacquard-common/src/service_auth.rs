@@ -344,6 +344,52 @@ impl PublicKey {
         })?;
         Ok(PublicKey::K256(key))
     }
+
+    /// Decode a `did:key` (or bare Multikey) string into a verification key.
+    pub fn from_did_key(did_key: &str) -> Result<Self, ServiceAuthError> {
+        let multikey = did_key.strip_prefix("did:key:").unwrap_or(did_key);
+        let key = crate::types::crypto::PublicKey::decode(multikey).map_err(|e| {
+            ServiceAuthError::Crypto(CowStr::Owned(format_smolstr!("invalid did:key: {}", e)))
+        })?;
+        match key.codec {
+            #[cfg(feature = "crypto-p256")]
+            crate::types::crypto::KeyCodec::P256 => Self::from_p256_bytes(&key.bytes),
+            #[cfg(feature = "crypto-k256")]
+            crate::types::crypto::KeyCodec::Secp256k1 => Self::from_k256_bytes(&key.bytes),
+            other => Err(ServiceAuthError::Crypto(CowStr::Owned(format_smolstr!(
+                "unsupported key type for verification: {:?}",
+                other
+            )))),
+        }
+    }
+
+    /// Verify a compact (r || s) ECDSA signature over `message`, hashed with SHA-256.
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), ServiceAuthError> {
+        match self {
+            #[cfg(feature = "crypto-p256")]
+            PublicKey::P256(key) => {
+                let sig = P256Signature::from_slice(signature).map_err(|e| {
+                    ServiceAuthError::Crypto(CowStr::Owned(format_smolstr!(
+                        "invalid ES256 signature: {}",
+                        e
+                    )))
+                })?;
+                key.verify(message, &sig)
+                    .map_err(|_| ServiceAuthError::InvalidSignature)
+            }
+            #[cfg(feature = "crypto-k256")]
+            PublicKey::K256(key) => {
+                let sig = K256Signature::from_slice(signature).map_err(|e| {
+                    ServiceAuthError::Crypto(CowStr::Owned(format_smolstr!(
+                        "invalid ES256K signature: {}",
+                        e
+                    )))
+                })?;
+                key.verify(message, &sig)
+                    .map_err(|_| ServiceAuthError::InvalidSignature)
+            }
+        }
+    }
 }
 
 /// Verify a JWT signature using the provided public key.
@@ -359,30 +405,10 @@ pub fn verify_signature(
 
     match (alg, public_key) {
         #[cfg(feature = "crypto-p256")]
-        ("ES256", PublicKey::P256(key)) => {
-            let sig = P256Signature::from_slice(signature).map_err(|e| {
-                ServiceAuthError::Crypto(CowStr::Owned(format_smolstr!(
-                    "invalid ES256 signature: {}",
-                    e
-                )))
-            })?;
-            key.verify(signing_input, &sig)
-                .map_err(|_| ServiceAuthError::InvalidSignature)?;
-            Ok(())
-        }
+        ("ES256", PublicKey::P256(_)) => public_key.verify(signing_input, signature),
 
         #[cfg(feature = "crypto-k256")]
-        ("ES256K", PublicKey::K256(key)) => {
-            let sig = K256Signature::from_slice(signature).map_err(|e| {
-                ServiceAuthError::Crypto(CowStr::Owned(format_smolstr!(
-                    "invalid ES256K signature: {}",
-                    e
-                )))
-            })?;
-            key.verify(signing_input, &sig)
-                .map_err(|_| ServiceAuthError::InvalidSignature)?;
-            Ok(())
-        }
+        ("ES256K", PublicKey::K256(_)) => public_key.verify(signing_input, signature),
 
         _ => Err(ServiceAuthError::UnsupportedAlgorithm {
             alg: SmolStr::new(alg),
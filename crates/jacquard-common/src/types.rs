@@ -28,6 +28,8 @@ pub mod language;
 pub mod nsid;
 /// Record key types and validation
 pub mod recordkey;
+/// Opt-in `$type` dispatch to registered types
+pub mod registry;
 /// String types with format validation
 pub mod string;
 /// Timestamp Identifier (TID) types and generation
@@ -3,6 +3,7 @@ use crate::types::nsid::Nsid;
 use crate::types::recordkey::{RecordKey, Rkey};
 use crate::types::string::AtStrError;
 use crate::{CowStr, IntoStatic};
+use percent_encoding::percent_decode_str;
 use regex::Regex;
 use serde::Serializer;
 use serde::{Deserialize, Deserializer, Serialize, de::Error};
@@ -51,6 +52,7 @@ struct Inner<'u> {
 impl Clone for AtUri<'_> {
     fn clone(&self) -> Self {
         let uri = self.inner.borrow_uri();
+        let decoded = decoded_rkey(self.inner.borrow_path().as_ref());
 
         Self {
             inner: Inner::new(
@@ -64,7 +66,8 @@ impl Clone for AtUri<'_> {
                     if let Some(collection) = parts.name("collection") {
                         let collection = unsafe { Nsid::unchecked(collection.as_str()) };
                         let rkey = if let Some(rkey) = parts.name("rkey") {
-                            let rkey = unsafe { RecordKey::from(Rkey::unchecked(rkey.as_str())) };
+                            let rkey =
+                                unsafe { RecordKey::from(reparse_rkey(rkey.as_str(), &decoded)) };
                             Some(rkey)
                         } else {
                             None
@@ -123,6 +126,55 @@ pub static ATURI_REGEX: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r##"^at://(?<authority>[a-zA-Z0-9._:%-]+)(/(?<collection>[a-zA-Z0-9-.]+)(/(?<rkey>[a-zA-Z0-9._~:@!$&%')(*+,;=-]+))?)?(#(?<fragment>/[a-zA-Z0-9._~:@!$&%')(*+,;=\-\[\]/\\]*))?$"##).unwrap()
 });
 
+/// Record key from the rkey segment of a URI, percent-decoded if it contains escapes
+///
+/// The decoded key must still be a valid record key, so an escaped `/` or `#` can't smuggle
+/// extra path segments or a fragment into the rkey.
+fn parse_rkey(rkey: &str) -> Result<Rkey<'_>, AtStrError> {
+    if rkey.contains('%') {
+        let decoded = percent_decode_str(rkey).decode_utf8().map_err(|_| {
+            AtStrError::regex(
+                "record-key",
+                rkey,
+                SmolStr::new_static("percent-encoding is not valid UTF-8"),
+            )
+        })?;
+        Rkey::new_owned(decoded)
+    } else {
+        Rkey::new(rkey)
+    }
+}
+
+/// Unchecked counterpart of [`parse_rkey`], for segments that were already validated
+unsafe fn rkey_unchecked(rkey: &str) -> Rkey<'_> {
+    if rkey.contains('%') {
+        parse_rkey(rkey).unwrap_or_else(|_| unsafe { Rkey::unchecked(rkey) })
+    } else {
+        unsafe { Rkey::unchecked(rkey) }
+    }
+}
+
+/// Owned copy of a parsed rkey that had to be percent-decoded, for rebuilding the URI
+///
+/// Keys that borrow straight from the URI aren't copied; [`reparse_rkey`] re-borrows them.
+fn decoded_rkey(path: Option<&RepoPath<'_>>) -> Option<Rkey<'static>> {
+    match &path?.rkey.as_ref()?.0.0 {
+        CowStr::Owned(decoded) => Some(Rkey(CowStr::Owned(decoded.clone()))),
+        CowStr::Borrowed(_) => None,
+    }
+}
+
+/// Rkey for the `raw` segment of a URI rebuilt from one that was already parsed
+///
+/// Percent-encoded segments reuse the key from [`decoded_rkey`] rather than being decoded
+/// again on every clone; anything else borrows from `raw`.
+unsafe fn reparse_rkey<'r>(raw: &'r str, decoded: &Option<Rkey<'static>>) -> Rkey<'r> {
+    match decoded {
+        Some(rkey) if raw.contains('%') => rkey.clone(),
+        _ => unsafe { rkey_unchecked(raw) },
+    }
+}
+
 impl<'u> AtUri<'u> {
     /// Fallible constructor, validates, borrows from input
     pub fn new(uri: &'u str) -> Result<Self, AtStrError> {
@@ -135,7 +187,7 @@ impl<'u> AtUri<'u> {
                         .map_err(|e| AtStrError::wrap("at-uri-scheme", uri.to_string(), e))?;
                     let rkey = if let Some(rkey) = parts.name("rkey") {
                         let rkey =
-                            RecordKey::from(Rkey::new(rkey.as_str()).map_err(|e| {
+                            RecordKey::from(parse_rkey(rkey.as_str()).map_err(|e| {
                                 AtStrError::wrap("at-uri-scheme", uri.to_string(), e)
                             })?);
                         Some(rkey)
@@ -181,7 +233,8 @@ impl<'u> AtUri<'u> {
                 let path = if let Some(collection) = parts.name("collection") {
                     let collection = Nsid::raw(collection.as_str());
                     let rkey = if let Some(rkey) = parts.name("rkey") {
-                        let rkey = RecordKey::from(Rkey::raw(rkey.as_str()));
+                        let rkey =
+                            RecordKey::from(parse_rkey(rkey.as_str()).expect("Invalid rkey"));
                         Some(rkey)
                     } else {
                         None
@@ -222,7 +275,7 @@ impl<'u> AtUri<'u> {
                 let path = if let Some(collection) = parts.name("collection") {
                     let collection = unsafe { Nsid::unchecked(collection.as_str()) };
                     let rkey = if let Some(rkey) = parts.name("rkey") {
-                        let rkey = RecordKey::from(unsafe { Rkey::unchecked(rkey.as_str()) });
+                        let rkey = RecordKey::from(unsafe { rkey_unchecked(rkey.as_str()) });
                         Some(rkey)
                     } else {
                         None
@@ -337,6 +390,92 @@ impl<'u> AtUri<'u> {
     }
 }
 
+impl AtUri<'_> {
+    /// Start building a URI from its components
+    ///
+    /// ```
+    /// # use jacquard_common::types::aturi::AtUri;
+    /// # use jacquard_common::types::string::{Did, Nsid, Rkey};
+    /// let uri = AtUri::builder()
+    ///     .authority(Did::new_static("did:plc:abc123").unwrap())
+    ///     .collection(Nsid::new_static("app.bsky.feed.post").unwrap())
+    ///     .rkey(Rkey::new_static("3jk5").unwrap())
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(uri.as_str(), "at://did:plc:abc123/app.bsky.feed.post/3jk5");
+    /// ```
+    pub fn builder<'b>() -> AtUriBuilder<'b> {
+        AtUriBuilder::default()
+    }
+}
+
+/// Builder for [`AtUri`], created with [`AtUri::builder`]
+///
+/// Components are validated by their own types, so `build` only checks that they fit
+/// together: an authority is required, and a record key needs a collection.
+#[derive(Clone, Debug, Default)]
+pub struct AtUriBuilder<'b> {
+    authority: Option<AtIdentifier<'b>>,
+    collection: Option<Nsid<'b>>,
+    rkey: Option<Rkey<'b>>,
+    fragment: Option<CowStr<'b>>,
+}
+
+impl<'b> AtUriBuilder<'b> {
+    /// Repository the URI points into, a DID or a handle
+    pub fn authority(mut self, authority: impl Into<AtIdentifier<'b>>) -> Self {
+        self.authority = Some(authority.into());
+        self
+    }
+
+    /// Collection NSID
+    pub fn collection(mut self, collection: Nsid<'b>) -> Self {
+        self.collection = Some(collection);
+        self
+    }
+
+    /// Record key within the collection
+    pub fn rkey(mut self, rkey: Rkey<'b>) -> Self {
+        self.rkey = Some(rkey);
+        self
+    }
+
+    /// Fragment, starting with `/` (e.g. `/path`); can't be combined with a record key
+    pub fn fragment(mut self, fragment: impl Into<CowStr<'b>>) -> Self {
+        self.fragment = Some(fragment.into());
+        self
+    }
+
+    /// Assemble and validate the URI
+    pub fn build(self) -> Result<AtUri<'static>, AtStrError> {
+        let Some(authority) = self.authority else {
+            return Err(AtStrError::missing("at-uri-scheme", "at://", "authority"));
+        };
+        let has_rkey = self.rkey.is_some();
+        let mut uri = match (self.collection, self.rkey) {
+            (None, None) => format!("at://{authority}"),
+            (Some(collection), None) => format!("at://{authority}/{collection}"),
+            (Some(collection), Some(rkey)) => format!("at://{authority}/{collection}/{rkey}"),
+            (None, Some(rkey)) => {
+                let uri = format!("at://{authority}//{rkey}");
+                return Err(AtStrError::missing("at-uri-scheme", &uri, "collection"));
+            }
+        };
+        if let Some(fragment) = self.fragment {
+            uri.push('#');
+            uri.push_str(&fragment);
+            if has_rkey {
+                return Err(AtStrError::regex(
+                    "at-uri-scheme",
+                    &uri,
+                    SmolStr::new_static("fragment is not allowed alongside a record key"),
+                ));
+            }
+        }
+        AtUri::new_owned(uri)
+    }
+}
+
 impl AtUri<'static> {
     /// Owned constructor
     ///
@@ -363,7 +502,7 @@ impl AtUri<'static> {
                         AtStrError::wrap("at-uri-scheme", uri.as_ref().to_string(), e)
                     })?;
                     let rkey = if let Some(rkey) = parts.name("rkey") {
-                        let rkey = RecordKey::from(Rkey::new(rkey.as_str()).map_err(|e| {
+                        let rkey = RecordKey::from(parse_rkey(rkey.as_str()).map_err(|e| {
                             AtStrError::wrap("at-uri-scheme", uri.as_ref().to_string(), e)
                         })?);
                         Some(rkey)
@@ -375,6 +514,7 @@ impl AtUri<'static> {
                     None
                 };
 
+                let decoded = decoded_rkey(path.as_ref());
                 Ok(AtUri {
                     inner: Inner::new(
                         CowStr::Owned(uri.as_ref().to_smolstr()),
@@ -392,7 +532,7 @@ impl AtUri<'static> {
                                         unsafe { Nsid::unchecked(collection.as_str()) };
                                     let rkey = if let Some(rkey) = parts.name("rkey") {
                                         let rkey = unsafe {
-                                            RecordKey::from(Rkey::unchecked(rkey.as_str()))
+                                            RecordKey::from(reparse_rkey(rkey.as_str(), &decoded))
                                         };
                                         Some(rkey)
                                     } else {
@@ -443,7 +583,7 @@ impl AtUri<'static> {
                         .map_err(|e| AtStrError::wrap("at-uri-scheme", uri.to_string(), e))?;
                     let rkey = if let Some(rkey) = parts.name("rkey") {
                         let rkey =
-                            RecordKey::from(Rkey::new_static(rkey.as_str()).map_err(|e| {
+                            RecordKey::from(parse_rkey(rkey.as_str()).map_err(|e| {
                                 AtStrError::wrap("at-uri-scheme", uri.to_string(), e)
                             })?);
                         Some(rkey)
@@ -495,7 +635,7 @@ impl FromStr for AtUri<'_> {
                         .map_err(|e| AtStrError::wrap("at-uri-scheme", uri.to_string(), e))?;
                     let rkey = if let Some(rkey) = parts.name("rkey") {
                         let rkey =
-                            RecordKey::from(Rkey::new(rkey.as_str()).map_err(|e| {
+                            RecordKey::from(parse_rkey(rkey.as_str()).map_err(|e| {
                                 AtStrError::wrap("at-uri-scheme", uri.to_string(), e)
                             })?);
                         Some(rkey)
@@ -507,6 +647,7 @@ impl FromStr for AtUri<'_> {
                     None
                 };
 
+                let decoded = decoded_rkey(path.as_ref());
                 Ok(AtUri {
                     inner: Inner::new(
                         CowStr::Owned(uri.to_smolstr()),
@@ -524,7 +665,7 @@ impl FromStr for AtUri<'_> {
                                         unsafe { Nsid::unchecked(collection.as_str()) };
                                     let rkey = if let Some(rkey) = parts.name("rkey") {
                                         let rkey = unsafe {
-                                            RecordKey::from(Rkey::unchecked(rkey.as_str()))
+                                            RecordKey::from(reparse_rkey(rkey.as_str(), &decoded))
                                         };
                                         Some(rkey)
                                     } else {
@@ -568,6 +709,7 @@ impl IntoStatic for AtUri<'_> {
     type Output = AtUri<'static>;
 
     fn into_static(self) -> AtUri<'static> {
+        let decoded = decoded_rkey(self.inner.borrow_path().as_ref());
         AtUri {
             inner: Inner::new(
                 self.inner.borrow_uri().clone().into_static(),
@@ -581,8 +723,9 @@ impl IntoStatic for AtUri<'_> {
                         if let Some(collection) = parts.name("collection") {
                             let collection = unsafe { Nsid::unchecked(collection.as_str()) };
                             let rkey = if let Some(rkey) = parts.name("rkey") {
-                                let rkey =
-                                    unsafe { RecordKey::from(Rkey::unchecked(rkey.as_str())) };
+                                let rkey = unsafe {
+                                    RecordKey::from(reparse_rkey(rkey.as_str(), &decoded))
+                                };
                                 Some(rkey)
                             } else {
                                 None
@@ -671,7 +814,7 @@ impl<'d> TryFrom<CowStr<'d>> for AtUri<'d> {
                         .map_err(|e| AtStrError::wrap("at-uri-scheme", uri.to_string(), e))?;
                     let rkey = if let Some(rkey) = parts.name("rkey") {
                         let rkey =
-                            RecordKey::from(Rkey::new(rkey.as_str()).map_err(|e| {
+                            RecordKey::from(parse_rkey(rkey.as_str()).map_err(|e| {
                                 AtStrError::wrap("at-uri-scheme", uri.to_string(), e)
                             })?);
                         Some(rkey)
@@ -682,6 +825,7 @@ impl<'d> TryFrom<CowStr<'d>> for AtUri<'d> {
                 } else {
                     None
                 };
+                let decoded = decoded_rkey(_path.as_ref());
                 drop(parts);
 
                 Ok(AtUri {
@@ -698,8 +842,9 @@ impl<'d> TryFrom<CowStr<'d>> for AtUri<'d> {
                             if let Some(collection) = parts.name("collection") {
                                 let collection = unsafe { Nsid::unchecked(collection.as_str()) };
                                 let rkey = if let Some(rkey) = parts.name("rkey") {
-                                    let rkey =
-                                        unsafe { RecordKey::from(Rkey::unchecked(rkey.as_str())) };
+                                    let rkey = unsafe {
+                                        RecordKey::from(reparse_rkey(rkey.as_str(), &decoded))
+                                    };
                                     Some(rkey)
                                 } else {
                                     None
@@ -752,6 +897,7 @@ impl Deref for AtUri<'_> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::string::{Did, Handle};
 
     #[test]
     fn valid_at_uris() {
@@ -813,6 +959,80 @@ mod tests {
         assert!(AtUri::new("https://alice.test").is_err());
     }
 
+    #[test]
+    fn builder_round_trips() {
+        let uri = AtUri::builder()
+            .authority(Did::new_static("did:plc:abc123").unwrap())
+            .collection(Nsid::new_static("app.bsky.feed.post").unwrap())
+            .rkey(Rkey::new_static("3jk5").unwrap())
+            .build()
+            .unwrap();
+        assert_eq!(uri.as_str(), "at://did:plc:abc123/app.bsky.feed.post/3jk5");
+
+        let parsed = AtUri::new(uri.as_str()).unwrap();
+        assert_eq!(parsed.authority(), uri.authority());
+        assert_eq!(parsed.collection(), uri.collection());
+        assert_eq!(parsed.rkey(), uri.rkey());
+        assert_eq!(parsed.rkey().unwrap().as_ref(), "3jk5");
+        assert!(parsed.fragment().is_none());
+    }
+
+    #[test]
+    fn builder_requires_authority_and_collection() {
+        assert!(AtUri::builder().build().is_err());
+        let uri = AtUri::builder()
+            .authority(Handle::new_static("alice.test").unwrap())
+            .build()
+            .unwrap();
+        assert_eq!(uri.as_str(), "at://alice.test");
+        assert!(
+            AtUri::builder()
+                .authority(Handle::new_static("alice.test").unwrap())
+                .rkey(Rkey::new_static("123").unwrap())
+                .build()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn builder_fragment() {
+        let uri = AtUri::builder()
+            .authority(Did::new_static("did:plc:abc123").unwrap())
+            .collection(Nsid::new_static("app.bsky.feed.post").unwrap())
+            .fragment("/path")
+            .build()
+            .unwrap();
+        assert_eq!(uri.as_str(), "at://did:plc:abc123/app.bsky.feed.post#/path");
+        assert_eq!(uri.fragment().as_ref().unwrap().as_ref(), "/path");
+
+        // A fragment alongside a record key is rejected
+        assert!(
+            AtUri::builder()
+                .authority(Did::new_static("did:plc:abc123").unwrap())
+                .collection(Nsid::new_static("app.bsky.feed.post").unwrap())
+                .rkey(Rkey::new_static("3jk5").unwrap())
+                .fragment("/path")
+                .build()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn percent_encoded_rkey() {
+        let uri = AtUri::new("at://alice.test/com.example.foo/a%3Ab").unwrap();
+        assert_eq!(uri.rkey().unwrap().as_ref(), "a:b");
+        let owned = AtUri::new_owned("at://alice.test/com.example.foo/a%3Ab").unwrap();
+        assert_eq!(owned.rkey(), uri.rkey());
+        assert_eq!(owned.clone().rkey().unwrap().as_ref(), "a:b");
+        assert_eq!(owned.clone().into_static().rkey(), uri.rkey());
+        assert_eq!(uri.clone().rkey(), uri.rkey());
+
+        // Escapes can't smuggle a fragment or extra segment into the rkey
+        assert!(AtUri::new("at://alice.test/com.example.foo/123%23frag").is_err());
+        assert!(AtUri::new("at://alice.test/com.example.foo/1%2F2").is_err());
+        assert!(AtUri::new("at://alice.test/com.example.foo/%FF").is_err());
+    }
+
     #[test]
     fn max_length() {
         // Spec says 8KB max
@@ -1,5 +1,7 @@
+use crate::types::cid::{ATP_CID_HASH, IpldCid};
 use crate::{CowStr, IntoStatic, types::cid::CidLink};
 use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+use sha2::{Digest, Sha256};
 use smol_str::ToSmolStr;
 use std::convert::Infallible;
 use std::{fmt, hash::Hash, ops::Deref, str::FromStr};
@@ -68,6 +70,131 @@ impl IntoStatic for Blob<'_> {
     }
 }
 
+impl Blob<'_> {
+    /// Check that `data` is the content this blob references
+    ///
+    /// Recomputes the SHA-256 multihash of `data` and compares it with the `ref` CID,
+    /// and checks the length against `size`. Use on bytes from `getBlob` (or any other
+    /// source) before trusting them; see [`BlobVerifier`] to verify a stream without
+    /// buffering it.
+    pub fn verify_bytes(&self, data: &[u8]) -> Result<(), BlobError> {
+        let mut verifier = self.verifier()?;
+        verifier.update(data);
+        verifier.finish()
+    }
+
+    /// Start an incremental verification of this blob's content
+    pub fn verifier(&self) -> Result<BlobVerifier, BlobError> {
+        BlobVerifier::new(self)
+    }
+}
+
+/// Errors from verifying blob content against its reference
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error, miette::Diagnostic)]
+pub enum BlobError {
+    /// The blob's `ref` isn't a valid CID
+    #[error("invalid blob CID: {0}")]
+    InvalidCid(String),
+    /// The `ref` CID uses a hash function other than SHA-256
+    #[error("unsupported blob hash function 0x{0:x}")]
+    #[diagnostic(help("AT Protocol blob CIDs use SHA-256 (0x12)"))]
+    UnsupportedHash(u64),
+    /// Content length differs from the blob's `size`
+    #[error("blob size mismatch: expected {expected} bytes, got {actual}")]
+    SizeMismatch {
+        /// Size from the blob reference
+        expected: usize,
+        /// Number of bytes received
+        actual: usize,
+    },
+    /// Content hash differs from the blob's `ref`
+    #[error("blob hash mismatch: expected {expected}, got {actual}")]
+    HashMismatch {
+        /// CID from the blob reference
+        expected: String,
+        /// CID of the bytes received
+        actual: String,
+    },
+}
+
+/// Incremental blob content verifier
+///
+/// Feed chunks to [`update`](Self::update) as they arrive, then call
+/// [`finish`](Self::finish) to compare the total length and hash with the blob reference.
+///
+/// ```
+/// # use jacquard_common::types::blob::{Blob, MimeType};
+/// # use jacquard_common::types::cid::CidLink;
+/// # let blob = Blob {
+/// #     r#ref: CidLink::new_static("bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e"),
+/// #     mime_type: MimeType::new_static("text/plain"),
+/// #     size: 11,
+/// # };
+/// let mut verifier = blob.verifier()?;
+/// for chunk in [&b"hello "[..], &b"world"[..]] {
+///     verifier.update(chunk);
+/// }
+/// verifier.finish()?;
+/// # Ok::<(), jacquard_common::types::blob::BlobError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct BlobVerifier {
+    expected: IpldCid,
+    size: usize,
+    received: usize,
+    hasher: Sha256,
+}
+
+impl BlobVerifier {
+    /// Create a verifier for `blob`'s content
+    pub fn new(blob: &Blob<'_>) -> Result<Self, BlobError> {
+        let expected = blob
+            .r#ref
+            .to_ipld()
+            .map_err(|e| BlobError::InvalidCid(e.to_string()))?;
+        if expected.hash().code() != ATP_CID_HASH {
+            return Err(BlobError::UnsupportedHash(expected.hash().code()));
+        }
+        Ok(Self {
+            expected,
+            size: blob.size,
+            received: 0,
+            hasher: Sha256::new(),
+        })
+    }
+
+    /// Hash the next chunk of content
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.received += chunk.len();
+        self.hasher.update(chunk);
+    }
+
+    /// Number of bytes hashed so far
+    pub fn received(&self) -> usize {
+        self.received
+    }
+
+    /// Check the content seen so far against the blob reference
+    pub fn finish(self) -> Result<(), BlobError> {
+        if self.received != self.size {
+            return Err(BlobError::SizeMismatch {
+                expected: self.size,
+                actual: self.received,
+            });
+        }
+        let digest = self.hasher.finalize();
+        if self.expected.hash().digest() != &digest[..] {
+            let hash = cid::multihash::Multihash::wrap(ATP_CID_HASH, &digest)
+                .expect("SHA-256 digest fits in a multihash");
+            return Err(BlobError::HashMismatch {
+                expected: self.expected.to_string(),
+                actual: IpldCid::new_v1(self.expected.codec(), hash).to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
 /// Tagged blob reference with `$type` field for serde
 ///
 /// This enum provides the `{"$type": "blob"}` wrapper expected by AT Protocol's JSON format.
@@ -234,3 +361,54 @@ impl Deref for MimeType<'_> {
         self.as_str()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blob(size: usize) -> Blob<'static> {
+        // CIDv1 raw sha2-256 of "hello world"
+        Blob {
+            r#ref: CidLink::new_static(
+                "bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e",
+            ),
+            mime_type: MimeType::new_static("text/plain"),
+            size,
+        }
+    }
+
+    #[test]
+    fn verify_bytes_matches() {
+        assert_eq!(blob(11).verify_bytes(b"hello world"), Ok(()));
+
+        let mut verifier = blob(11).verifier().unwrap();
+        verifier.update(b"hello ");
+        verifier.update(b"world");
+        assert_eq!(verifier.received(), 11);
+        assert_eq!(verifier.finish(), Ok(()));
+    }
+
+    #[test]
+    fn verify_bytes_rejects_mismatch() {
+        assert_eq!(
+            blob(11).verify_bytes(b"hello"),
+            Err(BlobError::SizeMismatch {
+                expected: 11,
+                actual: 5
+            })
+        );
+        assert!(matches!(
+            blob(11).verify_bytes(b"hello_world"),
+            Err(BlobError::HashMismatch { .. })
+        ));
+
+        let bad = Blob {
+            r#ref: CidLink::new_static("not-a-cid"),
+            ..blob(11)
+        };
+        assert!(matches!(
+            bad.verify_bytes(b"hello world"),
+            Err(BlobError::InvalidCid(_))
+        ));
+    }
+}
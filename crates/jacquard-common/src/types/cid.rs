@@ -350,8 +350,14 @@ impl Serialize for CidLink<'_> {
             map.serialize_entry("$link", self.0.as_str())?;
             map.end()
         } else {
-            // CBOR: raw CID
-            self.0.serialize(serializer)
+            // CBOR: raw CID (tag 42), parsing the string form if that's all we have
+            match &self.0 {
+                Cid::Str(s) => match IpldCid::try_from(s.as_ref()) {
+                    Ok(cid) => cid.serialize(serializer),
+                    Err(_) => self.0.serialize(serializer),
+                },
+                Cid::Ipld { .. } => self.0.serialize(serializer),
+            }
         }
     }
 }
@@ -542,4 +548,17 @@ mod tests {
         assert_eq!(&*link, TEST_CID);
         assert_eq!(link.as_ref(), TEST_CID);
     }
+
+    #[test]
+    fn cidlink_str_cbor_matches_ipld() {
+        // A link that only holds the string form must still encode as a tag-42 CID
+        let from_str = serde_ipld_dagcbor::to_vec(&CidLink::str(TEST_CID)).unwrap();
+        let from_ipld =
+            serde_ipld_dagcbor::to_vec(&CidLink::ipld(IpldCid::try_from(TEST_CID).unwrap()))
+                .unwrap();
+        assert_eq!(from_str, from_ipld);
+
+        let parsed: CidLink = serde_ipld_dagcbor::from_slice(&from_str).unwrap();
+        assert_eq!(parsed.as_str(), TEST_CID);
+    }
 }
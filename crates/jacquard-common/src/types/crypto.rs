@@ -133,6 +133,32 @@ impl<'a> PublicKey<'a> {
 
     // decode_owned provided on PublicKey<'static>
 
+    /// JOSE algorithm identifier for signatures made with this key
+    ///
+    /// `ES256K` for secp256k1, `ES256` for P-256 and `EdDSA` for Ed25519.
+    /// Returns `None` for unknown codecs.
+    pub fn algorithm(&self) -> Option<&'static str> {
+        match self.codec {
+            KeyCodec::Ed25519 => Some("EdDSA"),
+            KeyCodec::Secp256k1 => Some("ES256K"),
+            KeyCodec::P256 => Some("ES256"),
+            KeyCodec::Unknown(_) => None,
+        }
+    }
+
+    /// Whether this build can verify signatures made with this key
+    ///
+    /// Depends on which `crypto-*` features are enabled, so callers can skip keys
+    /// they can't use instead of failing at verification time.
+    pub fn is_supported_for_verification(&self) -> bool {
+        match self.codec {
+            KeyCodec::Ed25519 => cfg!(feature = "crypto-ed25519"),
+            KeyCodec::Secp256k1 => cfg!(feature = "crypto-k256"),
+            KeyCodec::P256 => cfg!(feature = "crypto-p256"),
+            KeyCodec::Unknown(_) => false,
+        }
+    }
+
     /// Convert to ed25519_dalek verifying key (feature crypto-ed25519)
     #[cfg(feature = "crypto-ed25519")]
     pub fn to_ed25519(&self) -> Result<ed25519_dalek::VerifyingKey, CryptoError> {
@@ -270,6 +296,32 @@ mod tests {
         assert_eq!(pk.bytes.as_ref(), &key);
     }
 
+    #[test]
+    fn algorithm_and_verification_support() {
+        let mut key = [0u8; 33];
+        key[0] = 0x02;
+        let k256 = PublicKey::decode(&multikey(0xE7, &key)).expect("decode");
+        assert_eq!(k256.algorithm(), Some("ES256K"));
+        assert_eq!(
+            k256.is_supported_for_verification(),
+            cfg!(feature = "crypto-k256")
+        );
+
+        let p256 = PublicKey::decode(&multikey(0x1200, &key)).expect("decode");
+        assert_eq!(p256.algorithm(), Some("ES256"));
+        assert_eq!(
+            p256.is_supported_for_verification(),
+            cfg!(feature = "crypto-p256")
+        );
+
+        let unknown = PublicKey {
+            codec: KeyCodec::Unknown(0x1201),
+            bytes: Cow::Borrowed(&key[..]),
+        };
+        assert_eq!(unknown.algorithm(), None);
+        assert!(!unknown.is_supported_for_verification());
+    }
+
     #[cfg(feature = "crypto-ed25519")]
     #[test]
     fn ed25519_conversion_ok() {
@@ -23,7 +23,7 @@ use url::Url;
 ///   "id": "did:plc:alice",
 ///   "alsoKnownAs": ["at://alice.example"],
 ///   "service": [{
-///     "id": "#pds",
+///     "id": "#atproto_pds",
 ///     "type": "AtprotoPersonalDataServer",
 ///     "serviceEndpoint": "https://pds.example"
 ///   }],
@@ -79,6 +79,10 @@ impl crate::IntoStatic for DidDocument<'_> {
 
 impl<'a> DidDocument<'a> {
     /// Extract validated handles from `alsoKnownAs` entries like `at://\<handle\>`.
+    ///
+    /// Returns every handle the DID claims, in document order, so the first is the
+    /// primary one. Entries that aren't `at://` handles (other URI schemes, or `at://`
+    /// URIs with a DID or a path) are skipped.
     pub fn handles(&self) -> Vec<Handle<'static>> {
         self.also_known_as
             .as_ref()
@@ -92,6 +96,11 @@ impl<'a> DidDocument<'a> {
             .unwrap_or_default()
     }
 
+    /// The primary handle: the first valid handle in `alsoKnownAs`, if any.
+    pub fn primary_handle(&self) -> Option<Handle<'static>> {
+        self.handles().into_iter().next()
+    }
+
     /// Extract the first Multikey `publicKeyMultibase` value from verification methods.
     pub fn atproto_multikey(&self) -> Option<CowStr<'static>> {
         self.verification_method.as_ref().and_then(|methods| {
@@ -107,28 +116,29 @@ impl<'a> DidDocument<'a> {
         })
     }
 
-    /// Extract the AtprotoPersonalDataServer service endpoint as a `Url`.
-    /// Accepts endpoint as string or object (string preferred).
+    /// Extract the PDS endpoint as a `Url`.
+    ///
+    /// This is the `#atproto_pds` service, which must have type `AtprotoPersonalDataServer`.
     pub fn pds_endpoint(&self) -> Option<Url> {
-        self.service.as_ref().and_then(|services| {
-            services.iter().find_map(|s| {
-                if s.r#type.as_ref() == "AtprotoPersonalDataServer" {
-                    match &s.service_endpoint {
-                        Some(Data::String(strv)) => Url::parse(strv.as_ref()).ok(),
-                        Some(Data::Object(obj)) => {
-                            // Some documents may include structured endpoints; try common fields
-                            if let Some(Data::String(urlv)) = obj.0.get("url") {
-                                Url::parse(urlv.as_ref()).ok()
-                            } else {
-                                None
-                            }
-                        }
-                        _ => None,
-                    }
-                } else {
-                    None
-                }
-            })
+        self.service_by_id("atproto_pds")
+            .filter(|s| s.r#type.as_ref() == "AtprotoPersonalDataServer")
+            .and_then(Service::endpoint)
+            .and_then(|endpoint| Url::parse(endpoint).ok())
+    }
+
+    /// Endpoint of the service with the given id, such as `#atproto_labeler`.
+    ///
+    /// The leading `#` is optional. Services listed under the bare fragment or under the
+    /// full DID URL (`did:plc:...#atproto_labeler`) both match.
+    pub fn service_endpoint(&self, id: &str) -> Option<&str> {
+        self.service_by_id(id).and_then(Service::endpoint)
+    }
+
+    fn service_by_id(&self, id: &str) -> Option<&Service<'a>> {
+        let fragment = id.strip_prefix('#').unwrap_or(id);
+        self.service.as_deref()?.iter().find(|s| {
+            let local = s.id.strip_prefix(self.id.as_str()).unwrap_or(&s.id);
+            local.strip_prefix('#') == Some(fragment)
         })
     }
 
@@ -199,6 +209,22 @@ pub struct Service<'a> {
     pub extra_data: BTreeMap<SmolStr, Data<'a>>,
 }
 
+impl Service<'_> {
+    /// The endpoint URL as a string.
+    /// Accepts endpoint as string or object (string preferred).
+    pub fn endpoint(&self) -> Option<&str> {
+        match self.service_endpoint.as_ref()? {
+            Data::String(s) => Some(s.as_str()),
+            // Some documents may include structured endpoints; try common fields
+            Data::Object(obj) => match obj.0.get("url") {
+                Some(Data::String(url)) => Some(url.as_str()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
 impl crate::IntoStatic for Service<'_> {
     type Output = Service<'static>;
     fn into_static(self) -> Self::Output {
@@ -273,4 +299,82 @@ mod tests {
         // typed decode (may be ed25519, secp256k1, or p256 depending on multicodec)
         let _ = doc.atproto_public_key().expect("decode ok");
     }
+
+    #[test]
+    fn service_endpoints_by_fragment_or_full_id() {
+        let raw = json!({
+            "id": "did:plc:z72i7hdynmk6r22z27h6tvur",
+            "alsoKnownAs": ["at://bsky.app"],
+            "service": [
+                {
+                    "id": "#atproto_labeler",
+                    "type": "AtprotoLabeler",
+                    "serviceEndpoint": "https://mod.bsky.app"
+                },
+                {
+                    "id": "did:plc:z72i7hdynmk6r22z27h6tvur#atproto_pds",
+                    "type": "AtprotoPersonalDataServer",
+                    "serviceEndpoint": "https://puffball.us-east.host.bsky.network"
+                }
+            ]
+        })
+        .to_string();
+        let doc: DidDocument<'_> = serde_json::from_str(&raw).unwrap();
+        assert_eq!(
+            doc.pds_endpoint().unwrap().as_str(),
+            "https://puffball.us-east.host.bsky.network/"
+        );
+        assert_eq!(
+            doc.service_endpoint("#atproto_pds"),
+            Some("https://puffball.us-east.host.bsky.network")
+        );
+        assert_eq!(
+            doc.service_endpoint("atproto_labeler"),
+            Some("https://mod.bsky.app")
+        );
+        assert_eq!(doc.service_endpoint("#bsky_fg"), None);
+    }
+
+    #[test]
+    fn pds_endpoint_requires_matching_type() {
+        let raw = json!({
+            "id": "did:plc:example",
+            "service": [{
+                "id": "#atproto_pds",
+                "type": "SomethingElse",
+                "serviceEndpoint": "https://pds.example.com"
+            }]
+        })
+        .to_string();
+        let doc: DidDocument<'_> = serde_json::from_str(&raw).unwrap();
+        assert!(doc.pds_endpoint().is_none());
+        assert!(doc.service_endpoint("#atproto_pds").is_some());
+    }
+
+    #[test]
+    fn handles_keeps_order_and_skips_non_handles() {
+        let raw = json!({
+            "id": "did:plc:example",
+            "alsoKnownAs": [
+                "at://new.example.com",
+                "https://example.com/profile",
+                "at://did:plc:example",
+                "at://old.example.com/app.bsky.actor.profile/self",
+                "at://old.example.com",
+            ]
+        })
+        .to_string();
+        let doc: DidDocument<'_> = serde_json::from_str(&raw).unwrap();
+        let handles: Vec<_> = doc
+            .handles()
+            .iter()
+            .map(|h| h.as_str().to_owned())
+            .collect();
+        assert_eq!(handles, ["new.example.com", "old.example.com"]);
+        assert_eq!(doc.primary_handle().unwrap().as_str(), "new.example.com");
+
+        let doc: DidDocument<'_> = serde_json::from_str(r#"{"id":"did:plc:example"}"#).unwrap();
+        assert!(doc.handles().is_empty());
+        assert!(doc.primary_handle().is_none());
+    }
 }
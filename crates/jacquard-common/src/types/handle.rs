@@ -1,5 +1,5 @@
+use crate::types::DISALLOWED_TLDS;
 use crate::types::string::AtStrError;
-use crate::types::{DISALLOWED_TLDS, ends_with};
 use crate::{CowStr, IntoStatic};
 use regex::Regex;
 use serde::{Deserialize, Deserializer, Serialize, de::Error};
@@ -24,6 +24,8 @@ use std::{ops::Deref, str::FromStr};
 ///
 /// Certain TLDs are disallowed (.local, .localhost, .arpa, .invalid, .internal, .example, .alt, .onion).
 ///
+/// [`Handle::validate`] reports which of these rules a string breaks, as a [`HandleInvalid`].
+///
 /// See: <https://atproto.com/specs/handle>
 #[derive(Clone, PartialEq, Eq, Serialize, Hash)]
 #[serde(transparent)]
@@ -34,7 +36,153 @@ pub struct Handle<'h>(pub(crate) CowStr<'h>);
 pub static HANDLE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"^([a-zA-Z0-9]([a-zA-Z0-9-]{0,61}[a-zA-Z0-9])?\.)+[a-zA-Z]([a-zA-Z0-9-]{0,61}[a-zA-Z0-9])?$").unwrap()
 });
+/// Maximum length of a handle, in characters
+const MAX_HANDLE_LEN: usize = 253;
+
+/// Maximum length of a single handle label, in characters
+const MAX_LABEL_LEN: usize = 63;
+
+/// Why a string isn't a valid handle
+///
+/// Returned by [`Handle::validate`]. Each variant is a distinct rule from the
+/// [handle spec](https://atproto.com/specs/handle), so the reason can be shown to
+/// whoever typed the handle.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error, miette::Diagnostic)]
+pub enum HandleInvalid {
+    /// Longer than 253 characters
+    #[error("handle is {len} characters long, over the limit of 253")]
+    #[diagnostic(code(jacquard::types::handle::too_long))]
+    TooLong {
+        /// Length of the handle
+        len: usize,
+    },
+    /// Fewer than two labels, e.g. `alice`
+    #[error("handle needs at least two labels separated by a dot, like `alice.example.com`")]
+    #[diagnostic(code(jacquard::types::handle::too_few_labels))]
+    TooFewLabels,
+    /// Two dots in a row, or a leading or trailing dot
+    #[error("handle has an empty label at position {position}")]
+    #[diagnostic(code(jacquard::types::handle::empty_label))]
+    EmptyLabel {
+        /// Byte offset where the empty label sits
+        position: usize,
+    },
+    /// A character other than an ASCII letter, digit or hyphen
+    #[error("`{character}` at position {position} isn't allowed in a handle")]
+    #[diagnostic(
+        code(jacquard::types::handle::disallowed_character),
+        help("handles may only contain ASCII letters, digits, hyphens and dots")
+    )]
+    DisallowedCharacter {
+        /// The offending character
+        character: char,
+        /// Byte offset of the character
+        position: usize,
+    },
+    /// A label starts or ends with a hyphen
+    #[error("label `{label}` starts or ends with a hyphen")]
+    #[diagnostic(code(jacquard::types::handle::hyphen_at_edge))]
+    HyphenAtEdge {
+        /// The offending label
+        label: SmolStr,
+    },
+    /// A label is longer than 63 characters
+    #[error("label `{label}` is {len} characters long, over the limit of 63")]
+    #[diagnostic(code(jacquard::types::handle::label_too_long))]
+    LabelTooLong {
+        /// The offending label
+        label: SmolStr,
+        /// Length of the label
+        len: usize,
+    },
+    /// The last label starts with a digit
+    #[error("top-level domain `{tld}` starts with a digit")]
+    #[diagnostic(code(jacquard::types::handle::numeric_tld))]
+    TldStartsWithDigit {
+        /// The top-level domain
+        tld: SmolStr,
+    },
+    /// The top-level domain is reserved, such as `.local` or `.arpa`
+    #[error("`.{tld}` is a reserved top-level domain and can't be used in a handle")]
+    #[diagnostic(code(jacquard::types::handle::reserved_tld))]
+    ReservedTld {
+        /// The top-level domain
+        tld: SmolStr,
+    },
+}
+
+impl HandleInvalid {
+    /// Convert to the general string error returned by the constructors
+    fn into_at_str_error(self, handle: &str) -> AtStrError {
+        match self {
+            HandleInvalid::TooLong { len } => {
+                AtStrError::too_long("handle", handle, MAX_HANDLE_LEN, len)
+            }
+            HandleInvalid::ReservedTld { .. } => {
+                AtStrError::disallowed("handle", handle, DISALLOWED_TLDS)
+            }
+            reason => AtStrError::regex("handle", handle, reason.to_smolstr()),
+        }
+    }
+}
+
 impl<'h> Handle<'h> {
+    /// Check `handle` against the handle syntax rules, saying which one it breaks
+    ///
+    /// Unlike the constructors, this doesn't strip an `@` or `at://` prefix, and doesn't
+    /// make an exception for `handle.invalid`.
+    pub fn validate(handle: &str) -> Result<(), HandleInvalid> {
+        if handle.len() > MAX_HANDLE_LEN {
+            return Err(HandleInvalid::TooLong { len: handle.len() });
+        }
+        if !handle.contains('.') {
+            return Err(HandleInvalid::TooFewLabels);
+        }
+
+        let mut position = 0;
+        let mut tld = "";
+        for label in handle.split('.') {
+            if label.is_empty() {
+                return Err(HandleInvalid::EmptyLabel { position });
+            }
+            if let Some((offset, character)) = label
+                .char_indices()
+                .find(|(_, c)| !c.is_ascii_alphanumeric() && *c != '-')
+            {
+                return Err(HandleInvalid::DisallowedCharacter {
+                    character,
+                    position: position + offset,
+                });
+            }
+            if label.starts_with('-') || label.ends_with('-') {
+                return Err(HandleInvalid::HyphenAtEdge {
+                    label: label.into(),
+                });
+            }
+            if label.len() > MAX_LABEL_LEN {
+                return Err(HandleInvalid::LabelTooLong {
+                    label: label.into(),
+                    len: label.len(),
+                });
+            }
+            position += label.len() + 1;
+            tld = label;
+        }
+
+        if tld.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(HandleInvalid::TldStartsWithDigit { tld: tld.into() });
+        }
+        if DISALLOWED_TLDS
+            .iter()
+            .any(|reserved| reserved[1..].eq_ignore_ascii_case(tld))
+        {
+            return Err(HandleInvalid::ReservedTld {
+                tld: tld.to_ascii_lowercase().into(),
+            });
+        }
+        Ok(())
+    }
+
     /// Fallible constructor, validates, borrows from input
     ///
     /// Accepts (and strips) preceding '@' or 'at://' if present
@@ -44,38 +192,21 @@ impl<'h> Handle<'h> {
             .or_else(|| handle.strip_prefix('@'))
             .unwrap_or(handle);
 
-        if stripped.len() > 253 {
-            Err(AtStrError::too_long(
-                "handle",
-                stripped,
-                253,
-                stripped.len(),
-            ))
-        } else if !HANDLE_REGEX.is_match(stripped) {
-            Err(AtStrError::regex(
-                "handle",
-                stripped,
-                SmolStr::new_static("invalid"),
-            ))
-        } else if ends_with(stripped, DISALLOWED_TLDS) {
+        match Self::validate(stripped) {
+            Ok(()) => Ok(Self(CowStr::Borrowed(stripped))),
             // speicifically pass this through as it is returned in instances where someone
             // has screwed up their handle, and it's awkward to fail so early
-            if handle == "handle.invalid" {
+            Err(HandleInvalid::ReservedTld { .. }) if handle == "handle.invalid" => {
                 Ok(Self(CowStr::Borrowed(stripped)))
-            } else {
-                Err(AtStrError::disallowed("handle", stripped, DISALLOWED_TLDS))
             }
-        } else {
-            Ok(Self(CowStr::Borrowed(stripped)))
+            Err(e) => Err(e.into_at_str_error(stripped)),
         }
     }
 
     /// confirm that this is a (syntactically) valid handle (as we pass-through
     /// "handle.invalid" during construction)
     pub fn is_valid(&self) -> bool {
-        self.0.len() <= 253
-            && HANDLE_REGEX.is_match(&self.0)
-            && !ends_with(&self.0, DISALLOWED_TLDS)
+        Self::validate(&self.0).is_ok()
     }
 
     /// Fallible constructor, validates, takes ownership
@@ -86,19 +217,8 @@ impl<'h> Handle<'h> {
             .or_else(|| handle.strip_prefix('@'))
             .unwrap_or(handle);
         let handle = stripped;
-        if handle.len() > 253 {
-            Err(AtStrError::too_long("handle", handle, 253, handle.len()))
-        } else if !HANDLE_REGEX.is_match(handle) {
-            Err(AtStrError::regex(
-                "handle",
-                handle,
-                SmolStr::new_static("invalid"),
-            ))
-        } else if ends_with(handle, DISALLOWED_TLDS) {
-            Err(AtStrError::disallowed("handle", handle, DISALLOWED_TLDS))
-        } else {
-            Ok(Self(CowStr::Owned(handle.to_smolstr())))
-        }
+        Self::validate(handle).map_err(|e| e.into_at_str_error(handle))?;
+        Ok(Self(CowStr::Owned(handle.to_smolstr())))
     }
 
     /// Fallible constructor, validates, doesn't allocate
@@ -108,19 +228,8 @@ impl<'h> Handle<'h> {
             .or_else(|| handle.strip_prefix('@'))
             .unwrap_or(handle);
         let handle = stripped;
-        if handle.len() > 253 {
-            Err(AtStrError::too_long("handle", handle, 253, handle.len()))
-        } else if !HANDLE_REGEX.is_match(handle) {
-            Err(AtStrError::regex(
-                "handle",
-                handle,
-                SmolStr::new_static("invalid"),
-            ))
-        } else if ends_with(handle, DISALLOWED_TLDS) {
-            Err(AtStrError::disallowed("handle", handle, DISALLOWED_TLDS))
-        } else {
-            Ok(Self(CowStr::new_static(handle)))
-        }
+        Self::validate(handle).map_err(|e| e.into_at_str_error(handle))?;
+        Ok(Self(CowStr::new_static(handle)))
     }
 
     /// Fallible constructor, validates, borrows from input if possible
@@ -135,19 +244,8 @@ impl<'h> Handle<'h> {
         } else {
             handle
         };
-        if handle.len() > 253 {
-            Err(AtStrError::too_long("handle", &handle, 253, handle.len()))
-        } else if !HANDLE_REGEX.is_match(&handle) {
-            Err(AtStrError::regex(
-                "handle",
-                &handle,
-                SmolStr::new_static("invalid"),
-            ))
-        } else if ends_with(&handle, DISALLOWED_TLDS) {
-            Err(AtStrError::disallowed("handle", &handle, DISALLOWED_TLDS))
-        } else {
-            Ok(Self(handle))
-        }
+        Self::validate(&handle).map_err(|e| e.into_at_str_error(&handle))?;
+        Ok(Self(handle))
     }
 
     /// Infallible constructor for when you *know* the string is a valid handle.
@@ -162,15 +260,10 @@ impl<'h> Handle<'h> {
             .or_else(|| handle.strip_prefix('@'))
             .unwrap_or(handle);
         let handle = stripped;
-        if handle.len() > 253 {
-            panic!("handle too long")
-        } else if !HANDLE_REGEX.is_match(handle) {
-            panic!("Invalid handle")
-        } else if ends_with(handle, DISALLOWED_TLDS) {
-            panic!("top-level domain not allowed in handles")
-        } else {
-            Self(CowStr::Borrowed(handle))
+        if let Err(e) = Self::validate(handle) {
+            panic!("Invalid handle: {}", e)
         }
+        Self(CowStr::Borrowed(handle))
     }
 
     /// Infallible constructor for when you *know* the string is a valid handle.
@@ -371,4 +464,101 @@ mod tests {
         assert!(Handle::new(".foo.com").is_err());
         assert!(Handle::new("foo.com.").is_err());
     }
+
+    #[test]
+    fn validate_accepts_valid_handles() {
+        for handle in [
+            "alice.bsky.social",
+            "a.b",
+            "XN--LDK.example.com",
+            "8.cn",
+            "name-with-dash.com",
+            "handle.test",
+        ] {
+            assert_eq!(Handle::validate(handle), Ok(()), "{handle}");
+        }
+    }
+
+    #[test]
+    fn validate_reports_each_reason() {
+        let too_long = format!("{}comx", "a.".repeat(125));
+        assert_eq!(
+            Handle::validate(&too_long),
+            Err(HandleInvalid::TooLong { len: 254 })
+        );
+        assert_eq!(Handle::validate("alice"), Err(HandleInvalid::TooFewLabels));
+        assert_eq!(
+            Handle::validate("alice..com"),
+            Err(HandleInvalid::EmptyLabel { position: 6 })
+        );
+        assert_eq!(
+            Handle::validate(".alice.com"),
+            Err(HandleInvalid::EmptyLabel { position: 0 })
+        );
+        assert_eq!(
+            Handle::validate("al_ice.com"),
+            Err(HandleInvalid::DisallowedCharacter {
+                character: '_',
+                position: 2
+            })
+        );
+        assert_eq!(
+            Handle::validate("alice.bsky.sociäl"),
+            Err(HandleInvalid::DisallowedCharacter {
+                character: 'ä',
+                position: 15
+            })
+        );
+        assert_eq!(
+            Handle::validate("alice.-bsky.social"),
+            Err(HandleInvalid::HyphenAtEdge {
+                label: "-bsky".into()
+            })
+        );
+        assert_eq!(
+            Handle::validate("alice-.com"),
+            Err(HandleInvalid::HyphenAtEdge {
+                label: "alice-".into()
+            })
+        );
+        let long_label = "a".repeat(64);
+        assert_eq!(
+            Handle::validate(&format!("{long_label}.com")),
+            Err(HandleInvalid::LabelTooLong {
+                label: long_label.into(),
+                len: 64
+            })
+        );
+        assert_eq!(
+            Handle::validate("alice.9com"),
+            Err(HandleInvalid::TldStartsWithDigit { tld: "9com".into() })
+        );
+        assert_eq!(
+            Handle::validate("alice.local"),
+            Err(HandleInvalid::ReservedTld {
+                tld: "local".into()
+            })
+        );
+        assert_eq!(
+            Handle::validate("printer.ARPA"),
+            Err(HandleInvalid::ReservedTld { tld: "arpa".into() })
+        );
+    }
+
+    #[test]
+    fn constructors_carry_validate_reason() {
+        use crate::types::string::StrParseKind;
+
+        let err = Handle::new("alice..com").unwrap_err();
+        assert!(matches!(
+            err.kind,
+            StrParseKind::RegexFail { ref message, .. } if message.contains("empty label")
+        ));
+        assert!(matches!(
+            Handle::new("alice.local").unwrap_err().kind,
+            StrParseKind::Disallowed { .. }
+        ));
+        assert!(Handle::new("handle.invalid").is_ok());
+        assert!(!Handle::new("handle.invalid").unwrap().is_valid());
+    }
 }
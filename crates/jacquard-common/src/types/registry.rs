@@ -0,0 +1,296 @@
+//! Opt-in dynamic dispatch from a record's `$type` to a registered Rust type.
+//!
+//! Jacquard deliberately has no closed `KnownRecords` enum. When you do want to turn an
+//! arbitrary [`Data`] value into "whatever generated type it is", build a [`TypeRegistry`]
+//! with the types you care about and let it dispatch on the `$type` field.
+//!
+//! ```
+//! # use jacquard_common::types::registry::TypeRegistry;
+//! # use jacquard_common::types::value::Data;
+//! # use serde::Deserialize;
+//! #[derive(Debug, Deserialize, PartialEq)]
+//! struct Like {
+//!     subject: String,
+//! }
+//!
+//! let mut registry = TypeRegistry::new();
+//! registry.register_as::<Like>("com.example.like");
+//!
+//! let json = serde_json::json!({"$type": "com.example.like", "subject": "at://did:plc:abc/x/y"});
+//! let data = Data::from_json(&json).unwrap();
+//! let like = registry.parse(&data).unwrap();
+//! assert_eq!(
+//!     like.downcast_ref::<Like>().unwrap().subject,
+//!     "at://did:plc:abc/x/y"
+//! );
+//! ```
+//!
+//! With the `inventory` feature, types can register themselves at link time via
+//! [`register_type!`](crate::register_type) and be collected with
+//! [`TypeRegistry::from_inventory`].
+
+use std::any::Any;
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use smol_str::SmolStr;
+
+use crate::types::collection::Collection;
+use crate::types::value::{Data, DataDeserializerError, from_data_owned};
+
+/// A type-erased parsed value returned by [`TypeRegistry::parse`]
+pub type AnyRecord = Box<dyn Any + Send + Sync>;
+
+/// Parser function stored in a [`TypeRegistry`]
+pub type ParseFn = fn(&Data<'_>) -> Result<AnyRecord, DataDeserializerError>;
+
+/// Errors from [`TypeRegistry::parse`]
+#[derive(Debug, Clone, thiserror::Error, miette::Diagnostic)]
+pub enum RegistryError {
+    /// The value is not an object with a string `$type` field
+    #[error("value has no $type field")]
+    MissingType,
+    /// No parser is registered for the `$type`
+    #[error("no type registered for {0}")]
+    UnknownType(SmolStr),
+    /// The registered parser failed to deserialize the value
+    #[error("failed to deserialize {nsid}: {source}")]
+    Deserialize {
+        /// The `$type` that was dispatched on
+        nsid: SmolStr,
+        /// Underlying deserializer error
+        #[source]
+        source: DataDeserializerError,
+    },
+}
+
+/// Registry mapping `$type` NSIDs to parsers
+///
+/// Cheap to clone; parsers are plain function pointers.
+#[derive(Debug, Clone, Default)]
+pub struct TypeRegistry {
+    parsers: HashMap<SmolStr, ParseFn>,
+}
+
+impl TypeRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry containing every type submitted with [`register_type!`](crate::register_type)
+    #[cfg(feature = "inventory")]
+    pub fn from_inventory() -> Self {
+        let mut registry = Self::new();
+        for entry in inventory::iter::<RegisteredType> {
+            registry.register_with(entry.nsid, entry.parse);
+        }
+        registry
+    }
+
+    /// Register a record type under its [`Collection::NSID`]
+    pub fn register<T>(&mut self) -> &mut Self
+    where
+        T: Collection + Deserialize<'static> + Send + Sync + 'static,
+    {
+        self.register_as::<T>(T::NSID)
+    }
+
+    /// Register a type under an explicit `$type` value
+    ///
+    /// Useful for non-record object types (e.g. `app.bsky.embed.images`) or for
+    /// overriding a previously registered parser.
+    pub fn register_as<T>(&mut self, nsid: impl Into<SmolStr>) -> &mut Self
+    where
+        T: Deserialize<'static> + Send + Sync + 'static,
+    {
+        self.register_with(nsid, parse_erased::<T>)
+    }
+
+    /// Register a custom parser function under an explicit `$type` value
+    pub fn register_with(&mut self, nsid: impl Into<SmolStr>, parse: ParseFn) -> &mut Self {
+        self.parsers.insert(nsid.into(), parse);
+        self
+    }
+
+    /// Whether a parser is registered for the given `$type`
+    pub fn contains(&self, nsid: &str) -> bool {
+        self.parsers.contains_key(nsid)
+    }
+
+    /// Number of registered types
+    pub fn len(&self) -> usize {
+        self.parsers.len()
+    }
+
+    /// Whether the registry is empty
+    pub fn is_empty(&self) -> bool {
+        self.parsers.is_empty()
+    }
+
+    /// Iterate over the registered `$type` values
+    pub fn nsids(&self) -> impl Iterator<Item = &str> {
+        self.parsers.keys().map(SmolStr::as_str)
+    }
+
+    /// Parse a value into the type registered for its `$type` field
+    ///
+    /// Downcast the result with [`Box::downcast`] or [`Any::downcast_ref`].
+    pub fn parse(&self, data: &Data<'_>) -> Result<AnyRecord, RegistryError> {
+        let nsid = type_of(data).ok_or(RegistryError::MissingType)?;
+        let parse = self
+            .parsers
+            .get(nsid)
+            .ok_or_else(|| RegistryError::UnknownType(SmolStr::new(nsid)))?;
+        parse(data).map_err(|source| RegistryError::Deserialize {
+            nsid: SmolStr::new(nsid),
+            source,
+        })
+    }
+
+    /// Parse a value and downcast it to `T`
+    ///
+    /// Returns `Ok(None)` if the value parsed successfully but its registered type is not `T`.
+    pub fn parse_as<T: Any>(&self, data: &Data<'_>) -> Result<Option<T>, RegistryError> {
+        Ok(self.parse(data)?.downcast::<T>().ok().map(|b| *b))
+    }
+}
+
+/// Read the `$type` field of an object value
+fn type_of<'a>(data: &'a Data<'_>) -> Option<&'a str> {
+    match data {
+        Data::Object(obj) => match obj.0.get("$type")? {
+            Data::String(s) => Some(s.as_str()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Type-erased parser for `T`, usable as a [`ParseFn`]
+pub fn parse_erased<T>(data: &Data<'_>) -> Result<AnyRecord, DataDeserializerError>
+where
+    T: Deserialize<'static> + Send + Sync + 'static,
+{
+    Ok(Box::new(from_data_owned::<T>(data.clone())?))
+}
+
+/// Link-time registration entry collected by [`TypeRegistry::from_inventory`]
+#[cfg(feature = "inventory")]
+#[derive(Debug)]
+pub struct RegisteredType {
+    /// The `$type` value to dispatch on
+    pub nsid: &'static str,
+    /// Parser for the type
+    pub parse: ParseFn,
+}
+
+#[cfg(feature = "inventory")]
+inventory::collect!(RegisteredType);
+
+/// Submit a type for inclusion in [`TypeRegistry::from_inventory`]
+///
+/// With one argument the type must implement [`Collection`] and is registered under
+/// its NSID; pass an explicit `$type` string as the second argument otherwise.
+///
+/// ```ignore
+/// jacquard_common::register_type!(Post<'static>);
+/// jacquard_common::register_type!(Images<'static>, "app.bsky.embed.images");
+/// ```
+#[cfg(feature = "inventory")]
+#[macro_export]
+macro_rules! register_type {
+    ($ty:ty) => {
+        $crate::register_type!($ty, <$ty as $crate::types::collection::Collection>::NSID);
+    };
+    ($ty:ty, $nsid:expr) => {
+        $crate::inventory::submit! {
+            $crate::types::registry::RegisteredType {
+                nsid: $nsid,
+                parse: $crate::types::registry::parse_erased::<$ty>,
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Like {
+        subject: String,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Follow {
+        subject: String,
+        #[serde(rename = "createdAt")]
+        created_at: String,
+    }
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::new();
+        registry
+            .register_as::<Like>("com.example.like")
+            .register_as::<Follow>("com.example.follow");
+        registry
+    }
+
+    #[test]
+    fn dispatches_on_type() {
+        let registry = registry();
+        assert_eq!(registry.len(), 2);
+        assert!(registry.contains("com.example.follow"));
+
+        let json = serde_json::json!({
+            "$type": "com.example.follow",
+            "subject": "did:plc:abc",
+            "createdAt": "2024-01-01T00:00:00Z",
+        });
+        let data = Data::from_json(&json).unwrap();
+        let parsed = registry.parse(&data).unwrap();
+        let follow = parsed.downcast_ref::<Follow>().unwrap();
+        assert_eq!(follow.subject, "did:plc:abc");
+        assert!(parsed.downcast_ref::<Like>().is_none());
+
+        assert_eq!(registry.parse_as::<Like>(&data).unwrap(), None);
+        assert!(registry.parse_as::<Follow>(&data).unwrap().is_some());
+    }
+
+    #[test]
+    fn reports_missing_and_unknown_types() {
+        let registry = registry();
+
+        let json = serde_json::json!({"subject": "x"});
+        let data = Data::from_json(&json).unwrap();
+        assert!(matches!(
+            registry.parse(&data),
+            Err(RegistryError::MissingType)
+        ));
+
+        let json = serde_json::json!({"$type": "com.example.repost", "subject": "x"});
+        let data = Data::from_json(&json).unwrap();
+        assert!(matches!(
+            registry.parse(&data),
+            Err(RegistryError::UnknownType(nsid)) if nsid == "com.example.repost"
+        ));
+
+        let json = serde_json::json!({"$type": "com.example.follow", "subject": "x"});
+        let data = Data::from_json(&json).unwrap();
+        assert!(matches!(
+            registry.parse(&data),
+            Err(RegistryError::Deserialize { nsid, .. }) if nsid == "com.example.follow"
+        ));
+    }
+
+    #[cfg(feature = "inventory")]
+    crate::register_type!(Like, "com.example.inventory.like");
+
+    #[cfg(feature = "inventory")]
+    #[test]
+    fn collects_inventory_registrations() {
+        let registry = TypeRegistry::from_inventory();
+        assert!(registry.contains("com.example.inventory.like"));
+    }
+}
@@ -7,11 +7,11 @@ use crate::IntoStatic;
 pub use crate::{
     CowStr,
     types::{
-        aturi::AtUri,
+        aturi::{AtUri, AtUriBuilder},
         cid::{Cid, CidLink},
         datetime::Datetime,
         did::Did,
-        handle::Handle,
+        handle::{Handle, HandleInvalid},
         ident::AtIdentifier,
         language::Language,
         nsid::Nsid,
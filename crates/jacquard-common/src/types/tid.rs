@@ -2,6 +2,7 @@ use serde::{Deserialize, Deserializer, Serialize, de::Error};
 use smol_str::{SmolStr, SmolStrBuilder};
 use std::fmt;
 use std::sync::LazyLock;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::SystemTime;
 use std::{ops::Deref, str::FromStr};
 
@@ -47,8 +48,12 @@ static TID_REGEX: LazyLock<Regex> = LazyLock::new(|| {
 /// TIDs are sortable by timestamp and suitable for use in URLs. Generate new TIDs with
 /// `Tid::now()` or `Tid::now_with_clock_id()`.
 ///
+/// The [`Ord`] impl compares the string forms byte-wise. Because the encoding is
+/// fixed-width and its alphabet is in ascending ASCII order, this is the same as
+/// comparing the underlying integers: TIDs sort by timestamp first, then by clock ID.
+///
 /// See: <https://atproto.com/specs/tid>
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Hash)]
 #[serde(transparent)]
 #[repr(transparent)]
 pub struct Tid(SmolStr);
@@ -127,10 +132,24 @@ impl Tid {
     }
 
     /// Extract the timestamp component (microseconds since UNIX epoch)
+    ///
+    /// See [`Tid::datetime`] for the same value as a wall-clock time.
     pub fn timestamp(&self) -> u64 {
         s32decode(self.0[0..11].to_owned())
     }
 
+    /// Extract the timestamp component as a UTC wall-clock time
+    pub fn datetime(&self) -> chrono::DateTime<chrono::Utc> {
+        // 53 bits of microseconds is ~285 years past the epoch, well within chrono's range
+        chrono::DateTime::from_timestamp_micros(self.timestamp() as i64)
+            .expect("53-bit TID timestamp is within chrono's range")
+    }
+
+    /// Extract the 10-bit clock identifier
+    pub fn clock_id(&self) -> u16 {
+        (s32decode(self.0[11..13].to_owned()) & 0x3FF) as u16
+    }
+
     /// Compare two TIDs chronologically (newer > older)
     ///
     /// Returns 1 if self is newer, -1 if older, 0 if equal
@@ -337,6 +356,67 @@ impl Default for Ticker {
     }
 }
 
+/// Shared TID generator for a single clock ID
+///
+/// Each call to [`TidClock::next`] returns a TID strictly newer than every TID the clock
+/// has issued before, so TIDs from one clock never collide and sort in issue order. When
+/// called faster than the microsecond clock ticks (or if the system clock steps backwards),
+/// the timestamp is bumped past the last one issued instead.
+///
+/// `next` takes `&self` and is safe to call from many threads, so one clock can be shared
+/// (e.g. in an `Arc` or a `static`) by everything writing records for a repo.
+///
+/// ```
+/// use jacquard_common::types::tid::TidClock;
+///
+/// let clock = TidClock::new(7);
+/// let first = clock.next();
+/// let second = clock.next();
+/// assert!(second.newer_than(&first));
+/// assert_eq!(second.clock_id(), 7);
+/// ```
+#[derive(Debug)]
+pub struct TidClock {
+    last_timestamp: AtomicU64,
+    clock_id: u16,
+}
+
+impl TidClock {
+    /// Create a generator stamping TIDs with `clock_id`
+    ///
+    /// Only the low 10 bits of `clock_id` are used.
+    pub fn new(clock_id: u16) -> Self {
+        Self {
+            last_timestamp: AtomicU64::new(0),
+            clock_id: clock_id & 0x03FF,
+        }
+    }
+
+    /// The 10-bit clock ID stamped on every TID from this clock
+    pub fn clock_id(&self) -> u16 {
+        self.clock_id
+    }
+
+    /// Generate the next TID
+    pub fn next(&self) -> Tid {
+        // mask to 53 bits
+        let now = chrono::Utc::now().timestamp_micros() as u64 & 0x001F_FFFF_FFFF_FFFF;
+        let mut last = self.last_timestamp.load(Ordering::Relaxed);
+        loop {
+            let timestamp = if now > last { now } else { last + 1 };
+            match self.last_timestamp.compare_exchange_weak(
+                last,
+                timestamp,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return Tid::from_time(timestamp, self.clock_id as u32),
+                Err(current) => last = current,
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -395,6 +475,52 @@ mod tests {
         assert!(tid1.older_than(&tid2));
     }
 
+    #[test]
+    fn timestamp_and_clock_id_round_trip() {
+        let time = chrono::DateTime::parse_from_rfc3339("2024-06-01T12:34:56.789012Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let tid = Tid::from_datetime(LimitedU32::from_str("517").unwrap(), time);
+
+        assert_eq!(tid.datetime(), time);
+        assert_eq!(tid.timestamp(), time.timestamp_micros() as u64);
+        assert_eq!(tid.clock_id(), 517);
+
+        let tid = Tid::from_time(1_700_000_000_000_000, 1023);
+        assert_eq!(tid.timestamp(), 1_700_000_000_000_000);
+        assert_eq!(tid.clock_id(), 1023);
+
+        let zero = Tid::new("2222222222222").unwrap();
+        assert_eq!(zero.datetime(), chrono::DateTime::UNIX_EPOCH);
+        assert_eq!(zero.clock_id(), 0);
+    }
+
+    #[test]
+    fn ordering_matches_time_order() {
+        let base = 1_700_000_000_000_000u64;
+        let mut tids = vec![
+            Tid::from_time(base + 1, 0),
+            Tid::from_time(base, 1023),
+            Tid::from_time(base, 0),
+            Tid::from_time(base + 1_000_000, 5),
+            Tid::from_time(base - 1, 1023),
+        ];
+        tids.sort();
+
+        let keys: Vec<_> = tids.iter().map(|t| (t.timestamp(), t.clock_id())).collect();
+        let mut expected = keys.clone();
+        expected.sort();
+        assert_eq!(keys, expected);
+
+        // Ord, string order and compare_to all agree
+        for a in &tids {
+            for b in &tids {
+                assert_eq!(a.cmp(b), a.as_str().cmp(b.as_str()));
+                assert_eq!(a.cmp(b) as i8, a.compare_to(b));
+            }
+        }
+    }
+
     #[test]
     fn ticker_monotonic() {
         let mut ticker = Ticker::new();
@@ -405,4 +531,35 @@ mod tests {
         assert!(tid2.newer_than(&tid1));
         assert!(tid3.newer_than(&tid2));
     }
+
+    #[test]
+    fn tid_clock_strictly_increasing() {
+        let clock = TidClock::new(42);
+        let mut prev = clock.next();
+        for _ in 0..100_000 {
+            let tid = clock.next();
+            assert!(tid > prev, "{} not after {}", tid, prev);
+            assert_eq!(tid.clock_id(), 42);
+            prev = tid;
+        }
+    }
+
+    #[test]
+    fn tid_clock_shared_across_threads() {
+        let clock = std::sync::Arc::new(TidClock::new(1));
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let clock = clock.clone();
+                std::thread::spawn(move || (0..10_000).map(|_| clock.next()).collect::<Vec<_>>())
+            })
+            .collect();
+
+        let mut all = std::collections::HashSet::new();
+        for handle in handles {
+            for tid in handle.join().unwrap() {
+                assert!(all.insert(tid), "duplicate TID");
+            }
+        }
+        assert_eq!(all.len(), 40_000);
+    }
 }
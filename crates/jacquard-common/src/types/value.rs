@@ -1,19 +1,29 @@
 use crate::{
     IntoStatic,
-    types::{DataModelType, LexiconStringType, UriType, blob::Blob, string::*},
+    types::{
+        DataModelType, LexiconStringType, UriType,
+        blob::{Blob, MimeType},
+        cid::CidLink,
+        string::*,
+    },
 };
 use bytes::Bytes;
 use ipld_core::ipld::Ipld;
 use smol_str::{SmolStr, ToSmolStr};
 use std::collections::BTreeMap;
 
+/// Canonical DAG-CBOR checks
+pub mod canonical;
 /// Conversion utilities for Data types
 pub mod convert;
+/// Insertion-ordered serialization of Data values
+pub mod ordered;
 /// String parsing for AT Protocol types
 pub mod parsing;
 /// Serde implementations for Data types
 pub mod serde_impl;
 
+pub use ordered::OrderedData;
 pub use serde_impl::{DataDeserializerError, RawDataSerializerError};
 
 #[cfg(test)]
@@ -54,6 +64,18 @@ pub enum AtDataError {
     /// Floating point numbers are not allowed in AT Protocol
     #[error("floating point numbers not allowed in AT protocol data")]
     FloatNotAllowed,
+    /// Value doesn't have the shape of a blob reference
+    #[error("invalid blob: {0}")]
+    InvalidBlob(&'static str),
+    /// Integer doesn't fit in the signed 64-bit range of the data model
+    #[error("integer {0} out of range for AT protocol data")]
+    IntegerOutOfRange(u64),
+    /// `{"$bytes": ...}` value that isn't base64
+    #[error("invalid $bytes: not a base64 string")]
+    InvalidBytes,
+    /// `{"$link": ...}` value that isn't a CID
+    #[error("invalid $link: {0:?} is not a CID")]
+    InvalidCidLink(SmolStr),
 }
 
 impl<'s> Data<'s> {
@@ -93,6 +115,99 @@ impl<'s> Data<'s> {
             Data::Blob(_) => DataModelType::Blob,
         }
     }
+
+    /// Get the blob, if this value was parsed as one
+    ///
+    /// Only matches [`Data::Blob`]; use [`to_blob`](Self::to_blob) to also accept blob-shaped
+    /// objects that type inference didn't recognize.
+    pub fn as_blob(&self) -> Option<&Blob<'s>> {
+        match self {
+            Data::Blob(blob) => Some(blob),
+            _ => None,
+        }
+    }
+
+    /// Reconstruct a blob from this value
+    ///
+    /// Accepts a [`Data::Blob`], or an object with a `ref` link, `mimeType` and `size`
+    /// whether or not it has `$type: "blob"`. The legacy `{cid, mimeType}` shape is also
+    /// accepted, with a size of 0.
+    pub fn to_blob(&self) -> Result<Blob<'s>, AtDataError> {
+        let map = match self {
+            Data::Blob(blob) => return Ok(blob.clone()),
+            Data::Object(Object(map)) => map,
+            _ => return Err(AtDataError::InvalidBlob("expected an object")),
+        };
+        match map.get("$type") {
+            None => {}
+            Some(Data::String(t)) if t.as_str() == "blob" => {}
+            Some(_) => return Err(AtDataError::InvalidBlob("`$type` is not \"blob\"")),
+        }
+
+        let mime_type = match map.get("mimeType") {
+            Some(Data::String(s)) => MimeType::new_owned(s.as_str()),
+            Some(_) => return Err(AtDataError::InvalidBlob("`mimeType` is not a string")),
+            None => return Err(AtDataError::InvalidBlob("missing `mimeType`")),
+        };
+
+        let Some(link) = map.get("ref") else {
+            // Legacy blobs: {"cid": "...", "mimeType": "..."}
+            return match map.get("cid") {
+                Some(Data::String(cid)) => Ok(Blob {
+                    r#ref: CidLink::cow_str(CowStr::copy_from_str(cid.as_str())),
+                    mime_type,
+                    size: 0,
+                }),
+                _ => Err(AtDataError::InvalidBlob("missing `ref`")),
+            };
+        };
+        let r#ref = match link {
+            Data::CidLink(cid) => CidLink(cid.clone()),
+            Data::Object(Object(link)) => match link.get("$link") {
+                Some(Data::String(cid)) => CidLink::cow_str(CowStr::copy_from_str(cid.as_str())),
+                _ => return Err(AtDataError::InvalidBlob("`ref` is not a CID link")),
+            },
+            _ => return Err(AtDataError::InvalidBlob("`ref` is not a CID link")),
+        };
+
+        let size = match map.get("size") {
+            Some(Data::Integer(size)) => usize::try_from(*size)
+                .map_err(|_| AtDataError::InvalidBlob("`size` is negative"))?,
+            Some(_) => return Err(AtDataError::InvalidBlob("`size` is not an integer")),
+            None => return Err(AtDataError::InvalidBlob("missing `size`")),
+        };
+
+        Ok(Blob {
+            r#ref,
+            mime_type,
+            size,
+        })
+    }
+
+    /// Look up a nested value by RFC 6901 JSON Pointer, e.g. `/embed/images/0/alt`
+    ///
+    /// The empty pointer refers to `self`. `~1` and `~0` in a segment unescape to `/` and
+    /// `~`. A segment indexes an array only if it is a plain decimal index within bounds;
+    /// on an object it is always a key, even when numeric. Returns `None` for a missing
+    /// segment or for a segment applied to anything other than an array or object
+    /// (including a parsed [`Data::Blob`]).
+    pub fn pointer(&self, path: &str) -> Option<&Data<'s>> {
+        pointer_segments(path)?.try_fold(self, |data, segment| match data {
+            Data::Object(Object(map)) => map.get(segment.as_str()),
+            Data::Array(Array(items)) => items.get(array_index(&segment)?),
+            _ => None,
+        })
+    }
+
+    /// Mutable version of [`pointer`](Self::pointer), for patching a value in place
+    pub fn pointer_mut(&mut self, path: &str) -> Option<&mut Data<'s>> {
+        pointer_segments(path)?.try_fold(self, |data, segment| match data {
+            Data::Object(Object(map)) => map.get_mut(segment.as_str()),
+            Data::Array(Array(items)) => items.get_mut(array_index(&segment)?),
+            _ => None,
+        })
+    }
+
     /// Parse a Data value from a JSON value
     pub fn from_json(json: &'s serde_json::Value) -> Result<Self, AtDataError> {
         Ok(if let Some(value) = json.as_bool() {
@@ -105,6 +220,8 @@ impl<'s> Data<'s> {
             Self::Array(Array::from_json(value)?)
         } else if let Some(value) = json.as_object() {
             Object::from_json(value)?
+        } else if let Some(value) = json.as_u64() {
+            return Err(AtDataError::IntegerOutOfRange(value));
         } else if json.is_f64() {
             return Err(AtDataError::FloatNotAllowed);
         } else {
@@ -112,11 +229,29 @@ impl<'s> Data<'s> {
         })
     }
 
+    /// Parse an owned Data value from a borrowed JSON value
+    ///
+    /// Inverse of `serde_json::Value::from(&data)`: `{"$link": ...}` and `{"$bytes": ...}`
+    /// objects come back as [`Data::CidLink`] and [`Data::Bytes`], so a value survives the
+    /// round trip unchanged.
+    pub fn from_json_value(json: &serde_json::Value) -> Result<Data<'static>, AtDataError> {
+        Data::from_json(json).map(|data| data.into_static())
+    }
+
     /// Parse a Data value from a JSON value (owned)
     pub fn from_json_owned(json: serde_json::Value) -> Result<Data<'static>, AtDataError> {
         Data::from_json(&json).map(|data| data.into_static())
     }
 
+    /// Parse a Data value directly from JSON bytes using `simd-json`
+    ///
+    /// The input is parsed in place and is left in an unspecified state afterwards.
+    /// Strings borrow from `json` where possible.
+    #[cfg(feature = "simd-json")]
+    pub fn from_json_mut(json: &'s mut [u8]) -> Result<Self, crate::error::DecodeError> {
+        Ok(simd_json::serde::from_slice(json)?)
+    }
+
     /// Parse a Data value from an IPLD value (CBOR)
     pub fn from_cbor(cbor: &'s Ipld) -> Result<Self, AtDataError> {
         Ok(match cbor {
@@ -133,6 +268,60 @@ impl<'s> Data<'s> {
             Ipld::Link(cid) => Self::CidLink(Cid::ipld(*cid)),
         })
     }
+
+    /// Encode as canonical DAG-CBOR
+    ///
+    /// Map keys are always written in DAG-CBOR's canonical order (length first, then
+    /// bytewise), so the output - and any CID computed from it - depends only on the
+    /// value. Use this rather than JSON when hashing or signing.
+    pub fn to_canonical_dag_cbor(
+        &self,
+    ) -> Result<Vec<u8>, serde_ipld_dagcbor::EncodeError<std::collections::TryReserveError>> {
+        serde_ipld_dagcbor::to_vec(self)
+    }
+
+    /// Alias for [`to_canonical_dag_cbor`](Self::to_canonical_dag_cbor)
+    ///
+    /// The encoder's output is canonical; [`is_canonical_cbor`](Self::is_canonical_cbor)
+    /// checks bytes that came from elsewhere.
+    pub fn to_canonical_cbor(
+        &self,
+    ) -> Result<Vec<u8>, serde_ipld_dagcbor::EncodeError<std::collections::TryReserveError>> {
+        self.to_canonical_dag_cbor()
+    }
+
+    /// Whether `bytes` holds exactly one value in canonical DAG-CBOR form
+    ///
+    /// Checks definite lengths, shortest-form integers and lengths, map keys that are
+    /// strings sorted by length then bytewise with no duplicates, CIDs as tag 42, and no
+    /// floats. Use this on blocks that arrived from elsewhere before trusting their CIDs
+    /// to match a re-encoding.
+    pub fn is_canonical_cbor(bytes: &[u8]) -> bool {
+        canonical::is_canonical(bytes)
+    }
+}
+
+/// Split a JSON Pointer into unescaped segments, or `None` if it doesn't start with `/`
+fn pointer_segments(path: &str) -> Option<impl Iterator<Item = String> + '_> {
+    let segments = match path {
+        "" => None,
+        _ => Some(path.strip_prefix('/')?.split('/')),
+    };
+    Some(
+        segments
+            .into_iter()
+            .flatten()
+            .map(|segment| segment.replace("~1", "/").replace("~0", "~")),
+    )
+}
+
+/// Parse a JSON Pointer array index: decimal digits with no leading zero
+fn array_index(segment: &str) -> Option<usize> {
+    let leading_zero = segment.len() > 1 && segment.starts_with('0');
+    if leading_zero || !segment.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    segment.parse().ok()
 }
 
 impl IntoStatic for Data<'_> {
@@ -200,6 +389,16 @@ impl<'s> Object<'s> {
     pub fn from_json(
         json: &'s serde_json::Map<String, serde_json::Value>,
     ) -> Result<Data<'s>, AtDataError> {
+        if json.len() == 1 {
+            if let Some(link) = json.get("$link").and_then(|v| v.as_str()) {
+                return parsing::cid_link(link);
+            }
+            if let Some(bytes) = json.get("$bytes").and_then(|v| v.as_str()) {
+                return parsing::try_decode_bytes(bytes)
+                    .map(Data::Bytes)
+                    .ok_or(AtDataError::InvalidBytes);
+            }
+        }
         if let Some(type_field) = json.get("$type").and_then(|v| v.as_str()) {
             if parsing::infer_from_type(type_field) == DataModelType::Blob {
                 if let Some(blob) = parsing::json_to_blob(json) {
@@ -232,7 +431,7 @@ impl<'s> Object<'s> {
                 DataModelType::CidLink => {
                     if let Some(value) = value.as_object() {
                         if let Some(value) = value.get("$link").and_then(|v| v.as_str()) {
-                            map.insert(key.to_smolstr(), Data::CidLink(Cid::Str(value.into())));
+                            map.insert(key.to_smolstr(), parsing::cid_link(value)?);
                         } else {
                             map.insert(key.to_smolstr(), Object::from_json(value)?);
                         }
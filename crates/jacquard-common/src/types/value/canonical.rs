@@ -0,0 +1,110 @@
+//! Canonical DAG-CBOR checks
+//!
+//! DAG-CBOR allows exactly one encoding of each value: definite lengths, integers and
+//! lengths in their shortest form, map keys sorted by length and then bytewise, CIDs as
+//! tag 42, and (for atproto) no floats. [`is_canonical`] checks a buffer against those
+//! rules without decoding it into a [`Data`](super::Data).
+
+/// CBOR tag for CIDs in DAG-CBOR
+const CID_TAG: u64 = 42;
+
+/// Nesting limit, so hostile input can't exhaust the stack
+const MAX_DEPTH: usize = 256;
+
+/// Whether `bytes` holds exactly one value in canonical DAG-CBOR form
+pub fn is_canonical(bytes: &[u8]) -> bool {
+    let mut reader = Reader { bytes, pos: 0 };
+    reader.item(0).is_some() && reader.pos == bytes.len()
+}
+
+struct Reader<'b> {
+    bytes: &'b [u8],
+    pos: usize,
+}
+
+impl<'b> Reader<'b> {
+    fn take(&mut self, len: usize) -> Option<&'b [u8]> {
+        let end = self.pos.checked_add(len)?;
+        let slice = self.bytes.get(self.pos..end)?;
+        self.pos = end;
+        Some(slice)
+    }
+
+    /// Read an item head, returning the major type and its argument
+    ///
+    /// Rejects indefinite lengths and arguments not in their shortest form.
+    fn head(&mut self) -> Option<(u8, u64)> {
+        let initial = *self.take(1)?.first()?;
+        let major = initial >> 5;
+        let info = initial & 0x1f;
+        let (arg, min) = match info {
+            0..=23 => return Some((major, info as u64)),
+            24 => (self.take(1)?[0] as u64, 24),
+            25 => (
+                u16::from_be_bytes(self.take(2)?.try_into().ok()?) as u64,
+                0x100,
+            ),
+            26 => (
+                u32::from_be_bytes(self.take(4)?.try_into().ok()?) as u64,
+                0x1_0000,
+            ),
+            27 => (
+                u64::from_be_bytes(self.take(8)?.try_into().ok()?),
+                0x1_0000_0000,
+            ),
+            _ => return None,
+        };
+        (arg >= min).then_some((major, arg))
+    }
+
+    fn item(&mut self, depth: usize) -> Option<()> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        match self.head()? {
+            // Integers
+            (0 | 1, _) => Some(()),
+            // Byte string
+            (2, len) => self.take(usize::try_from(len).ok()?).map(|_| ()),
+            // Text string
+            (3, len) => self.text_body(len).map(|_| ()),
+            (4, len) => (0..len).try_for_each(|_| self.item(depth + 1)),
+            (5, len) => {
+                let mut previous: Option<&[u8]> = None;
+                for _ in 0..len {
+                    let key = self.text()?;
+                    // Length first, then bytewise; equal keys are duplicates
+                    if previous.is_some_and(|prev| (prev.len(), prev) >= (key.len(), key)) {
+                        return None;
+                    }
+                    previous = Some(key);
+                    self.item(depth + 1)?;
+                }
+                Some(())
+            }
+            (6, CID_TAG) => {
+                let (major, len) = self.head()?;
+                let cid = self.take(usize::try_from(len).ok()?)?;
+                // Byte string with the multibase identity prefix
+                (major == 2 && cid.first() == Some(&0)).then_some(())
+            }
+            // false, true, null; no floats, undefined or other simple values
+            (7, 20..=22) => Some(()),
+            _ => None,
+        }
+    }
+
+    /// Read a whole text string item
+    fn text(&mut self) -> Option<&'b [u8]> {
+        match self.head()? {
+            (3, len) => self.text_body(len),
+            _ => None,
+        }
+    }
+
+    fn text_body(&mut self, len: u64) -> Option<&'b [u8]> {
+        let text = self.take(usize::try_from(len).ok()?)?;
+        std::str::from_utf8(text).ok()?;
+        Some(text)
+    }
+}
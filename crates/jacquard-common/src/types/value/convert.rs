@@ -123,6 +123,15 @@ macro_rules! derive_into_atproto {
     };
 }
 
+/// Encodes CIDs as `{"$link": ...}` and bytes as `{"$bytes": ...}`, the same as serializing
+/// to JSON text; [`Data::from_json_value`] reverses it.
+impl From<&Data<'_>> for serde_json::Value {
+    fn from(data: &Data<'_>) -> Self {
+        // Object keys are always strings, so this can't fail
+        serde_json::to_value(data).expect("Data always serializes to JSON")
+    }
+}
+
 impl From<String> for Data<'_> {
     fn from(t: String) -> Self {
         Data::String(AtprotoStr::new_owned(t))
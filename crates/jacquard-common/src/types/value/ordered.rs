@@ -0,0 +1,278 @@
+use crate::types::value::{Array, Data, Object};
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+use smol_str::SmolStr;
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// [`Data`] that remembers the order object keys were read in
+///
+/// [`Object`] is backed by a `BTreeMap`, so a plain `Data` always serializes keys in
+/// lexicographic order. `OrderedData` records the key order seen when deserializing and
+/// writes objects back out in that order, which keeps JSON diffs of hand-edited,
+/// config-like records readable.
+///
+/// The recorded order only affects serialization through `serde`. Signing, CIDs and repo
+/// storage must go through [`Data::to_canonical_dag_cbor`] (or
+/// [`to_canonical_dag_cbor`](Self::to_canonical_dag_cbor) here), which ignores it and emits
+/// DAG-CBOR's canonical key order, so a record hashes the same no matter how its JSON
+/// source was laid out.
+///
+/// Keys added through [`data_mut`](Self::data_mut) that weren't in the original input are
+/// written after the known keys, in lexicographic order; removed keys are skipped.
+///
+/// Deserialization is only supported from human-readable formats. DAG-CBOR maps are
+/// canonically ordered already, so decode those into [`Data`].
+///
+/// ```
+/// # use jacquard_common::OrderedData;
+/// let json = r#"{"name":"feed","enabled":true,"avatar":null}"#;
+/// let data: OrderedData = serde_json::from_str(json).unwrap();
+/// assert_eq!(serde_json::to_string(&data).unwrap(), json);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderedData<'s> {
+    data: Data<'s>,
+    order: KeyOrder,
+}
+
+/// Key order of an object, and of any objects nested inside it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct KeyOrder {
+    keys: Vec<(SmolStr, KeyOrder)>,
+    items: Vec<KeyOrder>,
+}
+
+static UNORDERED: KeyOrder = KeyOrder {
+    keys: Vec::new(),
+    items: Vec::new(),
+};
+
+impl KeyOrder {
+    fn item(&self, index: usize) -> &KeyOrder {
+        self.items.get(index).unwrap_or(&UNORDERED)
+    }
+}
+
+impl<'s> OrderedData<'s> {
+    /// The underlying data
+    pub fn data(&self) -> &Data<'s> {
+        &self.data
+    }
+
+    /// Mutable access to the underlying data
+    ///
+    /// The recorded key order is kept; see the type docs for how new keys are placed.
+    pub fn data_mut(&mut self) -> &mut Data<'s> {
+        &mut self.data
+    }
+
+    /// Discard the recorded key order
+    pub fn into_data(self) -> Data<'s> {
+        self.data
+    }
+
+    /// Encode as canonical DAG-CBOR, ignoring the recorded key order
+    pub fn to_canonical_dag_cbor(
+        &self,
+    ) -> Result<Vec<u8>, serde_ipld_dagcbor::EncodeError<std::collections::TryReserveError>> {
+        self.data.to_canonical_dag_cbor()
+    }
+}
+
+impl<'s> From<Data<'s>> for OrderedData<'s> {
+    /// Wrap data with no recorded order; objects serialize in lexicographic key order
+    fn from(data: Data<'s>) -> Self {
+        Self {
+            data,
+            order: KeyOrder::default(),
+        }
+    }
+}
+
+impl<'s> From<OrderedData<'s>> for Data<'s> {
+    fn from(data: OrderedData<'s>) -> Self {
+        data.data
+    }
+}
+
+impl Serialize for OrderedData<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        Ordered(&self.data, &self.order).serialize(serializer)
+    }
+}
+
+/// Borrowed value paired with its key order, for serialization
+struct Ordered<'a, 's>(&'a Data<'s>, &'a KeyOrder);
+
+impl Serialize for Ordered<'_, '_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let Ordered(data, order) = *self;
+        match data {
+            Data::Object(Object(map)) => {
+                let known: BTreeSet<&str> = order.keys.iter().map(|(k, _)| k.as_str()).collect();
+                let mut ser = serializer.serialize_map(Some(map.len()))?;
+                for (key, child) in &order.keys {
+                    if let Some(value) = map.get(key) {
+                        ser.serialize_entry(key, &Ordered(value, child))?;
+                    }
+                }
+                for (key, value) in map {
+                    if !known.contains(key.as_str()) {
+                        ser.serialize_entry(key, &Ordered(value, &UNORDERED))?;
+                    }
+                }
+                ser.end()
+            }
+            Data::Array(Array(items)) => {
+                let mut ser = serializer.serialize_seq(Some(items.len()))?;
+                for (i, item) in items.iter().enumerate() {
+                    ser.serialize_element(&Ordered(item, order.item(i)))?;
+                }
+                ser.end()
+            }
+            other => other.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for OrderedData<'_> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if !deserializer.is_human_readable() {
+            return Err(de::Error::custom(
+                "OrderedData can only be deserialized from human-readable formats",
+            ));
+        }
+        let (value, order) = Node::deserialize(deserializer)?.split();
+        let data = Data::deserialize(value).map_err(de::Error::custom)?;
+        Ok(Self { data, order })
+    }
+}
+
+/// JSON-like tree that keeps object entries in input order
+enum Node {
+    Leaf(serde_json::Value),
+    Array(Vec<Node>),
+    Object(Vec<(SmolStr, Node)>),
+}
+
+impl Node {
+    /// Separate into a plain JSON value (for [`Data`]'s own type inference) and the key order
+    fn split(self) -> (serde_json::Value, KeyOrder) {
+        match self {
+            Node::Leaf(value) => (value, KeyOrder::default()),
+            Node::Array(items) => {
+                let (values, items) = items.into_iter().map(Node::split).unzip();
+                let order = KeyOrder {
+                    keys: Vec::new(),
+                    items,
+                };
+                (serde_json::Value::Array(values), order)
+            }
+            Node::Object(entries) => {
+                let mut map = serde_json::Map::new();
+                let mut keys = Vec::with_capacity(entries.len());
+                for (key, node) in entries {
+                    let (value, order) = node.split();
+                    map.insert(key.to_string(), value);
+                    keys.retain(|(k, _): &(SmolStr, KeyOrder)| *k != key);
+                    keys.push((key, order));
+                }
+                let order = KeyOrder {
+                    keys,
+                    items: Vec::new(),
+                };
+                (serde_json::Value::Object(map), order)
+            }
+        }
+    }
+}
+
+struct NodeVisitor;
+
+impl<'de> Visitor<'de> for NodeVisitor {
+    type Value = Node;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any valid AT Protocol data value")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Node, E> {
+        Ok(Node::Leaf(serde_json::Value::Null))
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Node, E> {
+        self.visit_unit()
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Node, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Node, E> {
+        Ok(Node::Leaf(v.into()))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Node, E> {
+        Ok(Node::Leaf(v.into()))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Node, E> {
+        Ok(Node::Leaf(v.into()))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Node, E> {
+        Ok(Node::Leaf(v.into()))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Node, E> {
+        Ok(Node::Leaf(v.into()))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Node, E> {
+        Ok(Node::Leaf(v.into()))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Node, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(Node::Array(items))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Node, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entries = Vec::with_capacity(map.size_hint().unwrap_or(0));
+        while let Some(key) = map.next_key::<SmolStr>()? {
+            entries.push((key, map.next_value()?));
+        }
+        Ok(Node::Object(entries))
+    }
+}
+
+impl<'de> Deserialize<'de> for Node {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(NodeVisitor)
+    }
+}
@@ -310,17 +310,31 @@ pub fn infer_from_type(type_field: &str) -> DataModelType {
 
 /// decode a base64 byte string into atproto data
 pub fn decode_bytes<'s>(bytes: &str) -> Data<'s> {
+    match try_decode_bytes(bytes) {
+        Some(bytes) => Data::Bytes(bytes),
+        None => Data::String(AtprotoStr::String(CowStr::Borrowed(bytes).into_static())),
+    }
+}
+
+/// decode a base64 byte string, or `None` if it isn't base64 in any common alphabet
+pub fn try_decode_bytes(bytes: &str) -> Option<Bytes> {
     // First one should just work. rest are insurance.
-    if let Ok(bytes) = BASE64_STANDARD.decode(bytes) {
-        Data::Bytes(Bytes::from_owner(bytes))
-    } else if let Ok(bytes) = BASE64_STANDARD_NO_PAD.decode(bytes) {
-        Data::Bytes(Bytes::from_owner(bytes))
-    } else if let Ok(bytes) = BASE64_URL_SAFE.decode(bytes) {
-        Data::Bytes(Bytes::from_owner(bytes))
-    } else if let Ok(bytes) = BASE64_URL_SAFE_NO_PAD.decode(bytes) {
-        Data::Bytes(Bytes::from_owner(bytes))
+    BASE64_STANDARD
+        .decode(bytes)
+        .or_else(|_| BASE64_STANDARD_NO_PAD.decode(bytes))
+        .or_else(|_| BASE64_URL_SAFE.decode(bytes))
+        .or_else(|_| BASE64_URL_SAFE_NO_PAD.decode(bytes))
+        .ok()
+        .map(Bytes::from_owner)
+}
+
+/// parse the string of a `{"$link": ...}` object into a CID link, rejecting non-CIDs
+pub fn cid_link<'s>(link: &'s str) -> Result<Data<'s>, AtDataError> {
+    let cid = Cid::str(link);
+    if cid.is_valid() {
+        Ok(Data::CidLink(cid))
     } else {
-        Data::String(AtprotoStr::String(CowStr::Borrowed(bytes).into_static()))
+        Err(AtDataError::InvalidCidLink(link.into()))
     }
 }
 
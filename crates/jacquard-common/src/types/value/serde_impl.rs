@@ -126,7 +126,12 @@ impl<'de: 'v, 'v> serde::de::Visitor<'v> for DataVisitor {
     where
         E: serde::de::Error,
     {
-        Ok(Data::Integer((v % (i64::MAX as u64)) as i64))
+        i64::try_from(v).map(Data::Integer).map_err(|_| {
+            E::invalid_value(
+                serde::de::Unexpected::Unsigned(v),
+                &"a signed 64-bit integer",
+            )
+        })
     }
 
     fn visit_f64<E>(self, _v: f64) -> Result<Self::Value, E>
@@ -213,6 +213,53 @@ fn blob_deserialization() {
     }
 }
 
+#[test]
+fn blob_from_untyped_object() {
+    let cid = "bafyreih4g7bvo6hdq2juolev5bfzpbo4ewkxh5mzxwgvkjp3kitc6hqkha";
+
+    // No `$type`, so parsing leaves it as a plain object
+    let json = serde_json::json!({
+        "ref": {"$link": cid},
+        "mimeType": "image/jpeg",
+        "size": 2048
+    });
+    let data = Data::from_json(&json).unwrap();
+    assert!(data.as_blob().is_none());
+    let blob = data.to_blob().unwrap();
+    assert_eq!(blob.r#ref.as_str(), cid);
+    assert_eq!(blob.mime_type.as_str(), "image/jpeg");
+    assert_eq!(blob.size, 2048);
+
+    // Typed blobs come straight through
+    let typed = Data::Blob(blob.clone());
+    assert_eq!(typed.as_blob(), Some(&blob));
+    assert_eq!(typed.to_blob().unwrap(), blob);
+
+    // Legacy shape
+    let json = serde_json::json!({"cid": cid, "mimeType": "image/png"});
+    let legacy = Data::from_json(&json).unwrap().to_blob().unwrap();
+    assert_eq!(legacy.r#ref.as_str(), cid);
+    assert_eq!(legacy.size, 0);
+}
+
+#[test]
+fn blob_from_invalid_object() {
+    let json = serde_json::json!({"mimeType": "image/png", "size": 1});
+    assert_eq!(
+        Data::from_json(&json).unwrap().to_blob(),
+        Err(AtDataError::InvalidBlob("missing `ref`"))
+    );
+
+    let json = serde_json::json!({
+        "$type": "app.bsky.embed.images",
+        "ref": {"$link": "bafyreih4g7bvo6hdq2juolev5bfzpbo4ewkxh5mzxwgvkjp3kitc6hqkha"},
+        "mimeType": "image/png",
+        "size": 1
+    });
+    assert!(Data::from_json(&json).unwrap().to_blob().is_err());
+    assert!(Data::Integer(1).to_blob().is_err());
+}
+
 #[test]
 #[ignore]
 fn reject_floats() {
@@ -813,3 +860,246 @@ fn test_option_vec_deserialization() {
     assert_eq!(result.text, "null test");
     assert_eq!(result.langs, None);
 }
+
+#[cfg(feature = "simd-json")]
+#[test]
+fn from_json_mut_matches_serde_json() {
+    let json = serde_json::json!({
+        "$type": "app.bsky.feed.post",
+        "text": "hello \"world\" 🦋",
+        "createdAt": "2024-10-16T18:02:11.000Z",
+        "langs": ["en"],
+        "reply": {
+            "root": {
+                "uri": "at://did:plc:z72i7hdynmk6r22z27h6tvur/app.bsky.feed.post/3l2s5xxv2ze2c",
+                "cid": "bafyreihtzsyfxt4qbc3sbf6ooxagwyuxphwuwxhrxwaesh5ozrr7uqzdna"
+            }
+        },
+        "count": 42,
+        "nothing": null
+    });
+    let bytes = serde_json::to_vec(&json).unwrap();
+
+    let expected: Data = serde_json::from_slice(&bytes).unwrap();
+    let mut scratch = bytes.clone();
+    let data = Data::from_json_mut(&mut scratch).unwrap();
+    assert_eq!(data, expected);
+
+    let mut invalid = b"{\"x\": ".to_vec();
+    assert!(Data::from_json_mut(&mut invalid).is_err());
+}
+
+#[test]
+fn ordered_data_preserves_key_order() {
+    let json = r#"{"zeta":1,"alpha":{"y":"b","x":"a"},"items":[{"b":1,"a":2}],"avatar":{"$type":"blob","ref":{"$link":"bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku"},"mimeType":"image/png","size":10}}"#;
+    let mut data: OrderedData = serde_json::from_str(json).unwrap();
+    assert!(matches!(
+        data.data(),
+        Data::Object(obj) if matches!(obj.0.get("avatar"), Some(Data::Blob(_)))
+    ));
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&serde_json::to_string(&data).unwrap()).unwrap(),
+        serde_json::from_str::<serde_json::Value>(json).unwrap()
+    );
+    let out = serde_json::to_string(&data).unwrap();
+    assert!(out.starts_with(r#"{"zeta":1,"alpha":{"y":"b","x":"a"},"items":[{"b":1,"a":2}]"#));
+
+    // New keys go after the recorded ones; removed keys disappear
+    if let Data::Object(obj) = data.data_mut() {
+        obj.0.remove("alpha");
+        obj.0.insert("beta".into(), Data::Boolean(true));
+    }
+    let out = serde_json::to_string(&data).unwrap();
+    assert!(out.starts_with(r#"{"zeta":1,"items":[{"b":1,"a":2}],"avatar":{"#));
+    assert!(out.ends_with(r#","beta":true}"#));
+
+    // Plain Data is still sorted
+    let plain = serde_json::to_string(data.data()).unwrap();
+    assert!(plain.starts_with(r#"{"avatar":"#));
+}
+
+#[test]
+fn ordered_data_canonical_cbor_ignores_order() {
+    let a: OrderedData =
+        serde_json::from_str(r#"{"text":"hi","createdAt":"2024-01-01T00:00:00Z","n":1}"#).unwrap();
+    let b: OrderedData =
+        serde_json::from_str(r#"{"n":1,"createdAt":"2024-01-01T00:00:00Z","text":"hi"}"#).unwrap();
+    assert_ne!(
+        serde_json::to_string(&a).unwrap(),
+        serde_json::to_string(&b).unwrap()
+    );
+    let cbor = a.to_canonical_dag_cbor().unwrap();
+    assert_eq!(cbor, b.to_canonical_dag_cbor().unwrap());
+    assert_eq!(cbor, a.data().to_canonical_dag_cbor().unwrap());
+
+    let decoded: Data = serde_ipld_dagcbor::from_slice(&cbor).unwrap();
+    assert_eq!(&decoded, a.data());
+    assert!(serde_ipld_dagcbor::from_slice::<OrderedData>(&cbor).is_err());
+}
+
+/// String at a JSON Pointer, if there is one
+fn str_at<'a>(data: &'a Data<'_>, path: &str) -> Option<&'a str> {
+    match data.pointer(path)? {
+        Data::String(s) => Some(s.as_str()),
+        _ => None,
+    }
+}
+
+#[test]
+fn json_pointer_lookup() {
+    let json = serde_json::json!({
+        "embed": {
+            "images": [
+                {"alt": "first"},
+                {"alt": "second"}
+            ]
+        },
+        "a/b": 1,
+        "m~n": 2,
+        "0": "numeric key",
+        "": "empty key"
+    });
+    let data = Data::from_json(&json).unwrap();
+
+    assert_eq!(data.pointer(""), Some(&data));
+    assert_eq!(str_at(&data, "/embed/images/1/alt"), Some("second"));
+    assert_eq!(data.pointer("/a~1b"), Some(&Data::Integer(1)));
+    assert_eq!(data.pointer("/m~0n"), Some(&Data::Integer(2)));
+    assert_eq!(str_at(&data, "/"), Some("empty key"));
+
+    // Numeric segments are keys on objects
+    assert_eq!(str_at(&data, "/0"), Some("numeric key"));
+
+    // Missing segments, bad indices and type mismatches
+    assert_eq!(data.pointer("embed"), None);
+    assert_eq!(data.pointer("/missing"), None);
+    assert_eq!(data.pointer("/embed/images/2"), None);
+    assert_eq!(data.pointer("/embed/images/01"), None);
+    assert_eq!(data.pointer("/embed/images/-"), None);
+    assert_eq!(data.pointer("/embed/images/alt"), None);
+    assert_eq!(data.pointer("/a~1b/c"), None);
+}
+
+#[test]
+fn json_pointer_mut_patches_in_place() {
+    let json = serde_json::json!({"embed": {"images": [{"alt": "old"}]}});
+    let mut data = Data::from_json(&json).unwrap().into_static();
+
+    *data.pointer_mut("/embed/images/0/alt").unwrap() =
+        Data::String(AtprotoStr::String("new".into()));
+    assert_eq!(str_at(&data, "/embed/images/0/alt"), Some("new"));
+    assert!(data.pointer_mut("/embed/images/1").is_none());
+}
+
+#[test]
+fn canonical_cbor_reencodes_non_canonical_input() {
+    // {"bb": 1, "a": 2}: keys out of length-first order
+    let unsorted = [0xa2, 0x62, b'b', b'b', 0x01, 0x61, b'a', 0x02];
+    assert!(!Data::is_canonical_cbor(&unsorted));
+
+    let decoded: Data = serde_ipld_dagcbor::from_slice(&unsorted).unwrap();
+    let canonical = decoded.to_canonical_cbor().unwrap();
+    assert_ne!(canonical.as_slice(), unsorted.as_slice());
+    assert_eq!(canonical, [0xa2, 0x61, b'a', 0x02, 0x62, b'b', b'b', 0x01]);
+    assert!(Data::is_canonical_cbor(&canonical));
+}
+
+#[test]
+fn canonical_cbor_output_is_stable() {
+    let json = serde_json::json!({
+        "text": "hello",
+        "count": 300,
+        "tags": ["a", "bb"],
+        "link": {"$link": "bafyreih4g7bvo6hdq2juolev5bfzpbo4ewkxh5mzxwgvkjp3kitc6hqkha"},
+        "raw": {"$bytes": "aGVsbG8="},
+        "nested": {"zz": null, "b": false, "aaa": -1000}
+    });
+    let data = Data::from_json(&json).unwrap();
+    let first = data.to_canonical_cbor().unwrap();
+    assert!(Data::is_canonical_cbor(&first));
+    assert_eq!(first, data.to_canonical_cbor().unwrap());
+
+    let decoded: Data = serde_ipld_dagcbor::from_slice(&first).unwrap();
+    assert_eq!(decoded.to_canonical_cbor().unwrap(), first);
+}
+
+#[test]
+fn canonical_cbor_rejects_non_canonical_forms() {
+    for bytes in [
+        &[0x18, 0x05][..],                           // 5 in a one-byte argument
+        &[0x19, 0x00, 0x10],                         // 16 in a two-byte argument
+        &[0x9f, 0x01, 0xff],                         // indefinite-length array
+        &[0xfb, 0, 0, 0, 0, 0, 0, 0, 0],             // float
+        &[0xf7],                                     // undefined
+        &[0xa2, 0x61, b'a', 0x01, 0x61, b'a', 0x02], // duplicate key
+        &[0xa1, 0x01, 0x02],                         // non-string key
+        &[0xc1, 0x01],                               // tag other than 42
+        &[0x01, 0x02],                               // trailing bytes
+        &[0x62, b'a'],                               // truncated
+        &[],
+    ] {
+        assert!(!Data::is_canonical_cbor(bytes), "{bytes:02x?}");
+    }
+    assert!(Data::is_canonical_cbor(&[0x18, 0x18]));
+    assert!(Data::is_canonical_cbor(&[0x39, 0x01, 0x00]));
+}
+
+#[test]
+fn json_value_round_trip_is_lossless() {
+    let json = serde_json::json!({
+        "$type": "app.bsky.embed.images",
+        "images": [{
+            "alt": "a cat",
+            "image": {
+                "$type": "blob",
+                "ref": {"$link": "bafyreih4g7bvo6hdq2juolev5bfzpbo4ewkxh5mzxwgvkjp3kitc6hqkha"},
+                "mimeType": "image/png",
+                "size": 12345
+            }
+        }],
+        "link": {"$link": "bafyreih4g7bvo6hdq2juolev5bfzpbo4ewkxh5mzxwgvkjp3kitc6hqkha"},
+        "raw": {"$bytes": "aGVsbG8="},
+        "count": -7
+    });
+    let data = Data::from_json_value(&json).unwrap();
+    assert!(matches!(
+        data.pointer("/images/0/image"),
+        Some(Data::Blob(_))
+    ));
+    assert!(matches!(data.pointer("/link"), Some(Data::CidLink(_))));
+    assert!(matches!(data.pointer("/raw"), Some(Data::Bytes(_))));
+
+    let value = serde_json::Value::from(&data);
+    assert_eq!(value, json);
+    assert_eq!(Data::from_json_value(&value).unwrap(), data);
+}
+
+#[test]
+fn json_value_rejects_out_of_range_integers() {
+    let json = serde_json::json!({"big": u64::MAX});
+    assert_eq!(
+        Data::from_json_value(&json),
+        Err(AtDataError::IntegerOutOfRange(u64::MAX))
+    );
+    let text = json.to_string();
+    assert!(serde_json::from_str::<Data>(&text).is_err());
+}
+
+#[test]
+fn json_value_rejects_invalid_bytes_and_links() {
+    let json = serde_json::json!({"raw": {"$bytes": "not base64!"}});
+    assert_eq!(Data::from_json_value(&json), Err(AtDataError::InvalidBytes));
+
+    let json = serde_json::json!({"link": {"$link": "not-a-cid"}});
+    assert_eq!(
+        Data::from_json_value(&json),
+        Err(AtDataError::InvalidCidLink("not-a-cid".into()))
+    );
+
+    // Nested under a blob-shaped key
+    let json = serde_json::json!({"ref": {"$link": "bafynope"}});
+    assert!(matches!(
+        Data::from_json_value(&json),
+        Err(AtDataError::InvalidCidLink(_))
+    ));
+}
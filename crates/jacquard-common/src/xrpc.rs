@@ -22,24 +22,31 @@ pub use streaming::{
 #[cfg(feature = "websocket")]
 pub mod subscription;
 
+#[cfg(feature = "streaming")]
+pub mod paginate;
+#[cfg(feature = "streaming")]
+pub use paginate::{PaginateError, XrpcPaginator};
+
 #[cfg(feature = "streaming")]
 use crate::StreamError;
 use crate::error::DecodeError;
 use crate::http_client::HttpClient;
 #[cfg(feature = "streaming")]
 use crate::http_client::HttpClientExt;
+use crate::types::string::Did;
 use crate::types::value::Data;
 use crate::{AuthorizationToken, error::AuthError};
 use crate::{CowStr, error::XrpcResult};
 use crate::{IntoStatic, types::value::RawData};
 use bytes::Bytes;
 use http::{
-    HeaderName, HeaderValue, Request, StatusCode,
+    HeaderMap, HeaderName, HeaderValue, Request, StatusCode,
     header::{AUTHORIZATION, CONTENT_TYPE},
 };
 use serde::{Deserialize, Serialize};
 use smol_str::SmolStr;
 use std::fmt::{self, Debug};
+use std::sync::Arc;
 use std::{error::Error, marker::PhantomData};
 #[cfg(feature = "websocket")]
 pub use subscription::{
@@ -188,6 +195,28 @@ pub trait XrpcEndpoint {
     type Response: XrpcResp;
 }
 
+/// Cursor-paginated XRPC query
+///
+/// Implemented by the code generation for queries that take a `cursor` parameter and return a
+/// `cursor` alongside a single array of results. Drive one with `XrpcPaginator` (the
+/// `streaming` feature) to page through the whole listing.
+pub trait Paginated: XrpcRequest {
+    /// Element type of the list this query returns
+    type Item;
+
+    /// The cursor this request will send, if any
+    fn cursor(&self) -> Option<&str>;
+
+    /// Replace the cursor this request will send
+    fn set_cursor(&mut self, cursor: Option<CowStr<'static>>);
+
+    /// The cursor for the next page, as returned in a response
+    fn next_cursor<'o>(output: &'o RespOutput<'static, Self::Response>) -> Option<&'o str>;
+
+    /// Take the page's items out of a response
+    fn items(output: RespOutput<'static, Self::Response>) -> Vec<Self::Item>;
+}
+
 /// Error type for XRPC endpoints that don't define any errors
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct GenericError<'a>(#[serde(borrow)] Data<'a>);
@@ -207,19 +236,113 @@ impl IntoStatic for GenericError<'_> {
     }
 }
 
+/// Chooses the `atproto-proxy` target for each XRPC method.
+///
+/// Install one in a client's default [`CallOptions`] to route methods to services by NSID
+/// (for example `chat.bsky.*` to the chat service) instead of setting a proxy on every call.
+/// See [`ProxyRoutes`] for a table-driven implementation.
+///
+/// ```no_run
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use jacquard_common::types::string::Did;
+/// use jacquard_common::xrpc::{CallOptions, ProxyRoutes};
+///
+/// let routes = ProxyRoutes::new()
+///     .route("chat.bsky.*", Did::new_static("did:web:api.bsky.chat")?, "bsky_chat")
+///     .fallback(Did::new_static("did:web:api.bsky.app")?, "bsky_appview");
+/// let opts = CallOptions::default().with_proxy_router(routes);
+/// // session.set_options(opts).await;
+/// # Ok(())
+/// # }
+/// ```
+pub trait ProxyRouter: Send + Sync {
+    /// The service DID and service id to proxy `nsid` to, or `None` to send it to the
+    /// client's own host.
+    fn target(&self, nsid: &str) -> Option<(Did<'_>, &str)>;
+}
+
+/// [`ProxyRouter`] backed by a list of NSID patterns.
+///
+/// A pattern is either an exact NSID or a prefix ending in `.*`, such as `chat.bsky.*`.
+/// Routes are checked in the order they were added and the first match wins; methods
+/// that match nothing go to the fallback, if one is set.
+#[derive(Debug, Clone, Default)]
+pub struct ProxyRoutes {
+    routes: Vec<(SmolStr, Did<'static>, SmolStr)>,
+    fallback: Option<(Did<'static>, SmolStr)>,
+}
+
+impl ProxyRoutes {
+    /// Create an empty routing table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Proxy methods matching `pattern` to the `service` entry of `did`'s DID document.
+    pub fn route(
+        mut self,
+        pattern: impl Into<SmolStr>,
+        did: Did<'_>,
+        service: impl Into<SmolStr>,
+    ) -> Self {
+        self.routes
+            .push((pattern.into(), did.into_static(), service.into()));
+        self
+    }
+
+    /// Proxy methods that match no route to the `service` entry of `did`'s DID document.
+    pub fn fallback(mut self, did: Did<'_>, service: impl Into<SmolStr>) -> Self {
+        self.fallback = Some((did.into_static(), service.into()));
+        self
+    }
+}
+
+impl ProxyRouter for ProxyRoutes {
+    fn target(&self, nsid: &str) -> Option<(Did<'_>, &str)> {
+        let matches = |pattern: &str| match pattern.strip_suffix('*') {
+            Some(prefix) if prefix.ends_with('.') => nsid.starts_with(prefix),
+            _ => pattern == nsid,
+        };
+        self.routes
+            .iter()
+            .find(|(pattern, _, _)| matches(pattern))
+            .map(|(_, did, service)| (did, service))
+            .or(self.fallback.as_ref().map(|(did, service)| (did, service)))
+            .map(|(did, service)| (did.clone(), service.as_str()))
+    }
+}
+
 /// Per-request options for XRPC calls.
-#[derive(Debug, Default, Clone)]
+///
+/// Non-exhaustive so options can be added without breaking callers: start from
+/// [`CallOptions::default`] and set fields or use the `with_*` methods.
+#[derive(Default, Clone)]
+#[non_exhaustive]
 pub struct CallOptions<'a> {
     /// Optional Authorization to apply (`Bearer` or `DPoP`).
     pub auth: Option<AuthorizationToken<'a>>,
     /// `atproto-proxy` header value.
     pub atproto_proxy: Option<CowStr<'a>>,
+    /// Picks an `atproto-proxy` header per method when `atproto_proxy` is unset.
+    pub proxy_router: Option<Arc<dyn ProxyRouter>>,
     /// `atproto-accept-labelers` header values.
     pub atproto_accept_labelers: Option<Vec<CowStr<'a>>>,
     /// Extra headers to attach to this request.
     pub extra_headers: Vec<(HeaderName, HeaderValue)>,
 }
 
+impl Debug for CallOptions<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CallOptions")
+            .field("auth", &self.auth)
+            .field("atproto_proxy", &self.atproto_proxy)
+            .field("proxy_router", &self.proxy_router.as_ref().map(|_| ".."))
+            .field("atproto_accept_labelers", &self.atproto_accept_labelers)
+            .field("extra_headers", &self.extra_headers)
+            .finish()
+    }
+}
+
 impl IntoStatic for CallOptions<'_> {
     type Output = CallOptions<'static>;
 
@@ -227,6 +350,7 @@ impl IntoStatic for CallOptions<'_> {
         CallOptions {
             auth: self.auth.map(|auth| auth.into_static()),
             atproto_proxy: self.atproto_proxy.map(|proxy| proxy.into_static()),
+            proxy_router: self.proxy_router,
             atproto_accept_labelers: self
                 .atproto_accept_labelers
                 .map(|labelers| labelers.into_static()),
@@ -235,6 +359,68 @@ impl IntoStatic for CallOptions<'_> {
     }
 }
 
+impl<'a> CallOptions<'a> {
+    /// Layer `overrides` on top of these options, returning the combined options.
+    ///
+    /// Precedence, field by field:
+    /// - `auth`: the override's token if set, otherwise ours.
+    /// - `atproto_proxy`: the override's proxy if set, otherwise ours. There is no way to
+    ///   clear an inherited proxy through a merge; build the options directly for that.
+    /// - `proxy_router`: the override's router if set, otherwise ours.
+    /// - `atproto_accept_labelers`: the override's list replaces ours wholesale if set (the
+    ///   lists are not combined). `Some(vec![])` therefore suppresses the header even if we
+    ///   had labelers configured.
+    /// - `extra_headers`: unioned. Any of our headers whose name appears in the override
+    ///   are dropped, and all of the override's headers are kept, including repeated names.
+    ///
+    /// This is useful for stateful clients which keep session-wide defaults and want to
+    /// apply request-scoped options on top of them.
+    pub fn merge(&self, overrides: &CallOptions<'a>) -> CallOptions<'a> {
+        let mut extra_headers: Vec<(HeaderName, HeaderValue)> = self
+            .extra_headers
+            .iter()
+            .filter(|(name, _)| !overrides.extra_headers.iter().any(|(n, _)| n == name))
+            .cloned()
+            .collect();
+        extra_headers.extend(overrides.extra_headers.iter().cloned());
+
+        CallOptions {
+            auth: overrides.auth.clone().or_else(|| self.auth.clone()),
+            atproto_proxy: overrides
+                .atproto_proxy
+                .clone()
+                .or_else(|| self.atproto_proxy.clone()),
+            proxy_router: overrides
+                .proxy_router
+                .clone()
+                .or_else(|| self.proxy_router.clone()),
+            atproto_accept_labelers: overrides
+                .atproto_accept_labelers
+                .clone()
+                .or_else(|| self.atproto_accept_labelers.clone()),
+            extra_headers,
+        }
+    }
+
+    /// Route methods to `atproto-proxy` targets with `router` when `atproto_proxy` is unset.
+    pub fn with_proxy_router(mut self, router: impl ProxyRouter + 'static) -> Self {
+        self.proxy_router = Some(Arc::new(router));
+        self
+    }
+
+    /// The `atproto-proxy` header value to send with a call to `nsid`.
+    ///
+    /// An explicit `atproto_proxy` always wins; otherwise the `proxy_router` is asked.
+    pub fn proxy_for(&self, nsid: &str) -> Option<CowStr<'_>> {
+        if let Some(proxy) = &self.atproto_proxy {
+            return Some(proxy.clone());
+        }
+        let router = self.proxy_router.as_deref()?;
+        let (did, service) = router.target(nsid)?;
+        Some(CowStr::from(format!("{did}#{service}")))
+    }
+}
+
 /// Extension for stateless XRPC calls on any `HttpClient`.
 ///
 /// Example
@@ -260,6 +446,10 @@ pub trait XrpcExt: HttpClient {
             client: self,
             base,
             opts: CallOptions::default(),
+            #[cfg(feature = "retry")]
+            retry: None,
+            #[cfg(feature = "timeout")]
+            timeout: None,
         }
     }
 }
@@ -403,6 +593,10 @@ pub struct XrpcCall<'a, C: HttpClient> {
     pub(crate) client: &'a C,
     pub(crate) base: Url,
     pub(crate) opts: CallOptions<'a>,
+    #[cfg(feature = "retry")]
+    pub(crate) retry: Option<crate::retry::RetryPolicy>,
+    #[cfg(feature = "timeout")]
+    pub(crate) timeout: Option<std::time::Duration>,
 }
 
 impl<'a, C: HttpClient> XrpcCall<'a, C> {
@@ -431,6 +625,37 @@ impl<'a, C: HttpClient> XrpcCall<'a, C> {
         self.opts = opts;
         self
     }
+    /// Layer `overrides` on top of the builder's current options.
+    ///
+    /// See [`CallOptions::merge`] for the precedence rules.
+    pub fn merge_options(mut self, overrides: &CallOptions<'a>) -> Self {
+        self.opts = self.opts.merge(overrides);
+        self
+    }
+
+    /// Retry transient failures according to `policy`.
+    ///
+    /// Retries only apply to queries unless the policy opts in to procedures.
+    #[cfg(feature = "retry")]
+    pub fn retry(mut self, policy: crate::retry::RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// Give up waiting for a response after `limit`.
+    ///
+    /// Bounds each HTTP round trip, so with [`retry`](Self::retry) every attempt gets the
+    /// full limit. For streaming calls it bounds the wait for the response head, not the
+    /// body. Fails with a transport error whose source is
+    /// [`TransportError::Timeout`](crate::error::TransportError::Timeout), as a
+    /// [`ClientError`](crate::error::ClientError) (see
+    /// [`transport_error`](crate::error::ClientError::transport_error)) or, when streaming, a
+    /// `StreamError`.
+    #[cfg(feature = "timeout")]
+    pub fn timeout(mut self, limit: std::time::Duration) -> Self {
+        self.timeout = Some(limit);
+        self
+    }
 
     /// Send the given typed XRPC request and return a response wrapper.
     ///
@@ -446,48 +671,101 @@ impl<'a, C: HttpClient> XrpcCall<'a, C> {
         R: XrpcRequest,
         <R as XrpcRequest>::Response: Send + Sync,
     {
-        let http_request = build_http_request(&self.base, request, &self.opts)?;
-
-        let http_response = self
-            .client
-            .send_http(http_request)
-            .await
-            .map_err(|e| crate::error::ClientError::transport(e))?;
+        #[cfg(feature = "retry")]
+        let http_response = match &self.retry {
+            Some(policy) if policy.applies_to(R::METHOD) => {
+                self.send_http_retrying(request, policy).await?
+            }
+            _ => self.send_http_once(request).await?,
+        };
+        #[cfg(not(feature = "retry"))]
+        let http_response = self.send_http_once(request).await?;
 
         process_response(http_response)
     }
+
+    async fn send_http_once<R>(&self, request: &R) -> XrpcResult<http::Response<Vec<u8>>>
+    where
+        R: XrpcRequest,
+    {
+        let http_request = build_http_request(&self.base, request, &self.opts)?;
+        let response = self.client.send_http(http_request);
+
+        #[cfg(feature = "timeout")]
+        let response = match self.timeout {
+            Some(limit) => n0_future::time::timeout(limit, response)
+                .await
+                .map_err(|_| crate::error::TransportError::Timeout)?,
+            None => response.await,
+        };
+        #[cfg(not(feature = "timeout"))]
+        let response = response.await;
+
+        response.map_err(crate::error::ClientError::transport)
+    }
+
+    #[cfg(feature = "retry")]
+    async fn send_http_retrying<R>(
+        &self,
+        request: &R,
+        policy: &crate::retry::RetryPolicy,
+    ) -> XrpcResult<http::Response<Vec<u8>>>
+    where
+        R: XrpcRequest,
+    {
+        let max_attempts = policy.backoff.max_attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            let response = self.send_http_once(request).await?;
+            if attempt >= max_attempts || !policy.is_retryable(response.status()) {
+                return Ok(response);
+            }
+            let delay = policy.delay_for(attempt, response.headers());
+            #[cfg(feature = "tracing")]
+            tracing::debug!(attempt, max_attempts, status = %response.status(), ?delay, "retrying xrpc call");
+            n0_future::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
 }
 
 /// Process the HTTP response from the server into a proper xrpc response statelessly.
 ///
-/// Exposed to make things more easily pluggable
+/// Exposed to make things more easily pluggable. Built on [`Response::from_parts`], after
+/// screening out responses that can't be XRPC results: a 401 carrying `WWW-Authenticate`
+/// becomes an auth error, and error statuses other than 400/401 become an
+/// [`HttpError`](crate::error::HttpError), with the response headers available from
+/// [`ClientError::headers`](crate::error::ClientError::headers).
 #[inline]
 pub fn process_response<Resp>(http_response: http::Response<Vec<u8>>) -> XrpcResult<Response<Resp>>
 where
     Resp: XrpcResp,
 {
-    let status = http_response.status();
+    let (parts, body) = http_response.into_parts();
+    let status = parts.status;
     // If the server returned 401 with a WWW-Authenticate header, expose it so higher layers
     // (e.g., DPoP handling) can detect `error="invalid_token"` and trigger refresh.
     #[allow(deprecated)]
     if status.as_u16() == 401 {
-        if let Some(hv) = http_response.headers().get(http::header::WWW_AUTHENTICATE) {
+        if let Some(hv) = parts.headers.get(http::header::WWW_AUTHENTICATE) {
             return Err(crate::error::ClientError::auth(
                 crate::error::AuthError::Other(hv.clone()),
             ));
         }
     }
-    let buffer = Bytes::from(http_response.into_body());
+    let buffer = Bytes::from(body);
 
     if !status.is_success() && !matches!(status.as_u16(), 400 | 401) {
-        return Err(crate::error::HttpError {
+        let error: crate::error::ClientError = crate::error::HttpError {
             status,
             body: Some(buffer),
         }
-        .into());
+        .into();
+        // Keep the headers, so e.g. `ratelimit-*` on a 429 can still be read
+        return Err(error.with_headers(parts.headers));
     }
 
-    Ok(Response::new(buffer, status))
+    Ok(Response::from_parts(status, parts.headers, buffer))
 }
 
 /// HTTP headers commonly used in XRPC requests
@@ -567,7 +845,7 @@ where
         builder = builder.header(Header::Authorization, hv);
     }
 
-    if let Some(proxy) = &opts.atproto_proxy {
+    if let Some(proxy) = opts.proxy_for(<R as XrpcRequest>::NSID) {
         builder = builder.header(Header::AtprotoProxy, proxy.as_ref());
     }
     if let Some(labelers) = &opts.atproto_accept_labelers {
@@ -607,6 +885,10 @@ where
     _marker: PhantomData<fn() -> Resp>,
     buffer: Bytes,
     status: StatusCode,
+    headers: HeaderMap,
+    /// Body taken out of `buffer` for in-place parsing, see [`Response::parse_mut`]
+    #[cfg(feature = "simd-json")]
+    scratch: Vec<u8>,
 }
 
 impl<R> Response<R>
@@ -615,18 +897,53 @@ where
 {
     /// Create a new response from a buffer and status code
     pub fn new(buffer: Bytes, status: StatusCode) -> Self {
+        Self::from_parts(status, HeaderMap::new(), buffer)
+    }
+
+    /// Create a response from an HTTP response received by your own transport
+    ///
+    /// Lets middleware that already holds a response (e.g. a `reqwest::Response` from a
+    /// caching or tracing layer) get typed parsing without going back through [`XrpcCall`]:
+    ///
+    /// ```ignore
+    /// let status = resp.status();
+    /// let headers = resp.headers().clone();
+    /// let body = resp.bytes().await?;
+    /// let output = Response::<GetProfileResponse>::from_parts(status, headers, body).into_output()?;
+    /// ```
+    ///
+    /// No status screening is done here; see [`process_response`] for the checks the
+    /// built-in send path applies first.
+    pub fn from_parts(status: StatusCode, headers: HeaderMap, body: Bytes) -> Self {
         Self {
-            buffer,
+            buffer: body,
             status,
+            headers,
             _marker: PhantomData,
+            #[cfg(feature = "simd-json")]
+            scratch: Vec::new(),
         }
     }
 
+    /// NSID of the XRPC method this response belongs to
+    ///
+    /// Handy for logging and per-endpoint metrics in middleware that's generic over `R`.
+    pub fn nsid(&self) -> &'static str {
+        R::NSID
+    }
+
     /// Get the HTTP status code
     pub fn status(&self) -> StatusCode {
         self.status
     }
 
+    /// Get the HTTP response headers
+    ///
+    /// Empty for responses created with [`Response::new`].
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
     /// Get the raw buffer
     pub fn buffer(&self) -> &Bytes {
         &self.buffer
@@ -680,6 +997,28 @@ where
         }
     }
 
+    /// Parse the response with `simd-json`, in place in the response body
+    ///
+    /// `simd-json` rewrites the input as it parses, so this takes the body out of the
+    /// response instead of copying it. Converting the [`Bytes`] into a mutable buffer is
+    /// free when nothing else holds a reference to it, which is the case for bodies read
+    /// by [`XrpcCall`]. Afterwards [`Response::buffer`] is empty and the response can't be
+    /// parsed again; use [`Response::parse`] if you need to keep the raw body around.
+    ///
+    /// Only successful responses whose output encoding is `application/json` take the SIMD
+    /// path. Error responses and other encodings go through [`Response::parse`], which also
+    /// honours any [`XrpcResp::decode_output`] override.
+    #[cfg(feature = "simd-json")]
+    pub fn parse_mut<'s>(&'s mut self) -> Result<RespOutput<'s, R>, XrpcError<RespErr<'s, R>>> {
+        if !self.status.is_success() || R::ENCODING != "application/json" {
+            return self.parse();
+        }
+
+        self.scratch = Vec::from(std::mem::take(&mut self.buffer));
+        simd_json::serde::from_slice(&mut self.scratch)
+            .map_err(|e| XrpcError::Decode(DecodeError::SimdJson(e)))
+    }
+
     /// Parse this as validated, loosely typed atproto data.
     ///
     /// NOTE: If the response is an error, it will still parse as the matching error type for the request.
@@ -812,11 +1151,7 @@ where
     /// depends on ensuring the buffer actually contains data that can deserialize to `NEW`.
     /// Incorrect conversion will cause deserialization errors at runtime.
     pub fn transmute<NEW: XrpcResp>(self) -> Response<NEW> {
-        Response {
-            buffer: self.buffer,
-            status: self.status,
-            _marker: PhantomData,
-        }
+        Response::from_parts(self.status, self.headers, self.buffer)
     }
 }
 
@@ -1049,11 +1384,17 @@ impl<'a, C: HttpClient + HttpClientExt> XrpcCall<'a, C> {
         let http_request =
             build_http_request(&self.base, request, &self.opts).map_err(StreamError::transport)?;
 
-        let http_response = self
-            .client
-            .send_http_streaming(http_request)
-            .await
-            .map_err(StreamError::transport)?;
+        let http_response = self.client.send_http_streaming(http_request);
+        #[cfg(feature = "timeout")]
+        let http_response = match self.timeout {
+            Some(limit) => n0_future::time::timeout(limit, http_response)
+                .await
+                .map_err(|_| StreamError::transport(crate::error::TransportError::Timeout))?,
+            None => http_response.await,
+        };
+        #[cfg(not(feature = "timeout"))]
+        let http_response = http_response.await;
+        let http_response = http_response.map_err(StreamError::transport)?;
         let (parts, body) = http_response.into_parts();
 
         Ok(StreamingResponse::new(parts, body))
@@ -1094,7 +1435,7 @@ impl<'a, C: HttpClient + HttpClientExt> XrpcCall<'a, C> {
             builder = builder.header(Header::Authorization, hv);
         }
 
-        if let Some(proxy) = &self.opts.atproto_proxy {
+        if let Some(proxy) = self.opts.proxy_for(<S::Request as XrpcRequest>::NSID) {
             builder = builder.header(Header::AtprotoProxy, proxy.as_ref());
         }
         if let Some(labelers) = &self.opts.atproto_accept_labelers {
@@ -1118,11 +1459,17 @@ impl<'a, C: HttpClient + HttpClientExt> XrpcCall<'a, C> {
 
         let body_stream = Box::pin(stream.0.map_ok(|f| f.buffer));
 
-        let resp = self
-            .client
-            .send_http_bidirectional(parts, body_stream)
-            .await
-            .map_err(StreamError::transport)?;
+        let resp = self.client.send_http_bidirectional(parts, body_stream);
+        #[cfg(feature = "timeout")]
+        let resp = match self.timeout {
+            Some(limit) => n0_future::time::timeout(limit, resp)
+                .await
+                .map_err(|_| StreamError::transport(crate::error::TransportError::Timeout))?,
+            None => resp.await,
+        };
+        #[cfg(not(feature = "timeout"))]
+        let resp = resp.await;
+        let resp = resp.map_err(StreamError::transport)?;
 
         let (parts, body) = resp.into_parts();
 
@@ -1242,4 +1589,370 @@ mod tests {
             assert!(!uri.contains("//xrpc"));
         }
     }
+
+    #[cfg(feature = "simd-json")]
+    #[test]
+    fn parse_mut_matches_parse() {
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct Out<'a> {
+            #[serde(borrow)]
+            text: CowStr<'a>,
+            count: i64,
+        }
+        impl IntoStatic for Out<'_> {
+            type Output = Out<'static>;
+            fn into_static(self) -> Self::Output {
+                Out {
+                    text: self.text.into_static(),
+                    count: self.count,
+                }
+            }
+        }
+        struct OutResp;
+        impl XrpcResp for OutResp {
+            const NSID: &'static str = "test.dummy";
+            const ENCODING: &'static str = "application/json";
+            type Output<'de> = Out<'de>;
+            type Err<'de> = DummyErr<'de>;
+        }
+
+        let json = br#"{"text":"hello \"world\" \u00e9","count":3}"#;
+        let expected = Response::<OutResp>::new(Bytes::from_static(json), StatusCode::OK)
+            .parse()
+            .unwrap()
+            .into_static();
+        let mut resp: Response<OutResp> = Response::new(Bytes::from(json.to_vec()), StatusCode::OK);
+        assert_eq!(resp.parse_mut().unwrap(), expected);
+        // The body was consumed by the in-place parse
+        assert!(resp.buffer().is_empty());
+        assert!(matches!(resp.parse_mut(), Err(XrpcError::Decode(_))));
+
+        // Errors fall back to the regular path
+        let body = serde_json::json!({"error":"InvalidRequest","message":"missing"});
+        let buf = Bytes::from(serde_json::to_vec(&body).unwrap());
+        let mut resp: Response<OutResp> = Response::new(buf, StatusCode::BAD_REQUEST);
+        assert!(matches!(resp.parse_mut(), Err(XrpcError::Generic(_))));
+    }
+
+    #[test]
+    fn from_parts_keeps_headers_and_parses() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-cache", HeaderValue::from_static("hit"));
+        let body = serde_json::json!({"error":"InvalidRequest","message":"missing"});
+        let buf = Bytes::from(serde_json::to_vec(&body).unwrap());
+
+        let resp: Response<DummyResp> =
+            Response::from_parts(StatusCode::BAD_REQUEST, headers, buf.clone());
+        assert_eq!(resp.headers()["x-cache"], "hit");
+        assert_eq!(resp.nsid(), "test.dummy");
+        assert!(matches!(resp.parse(), Err(XrpcError::Generic(_))));
+        assert_eq!(resp.transmute::<DummyResp>().headers()["x-cache"], "hit");
+
+        // process_response goes through the same constructor
+        let http_response = http::Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .header("x-cache", "miss")
+            .body(buf.to_vec())
+            .unwrap();
+        let resp = process_response::<DummyResp>(http_response).unwrap();
+        assert_eq!(resp.headers()["x-cache"], "miss");
+        assert_eq!(resp.buffer(), &buf);
+    }
+
+    #[test]
+    fn call_options_merge_precedence() {
+        let user_agent = http::header::USER_AGENT;
+        let trace = HeaderName::from_static("x-trace");
+
+        let defaults = CallOptions {
+            auth: Some(AuthorizationToken::Bearer(CowStr::new_static("session"))),
+            atproto_proxy: Some(CowStr::new_static("did:web:api.bsky.app#bsky_appview")),
+            proxy_router: None,
+            atproto_accept_labelers: Some(vec![CowStr::new_static("did:plc:default")]),
+            extra_headers: vec![
+                (user_agent.clone(), HeaderValue::from_static("default")),
+                (trace.clone(), HeaderValue::from_static("a")),
+            ],
+        };
+
+        // Empty overrides leave the defaults untouched
+        let merged = defaults.merge(&CallOptions::default());
+        assert!(
+            matches!(merged.auth, Some(AuthorizationToken::Bearer(ref t)) if t.as_ref() == "session")
+        );
+        assert_eq!(merged.atproto_proxy, defaults.atproto_proxy);
+        assert_eq!(
+            merged.atproto_accept_labelers,
+            defaults.atproto_accept_labelers
+        );
+        assert_eq!(merged.extra_headers, defaults.extra_headers);
+
+        let overrides = CallOptions {
+            auth: None,
+            atproto_proxy: Some(CowStr::new_static("did:web:example.com#service")),
+            proxy_router: None,
+            atproto_accept_labelers: Some(vec![]),
+            extra_headers: vec![
+                (user_agent.clone(), HeaderValue::from_static("override")),
+                (user_agent.clone(), HeaderValue::from_static("override-2")),
+            ],
+        };
+        let merged = defaults.merge(&overrides);
+        assert!(
+            matches!(merged.auth, Some(AuthorizationToken::Bearer(ref t)) if t.as_ref() == "session")
+        );
+        assert_eq!(
+            merged.atproto_proxy.as_deref(),
+            Some("did:web:example.com#service")
+        );
+        // Labeler lists are replaced, not combined
+        assert_eq!(merged.atproto_accept_labelers, Some(vec![]));
+        assert_eq!(
+            merged.extra_headers,
+            vec![
+                (trace, HeaderValue::from_static("a")),
+                (user_agent.clone(), HeaderValue::from_static("override")),
+                (user_agent, HeaderValue::from_static("override-2")),
+            ]
+        );
+    }
+
+    #[test]
+    fn proxy_routes_pick_header_per_nsid() {
+        let routes = ProxyRoutes::new()
+            .route(
+                "chat.bsky.*",
+                Did::new_static("did:web:api.bsky.chat").unwrap(),
+                "bsky_chat",
+            )
+            .route(
+                "app.bsky.feed.getTimeline",
+                Did::new_static("did:web:feeds.example").unwrap(),
+                "feeds",
+            )
+            .fallback(
+                Did::new_static("did:web:api.bsky.app").unwrap(),
+                "bsky_appview",
+            );
+        let opts = CallOptions {
+            proxy_router: Some(Arc::new(routes)),
+            ..Default::default()
+        };
+
+        let proxy = |nsid| opts.proxy_for(nsid).map(|p| p.to_string());
+        assert_eq!(
+            proxy("chat.bsky.convo.listConvos").as_deref(),
+            Some("did:web:api.bsky.chat#bsky_chat")
+        );
+        assert_eq!(
+            proxy("app.bsky.feed.getTimeline").as_deref(),
+            Some("did:web:feeds.example#feeds")
+        );
+        assert_eq!(
+            proxy("app.bsky.feed.getTimelineX").as_deref(),
+            Some("did:web:api.bsky.app#bsky_appview")
+        );
+        // `chat.bsky.*` doesn't match the bare namespace or a different authority
+        assert_eq!(
+            proxy("chat.bskyx.foo").as_deref(),
+            Some("did:web:api.bsky.app#bsky_appview")
+        );
+
+        // An explicit proxy takes precedence over the router
+        let explicit = CallOptions {
+            atproto_proxy: Some(CowStr::new_static("did:web:example.com#service")),
+            ..opts.clone()
+        };
+        assert_eq!(
+            explicit.proxy_for("chat.bsky.convo.listConvos").as_deref(),
+            Some("did:web:example.com#service")
+        );
+
+        // Without a fallback, unmatched methods go to the client's own host
+        let routes = ProxyRoutes::new().route(
+            "chat.bsky.*",
+            Did::new_static("did:web:api.bsky.chat").unwrap(),
+            "bsky_chat",
+        );
+        assert_eq!(routes.target("app.bsky.feed.getTimeline"), None);
+        assert!(
+            CallOptions::default()
+                .proxy_for("chat.bsky.convo.listConvos")
+                .is_none()
+        );
+    }
+
+    #[cfg(feature = "retry")]
+    #[tokio::test]
+    async fn retry_policy_retries_transient_statuses() {
+        use crate::retry::{RetryConfig, RetryPolicy};
+        use std::collections::VecDeque;
+        use std::sync::Mutex;
+        use std::time::Duration;
+
+        /// Answers with the scripted statuses in order, then 200
+        struct Scripted {
+            statuses: Mutex<VecDeque<u16>>,
+            calls: Mutex<usize>,
+        }
+
+        impl Scripted {
+            fn new(statuses: &[u16]) -> Self {
+                Self {
+                    statuses: Mutex::new(statuses.iter().copied().collect()),
+                    calls: Mutex::new(0),
+                }
+            }
+
+            fn calls(&self) -> usize {
+                *self.calls.lock().unwrap()
+            }
+        }
+
+        impl HttpClient for Scripted {
+            type Error = std::convert::Infallible;
+
+            async fn send_http(
+                &self,
+                _request: http::Request<Vec<u8>>,
+            ) -> Result<http::Response<Vec<u8>>, Self::Error> {
+                *self.calls.lock().unwrap() += 1;
+                let status = self.statuses.lock().unwrap().pop_front().unwrap_or(200);
+                Ok(http::Response::builder()
+                    .status(status)
+                    .body(b"{\"error\":\"Busy\"}".to_vec())
+                    .unwrap())
+            }
+        }
+
+        #[derive(Serialize, Deserialize)]
+        struct Ping;
+        impl XrpcRequest for Ping {
+            const NSID: &'static str = "test.dummy";
+            const METHOD: XrpcMethod = XrpcMethod::Query;
+            type Response = DummyResp;
+        }
+
+        let policy = RetryPolicy::new(RetryConfig {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+            jitter: 0.0,
+        });
+        let base = Url::parse("https://pds.example.com").unwrap();
+
+        let client = Scripted::new(&[503, 503]);
+        let resp = client
+            .xrpc(base.clone())
+            .retry(policy.clone())
+            .send(&Ping)
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(client.calls(), 3);
+
+        let client = Scripted::new(&[400]);
+        let resp = client
+            .xrpc(base.clone())
+            .retry(policy.clone())
+            .send(&Ping)
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(client.calls(), 1);
+
+        // Procedures need an explicit opt-in
+        let client = Scripted::new(&[503]);
+        assert!(
+            client
+                .xrpc(base.clone())
+                .retry(policy.clone())
+                .send(&DummyReq)
+                .await
+                .is_err()
+        );
+        assert_eq!(client.calls(), 1);
+
+        let client = Scripted::new(&[503]);
+        let resp = client
+            .xrpc(base)
+            .retry(policy.retry_procedures(true))
+            .send(&DummyReq)
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(client.calls(), 2);
+    }
+
+    #[cfg(feature = "retry")]
+    #[test]
+    fn retry_after_overrides_backoff() {
+        use crate::retry::{RetryConfig, RetryPolicy, retry_after};
+        use std::time::Duration;
+
+        let policy = RetryPolicy::new(RetryConfig {
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(10),
+            jitter: 0.0,
+            ..Default::default()
+        });
+        let mut headers = HeaderMap::new();
+        assert_eq!(policy.delay_for(1, &headers), Duration::from_millis(100));
+
+        headers.insert(http::header::RETRY_AFTER, HeaderValue::from_static("2"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(2)));
+        assert_eq!(policy.delay_for(1, &headers), Duration::from_secs(2));
+
+        headers.insert(http::header::RETRY_AFTER, HeaderValue::from_static("3600"));
+        assert_eq!(policy.delay_for(1, &headers), Duration::from_secs(10));
+
+        headers.insert(
+            http::header::RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers), Some(Duration::ZERO));
+    }
+
+    #[cfg(feature = "timeout")]
+    #[tokio::test]
+    async fn timeout_fires_on_slow_server() {
+        use crate::error::{ClientErrorKind, TransportError};
+        use std::time::Duration;
+
+        struct Slow;
+
+        impl HttpClient for Slow {
+            type Error = std::convert::Infallible;
+
+            async fn send_http(
+                &self,
+                _request: http::Request<Vec<u8>>,
+            ) -> Result<http::Response<Vec<u8>>, Self::Error> {
+                n0_future::time::sleep(Duration::from_millis(200)).await;
+                Ok(http::Response::builder().status(200).body(vec![]).unwrap())
+            }
+        }
+
+        let base = Url::parse("https://pds.example.com").unwrap();
+        let err = Slow
+            .xrpc(base.clone())
+            .timeout(Duration::from_millis(10))
+            .send(&DummyReq)
+            .await
+            .err()
+            .expect("request should time out");
+        assert!(matches!(err.kind(), ClientErrorKind::Transport));
+        assert!(matches!(
+            err.transport_error(),
+            Some(TransportError::Timeout)
+        ));
+
+        let resp = Slow
+            .xrpc(base)
+            .timeout(Duration::from_secs(5))
+            .send(&DummyReq)
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
 }
@@ -0,0 +1,301 @@
+//! Cursor pagination over XRPC list endpoints
+//!
+//! [`XrpcPaginator`] drives a [`Paginated`] query, threading the `cursor` from each response
+//! into the next request until the server stops returning one.
+//!
+//! ```no_run
+//! # async fn example<C, P>(client: &C, request: P)
+//! # where
+//! #     C: jacquard_common::xrpc::XrpcClient + Sync,
+//! #     P: jacquard_common::xrpc::Paginated + Clone + Send + Sync,
+//! #     P::Response: Send + Sync,
+//! #     for<'a> jacquard_common::xrpc::RespOutput<'a, P::Response>:
+//! #         jacquard_common::IntoStatic<Output = jacquard_common::xrpc::RespOutput<'static, P::Response>>,
+//! #     for<'a> jacquard_common::xrpc::RespErr<'a, P::Response>:
+//! #         jacquard_common::IntoStatic<Output = jacquard_common::xrpc::RespErr<'static, P::Response>>,
+//! # {
+//! use jacquard_common::xrpc::XrpcPaginator;
+//! use n0_future::StreamExt;
+//!
+//! let mut items = std::pin::pin!(XrpcPaginator::new(client, request).items());
+//! while let Some(item) = items.next().await {
+//!     let item = item.expect("page failed");
+//!     // ...
+//! }
+//! # }
+//! ```
+
+use super::{Paginated, RespErr, RespOutput, XrpcClient, XrpcError};
+use crate::error::ClientError;
+use crate::{CowStr, IntoStatic};
+use n0_future::{Stream, StreamExt};
+
+/// Error yielded by an [`XrpcPaginator`]
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+pub enum PaginateError<E: std::error::Error + IntoStatic> {
+    /// Sending a page request failed
+    #[error("page request failed: {0}")]
+    #[diagnostic(code(jacquard_common::xrpc::paginate::client))]
+    Client(#[from] ClientError),
+
+    /// The server answered a page request with an error
+    #[error("page request failed: {0}")]
+    #[diagnostic(code(jacquard_common::xrpc::paginate::xrpc))]
+    Xrpc(XrpcError<E>),
+}
+
+/// Result of one step of an [`XrpcPaginator`]
+pub type PaginateResult<T, P> =
+    Result<T, PaginateError<RespErr<'static, <P as super::XrpcRequest>::Response>>>;
+
+/// Pages through a [`Paginated`] query
+///
+/// Each page is requested with the cursor returned by the previous one, starting from
+/// whatever cursor the initial request carries. Paging stops once a response comes back
+/// without a cursor, with an empty one, or with the cursor that was just sent. The first
+/// error ends the stream.
+pub struct XrpcPaginator<'c, C, P> {
+    client: &'c C,
+    request: P,
+}
+
+impl<'c, C, P> XrpcPaginator<'c, C, P>
+where
+    C: XrpcClient + Sync,
+    P: Paginated + Clone + Send + Sync,
+    P::Response: Send + Sync,
+    for<'a> RespOutput<'a, P::Response>: IntoStatic<Output = RespOutput<'static, P::Response>>,
+    for<'a> RespErr<'a, P::Response>: IntoStatic<Output = RespErr<'static, P::Response>>,
+{
+    /// Page through `request` using `client`
+    pub fn new(client: &'c C, request: P) -> Self {
+        Self { client, request }
+    }
+
+    /// Stream of whole pages
+    pub fn pages(self) -> impl Stream<Item = PaginateResult<Vec<P::Item>, P>> {
+        n0_future::stream::unfold(Some(self), |state| async move {
+            let Self {
+                client,
+                mut request,
+            } = state?;
+
+            let response = match client.send(request.clone()).await {
+                Ok(response) => response,
+                Err(e) => return Some((Err(PaginateError::Client(e)), None)),
+            };
+            let output = match response.into_output() {
+                Ok(output) => output,
+                Err(e) => return Some((Err(PaginateError::Xrpc(e)), None)),
+            };
+
+            let next = P::next_cursor(&output)
+                .filter(|cursor| !cursor.is_empty() && Some(*cursor) != request.cursor())
+                .map(CowStr::copy_from_str);
+            let items = P::items(output);
+
+            let state = next.map(|cursor| {
+                request.set_cursor(Some(cursor));
+                Self { client, request }
+            });
+            Some((Ok(items), state))
+        })
+    }
+
+    /// Stream of individual items across all pages
+    pub fn items(self) -> impl Stream<Item = PaginateResult<P::Item, P>> {
+        self.pages().flat_map(|page| {
+            let items: Vec<_> = match page {
+                Ok(items) => items.into_iter().map(Ok).collect(),
+                Err(e) => vec![Err(e)],
+            };
+            n0_future::stream::iter(items)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::XrpcResult;
+    use crate::http_client::HttpClient;
+    use crate::xrpc::{
+        CallOptions, GenericError, XrpcExt, XrpcMethod, XrpcRequest, XrpcResp, XrpcResponse,
+    };
+    use serde::{Deserialize, Serialize};
+    use url::Url;
+
+    #[derive(Serialize, Deserialize, Clone)]
+    struct ListThings {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        cursor: Option<String>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct ListThingsOutput<'a> {
+        #[serde(borrow)]
+        cursor: Option<CowStr<'a>>,
+        things: Vec<i64>,
+    }
+
+    impl IntoStatic for ListThingsOutput<'_> {
+        type Output = ListThingsOutput<'static>;
+        fn into_static(self) -> Self::Output {
+            ListThingsOutput {
+                cursor: self.cursor.into_static(),
+                things: self.things,
+            }
+        }
+    }
+
+    struct ListThingsResponse;
+
+    impl XrpcResp for ListThingsResponse {
+        const NSID: &'static str = "com.example.listThings";
+        const ENCODING: &'static str = "application/json";
+        type Output<'de> = ListThingsOutput<'de>;
+        type Err<'de> = GenericError<'de>;
+    }
+
+    impl XrpcRequest for ListThings {
+        const NSID: &'static str = "com.example.listThings";
+        const METHOD: XrpcMethod = XrpcMethod::Query;
+        type Response = ListThingsResponse;
+    }
+
+    impl Paginated for ListThings {
+        type Item = i64;
+
+        fn cursor(&self) -> Option<&str> {
+            self.cursor.as_deref()
+        }
+
+        fn set_cursor(&mut self, cursor: Option<CowStr<'static>>) {
+            self.cursor = cursor.map(|c| c.to_string());
+        }
+
+        fn next_cursor<'o>(output: &'o ListThingsOutput<'static>) -> Option<&'o str> {
+            output.cursor.as_deref()
+        }
+
+        fn items(output: ListThingsOutput<'static>) -> Vec<i64> {
+            output.things
+        }
+    }
+
+    /// Serves `listThings` pages keyed by the request's query string
+    struct Pages(Vec<(&'static str, u16, serde_json::Value)>);
+
+    impl HttpClient for Pages {
+        type Error = std::convert::Infallible;
+
+        async fn send_http(
+            &self,
+            request: http::Request<Vec<u8>>,
+        ) -> Result<http::Response<Vec<u8>>, Self::Error> {
+            let query = request.uri().query().unwrap_or("");
+            let (status, body) = self
+                .0
+                .iter()
+                .find(|(q, _, _)| *q == query)
+                .map(|(_, status, body)| (*status, body.clone()))
+                .unwrap_or((404, serde_json::json!({})));
+            Ok(http::Response::builder()
+                .status(status)
+                .body(serde_json::to_vec(&body).unwrap())
+                .unwrap())
+        }
+    }
+
+    impl XrpcClient for Pages {
+        async fn base_uri(&self) -> Url {
+            Url::parse("https://pds.example.com").unwrap()
+        }
+
+        async fn send<R>(&self, request: R) -> XrpcResult<XrpcResponse<R>>
+        where
+            R: XrpcRequest + Send + Sync,
+            <R as XrpcRequest>::Response: Send + Sync,
+        {
+            self.xrpc(self.base_uri().await).send(&request).await
+        }
+
+        async fn send_with_opts<R>(
+            &self,
+            request: R,
+            _opts: CallOptions<'_>,
+        ) -> XrpcResult<XrpcResponse<R>>
+        where
+            R: XrpcRequest + Send + Sync,
+            <R as XrpcRequest>::Response: Send + Sync,
+        {
+            self.send(request).await
+        }
+    }
+
+    async fn collect(client: &Pages) -> Vec<PaginateResult<i64, ListThings>> {
+        XrpcPaginator::new(client, ListThings { cursor: None })
+            .items()
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn follows_cursor_until_empty() {
+        let client = Pages(vec![
+            (
+                "",
+                200,
+                serde_json::json!({"cursor": "a", "things": [1, 2]}),
+            ),
+            (
+                "cursor=a",
+                200,
+                serde_json::json!({"cursor": "b", "things": [3]}),
+            ),
+            (
+                "cursor=b",
+                200,
+                serde_json::json!({"cursor": "", "things": [4]}),
+            ),
+        ]);
+        let items: Vec<i64> = collect(&client)
+            .await
+            .into_iter()
+            .map(|item| item.unwrap())
+            .collect();
+        assert_eq!(items, vec![1, 2, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn stops_without_cursor_or_on_repeat() {
+        let client = Pages(vec![
+            ("", 200, serde_json::json!({"cursor": "a", "things": [1]})),
+            (
+                "cursor=a",
+                200,
+                serde_json::json!({"cursor": "a", "things": [2]}),
+            ),
+        ]);
+        assert_eq!(collect(&client).await.len(), 2);
+
+        let client = Pages(vec![("", 200, serde_json::json!({"things": [1]}))]);
+        assert_eq!(collect(&client).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn first_error_ends_stream() {
+        let client = Pages(vec![
+            ("", 200, serde_json::json!({"cursor": "a", "things": [1]})),
+            (
+                "cursor=a",
+                400,
+                serde_json::json!({"error": "InvalidRequest", "message": "bad cursor"}),
+            ),
+        ]);
+        let items = collect(&client).await;
+        assert_eq!(items.len(), 2);
+        assert_eq!(*items[0].as_ref().unwrap(), 1);
+        assert!(matches!(items[1], Err(PaginateError::Xrpc(_))));
+    }
+}
@@ -113,17 +113,140 @@ pub struct EventHeader {
     pub t: smol_str::SmolStr,
 }
 
+/// Header as it appears on the wire; error frames have no `t`
+#[derive(serde::Deserialize)]
+struct RawEventHeader {
+    op: i64,
+    t: Option<smol_str::SmolStr>,
+}
+
+/// Body of an error frame (`op: -1`)
+#[derive(serde::Deserialize)]
+struct ErrorFrameBody {
+    error: smol_str::SmolStr,
+    message: Option<String>,
+}
+
 /// Parse a framed DAG-CBOR message header and return the header plus remaining body bytes.
 ///
 /// Used for two-stage deserialization of subscription messages in formats like
-/// `com.atproto.sync.subscribeRepos`.
+/// `com.atproto.sync.subscribeRepos`. Error frames (`op: -1`) are returned as
+/// [`DecodeError::ErrorFrame`] so callers can tell e.g. `FutureCursor` apart from a
+/// malformed message.
 pub fn parse_event_header<'a>(bytes: &'a [u8]) -> Result<(EventHeader, &'a [u8]), DecodeError> {
     let mut cursor = std::io::Cursor::new(bytes);
-    let header: EventHeader = ciborium::de::from_reader(&mut cursor)?;
+    let header: RawEventHeader = ciborium::de::from_reader(&mut cursor)?;
     let position = cursor.position() as usize;
     drop(cursor); // explicit drop before reborrowing bytes
+    let body = &bytes[position..];
+
+    if header.op == -1 {
+        let frame: ErrorFrameBody = ciborium::de::from_reader(body)?;
+        return Err(DecodeError::ErrorFrame {
+            error: frame.error,
+            message: frame.message,
+        });
+    }
+    let t = header
+        .t
+        .ok_or_else(|| DecodeError::UnknownEventType(smol_str::SmolStr::default()))?;
+
+    Ok((EventHeader { op: header.op, t }, body))
+}
+
+/// Decode a framed DAG-CBOR message into a `$type`-tagged union.
+///
+/// The header's `t` is handed to the union as its `$type` before the body's own fields,
+/// so variants are matched on e.g. `#[serde(rename = "#commit")]`, as in generated
+/// subscription unions. Used by `#[derive(XrpcRequest)]` for DAG-CBOR subscriptions.
+pub fn decode_framed_union<'de, T: Deserialize<'de>>(bytes: &'de [u8]) -> Result<T, DecodeError> {
+    let (header, body) = parse_event_header(bytes)?;
+    let mut de = serde_ipld_dagcbor::de::Deserializer::from_slice(body);
+    let message = T::deserialize(TaggedBody {
+        tag: header.t,
+        inner: &mut de,
+    })?;
+    de.end()?;
+    Ok(message)
+}
+
+/// Deserializer for a message body that prepends a `$type` entry to its map
+struct TaggedBody<D> {
+    tag: smol_str::SmolStr,
+    inner: D,
+}
+
+impl<'de, D: serde::Deserializer<'de>> serde::Deserializer<'de> for TaggedBody<D> {
+    type Error = D::Error;
+
+    fn deserialize_any<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, D::Error> {
+        self.inner.deserialize_map(TaggedVisitor {
+            tag: self.tag,
+            visitor,
+        })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map struct enum
+        identifier ignored_any
+    }
+}
 
-    Ok((header, &bytes[position..]))
+struct TaggedVisitor<V> {
+    tag: smol_str::SmolStr,
+    visitor: V,
+}
+
+impl<'de, V: serde::de::Visitor<'de>> serde::de::Visitor<'de> for TaggedVisitor<V> {
+    type Value = V::Value;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("a message body map")
+    }
+
+    fn visit_map<A: serde::de::MapAccess<'de>>(self, map: A) -> Result<V::Value, A::Error> {
+        self.visitor.visit_map(TaggedMap {
+            tag: Some(self.tag),
+            key_sent: false,
+            map,
+        })
+    }
+}
+
+struct TaggedMap<A> {
+    tag: Option<smol_str::SmolStr>,
+    key_sent: bool,
+    map: A,
+}
+
+impl<'de, A: serde::de::MapAccess<'de>> serde::de::MapAccess<'de> for TaggedMap<A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: serde::de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, A::Error> {
+        use serde::de::IntoDeserializer;
+
+        if self.tag.is_some() && !self.key_sent {
+            self.key_sent = true;
+            return seed.deserialize("$type".into_deserializer()).map(Some);
+        }
+        self.map.next_key_seed(seed)
+    }
+
+    fn next_value_seed<S: serde::de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<S::Value, A::Error> {
+        use serde::de::IntoDeserializer;
+
+        match self.tag.take() {
+            Some(tag) => seed.deserialize(tag.as_str().into_deserializer()),
+            None => self.map.next_value_seed(seed),
+        }
+    }
 }
 
 /// Decode JSON messages from a WebSocket stream
@@ -76,9 +76,13 @@
 //! // - impl XrpcResp for GetThingResponse
 //! // - impl XrpcRequest for GetThing
 //! ```
+//!
+//! With `method = Subscription`, `output` is the message union and the derive implements
+//! `XrpcSubscription` (with a `{Name}Stream` marker) instead of `XrpcRequest`.
 
 use proc_macro::TokenStream;
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
 use syn::{Attribute, Data, DeriveInput, Fields, GenericParam, Ident, LitStr, parse_macro_input};
 
 /// Helper function to check if a struct derives bon::Builder or Builder
@@ -128,18 +132,32 @@ fn conflicts_with_builder_macro(ident: &Ident) -> bool {
 /// //     pub extra_data: BTreeMap<SmolStr, Data<'s>>,
 /// // }
 /// ```
+///
+/// If the lexicon has a field of its own called `extra_data`, name the catch-all field
+/// something else with `#[lexicon(extra = "overflow")]`.
 #[proc_macro_attribute]
-pub fn lexicon(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn lexicon(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut extra_name = format_ident!("extra_data");
+    let attr_parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("extra") {
+            let s: LitStr = meta.value()?.parse()?;
+            extra_name = s.parse()?;
+            Ok(())
+        } else {
+            Err(meta.error("unknown lexicon attribute, expected `extra = \"name\"`"))
+        }
+    });
+    parse_macro_input!(attr with attr_parser);
     let mut input = parse_macro_input!(item as DeriveInput);
 
     match &mut input.data {
         Data::Struct(data_struct) => {
             if let Fields::Named(fields) = &mut data_struct.fields {
-                // Check if extra_data field already exists
+                // Check if the catch-all field already exists
                 let has_extra_data = fields
                     .named
                     .iter()
-                    .any(|f| f.ident.as_ref().map(|i| i == "extra_data").unwrap_or(false));
+                    .any(|f| f.ident.as_ref().map(|i| *i == extra_name).unwrap_or(false));
 
                 if !has_extra_data {
                     // Check if the struct derives bon::Builder and doesn't conflict with builder macro
@@ -153,14 +171,14 @@ pub fn lexicon(_attr: TokenStream, item: TokenStream) -> TokenStream {
                         quote! { 'static }
                     };
 
-                    // Add the extra_data field with serde(borrow) if there's a lifetime
+                    // Add the catch-all field with serde(borrow) if there's a lifetime
                     let new_field: syn::Field = if input.generics.lifetimes().next().is_some() {
                         if has_bon_builder {
                             syn::parse_quote! {
                                 #[serde(flatten)]
                                 #[serde(borrow)]
                                 #[builder(default)]
-                                pub extra_data: ::std::collections::BTreeMap<
+                                pub #extra_name: ::std::collections::BTreeMap<
                                     ::jacquard_common::smol_str::SmolStr,
                                     ::jacquard_common::types::value::Data<#lifetime>
                                 >
@@ -169,7 +187,7 @@ pub fn lexicon(_attr: TokenStream, item: TokenStream) -> TokenStream {
                             syn::parse_quote! {
                                 #[serde(flatten)]
                                 #[serde(borrow)]
-                                pub extra_data: ::std::collections::BTreeMap<
+                                pub #extra_name: ::std::collections::BTreeMap<
                                     ::jacquard_common::smol_str::SmolStr,
                                     ::jacquard_common::types::value::Data<#lifetime>
                                 >
@@ -183,7 +201,7 @@ pub fn lexicon(_attr: TokenStream, item: TokenStream) -> TokenStream {
                                 #[serde(skip_serializing_if = "std::option::Option::is_none")]
                                 #[serde(default)]
                                 #[builder(default)]
-                                pub extra_data: Option<::std::collections::BTreeMap<
+                                pub #extra_name: Option<::std::collections::BTreeMap<
                                     ::jacquard_common::smol_str::SmolStr,
                                     ::jacquard_common::types::value::Data<'static>
                                 >>
@@ -193,7 +211,7 @@ pub fn lexicon(_attr: TokenStream, item: TokenStream) -> TokenStream {
                                 #[serde(flatten)]
                                 #[serde(skip_serializing_if = "std::option::Option::is_none")]
                                 #[serde(default)]
-                                pub extra_data:Option<::std::collections::BTreeMap<
+                                pub #extra_name: Option<::std::collections::BTreeMap<
                                     ::jacquard_common::smol_str::SmolStr,
                                     ::jacquard_common::types::value::Data<'static>
                                 >>
@@ -272,7 +290,9 @@ pub fn open_union(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// Derive macro for `IntoStatic` trait.
 ///
 /// Automatically implements conversion from borrowed to owned ('static) types.
-/// Works with structs and enums that have lifetime parameters.
+/// Works with structs and enums that have lifetime parameters. Const generic
+/// parameters are carried through unchanged, so `Foo<'a, const N: usize>` becomes
+/// `Foo<'static, N>`.
 ///
 /// # Example
 /// ```ignore
@@ -288,7 +308,19 @@ pub fn open_union(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// //     }
 /// // }
 /// ```
-#[proc_macro_derive(IntoStatic)]
+///
+/// Fields marked `#[into_static(skip)]` are moved into the output as they are, for fields
+/// whose type doesn't implement `IntoStatic` but is already `'static`:
+///
+/// ```ignore
+/// #[derive(IntoStatic)]
+/// struct Cached<'a> {
+///     text: CowStr<'a>,
+///     #[into_static(skip)]
+///     cache: Arc<Mutex<Cache>>,
+/// }
+/// ```
+#[proc_macro_derive(IntoStatic, attributes(into_static))]
 pub fn derive_into_static(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
@@ -327,6 +359,10 @@ pub fn derive_into_static(input: TokenStream) -> TokenStream {
                 .into();
         }
     };
+    let conversion = match conversion {
+        Ok(conversion) => conversion,
+        Err(e) => return e.to_compile_error().into(),
+    };
 
     let expanded = quote! {
         impl #impl_generics ::jacquard_common::IntoStatic for #name #ty_generics #where_clause {
@@ -341,82 +377,135 @@ pub fn derive_into_static(input: TokenStream) -> TokenStream {
     expanded.into()
 }
 
-fn generate_struct_conversion(name: &syn::Ident, fields: &Fields) -> proc_macro2::TokenStream {
+/// Whether a field is marked `#[into_static(skip)]`
+fn is_skipped(field: &syn::Field) -> syn::Result<bool> {
+    let mut skip = false;
+    for attr in &field.attrs {
+        if !attr.path().is_ident("into_static") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown into_static attribute, expected `skip`"))
+            }
+        })?;
+    }
+    Ok(skip)
+}
+
+/// Convert one field's value, or move it through unchanged if it's skipped
+fn convert_field(
+    field: &syn::Field,
+    value: proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+    if is_skipped(field)? {
+        Ok(value)
+    } else {
+        Ok(quote! { #value.into_static() })
+    }
+}
+
+fn generate_struct_conversion(
+    name: &syn::Ident,
+    fields: &Fields,
+) -> syn::Result<proc_macro2::TokenStream> {
     match fields {
         Fields::Named(fields) => {
-            let field_conversions = fields.named.iter().map(|f| {
-                let field_name = &f.ident;
-                quote! { #field_name: self.#field_name.into_static() }
-            });
-            quote! {
+            let field_conversions = fields
+                .named
+                .iter()
+                .map(|f| {
+                    let field_name = &f.ident;
+                    let value = convert_field(f, quote! { self.#field_name })?;
+                    Ok(quote! { #field_name: #value })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            Ok(quote! {
                 #name {
                     #(#field_conversions),*
                 }
-            }
+            })
         }
         Fields::Unnamed(fields) => {
-            let field_conversions = fields.unnamed.iter().enumerate().map(|(i, _)| {
-                let index = syn::Index::from(i);
-                quote! { self.#index.into_static() }
-            });
-            quote! {
+            let field_conversions = fields
+                .unnamed
+                .iter()
+                .enumerate()
+                .map(|(i, f)| {
+                    let index = syn::Index::from(i);
+                    convert_field(f, quote! { self.#index })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            Ok(quote! {
                 #name(#(#field_conversions),*)
-            }
-        }
-        Fields::Unit => {
-            quote! { #name }
+            })
         }
+        Fields::Unit => Ok(quote! { #name }),
     }
 }
 
 fn generate_enum_conversion(
     name: &syn::Ident,
     data_enum: &syn::DataEnum,
-) -> proc_macro2::TokenStream {
-    let variants = data_enum.variants.iter().map(|variant| {
-        let variant_name = &variant.ident;
-        match &variant.fields {
-            Fields::Named(fields) => {
-                let field_names: Vec<_> = fields.named.iter().map(|f| &f.ident).collect();
-                let field_conversions = field_names.iter().map(|field_name| {
-                    quote! { #field_name: #field_name.into_static() }
-                });
-                quote! {
-                    #name::#variant_name { #(#field_names),* } => {
-                        #name::#variant_name {
-                            #(#field_conversions),*
+) -> syn::Result<proc_macro2::TokenStream> {
+    let variants = data_enum
+        .variants
+        .iter()
+        .map(|variant| {
+            let variant_name = &variant.ident;
+            match &variant.fields {
+                Fields::Named(fields) => {
+                    let field_names: Vec<_> = fields.named.iter().map(|f| &f.ident).collect();
+                    let field_conversions = fields
+                        .named
+                        .iter()
+                        .map(|f| {
+                            let field_name = &f.ident;
+                            let value = convert_field(f, quote! { #field_name })?;
+                            Ok(quote! { #field_name: #value })
+                        })
+                        .collect::<syn::Result<Vec<_>>>()?;
+                    Ok(quote! {
+                        #name::#variant_name { #(#field_names),* } => {
+                            #name::#variant_name {
+                                #(#field_conversions),*
+                            }
                         }
-                    }
+                    })
                 }
-            }
-            Fields::Unnamed(fields) => {
-                let field_bindings: Vec<_> = (0..fields.unnamed.len())
-                    .map(|i| {
-                        syn::Ident::new(&format!("field_{}", i), proc_macro2::Span::call_site())
+                Fields::Unnamed(fields) => {
+                    let field_bindings: Vec<_> = (0..fields.unnamed.len())
+                        .map(|i| {
+                            syn::Ident::new(&format!("field_{}", i), proc_macro2::Span::call_site())
+                        })
+                        .collect();
+                    let field_conversions = fields
+                        .unnamed
+                        .iter()
+                        .zip(&field_bindings)
+                        .map(|(f, binding)| convert_field(f, quote! { #binding }))
+                        .collect::<syn::Result<Vec<_>>>()?;
+                    Ok(quote! {
+                        #name::#variant_name(#(#field_bindings),*) => {
+                            #name::#variant_name(#(#field_conversions),*)
+                        }
                     })
-                    .collect();
-                let field_conversions = field_bindings.iter().map(|binding| {
-                    quote! { #binding.into_static() }
-                });
-                quote! {
-                    #name::#variant_name(#(#field_bindings),*) => {
-                        #name::#variant_name(#(#field_conversions),*)
-                    }
                 }
-            }
-            Fields::Unit => {
-                quote! {
+                Fields::Unit => Ok(quote! {
                     #name::#variant_name => #name::#variant_name
-                }
+                }),
             }
-        }
-    });
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
 
-    quote! {
+    Ok(quote! {
         match self {
             #(#variants),*
         }
-    }
+    })
 }
 
 /// Derive macro for `XrpcRequest` trait.
@@ -427,10 +516,14 @@ fn generate_enum_conversion(
 /// # Attributes
 ///
 /// - `nsid`: Required. The NSID string (e.g., "com.example.myMethod")
-/// - `method`: Required. Either `Query` or `Procedure`
-/// - `output`: Required. The output type (must support lifetime param if request does)
+/// - `method`: Required. `Query`, `Procedure` or `Subscription`
+/// - `output`: Required. The output type (must support lifetime param if request does);
+///   for subscriptions, the message union
 /// - `error`: Optional. Error type (defaults to `GenericError`)
+/// - `encoding`: Subscriptions only. `DagCbor` (the default) or `Json`
+/// - `path`: Subscriptions only. WebSocket path, if not `/xrpc/{nsid}`
 /// - `server`: Optional flag. If present, generates `XrpcEndpoint` impl too
+///   (`SubscriptionEndpoint` for subscriptions)
 ///
 /// # Example
 /// ```ignore
@@ -450,6 +543,25 @@ fn generate_enum_conversion(
 /// - `GetThingResponse` struct implementing `XrpcResp`
 /// - `XrpcRequest` impl for `GetThing`
 /// - Optionally: `GetThingEndpoint` struct implementing `XrpcEndpoint` (if `server` flag present)
+///
+/// Subscriptions are derived on their parameters type:
+/// ```ignore
+/// #[derive(Serialize, Deserialize, XrpcRequest)]
+/// #[xrpc(
+///     nsid = "com.example.subscribeThings",
+///     method = Subscription,
+///     output = ThingMessage,
+/// )]
+/// struct SubscribeThings {
+///     pub cursor: Option<i64>,
+/// }
+/// ```
+///
+/// This generates a `SubscribeThingsStream` struct implementing `SubscriptionResp` and an
+/// `XrpcSubscription` impl for `SubscribeThings`. No response marker or `XrpcRequest` impl
+/// is generated. `output` must be a `$type`-tagged union. DAG-CBOR frames are decoded with
+/// `decode_framed_union`, which tags the body with the header's `t`, so variants are renamed
+/// to the short form (`#[serde(rename = "#commit")]`). JSON messages use the full `$type`.
 #[proc_macro_derive(XrpcRequest, attributes(xrpc))]
 pub fn derive_xrpc_request(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -463,6 +575,9 @@ pub fn derive_xrpc_request(input: TokenStream) -> TokenStream {
 fn xrpc_request_impl(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     // Parse attributes
     let attrs = parse_xrpc_attrs(&input.attrs)?;
+    if let XrpcMethod::Subscription(encoding) = &attrs.method {
+        return Ok(xrpc_subscription_impl(input, &attrs, encoding));
+    }
 
     let name = &input.ident;
     let generics = &input.generics;
@@ -534,17 +649,130 @@ fn xrpc_request_impl(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStrea
     Ok(output)
 }
 
+/// Subscription half of `#[derive(XrpcRequest)]`
+fn xrpc_subscription_impl(
+    input: &DeriveInput,
+    attrs: &XrpcAttrs,
+    encoding: &MessageEncoding,
+) -> proc_macro2::TokenStream {
+    let name = &input.ident;
+    let generics = &input.generics;
+    let has_lifetime = generics.lifetimes().next().is_some();
+    let lifetime = if has_lifetime {
+        quote! { <'_> }
+    } else {
+        quote! {}
+    };
+
+    let nsid = &attrs.nsid;
+    let output_ty = &attrs.output;
+    let error_ty = attrs
+        .error
+        .as_ref()
+        .map(|e| quote! { #e })
+        .unwrap_or_else(|| quote! { ::jacquard_common::xrpc::GenericError });
+    // Frames carry the variant in their header rather than a `$type` field, so DAG-CBOR
+    // messages need a framed decoder; JSON messages are tagged and use the default
+    let decode_message = match encoding {
+        MessageEncoding::Json => quote! {},
+        MessageEncoding::DagCbor => quote! {
+            fn decode_message<'de>(
+                bytes: &'de [u8],
+            ) -> Result<Self::Message<'de>, ::jacquard_common::error::DecodeError> {
+                ::jacquard_common::xrpc::subscription::decode_framed_union(bytes)
+            }
+        },
+    };
+    let encoding = match encoding {
+        MessageEncoding::Json => quote! { ::jacquard_common::xrpc::MessageEncoding::Json },
+        MessageEncoding::DagCbor => quote! { ::jacquard_common::xrpc::MessageEncoding::DagCbor },
+    };
+    // Point at `output = ...` when it isn't a message type the stream can decode
+    let assert_message = quote_spanned! {output_ty.span()=>
+        const _: fn() = || {
+            fn assert_message_union<'de, M>()
+            where
+                M: ::serde::Deserialize<'de> + ::jacquard_common::IntoStatic,
+            {
+            }
+            assert_message_union::<#output_ty<'_>>();
+        };
+    };
+    let custom_path = attrs.path.as_ref().map(|path| {
+        quote! { const CUSTOM_PATH: Option<&'static str> = Some(#path); }
+    });
+
+    let stream_name = format_ident!("{}Stream", name);
+
+    let mut output = quote! {
+        /// Stream marker for #name
+        pub struct #stream_name;
+
+        impl ::jacquard_common::xrpc::SubscriptionResp for #stream_name {
+            const NSID: &'static str = #nsid;
+            const ENCODING: ::jacquard_common::xrpc::MessageEncoding = #encoding;
+            type Message<'de> = #output_ty<'de>;
+            type Error<'de> = #error_ty<'de>;
+
+            #decode_message
+        }
+
+        #assert_message
+
+        impl #generics ::jacquard_common::xrpc::XrpcSubscription for #name #lifetime {
+            const NSID: &'static str = #nsid;
+            const ENCODING: ::jacquard_common::xrpc::MessageEncoding = #encoding;
+            #custom_path
+            type Stream = #stream_name;
+        }
+    };
+
+    if attrs.server {
+        let endpoint_name = format_ident!("{}Endpoint", name);
+        let path = attrs
+            .path
+            .clone()
+            .unwrap_or_else(|| format!("/xrpc/{}", nsid));
+        let params_type = if has_lifetime {
+            quote! { #name<'de> }
+        } else {
+            quote! { #name }
+        };
+
+        output.extend(quote! {
+            /// Endpoint marker for #name (server-side)
+            pub struct #endpoint_name;
+
+            impl ::jacquard_common::xrpc::SubscriptionEndpoint for #endpoint_name {
+                const PATH: &'static str = #path;
+                const ENCODING: ::jacquard_common::xrpc::MessageEncoding = #encoding;
+                type Params<'de> = #params_type;
+                type Stream = #stream_name;
+            }
+        });
+    }
+
+    output
+}
+
 struct XrpcAttrs {
     nsid: String,
     method: XrpcMethod,
     output: syn::Type,
     error: Option<syn::Type>,
+    path: Option<String>,
     server: bool,
 }
 
 enum XrpcMethod {
     Query,
     Procedure,
+    Subscription(MessageEncoding),
+}
+
+enum MessageEncoding {
+    Json,
+    DagCbor,
 }
 
 fn parse_xrpc_attrs(attrs: &[Attribute]) -> syn::Result<XrpcAttrs> {
@@ -552,6 +780,8 @@ fn parse_xrpc_attrs(attrs: &[Attribute]) -> syn::Result<XrpcAttrs> {
     let mut method = None;
     let mut output = None;
     let mut error = None;
+    let mut encoding = None;
+    let mut path = None;
     let mut server = false;
 
     for attr in attrs {
@@ -579,10 +809,15 @@ fn parse_xrpc_attrs(attrs: &[Attribute]) -> syn::Result<XrpcAttrs> {
                         method = Some(XrpcMethod::Procedure);
                         Ok(())
                     }
-                    other => {
-                        Err(meta
-                            .error(format!("unknown method: {}, use Query or Procedure", other)))
+                    "Subscription" => {
+                        // Encoding is filled in once all attributes are read
+                        method = Some(XrpcMethod::Subscription(MessageEncoding::DagCbor));
+                        Ok(())
                     }
+                    other => Err(meta.error(format!(
+                        "unknown method: {}, use Query, Procedure or Subscription",
+                        other
+                    ))),
                 }
             } else if meta.path.is_ident("output") {
                 let value = meta.value()?;
@@ -592,6 +827,28 @@ fn parse_xrpc_attrs(attrs: &[Attribute]) -> syn::Result<XrpcAttrs> {
                 let value = meta.value()?;
                 error = Some(value.parse()?);
                 Ok(())
+            } else if meta.path.is_ident("encoding") {
+                let _eq = meta.input.parse::<syn::Token![=]>()?;
+                let ident: Ident = meta.input.parse()?;
+                match ident.to_string().as_str() {
+                    "Json" => encoding = Some((MessageEncoding::Json, ident.span())),
+                    "DagCbor" => encoding = Some((MessageEncoding::DagCbor, ident.span())),
+                    other => {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            format!(
+                                "unknown encoding: {}, subscription messages are Json or DagCbor",
+                                other
+                            ),
+                        ));
+                    }
+                }
+                Ok(())
+            } else if meta.path.is_ident("path") {
+                let value = meta.value()?;
+                let s: LitStr = value.parse()?;
+                path = Some((s.value(), s.span()));
+                Ok(())
             } else if meta.path.is_ident("server") {
                 server = true;
                 Ok(())
@@ -607,24 +864,47 @@ fn parse_xrpc_attrs(attrs: &[Attribute]) -> syn::Result<XrpcAttrs> {
             "missing required `nsid` attribute",
         )
     })?;
-    let method = method.ok_or_else(|| {
+    let mut method = method.ok_or_else(|| {
         syn::Error::new(
             proc_macro2::Span::call_site(),
             "missing required `method` attribute",
         )
     })?;
-    let output = output.ok_or_else(|| {
+    let output: syn::Type = output.ok_or_else(|| {
         syn::Error::new(
             proc_macro2::Span::call_site(),
             "missing required `output` attribute",
         )
     })?;
 
+    match &mut method {
+        XrpcMethod::Subscription(message_encoding) => {
+            if let Some((encoding, _)) = encoding {
+                *message_encoding = encoding;
+            }
+        }
+        XrpcMethod::Query | XrpcMethod::Procedure => {
+            if let Some((_, span)) = encoding {
+                return Err(syn::Error::new(
+                    span,
+                    "`encoding` only applies to subscriptions; queries and procedures are JSON",
+                ));
+            }
+            if let Some((_, span)) = path {
+                return Err(syn::Error::new(
+                    span,
+                    "`path` only applies to subscriptions; XRPC endpoints live at /xrpc/{nsid}",
+                ));
+            }
+        }
+    }
+
     Ok(XrpcAttrs {
         nsid,
         method,
         output,
         error,
+        path: path.map(|(path, _)| path),
         server,
     })
 }
@@ -635,5 +915,8 @@ fn method_expr(method: &XrpcMethod) -> proc_macro2::TokenStream {
         XrpcMethod::Procedure => {
             quote! { ::jacquard_common::xrpc::XrpcMethod::Procedure("application/json") }
         }
+        XrpcMethod::Subscription(_) => {
+            unreachable!("subscriptions don't have an XRPC method")
+        }
     }
 }
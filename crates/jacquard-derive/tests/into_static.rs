@@ -1,3 +1,5 @@
+use std::marker::PhantomData;
+
 use jacquard_common::{CowStr, IntoStatic};
 use jacquard_derive::IntoStatic;
 
@@ -74,3 +76,74 @@ fn test_enum_into_static() {
         _ => panic!("wrong variant"),
     }
 }
+
+#[derive(IntoStatic)]
+struct ConstGeneric<'a, const N: usize> {
+    names: [CowStr<'a>; N],
+    bytes: [u8; N],
+}
+
+#[test]
+fn test_const_generic_into_static() {
+    let s = ConstGeneric {
+        names: [CowStr::from("a"), CowStr::from("b")],
+        bytes: [1, 2],
+    };
+    let static_s: ConstGeneric<'static, 2> = s.into_static();
+    assert_eq!(static_s.names[1].as_ref(), "b");
+    assert_eq!(static_s.bytes, [1, 2]);
+}
+
+/// Deliberately doesn't implement `IntoStatic`
+#[derive(Debug, PartialEq)]
+struct Handle(u64);
+
+#[derive(IntoStatic)]
+struct SkippedFields<'a> {
+    name: CowStr<'a>,
+    #[into_static(skip)]
+    marker: PhantomData<fn() -> Handle>,
+    #[into_static(skip)]
+    id: u64,
+    #[into_static(skip)]
+    handle: Handle,
+}
+
+#[derive(IntoStatic)]
+enum SkippedVariant<'a> {
+    Tuple(CowStr<'a>, #[into_static(skip)] Handle),
+    Named {
+        name: CowStr<'a>,
+        #[into_static(skip)]
+        handle: Handle,
+    },
+}
+
+#[test]
+fn test_skipped_fields_move_through() {
+    let name = String::from("test");
+    let s = SkippedFields {
+        name: CowStr::from(name.as_str()),
+        marker: PhantomData,
+        id: 7,
+        handle: Handle(42),
+    };
+    let static_s: SkippedFields<'static> = s.into_static();
+    drop(name);
+    assert_eq!(static_s.name.as_ref(), "test");
+    assert_eq!(static_s.marker, PhantomData);
+    assert_eq!(static_s.id, 7);
+    assert_eq!(static_s.handle, Handle(42));
+
+    let e: SkippedVariant<'static> =
+        SkippedVariant::Tuple(CowStr::from("a"), Handle(1)).into_static();
+    assert!(matches!(e, SkippedVariant::Tuple(ref s, Handle(1)) if s.as_ref() == "a"));
+    let e: SkippedVariant<'static> = SkippedVariant::Named {
+        name: CowStr::from("b"),
+        handle: Handle(2),
+    }
+    .into_static();
+    assert!(
+        matches!(e, SkippedVariant::Named { ref name, handle: Handle(2) } if name.as_ref() == "b")
+    );
+}
@@ -87,3 +87,30 @@ fn test_lexicon_roundtrip() {
         panic!("expected nested field to be an object");
     }
 }
+
+// A lexicon with a real `extra_data` field only compiles once the catch-all is renamed
+#[lexicon(extra = "overflow")]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct RecordWithExtraData<'s> {
+    text: &'s str,
+    extra_data: Option<&'s str>,
+}
+
+#[test]
+fn test_lexicon_renamed_extra_field() {
+    let json = r#"{"text":"hello","extraData":"declared","unknown":"field"}"#;
+
+    let record: RecordWithExtraData = serde_json::from_str(json).unwrap();
+
+    assert_eq!(record.text, "hello");
+    assert_eq!(record.extra_data, Some("declared"));
+    assert_eq!(record.overflow.len(), 1);
+    assert!(record.overflow.contains_key("unknown"));
+
+    let roundtrip: serde_json::Value = serde_json::to_value(&record).unwrap();
+    assert_eq!(
+        roundtrip,
+        serde_json::from_str::<serde_json::Value>(json).unwrap()
+    );
+}
@@ -0,0 +1,290 @@
+//! In-memory caching for identity resolution
+//!
+//! [`CachedResolver`] wraps any [`IdentityResolver`] and remembers DID documents and
+//! handle → DID results for a configurable time, so repeated lookups of the same identity
+//! don't go back to the network.
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! use std::time::Duration;
+//! use jacquard_identity::{PublicResolver, cache::CachedResolver, resolver::IdentityResolver};
+//! use jacquard_common::types::string::Handle;
+//!
+//! let resolver = CachedResolver::new(PublicResolver::default())
+//!     .with_doc_ttl(Duration::from_secs(30 * 60));
+//!
+//! let did = resolver.resolve_handle(&Handle::new("alice.bsky.social")?).await?;
+//! let doc = resolver.resolve_did_doc(&did).await?; // fetched
+//! let doc = resolver.resolve_did_doc(&did).await?; // served from the cache
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::resolver::{DidDocResponse, IdentityResolver, ResolverOptions, Result};
+use jacquard_common::smol_str::SmolStr;
+use jacquard_common::types::did::Did;
+use jacquard_common::types::string::Handle;
+use n0_future::time::Instant;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Default time a resolved DID document stays cached
+pub const DEFAULT_DOC_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Default time a handle → DID result stays cached
+///
+/// Shorter than [`DEFAULT_DOC_TTL`], since handles change hands more readily than documents.
+pub const DEFAULT_HANDLE_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Identity resolver that caches results from an inner resolver
+///
+/// DID documents and handle resolutions are kept in separate maps with their own TTLs.
+/// Entries are served until they expire and refreshed from the inner resolver on the next
+/// lookup after that. Failed resolutions, including DID documents served with a non-2xx
+/// status, are never cached. Handles are cached case-insensitively.
+pub struct CachedResolver<R> {
+    inner: R,
+    doc_ttl: Duration,
+    handle_ttl: Duration,
+    docs: Mutex<HashMap<SmolStr, (DidDocResponse, Instant)>>,
+    handles: Mutex<HashMap<SmolStr, (Did<'static>, Instant)>>,
+}
+
+impl<R> CachedResolver<R> {
+    /// Wrap `inner` with the default TTLs
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            doc_ttl: DEFAULT_DOC_TTL,
+            handle_ttl: DEFAULT_HANDLE_TTL,
+            docs: Mutex::new(HashMap::new()),
+            handles: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Set how long DID documents stay cached
+    pub fn with_doc_ttl(mut self, ttl: Duration) -> Self {
+        self.doc_ttl = ttl;
+        self
+    }
+
+    /// Set how long handle → DID results stay cached
+    pub fn with_handle_ttl(mut self, ttl: Duration) -> Self {
+        self.handle_ttl = ttl;
+        self
+    }
+
+    /// Access the wrapped resolver
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    /// Drop the cached document for `did`, along with any handles cached as resolving to it
+    pub fn invalidate(&self, did: &Did<'_>) {
+        self.docs.lock().unwrap().remove(did.as_str());
+        self.handles
+            .lock()
+            .unwrap()
+            .retain(|_, (cached, _)| cached.as_str() != did.as_str());
+    }
+
+    /// Drop every cached entry
+    pub fn clear(&self) {
+        self.docs.lock().unwrap().clear();
+        self.handles.lock().unwrap().clear();
+    }
+
+    fn doc_hit(&self, did: &Did<'_>) -> Option<DidDocResponse> {
+        fresh(&self.docs, did.as_str(), self.doc_ttl)
+    }
+
+    fn handle_hit(&self, handle: &Handle<'_>) -> Option<Did<'static>> {
+        fresh(&self.handles, &handle_key(handle), self.handle_ttl)
+    }
+
+    fn store_doc(&self, did: &Did<'_>, doc: &DidDocResponse) {
+        self.docs
+            .lock()
+            .unwrap()
+            .insert(did.as_str().into(), (doc.clone(), Instant::now()));
+    }
+
+    fn store_handle(&self, handle: &Handle<'_>, did: &Did<'static>) {
+        self.handles
+            .lock()
+            .unwrap()
+            .insert(handle_key(handle), (did.clone(), Instant::now()));
+    }
+}
+
+/// Cache key for `handle`; handles are case-insensitive
+fn handle_key(handle: &Handle<'_>) -> SmolStr {
+    SmolStr::new(handle.as_str().to_ascii_lowercase())
+}
+
+/// Look up `key`, evicting the entry if it has outlived `ttl`
+fn fresh<V: Clone>(
+    map: &Mutex<HashMap<SmolStr, (V, Instant)>>,
+    key: &str,
+    ttl: Duration,
+) -> Option<V> {
+    let mut map = map.lock().unwrap();
+    match map.get(key) {
+        Some((value, stored)) if stored.elapsed() < ttl => Some(value.clone()),
+        Some(_) => {
+            map.remove(key);
+            None
+        }
+        None => None,
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<R: IdentityResolver + Sync> IdentityResolver for CachedResolver<R> {
+    fn options(&self) -> &ResolverOptions {
+        self.inner.options()
+    }
+
+    async fn resolve_handle(&self, handle: &Handle<'_>) -> Result<Did<'static>> {
+        if let Some(did) = self.handle_hit(handle) {
+            return Ok(did);
+        }
+        let did = self.inner.resolve_handle(handle).await?;
+        self.store_handle(handle, &did);
+        Ok(did)
+    }
+
+    async fn resolve_did_doc(&self, did: &Did<'_>) -> Result<DidDocResponse> {
+        if let Some(doc) = self.doc_hit(did) {
+            return Ok(doc);
+        }
+        let doc = self.inner.resolve_did_doc(did).await?;
+        if doc.status.is_success() {
+            self.store_doc(did, &doc);
+        }
+        Ok(doc)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl<R: IdentityResolver> IdentityResolver for CachedResolver<R> {
+    fn options(&self) -> &ResolverOptions {
+        self.inner.options()
+    }
+
+    async fn resolve_handle(&self, handle: &Handle<'_>) -> Result<Did<'static>> {
+        if let Some(did) = self.handle_hit(handle) {
+            return Ok(did);
+        }
+        let did = self.inner.resolve_handle(handle).await?;
+        self.store_handle(handle, &did);
+        Ok(did)
+    }
+
+    async fn resolve_did_doc(&self, did: &Did<'_>) -> Result<DidDocResponse> {
+        if let Some(doc) = self.doc_hit(did) {
+            return Ok(doc);
+        }
+        let doc = self.inner.resolve_did_doc(did).await?;
+        if doc.status.is_success() {
+            self.store_doc(did, &doc);
+        }
+        Ok(doc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use http::StatusCode;
+    use jacquard_common::IntoStatic;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Resolver that counts how often it's asked to hit the "network"
+    #[derive(Default)]
+    struct Counting {
+        options: ResolverOptions,
+        status: StatusCode,
+        handles: AtomicUsize,
+        docs: AtomicUsize,
+    }
+
+    impl IdentityResolver for Counting {
+        fn options(&self) -> &ResolverOptions {
+            &self.options
+        }
+
+        async fn resolve_handle(&self, _handle: &Handle<'_>) -> Result<Did<'static>> {
+            self.handles.fetch_add(1, Ordering::SeqCst);
+            Ok(Did::new_static("did:plc:alice").unwrap())
+        }
+
+        async fn resolve_did_doc(&self, did: &Did<'_>) -> Result<DidDocResponse> {
+            self.docs.fetch_add(1, Ordering::SeqCst);
+            Ok(DidDocResponse {
+                buffer: Bytes::from(format!(r#"{{"id":"{did}"}}"#)),
+                status: self.status,
+                requested: Some(did.clone().into_static()),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn second_resolve_within_ttl_is_cached() {
+        let resolver = CachedResolver::new(Counting::default());
+        let handle = Handle::new("alice.example.com").unwrap();
+
+        let did = resolver.resolve_handle(&handle).await.unwrap();
+        let first = resolver.resolve_did_doc(&did).await.unwrap();
+        assert_eq!(resolver.inner().handles.load(Ordering::SeqCst), 1);
+        assert_eq!(resolver.inner().docs.load(Ordering::SeqCst), 1);
+
+        assert_eq!(resolver.resolve_handle(&handle).await.unwrap(), did);
+        let second = resolver.resolve_did_doc(&did).await.unwrap();
+        assert_eq!(second.buffer, first.buffer);
+        assert_eq!(resolver.inner().handles.load(Ordering::SeqCst), 1);
+        assert_eq!(resolver.inner().docs.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn expired_and_invalidated_entries_refresh() {
+        let resolver = CachedResolver::new(Counting::default()).with_doc_ttl(Duration::ZERO);
+        let handle = Handle::new("alice.example.com").unwrap();
+        let did = resolver.resolve_handle(&handle).await.unwrap();
+
+        resolver.resolve_did_doc(&did).await.unwrap();
+        resolver.resolve_did_doc(&did).await.unwrap();
+        assert_eq!(resolver.inner().docs.load(Ordering::SeqCst), 2);
+
+        resolver.invalidate(&did);
+        resolver.resolve_handle(&handle).await.unwrap();
+        assert_eq!(resolver.inner().handles.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn error_status_documents_are_not_cached() {
+        let resolver = CachedResolver::new(Counting {
+            status: StatusCode::NOT_FOUND,
+            ..Default::default()
+        });
+        let did = Did::new_static("did:plc:alice").unwrap();
+
+        let doc = resolver.resolve_did_doc(&did).await.unwrap();
+        assert_eq!(doc.status, StatusCode::NOT_FOUND);
+        resolver.resolve_did_doc(&did).await.unwrap();
+        assert_eq!(resolver.inner().docs.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn handle_cache_ignores_case() {
+        let resolver = CachedResolver::new(Counting::default());
+        let lower = Handle::new("alice.example.com").unwrap();
+        let mixed = Handle::new("Alice.Example.com").unwrap();
+
+        let did = resolver.resolve_handle(&lower).await.unwrap();
+        assert_eq!(resolver.resolve_handle(&mixed).await.unwrap(), did);
+        assert_eq!(resolver.inner().handles.load(Ordering::SeqCst), 1);
+    }
+}
@@ -55,6 +55,10 @@
 //! let resolver = resolver.with_system_dns();  // Enable DNS TXT resolution
 //! ```
 //!
+//! `JacquardResolver` is generic over any [`HttpClient`], so a custom or instrumented
+//! transport can stand in for `reqwest::Client` in [`JacquardResolver::new`]. reqwest
+//! itself is only a dependency with the default `reqwest-client` feature.
+//!
 //! ## Response types
 //!
 //! Resolution methods return wrapper types that own the response buffer, allowing
@@ -64,29 +68,45 @@
 //! - [`MiniDocResponse`] - Slingshot mini-doc response (partial)
 //!
 //! Both support `.parse()` for borrowing and validation.
+//!
+//! ## Caching
+//!
+//! With the `cache` feature, [`cache::CachedResolver`] wraps any resolver and keeps DID
+//! documents and handle resolutions in memory for a configurable TTL.
+//!
+//! ## PLC audit logs
+//!
+//! With the `plc-audit` feature, [`JacquardResolver::fetch_plc_audit_log`] fetches the
+//! operation history of a `did:plc` and [`plc::verify_plc_log`] checks it against the DID.
 
 // use crate::CowStr; // not currently needed directly here
 
 #![cfg_attr(target_arch = "wasm32", allow(unused))]
+#[cfg(feature = "cache")]
+pub mod cache;
+#[cfg(feature = "plc-audit")]
+pub mod plc;
 pub mod resolver;
 
 use crate::resolver::{
-    DidDocResponse, DidStep, HandleStep, IdentityError, IdentityResolver, MiniDoc, PlcSource,
-    ResolverOptions,
+    AttemptOutcome, DidDocResponse, DidStep, HandleSource, HandleStep, IdentityError,
+    IdentityResolver, MiniDoc, PlcSource, ResolutionTrace, ResolverOptions,
 };
 use bytes::Bytes;
+use http::StatusCode;
 use jacquard_api::com_atproto::identity::resolve_did;
 use jacquard_api::com_atproto::identity::resolve_handle::ResolveHandle;
 #[cfg(feature = "streaming")]
 use jacquard_common::ByteStream;
-use jacquard_common::http_client::HttpClient;
+use jacquard_common::http_client::{HttpClient, ResponseUrl};
 use jacquard_common::types::did::Did;
 use jacquard_common::types::did_doc::DidDocument;
 use jacquard_common::types::ident::AtIdentifier;
 use jacquard_common::xrpc::XrpcExt;
-use jacquard_common::{IntoStatic, types::string::Handle};
+use jacquard_common::{IntoStatic, smol_str, types::string::Handle};
+use n0_future::{FuturesUnordered, StreamExt};
 use percent_encoding::percent_decode_str;
-use reqwest::StatusCode;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use url::{ParseError, Url};
 
 #[cfg(all(feature = "dns", not(target_family = "wasm")))]
@@ -96,17 +116,24 @@ use {
 };
 
 /// Default resolver implementation with configurable fallback order.
+///
+/// Generic over the [`HttpClient`] used for every fetch (well-known, PLC directory, XRPC),
+/// so WASM or custom/instrumented transports can back resolution.
+#[cfg_attr(feature = "reqwest-client", doc = "Defaults to [`reqwest::Client`].")]
 #[derive(Clone)]
-pub struct JacquardResolver {
-    http: reqwest::Client,
+pub struct JacquardResolver<
+    #[cfg(feature = "reqwest-client")] T = reqwest::Client,
+    #[cfg(not(feature = "reqwest-client"))] T,
+> {
+    http: T,
     opts: ResolverOptions,
     #[cfg(feature = "dns")]
     dns: Option<Arc<TokioAsyncResolver>>,
 }
 
-impl JacquardResolver {
+impl<T> JacquardResolver<T> {
     /// Create a new instance of the default resolver with all options (except DNS) up front
-    pub fn new(http: reqwest::Client, opts: ResolverOptions) -> Self {
+    pub fn new(http: T, opts: ResolverOptions) -> Self {
         #[cfg(feature = "tracing")]
         tracing::info!(
             public_fallback = opts.public_fallback_for_handle,
@@ -125,7 +152,7 @@ impl JacquardResolver {
 
     #[cfg(feature = "dns")]
     /// Create a new instance of the default resolver with all options, plus default DNS, up front
-    pub fn new_dns(http: reqwest::Client, opts: ResolverOptions) -> Self {
+    pub fn new_dns(http: T, opts: ResolverOptions) -> Self {
         Self {
             http,
             opts,
@@ -168,6 +195,7 @@ impl JacquardResolver {
     ///
     /// - `did:web:example.com` → `https://example.com/.well-known/did.json`
     /// - `did:web:example.com:user:alice` → `https://example.com/user/alice/did.json`
+    /// - `did:web:localhost%3A8080` → `https://localhost:8080/.well-known/did.json`
     fn did_web_url(&self, did: &Did<'_>) -> resolver::Result<Url> {
         // did:web:example.com[:path:segments]
         let s = did.as_str();
@@ -178,6 +206,12 @@ impl JacquardResolver {
         let host = parts
             .next()
             .ok_or_else(|| IdentityError::unsupported_did_method(s))?;
+        // A port is percent-encoded in the host segment (`%3A`), so decode before parsing.
+        // Anything beyond host[:port] would change what the URL points at, so reject it.
+        let host = percent_decode_str(host).decode_utf8_lossy();
+        if host.is_empty() || host.contains(['/', '?', '#', '@', '\\']) {
+            return Err(IdentityError::unsupported_did_method(s));
+        }
         let mut url = Url::parse(&format!("https://{host}/"))?;
         let path: Vec<&str> = parts.collect();
         if path.is_empty() {
@@ -204,22 +238,9 @@ impl JacquardResolver {
         self.did_web_url(&did).unwrap().to_string()
     }
 
-    async fn get_json_bytes(&self, url: Url) -> resolver::Result<(Bytes, StatusCode)> {
-        let resp = self.http.get(url).send().await?;
-        let status = resp.status();
-        let buf = resp.bytes().await?;
-        Ok((buf, status))
-    }
-
-    async fn get_text(&self, url: Url) -> resolver::Result<String> {
-        let resp = self.http.get(url).send().await?;
-        if resp.status() == StatusCode::OK {
-            Ok(resp.text().await?)
-        } else {
-            Err(IdentityError::transport(
-                resp.error_for_status().unwrap_err(),
-            ))
-        }
+    /// Access the underlying HTTP client
+    pub fn http_client(&self) -> &T {
+        &self.http
     }
 
     #[cfg(feature = "dns")]
@@ -248,7 +269,214 @@ impl JacquardResolver {
     }
 }
 
-impl JacquardResolver {
+impl<T: HttpClient + Sync> JacquardResolver<T> {
+    async fn get_json_bytes(&self, url: Url) -> resolver::Result<(Bytes, StatusCode)> {
+        self.get_json_bytes_checked(url, &mut Vec::new()).await
+    }
+
+    /// Like `get_json_bytes`, but records redirects and non-JSON content types as warnings
+    async fn get_json_bytes_checked(
+        &self,
+        url: Url,
+        warnings: &mut Vec<IdentityWarning>,
+    ) -> resolver::Result<(Bytes, StatusCode)> {
+        let request = http::Request::get(url.as_str())
+            .body(Vec::new())
+            .map_err(IdentityError::transport)?;
+        let resp = self
+            .http
+            .send_http(request)
+            .await
+            .map_err(IdentityError::transport)?;
+        let status = resp.status();
+        if let Some(ResponseUrl(to)) = resp.extensions().get::<ResponseUrl>()
+            && *to != url
+        {
+            warnings.push(IdentityWarning::Redirected {
+                from: url,
+                to: to.clone(),
+            });
+        }
+        if status.is_success() {
+            let content_type = resp
+                .headers()
+                .get(http::header::CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok());
+            let is_json = content_type.is_some_and(|ct| {
+                let essence = ct.split(';').next().unwrap_or_default().trim();
+                essence.eq_ignore_ascii_case("application/json")
+                    || essence.to_ascii_lowercase().ends_with("+json")
+            });
+            if !is_json {
+                warnings.push(IdentityWarning::UnexpectedContentType {
+                    content_type: content_type.map(smol_str::SmolStr::new),
+                });
+            }
+        }
+        Ok((Bytes::from(resp.into_body()), status))
+    }
+
+    async fn get_text(&self, url: Url) -> resolver::Result<String> {
+        let (buf, status) = self.get_json_bytes(url).await?;
+        if status == StatusCode::OK {
+            String::from_utf8(buf.into()).map_err(IdentityError::transport)
+        } else {
+            Err(IdentityError::http_status(status))
+        }
+    }
+
+    /// Resolve a handle to a DID, recording every source consulted along the way.
+    ///
+    /// Follows the same fallback order as [`IdentityResolver::resolve_handle`]. On success the
+    /// trace's [`winner`](ResolutionTrace::winner) is the source that produced the DID. On failure
+    /// the rendered trace is attached to the error as context.
+    ///
+    /// With [`ResolverOptions::race_handle_steps`] set, all steps start at once and the first
+    /// DID found wins; the remaining steps are dropped mid-flight and don't appear in the trace.
+    pub async fn resolve_handle_traced(
+        &self,
+        handle: &Handle<'_>,
+    ) -> resolver::Result<(Did<'static>, ResolutionTrace)> {
+        let mut trace = ResolutionTrace::default();
+        if self.opts.race_handle_steps {
+            let mut pending: FuturesUnordered<_> = self
+                .opts
+                .handle_order
+                .iter()
+                .map(|&step| async move {
+                    let mut step_trace = ResolutionTrace::default();
+                    let did = self
+                        .resolve_handle_step(step, handle, &mut step_trace)
+                        .await;
+                    (step_trace, did)
+                })
+                .collect();
+            while let Some((step_trace, did)) = pending.next().await {
+                trace.attempts.extend(step_trace.attempts);
+                if let Some(did) = did {
+                    return Ok((did, trace));
+                }
+            }
+        } else {
+            for &step in &self.opts.handle_order {
+                if let Some(did) = self.resolve_handle_step(step, handle, &mut trace).await {
+                    return Ok((did, trace));
+                }
+            }
+        }
+        Err(IdentityError::invalid_well_known().with_context(trace.to_string()))
+    }
+
+    /// Try every source belonging to one handle step, stopping at the first that answers
+    ///
+    /// Holds no state across awaits beyond its own trace, so it can be dropped at any point
+    /// when racing steps.
+    async fn resolve_handle_step(
+        &self,
+        step: HandleStep,
+        handle: &Handle<'_>,
+        trace: &mut ResolutionTrace,
+    ) -> Option<Did<'static>> {
+        let host = handle.as_str();
+        match step {
+            HandleStep::DnsTxt => {
+                let outcome = self.resolve_handle_dns(host).await;
+                trace_attempt(trace, step, HandleSource::DnsTxt, outcome)
+            }
+            HandleStep::HttpsWellKnown => {
+                let outcome = match Url::parse(&format!("https://{host}/.well-known/atproto-did")) {
+                    Ok(url) => match self.get_text(url).await {
+                        Ok(text) => Self::parse_atproto_did_body(&text)
+                            .map_or_else(|e| failed(&e), AttemptOutcome::Resolved),
+                        Err(e) => failed(&e),
+                    },
+                    Err(e) => failed(&IdentityError::from(e)),
+                };
+                trace_attempt(trace, step, HandleSource::HttpsWellKnown, outcome)
+            }
+            HandleStep::PdsResolveHandle => {
+                // Prefer PDS XRPC via stateless client
+                let outcome = if self.opts.pds_fallback.is_some() {
+                    self.resolve_handle_via_pds(handle)
+                        .await
+                        .map_or_else(|e| failed(&e), AttemptOutcome::Resolved)
+                } else {
+                    AttemptOutcome::Skipped("no PDS fallback configured".into())
+                };
+                if let Some(did) = trace_attempt(trace, step, HandleSource::Pds, outcome) {
+                    return Some(did);
+                }
+                // Public unauth fallback
+                let outcome = if self.opts.public_fallback_for_handle {
+                    match Url::parse("https://public.api.bsky.app") {
+                        Ok(base) => self.resolve_handle_via_base(base, handle).await,
+                        Err(e) => failed(&IdentityError::from(e)),
+                    }
+                } else {
+                    AttemptOutcome::Skipped("public fallback disabled".into())
+                };
+                if let Some(did) = trace_attempt(trace, step, HandleSource::PublicApi, outcome) {
+                    return Some(did);
+                }
+                // Non-auth path: if PlcSource is Slingshot, use its resolveHandle endpoint.
+                let outcome = if let PlcSource::Slingshot { base } = &self.opts.plc_source {
+                    self.resolve_handle_via_base(base.clone(), handle).await
+                } else {
+                    AttemptOutcome::Skipped("PLC source is not Slingshot".into())
+                };
+                trace_attempt(trace, step, HandleSource::Slingshot, outcome)
+            }
+        }
+    }
+
+    #[allow(unused_variables)]
+    async fn resolve_handle_dns(&self, host: &str) -> AttemptOutcome {
+        #[cfg(feature = "dns")]
+        {
+            if self.dns.is_none() {
+                return AttemptOutcome::Skipped("no DNS resolver configured".into());
+            }
+            match self.dns_txt(host).await {
+                Ok(txts) => txts
+                    .iter()
+                    .filter_map(|txt| txt.strip_prefix("did="))
+                    .find_map(|did_str| Did::new(did_str).ok())
+                    .map(|did| AttemptOutcome::Resolved(did.into_static()))
+                    .unwrap_or_else(|| AttemptOutcome::Failed("no valid did= TXT record".into())),
+                Err(e) => failed(&e),
+            }
+        }
+        #[cfg(not(feature = "dns"))]
+        AttemptOutcome::Skipped("dns feature not enabled".into())
+    }
+
+    /// Unauthenticated `resolveHandle` against an arbitrary XRPC base (public API, Slingshot)
+    async fn resolve_handle_via_base(&self, mut url: Url, handle: &Handle<'_>) -> AttemptOutcome {
+        url.set_path("/xrpc/com.atproto.identity.resolveHandle");
+        match serde_html_form::to_string(ResolveHandle::new().handle((*handle).clone()).build()) {
+            Ok(qs) => url.set_query(Some(&qs)),
+            Err(e) => return AttemptOutcome::Failed(smol_str::format_smolstr!("{e}")),
+        }
+        let (buf, status) = match self.get_json_bytes(url).await {
+            Ok(res) => res,
+            Err(e) => return failed(&e),
+        };
+        if !status.is_success() {
+            return failed(&IdentityError::http_status(status));
+        }
+        let val = match serde_json::from_slice::<serde_json::Value>(&buf) {
+            Ok(val) => val,
+            Err(e) => return failed(&IdentityError::from(e)),
+        };
+        match val.get("did").and_then(|v| v.as_str()) {
+            Some(did_str) => match Did::new_owned(did_str) {
+                Ok(did) => AttemptOutcome::Resolved(did.into_static()),
+                Err(e) => AttemptOutcome::Failed(smol_str::format_smolstr!("invalid DID: {e}")),
+            },
+            None => AttemptOutcome::Failed("response missing did".into()),
+        }
+    }
+
     /// Resolve handle to DID via a PDS XRPC call (stateless, unauth by default)
     pub async fn resolve_handle_via_pds(
         &self,
@@ -300,6 +528,33 @@ impl JacquardResolver {
         Ok(doc_borrowed.into_static())
     }
 
+    /// Fetch the full operation history of a `did:plc` from the PLC directory
+    ///
+    /// Hits `{plc}/{did}/log/audit` and returns the operations oldest first, including
+    /// nullified ones. Pass `did` and the result to [`plc::verify_plc_log`] before trusting
+    /// it. Requires [`PlcSource::PlcDirectory`]; Slingshot doesn't serve audit logs.
+    #[cfg(feature = "plc-audit")]
+    pub async fn fetch_plc_audit_log(
+        &self,
+        did: &Did<'_>,
+    ) -> resolver::Result<Vec<plc::PlcOperation<'static>>> {
+        if !did.as_str().starts_with("did:plc:") {
+            return Err(IdentityError::unsupported_did_method(did.as_str()));
+        }
+        let PlcSource::PlcDirectory { base } = &self.opts.plc_source else {
+            return Err(IdentityError::unsupported_did_method(
+                "audit logs require a PLC directory source",
+            ));
+        };
+        let url = Url::parse(&format!("{}{}/log/audit", base, did.as_str()))?;
+        let (buf, status) = self.get_json_bytes(url).await?;
+        if !status.is_success() {
+            return Err(IdentityError::http_status(status));
+        }
+        let ops: Vec<plc::PlcOperation<'_>> = serde_json::from_slice(&buf)?;
+        Ok(ops.into_static())
+    }
+
     /// Fetch a minimal DID document via a Slingshot mini-doc endpoint, if your PlcSource uses Slingshot.
     /// Returns the raw response wrapper for borrowed parsing and validation.
     pub async fn fetch_mini_doc_via_slingshot(
@@ -332,107 +587,74 @@ impl JacquardResolver {
     }
 }
 
-impl IdentityResolver for JacquardResolver {
+impl<T: HttpClient + Sync> IdentityResolver for JacquardResolver<T> {
     fn options(&self) -> &ResolverOptions {
         &self.opts
     }
     #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), fields(handle = %handle)))]
     async fn resolve_handle(&self, handle: &Handle<'_>) -> resolver::Result<Did<'static>> {
-        let host = handle.as_str();
-        for step in &self.opts.handle_order {
-            match step {
-                HandleStep::DnsTxt => {
-                    #[cfg(feature = "dns")]
-                    {
-                        if let Ok(txts) = self.dns_txt(host).await {
-                            for txt in txts {
-                                if let Some(did_str) = txt.strip_prefix("did=") {
-                                    if let Ok(did) = Did::new(did_str) {
-                                        return Ok(did.into_static());
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-                HandleStep::HttpsWellKnown => {
-                    let url = Url::parse(&format!("https://{host}/.well-known/atproto-did"))?;
-                    if let Ok(text) = self.get_text(url).await {
-                        if let Ok(did) = Self::parse_atproto_did_body(&text) {
-                            return Ok(did);
-                        }
-                    }
-                }
-                HandleStep::PdsResolveHandle => {
-                    // Prefer PDS XRPC via stateless client
-                    if let Ok(did) = self.resolve_handle_via_pds(handle).await {
-                        return Ok(did);
-                    }
-                    // Public unauth fallback
-                    if self.opts.public_fallback_for_handle {
-                        if let Ok(mut url) = Url::parse("https://public.api.bsky.app") {
-                            url.set_path("/xrpc/com.atproto.identity.resolveHandle");
-                            if let Ok(qs) = serde_html_form::to_string(
-                                &ResolveHandle::new().handle((*handle).clone()).build(),
-                            ) {
-                                url.set_query(Some(&qs));
-                            } else {
-                                continue;
-                            }
-                            if let Ok((buf, status)) = self.get_json_bytes(url).await {
-                                if status.is_success() {
-                                    if let Ok(val) =
-                                        serde_json::from_slice::<serde_json::Value>(&buf)
-                                    {
-                                        if let Some(did_str) =
-                                            val.get("did").and_then(|v| v.as_str())
-                                        {
-                                            if let Ok(did) = Did::new_owned(did_str) {
-                                                return Ok(did.into_static());
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    // Non-auth path: if PlcSource is Slingshot, use its resolveHandle endpoint.
-                    if let PlcSource::Slingshot { base } = &self.opts.plc_source {
-                        let mut url = base.clone();
-                        url.set_path("/xrpc/com.atproto.identity.resolveHandle");
-                        if let Ok(qs) = serde_html_form::to_string(
-                            &ResolveHandle::new().handle((*handle).clone()).build(),
-                        ) {
-                            url.set_query(Some(&qs));
-                        } else {
-                            continue;
-                        }
-                        if let Ok((buf, status)) = self.get_json_bytes(url).await {
-                            if status.is_success() {
-                                if let Ok(val) = serde_json::from_slice::<serde_json::Value>(&buf) {
-                                    if let Some(did_str) = val.get("did").and_then(|v| v.as_str()) {
-                                        if let Ok(did) = Did::new_owned(did_str) {
-                                            return Ok(did.into_static());
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
+        self.resolve_handle_traced(handle).await.map(|(did, _)| did)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), fields(did = %did)))]
+    async fn resolve_did_doc(&self, did: &Did<'_>) -> resolver::Result<DidDocResponse> {
+        self.fetch_did_doc(did, &mut Vec::new()).await
+    }
+}
+
+impl<T: HttpClient + Sync> JacquardResolver<T> {
+    /// Resolve many DID documents, with at most `concurrency` requests in flight
+    ///
+    /// Each DID goes through [`resolve_did_doc`](IdentityResolver::resolve_did_doc) and gets
+    /// its own result, so one unresolvable DID doesn't fail the batch. Output order matches
+    /// `dids`. A `concurrency` of zero is treated as one.
+    pub async fn resolve_did_docs(
+        &self,
+        dids: &[Did<'_>],
+        concurrency: usize,
+    ) -> Vec<(Did<'static>, resolver::Result<DidDocResponse>)> {
+        // A fixed pool of workers pulling from a shared cursor bounds the concurrency
+        let next = AtomicUsize::new(0);
+        let mut workers: FuturesUnordered<_> = (0..concurrency.clamp(1, dids.len().max(1)))
+            .map(|_| async {
+                let mut done = Vec::new();
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(did) = dids.get(index) else {
+                        break done;
+                    };
+                    done.push((index, self.resolve_did_doc(did).await));
                 }
+            })
+            .collect();
+
+        let mut results: Vec<_> = dids.iter().map(|_| None).collect();
+        while let Some(done) = workers.next().await {
+            for (index, result) in done {
+                results[index] = Some(result);
             }
         }
-        Err(IdentityError::invalid_well_known())
+        dids.iter()
+            .zip(results)
+            .map(|(did, result)| {
+                let result = result.expect("every DID is handed to a worker");
+                (did.clone().into_static(), result)
+            })
+            .collect()
     }
 
-    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), fields(did = %did)))]
-    async fn resolve_did_doc(&self, did: &Did<'_>) -> resolver::Result<DidDocResponse> {
+    /// Walk `did_order` until a source answers, collecting soft problems along the way
+    async fn fetch_did_doc(
+        &self,
+        did: &Did<'_>,
+        warnings: &mut Vec<IdentityWarning>,
+    ) -> resolver::Result<DidDocResponse> {
         let s = did.as_str();
         for step in &self.opts.did_order {
             match step {
                 DidStep::DidWebHttps if s.starts_with("did:web:") => {
                     let url = self.did_web_url(did)?;
-                    if let Ok((buf, status)) = self.get_json_bytes(url).await {
+                    if let Ok((buf, status)) = self.get_json_bytes_checked(url, warnings).await {
                         return Ok(DidDocResponse {
                             buffer: buf,
                             status,
@@ -448,7 +670,7 @@ impl IdentityResolver for JacquardResolver {
                         }
                         PlcSource::Slingshot { base } => base.join(did.as_str())?,
                     };
-                    if let Ok((buf, status)) = self.get_json_bytes(url).await {
+                    if let Ok((buf, status)) = self.get_json_bytes_checked(url, warnings).await {
                         return Ok(DidDocResponse {
                             buffer: buf,
                             status,
@@ -469,7 +691,9 @@ impl IdentityResolver for JacquardResolver {
                     // Fallback: if Slingshot configured, return mini-doc response (partial doc)
                     if let PlcSource::Slingshot { base } = &self.opts.plc_source {
                         let url = self.slingshot_mini_doc_url(base, did.as_str())?;
-                        let (buf, status) = self.get_json_bytes(url).await?;
+                        let (buf, status) =
+                            self.get_json_bytes_checked(url.clone(), warnings).await?;
+                        warnings.push(IdentityWarning::PartialDocument { source: url });
                         return Ok(DidDocResponse {
                             buffer: buf,
                             status,
@@ -484,7 +708,7 @@ impl IdentityResolver for JacquardResolver {
     }
 }
 
-impl HttpClient for JacquardResolver {
+impl<T: HttpClient + Sync> HttpClient for JacquardResolver<T> {
     async fn send_http(
         &self,
         request: http::Request<Vec<u8>>,
@@ -492,11 +716,14 @@ impl HttpClient for JacquardResolver {
         self.http.send_http(request).await
     }
 
-    type Error = reqwest::Error;
+    type Error = T::Error;
 }
 
 #[cfg(feature = "streaming")]
-impl jacquard_common::http_client::HttpClientExt for JacquardResolver {
+impl<T> jacquard_common::http_client::HttpClientExt for JacquardResolver<T>
+where
+    T: jacquard_common::http_client::HttpClientExt + Sync,
+{
     /// Send HTTP request and return streaming response
     fn send_http_streaming(
         &self,
@@ -534,17 +761,111 @@ impl jacquard_common::http_client::HttpClientExt for JacquardResolver {
     }
 }
 
+/// Push an attempt onto the trace, returning the DID if it resolved
+fn trace_attempt(
+    trace: &mut ResolutionTrace,
+    step: HandleStep,
+    source: HandleSource,
+    outcome: AttemptOutcome,
+) -> Option<Did<'static>> {
+    let did = match &outcome {
+        AttemptOutcome::Resolved(did) => Some(did.clone()),
+        _ => None,
+    };
+    trace.push(step, source, outcome);
+    did
+}
+
+fn failed(e: &IdentityError) -> AttemptOutcome {
+    match e.context() {
+        Some(ctx) => AttemptOutcome::Failed(smol_str::format_smolstr!("{e}: {ctx}")),
+        None => AttemptOutcome::Failed(smol_str::format_smolstr!("{e}")),
+    }
+}
+
 /// Warnings produced during identity checks that are not fatal
+///
+/// Resolution succeeded, but something about the response was off. Callers that want to
+/// be stricter than the resolver can turn any of these into errors.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum IdentityWarning {
     /// The DID doc did not contain the expected handle alias under alsoKnownAs
     HandleAliasMismatch {
         #[allow(missing_docs)]
         expected: Handle<'static>,
     },
+    /// The HTTP client followed a redirect to fetch the document
+    Redirected {
+        /// URL that was requested
+        from: Url,
+        /// URL the document was finally served from
+        to: Url,
+    },
+    /// The document was served with a non-JSON `Content-Type` (or none at all)
+    UnexpectedContentType {
+        /// The `Content-Type` header value, if present
+        content_type: Option<smol_str::SmolStr>,
+    },
+    /// The document's `id` had leading or trailing whitespace, which was trimmed
+    DocIdWhitespace {
+        /// The `id` exactly as served
+        id: smol_str::SmolStr,
+    },
+    /// The document's `id` doesn't match the requested DID
+    DocIdMismatch {
+        /// The DID that was requested
+        expected: Did<'static>,
+        /// The document's `id`
+        actual: smol_str::SmolStr,
+    },
+    /// Only a Slingshot mini-doc was available, so the document is partial
+    PartialDocument {
+        /// URL the mini-doc was fetched from
+        source: Url,
+    },
+    /// A handle couldn't be resolved, so whatever referenced it was dropped
+    UnresolvedHandle {
+        /// The handle as written
+        handle: smol_str::SmolStr,
+        /// Why resolution failed
+        reason: smol_str::SmolStr,
+    },
+}
+
+impl std::fmt::Display for IdentityWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::HandleAliasMismatch { expected } => {
+                write!(
+                    f,
+                    "DID document doesn't list handle {expected} in alsoKnownAs"
+                )
+            }
+            Self::Redirected { from, to } => write!(f, "{from} redirected to {to}"),
+            Self::UnexpectedContentType {
+                content_type: Some(ct),
+            } => write!(f, "DID document served as {ct}, not JSON"),
+            Self::UnexpectedContentType { content_type: None } => {
+                write!(f, "DID document served without a content type")
+            }
+            Self::DocIdWhitespace { id } => {
+                write!(f, "DID document id {id:?} has surrounding whitespace")
+            }
+            Self::DocIdMismatch { expected, actual } => {
+                write!(f, "DID document id {actual} doesn't match {expected}")
+            }
+            Self::PartialDocument { source } => {
+                write!(f, "only a partial mini-doc was available from {source}")
+            }
+            Self::UnresolvedHandle { handle, reason } => {
+                write!(f, "handle {handle} didn't resolve: {reason}")
+            }
+        }
+    }
 }
 
-impl JacquardResolver {
+impl<T: HttpClient + Sync> JacquardResolver<T> {
     /// Resolve a handle to its DID, fetch the DID document, and return doc plus any warnings.
     /// This applies the default equality check on the document id (error with doc if mismatch).
     pub async fn resolve_handle_and_doc(
@@ -552,7 +873,7 @@ impl JacquardResolver {
         handle: &Handle<'_>,
     ) -> resolver::Result<(Did<'static>, DidDocResponse, Vec<IdentityWarning>)> {
         let did = self.resolve_handle(handle).await?;
-        let resp = self.resolve_did_doc(&did).await?;
+        let (resp, mut warnings) = self.resolve_did_doc_with_warnings(&did).await?;
         let resp_for_parse = resp.clone();
         let doc_borrowed = resp_for_parse.parse()?;
         if self.opts.validate_doc_id && doc_borrowed.id.as_str() != did.as_str() {
@@ -561,14 +882,11 @@ impl JacquardResolver {
                 doc_borrowed.clone().into_static(),
             ));
         }
-        let mut warnings = Vec::new();
-        // Check handle alias presence (soft warning)
-        let expected_alias = format!("at://{}", handle.as_str());
+        // Check handle alias presence (soft warning); handles are case-insensitive
         let has_alias = doc_borrowed
-            .also_known_as
-            .as_ref()
-            .map(|v| v.iter().any(|s| s.as_ref() == expected_alias))
-            .unwrap_or(false);
+            .handles()
+            .iter()
+            .any(|h| h.as_str().eq_ignore_ascii_case(handle.as_str()));
         if !has_alias {
             warnings.push(IdentityWarning::HandleAliasMismatch {
                 expected: handle.clone().into_static(),
@@ -577,6 +895,45 @@ impl JacquardResolver {
         Ok((did, resp, warnings))
     }
 
+    /// Fetch a DID document like [`IdentityResolver::resolve_did_doc`], also returning any
+    /// soft problems noticed along the way.
+    ///
+    /// Beyond what `resolve_did_doc` tolerates, a document whose `id` has surrounding
+    /// whitespace is accepted here: the `id` is trimmed in the returned buffer and a
+    /// [`IdentityWarning::DocIdWhitespace`] is reported. An `id` that doesn't match `did`
+    /// is reported as [`IdentityWarning::DocIdMismatch`] rather than an error, whatever
+    /// `validate_doc_id` is set to.
+    pub async fn resolve_did_doc_with_warnings(
+        &self,
+        did: &Did<'_>,
+    ) -> resolver::Result<(DidDocResponse, Vec<IdentityWarning>)> {
+        let mut warnings = Vec::new();
+        let mut resp = self.fetch_did_doc(did, &mut warnings).await?;
+        if !resp.status.is_success() {
+            return Ok((resp, warnings));
+        }
+
+        let Ok(mut json) = serde_json::from_slice::<serde_json::Value>(&resp.buffer) else {
+            return Ok((resp, warnings));
+        };
+        let Some(id) = json.get("id").and_then(|id| id.as_str()) else {
+            return Ok((resp, warnings));
+        };
+        let trimmed = id.trim();
+        if trimmed != did.as_str() {
+            warnings.push(IdentityWarning::DocIdMismatch {
+                expected: did.clone().into_static(),
+                actual: trimmed.into(),
+            });
+        }
+        if trimmed.len() != id.len() {
+            warnings.push(IdentityWarning::DocIdWhitespace { id: id.into() });
+            json["id"] = trimmed.to_owned().into();
+            resp.buffer = Bytes::from(serde_json::to_vec(&json)?);
+        }
+        Ok((resp, warnings))
+    }
+
     /// Build Slingshot mini-doc URL for an identifier (handle or DID)
     fn slingshot_mini_doc_url(&self, base: &Url, identifier: &str) -> resolver::Result<Url> {
         let mut url = base.clone();
@@ -629,8 +986,10 @@ impl MiniDocResponse {
 }
 
 /// Resolver specialized for unauthenticated/public flows using reqwest and stateless XRPC
-pub type PublicResolver = JacquardResolver;
+#[cfg(feature = "reqwest-client")]
+pub type PublicResolver = JacquardResolver<reqwest::Client>;
 
+#[cfg(feature = "reqwest-client")]
 impl Default for PublicResolver {
     /// Build a resolver with:
     /// - reqwest HTTP client
@@ -654,6 +1013,7 @@ impl Default for PublicResolver {
 
 /// Build a resolver configured to use Slingshot (`https://slingshot.microcosm.blue`) for PLC and
 /// mini-doc fallbacks, unauthenticated by default.
+#[cfg(feature = "reqwest-client")]
 pub fn slingshot_resolver_default() -> PublicResolver {
     let http = reqwest::Client::new();
     let mut opts = ResolverOptions::default();
@@ -670,7 +1030,7 @@ mod tests {
 
     #[test]
     fn did_web_urls() {
-        let r = JacquardResolver::new(reqwest::Client::new(), ResolverOptions::default());
+        let r = JacquardResolver::new(CannedClient::default(), ResolverOptions::default());
         assert_eq!(
             r.test_did_web_url_raw("did:web:example.com"),
             "https://example.com/.well-known/did.json"
@@ -681,9 +1041,280 @@ mod tests {
         );
     }
 
+    #[test]
+    fn did_web_url_with_port() {
+        let r = JacquardResolver::new(CannedClient::default(), ResolverOptions::default());
+        assert_eq!(
+            r.test_did_web_url_raw("did:web:localhost%3A8080"),
+            "https://localhost:8080/.well-known/did.json"
+        );
+        assert_eq!(
+            r.test_did_web_url_raw("did:web:example.com%3A3000:user:alice"),
+            "https://example.com:3000/user/alice/did.json"
+        );
+        assert_eq!(
+            r.test_did_web_url_raw("did:web:localhost%3A8080:user:alice"),
+            "https://localhost:8080/user/alice/did.json"
+        );
+        // Lowercase escapes decode the same way
+        assert_eq!(
+            r.test_did_web_url_raw("did:web:localhost%3a8080"),
+            "https://localhost:8080/.well-known/did.json"
+        );
+
+        // Encoded characters that would change the authority are rejected
+        let did = Did::new("did:web:example.com%2Fevil").unwrap();
+        assert!(r.did_web_url(&did).is_err());
+    }
+
+    #[test]
+    fn did_web_url_deep_path() {
+        let r = JacquardResolver::new(CannedClient::default(), ResolverOptions::default());
+        // Colons separate segments; an encoded colon stays inside its segment
+        assert_eq!(
+            r.test_did_web_url_raw("did:web:example.com:orgs:acme%3Ateam:users:alice"),
+            "https://example.com/orgs/acme:team/users/alice/did.json"
+        );
+        assert_eq!(
+            r.test_did_web_url_raw("did:web:example.com%3A8443:a:b%20c:d"),
+            "https://example.com:8443/a/b%20c/d/did.json"
+        );
+    }
+
+    /// Serves canned responses keyed by URL, for exercising the resolver without reqwest
+    ///
+    /// JSON bodies are served as `application/json`, anything else as `text/plain`.
+    /// Requests for a URL in `redirects` are answered from its target, which is reported
+    /// through [`ResponseUrl`] like a redirect-following client would. URLs in `delays`
+    /// take that long to answer.
+    #[derive(Clone, Default)]
+    struct CannedClient {
+        responses: std::collections::HashMap<String, (StatusCode, &'static str)>,
+        redirects: std::collections::HashMap<String, String>,
+        content_types: std::collections::HashMap<String, &'static str>,
+        delays: std::collections::HashMap<String, std::time::Duration>,
+    }
+
+    impl HttpClient for CannedClient {
+        type Error = std::convert::Infallible;
+
+        async fn send_http(
+            &self,
+            request: http::Request<Vec<u8>>,
+        ) -> core::result::Result<http::Response<Vec<u8>>, Self::Error> {
+            let requested = request.uri().to_string();
+            let url = self.redirects.get(&requested).unwrap_or(&requested);
+            if let Some(delay) = self.delays.get(url) {
+                tokio::time::sleep(*delay).await;
+            }
+            let (status, body) = self
+                .responses
+                .get(url)
+                .copied()
+                .unwrap_or((StatusCode::NOT_FOUND, ""));
+            let default_type = if body.starts_with('{') {
+                "application/json"
+            } else {
+                "text/plain"
+            };
+            let content_type = self.content_types.get(url).copied().unwrap_or(default_type);
+            Ok(http::Response::builder()
+                .status(status)
+                .header(http::header::CONTENT_TYPE, content_type)
+                .extension(ResponseUrl(Url::parse(url).unwrap()))
+                .body(body.as_bytes().to_vec())
+                .unwrap())
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves_with_custom_http_client() {
+        let mut http = CannedClient::default();
+        http.responses.insert(
+            "https://alice.example.com/.well-known/atproto-did".into(),
+            (StatusCode::OK, "did:plc:hdhoaan3xa3jiuq4fg4mefid\n"),
+        );
+        http.responses.insert(
+            "https://plc.directory/did:plc:hdhoaan3xa3jiuq4fg4mefid".into(),
+            (
+                StatusCode::OK,
+                r#"{"id": "did:plc:hdhoaan3xa3jiuq4fg4mefid", "alsoKnownAs": ["at://alice.example.com"]}"#,
+            ),
+        );
+        let opts = ResolverOptions {
+            handle_order: vec![HandleStep::HttpsWellKnown],
+            did_order: vec![DidStep::PlcHttp],
+            ..Default::default()
+        };
+        let resolver = JacquardResolver::new(http, opts);
+
+        let handle = Handle::new("alice.example.com").unwrap();
+        let (did, resp, warnings) = resolver.resolve_handle_and_doc(&handle).await.unwrap();
+        assert_eq!(did.as_str(), "did:plc:hdhoaan3xa3jiuq4fg4mefid");
+        assert_eq!(resp.parse().unwrap().id.as_str(), did.as_str());
+        assert!(warnings.is_empty());
+
+        let missing = Handle::new("bob.example.com").unwrap();
+        assert!(resolver.resolve_handle(&missing).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn racing_handle_steps_takes_fastest() {
+        let slow = "https://pds.example.com/xrpc/com.atproto.identity.resolveHandle?handle=alice.example.com";
+        let mut http = CannedClient::default();
+        http.responses.insert(
+            slow.into(),
+            (
+                StatusCode::OK,
+                r#"{"did": "did:plc:slowslowslowslowslowslow"}"#,
+            ),
+        );
+        http.delays
+            .insert(slow.into(), std::time::Duration::from_secs(60));
+        http.responses.insert(
+            "https://alice.example.com/.well-known/atproto-did".into(),
+            (StatusCode::OK, "did:plc:hdhoaan3xa3jiuq4fg4mefid\n"),
+        );
+        let opts = ResolverOptions {
+            pds_fallback: Some(Url::parse("https://pds.example.com").unwrap()),
+            handle_order: vec![HandleStep::PdsResolveHandle, HandleStep::HttpsWellKnown],
+            public_fallback_for_handle: false,
+            race_handle_steps: true,
+            ..Default::default()
+        };
+        let resolver = JacquardResolver::new(http, opts);
+
+        let handle = Handle::new("alice.example.com").unwrap();
+        let (did, trace) = tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            resolver.resolve_handle_traced(&handle),
+        )
+        .await
+        .expect("slow step should not hold up the race")
+        .unwrap();
+        assert_eq!(did.as_str(), "did:plc:hdhoaan3xa3jiuq4fg4mefid");
+        assert_eq!(trace.winner().unwrap().source, HandleSource::HttpsWellKnown);
+        assert_eq!(trace.attempts.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn batch_did_resolution_keeps_order_and_per_item_results() {
+        let mut http = CannedClient::default();
+        for did in [
+            "did:plc:alicealicealicealicealic",
+            "did:plc:bobbobbobbobbobbobbobbob",
+        ] {
+            let url = format!("https://plc.directory/{did}");
+            let body: &'static str = format!(r#"{{"id": "{did}"}}"#).leak();
+            http.responses.insert(url.clone(), (StatusCode::OK, body));
+            // The first DID answers last, so completion order differs from input order
+            if did.contains("alice") {
+                http.delays
+                    .insert(url, std::time::Duration::from_millis(50));
+            }
+        }
+        let resolver = JacquardResolver::new(http, ResolverOptions::default());
+
+        let dids = [
+            Did::new("did:plc:alicealicealicealicealic").unwrap(),
+            Did::new("did:plc:missingmissingmissingmis").unwrap(),
+            Did::new("did:plc:bobbobbobbobbobbobbobbob").unwrap(),
+            Did::new("did:key:zQ3shokFTS3brHcDQrn82RUDfCZESWL1ZdCEJwekUDPQiYBme").unwrap(),
+        ];
+        let results = resolver.resolve_did_docs(&dids, 2).await;
+
+        assert_eq!(results.len(), dids.len());
+        for ((did, result), expected) in results.iter().zip(&dids) {
+            assert_eq!(did, expected);
+            let parsed = result.as_ref().ok().and_then(|resp| resp.parse().ok());
+            match did.as_str() {
+                "did:plc:alicealicealicealicealic" | "did:plc:bobbobbobbobbobbobbobbob" => {
+                    assert_eq!(parsed.unwrap().id.as_str(), did.as_str())
+                }
+                _ => assert!(parsed.is_none(), "{did} should not resolve"),
+            }
+        }
+        assert!(results[3].1.is_err());
+    }
+
+    #[tokio::test]
+    async fn did_doc_warnings() {
+        let did = Did::new("did:plc:hdhoaan3xa3jiuq4fg4mefid").unwrap();
+        let mut http = CannedClient::default();
+        http.redirects.insert(
+            "https://plc.directory/did:plc:hdhoaan3xa3jiuq4fg4mefid".into(),
+            "https://plc.example.com/did:plc:hdhoaan3xa3jiuq4fg4mefid".into(),
+        );
+        http.responses.insert(
+            "https://plc.example.com/did:plc:hdhoaan3xa3jiuq4fg4mefid".into(),
+            (
+                StatusCode::OK,
+                r#"{"id": "did:plc:hdhoaan3xa3jiuq4fg4mefid \n", "alsoKnownAs": ["at://alice.example.com"]}"#,
+            ),
+        );
+        http.content_types.insert(
+            "https://plc.example.com/did:plc:hdhoaan3xa3jiuq4fg4mefid".into(),
+            "text/html; charset=utf-8",
+        );
+        let opts = ResolverOptions {
+            did_order: vec![DidStep::PlcHttp],
+            ..Default::default()
+        };
+        let resolver = JacquardResolver::new(http, opts);
+
+        // The plain method doesn't tolerate the padded id
+        let plain = resolver.resolve_did_doc(&did).await.unwrap();
+        assert!(plain.parse().is_err());
+
+        let (resp, warnings) = resolver.resolve_did_doc_with_warnings(&did).await.unwrap();
+        assert_eq!(resp.parse().unwrap().id, did);
+        assert_eq!(
+            warnings,
+            vec![
+                IdentityWarning::Redirected {
+                    from: "https://plc.directory/did:plc:hdhoaan3xa3jiuq4fg4mefid"
+                        .parse()
+                        .unwrap(),
+                    to: "https://plc.example.com/did:plc:hdhoaan3xa3jiuq4fg4mefid"
+                        .parse()
+                        .unwrap(),
+                },
+                IdentityWarning::UnexpectedContentType {
+                    content_type: Some("text/html; charset=utf-8".into()),
+                },
+                IdentityWarning::DocIdWhitespace {
+                    id: "did:plc:hdhoaan3xa3jiuq4fg4mefid \n".into(),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn did_doc_id_mismatch_is_a_warning() {
+        let mut http = CannedClient::default();
+        http.responses.insert(
+            "https://plc.directory/did:plc:hdhoaan3xa3jiuq4fg4mefid".into(),
+            (StatusCode::OK, r#"{"id": "did:plc:someoneelse"}"#),
+        );
+        let opts = ResolverOptions {
+            did_order: vec![DidStep::PlcHttp],
+            ..Default::default()
+        };
+        let resolver = JacquardResolver::new(http, opts);
+        let did = Did::new("did:plc:hdhoaan3xa3jiuq4fg4mefid").unwrap();
+        let (_, warnings) = resolver.resolve_did_doc_with_warnings(&did).await.unwrap();
+        assert_eq!(
+            warnings,
+            vec![IdentityWarning::DocIdMismatch {
+                expected: did.into_static(),
+                actual: "did:plc:someoneelse".into(),
+            }]
+        );
+    }
+
     #[test]
     fn slingshot_mini_doc_url_build() {
-        let r = JacquardResolver::new(reqwest::Client::new(), ResolverOptions::default());
+        let r = JacquardResolver::new(CannedClient::default(), ResolverOptions::default());
         let base = Url::parse("https://slingshot.microcosm.blue").unwrap();
         let url = r.slingshot_mini_doc_url(&base, "bad-example.com").unwrap();
         assert_eq!(
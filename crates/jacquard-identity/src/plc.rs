@@ -0,0 +1,461 @@
+//! PLC operation logs
+//!
+//! A `did:plc` document is the end state of a chain of signed operations kept by the PLC
+//! directory. [`PlcOperation`] is one entry of that chain as served from
+//! `{plc}/{did}/log/audit` (fetch it with
+//! [`JacquardResolver::fetch_plc_audit_log`](crate::JacquardResolver::fetch_plc_audit_log)),
+//! and [`verify_plc_log`] checks that the operations belong to the DID (the genesis operation
+//! hashes to its identifier) and form an unbroken chain, each signed by a rotation key the one
+//! before it allowed.
+
+use crate::resolver::{IdentityError, Result};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use jacquard_common::service_auth::PublicKey;
+use jacquard_common::types::cid::{ATP_CID_BASE, ATP_CID_HASH};
+use jacquard_common::types::crypto::DAG_CBOR;
+use jacquard_common::types::did::Did;
+use jacquard_common::types::string::Datetime;
+use jacquard_common::types::value::{Array, Data, Object};
+use jacquard_common::{CowStr, IntoStatic};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// One entry of a PLC audit log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlcOperation<'a> {
+    /// DID the operation belongs to
+    #[serde(borrow)]
+    pub did: Did<'a>,
+    /// The signed operation itself
+    #[serde(borrow)]
+    pub operation: Data<'a>,
+    /// CID of the signed operation, referenced by the next operation's `prev`
+    #[serde(borrow)]
+    pub cid: CowStr<'a>,
+    /// Whether the operation was later invalidated by a higher-priority rotation key
+    #[serde(default)]
+    pub nullified: bool,
+    /// When the directory accepted the operation
+    pub created_at: Datetime,
+}
+
+impl IntoStatic for PlcOperation<'_> {
+    type Output = PlcOperation<'static>;
+    fn into_static(self) -> Self::Output {
+        PlcOperation {
+            did: self.did.into_static(),
+            operation: self.operation.into_static(),
+            cid: self.cid.into_static(),
+            nullified: self.nullified,
+            created_at: self.created_at,
+        }
+    }
+}
+
+impl<'a> PlcOperation<'a> {
+    fn field(&self, key: &str) -> Option<&Data<'a>> {
+        match &self.operation {
+            Data::Object(Object(map)) => map.get(key),
+            _ => None,
+        }
+    }
+
+    fn str_field(&self, key: &str) -> Option<&str> {
+        match self.field(key)? {
+            Data::String(s) => Some(s.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Operation type: `plc_operation`, `plc_tombstone`, or the legacy genesis `create`
+    pub fn op_type(&self) -> Option<&str> {
+        self.str_field("type")
+    }
+
+    /// CID of the operation this one replaces, `None` for the genesis operation
+    pub fn prev(&self) -> Option<&str> {
+        self.str_field("prev")
+    }
+
+    /// Base64url signature over the operation's unsigned DAG-CBOR encoding
+    pub fn signature(&self) -> Option<&str> {
+        self.str_field("sig")
+    }
+
+    /// `did:key` rotation keys this operation authorizes to sign the next one
+    ///
+    /// Legacy `create` operations authorize their recovery and signing keys. Tombstones
+    /// authorize nothing.
+    pub fn rotation_keys(&self) -> Vec<&str> {
+        match self.op_type() {
+            Some("create") => ["recoveryKey", "signingKey"]
+                .into_iter()
+                .filter_map(|key| self.str_field(key))
+                .collect(),
+            _ => match self.field("rotationKeys") {
+                Some(Data::Array(Array(keys))) => keys
+                    .iter()
+                    .filter_map(|key| match key {
+                        Data::String(s) => Some(s.as_str()),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            },
+        }
+    }
+
+    /// The bytes the signature covers: the operation without `sig`, as canonical DAG-CBOR
+    pub fn unsigned_bytes(&self) -> Result<Vec<u8>> {
+        let Data::Object(Object(map)) = &self.operation else {
+            return Err(IdentityError::invalid_plc_log("operation is not an object"));
+        };
+        let mut unsigned = map.clone();
+        unsigned.remove("sig");
+        Data::Object(Object(unsigned))
+            .to_canonical_cbor()
+            .map_err(IdentityError::serialization)
+    }
+
+    /// CID of the signed operation, computed from its DAG-CBOR encoding
+    ///
+    /// For an untampered log this equals [`cid`](Self::cid), which is what the next
+    /// operation's `prev` refers to.
+    pub fn compute_cid(&self) -> Result<String> {
+        operation_cid(&self.operation)
+    }
+
+    /// Check the signature against one of `keys`
+    fn signed_by_any(&self, keys: &[&str]) -> Result<()> {
+        let sig = self
+            .signature()
+            .ok_or_else(|| IdentityError::invalid_plc_log("operation is unsigned"))?;
+        let sig = URL_SAFE_NO_PAD
+            .decode(sig.trim_end_matches('='))
+            .map_err(IdentityError::serialization)?;
+        let message = self.unsigned_bytes()?;
+        let signed = keys.iter().any(|key| {
+            PublicKey::from_did_key(key).is_ok_and(|key| key.verify(&message, &sig).is_ok())
+        });
+        if signed {
+            Ok(())
+        } else {
+            Err(IdentityError::invalid_plc_log(
+                "signature does not match any authorized rotation key",
+            ))
+        }
+    }
+}
+
+/// CID (CIDv1, DAG-CBOR, SHA-256) of a signed operation
+fn operation_cid(operation: &Data<'_>) -> Result<String> {
+    let bytes = operation
+        .to_canonical_cbor()
+        .map_err(IdentityError::serialization)?;
+    let digest = Sha256::digest(&bytes);
+    let hash = cid::multihash::Multihash::<64>::wrap(ATP_CID_HASH, &digest)
+        .map_err(IdentityError::serialization)?;
+    Ok(cid::Cid::new_v1(DAG_CBOR, hash).to_string())
+}
+
+/// The `did:plc` a signed genesis operation creates
+///
+/// The identifier is the first 24 characters of the base32 SHA-256 of the operation's
+/// DAG-CBOR encoding, signature included.
+pub fn genesis_did(genesis: &PlcOperation<'_>) -> Result<String> {
+    let bytes = genesis
+        .operation
+        .to_canonical_cbor()
+        .map_err(IdentityError::serialization)?;
+    let mut id = ATP_CID_BASE.encode(Sha256::digest(&bytes));
+    id.truncate(24);
+    Ok(format!("did:plc:{id}"))
+}
+
+/// Verify the PLC audit log of `did`
+///
+/// Every entry must be for `did`, and the genesis operation must hash to it (see
+/// [`genesis_did`]), so a log made up by a mirror with its own keys is rejected. Nullified
+/// operations are then skipped. Each operation's CID is recomputed from its signed bytes and
+/// must match the one the directory served. The genesis operation must be signed by one of
+/// its own rotation keys, and every later operation must reference the previous one through
+/// `prev` and be signed by one of the previous operation's rotation keys. Nothing may follow
+/// a tombstone.
+pub fn verify_plc_log(did: &Did<'_>, ops: &[PlcOperation<'_>]) -> Result<()> {
+    if ops.is_empty() {
+        return Err(IdentityError::invalid_plc_log("log is empty"));
+    }
+    let mut previous: Option<&PlcOperation<'_>> = None;
+    for (index, op) in ops.iter().enumerate() {
+        let context = |e: IdentityError| e.with_context(format!("operation {index} ({})", op.cid));
+        if op.did != *did {
+            return Err(context(IdentityError::invalid_plc_log(
+                "operation belongs to a different DID",
+            )));
+        }
+        if op.nullified {
+            continue;
+        }
+        if op.compute_cid().map_err(context)? != op.cid.as_str() {
+            return Err(context(IdentityError::invalid_plc_log(
+                "cid does not match the signed operation",
+            )));
+        }
+        match previous {
+            None => {
+                if op.prev().is_some() {
+                    return Err(context(IdentityError::invalid_plc_log(
+                        "first operation is not a genesis operation",
+                    )));
+                }
+                if genesis_did(op).map_err(context)? != did.as_str() {
+                    return Err(context(IdentityError::invalid_plc_log(
+                        "genesis operation does not hash to the DID",
+                    )));
+                }
+                op.signed_by_any(&op.rotation_keys()).map_err(context)?;
+            }
+            Some(prev) => {
+                if prev.op_type() == Some("plc_tombstone") {
+                    return Err(context(IdentityError::invalid_plc_log(
+                        "operation follows a tombstone",
+                    )));
+                }
+                if op.prev() != Some(prev.cid.as_str()) {
+                    return Err(context(IdentityError::invalid_plc_log(
+                        "prev does not reference the preceding operation",
+                    )));
+                }
+                op.signed_by_any(&prev.rotation_keys()).map_err(context)?;
+            }
+        }
+        previous = Some(op);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jacquard_common::types::crypto::multikey;
+    use k256::ecdsa::{Signature, SigningKey, signature::Signer};
+
+    fn did_key(key: &SigningKey) -> String {
+        let point = key.verifying_key().to_encoded_point(true);
+        format!("did:key:{}", multikey(0xE7, point.as_bytes()))
+    }
+
+    /// Sign `op` with `signer`, returning the signed operation and its CID
+    fn sign(mut op: serde_json::Value, signer: &SigningKey) -> (serde_json::Value, String) {
+        // Same path the log is parsed through, so strings are encoded verbatim
+        let text = op.to_string();
+        let unsigned: Data = serde_json::from_str(&text).unwrap();
+        let sig: Signature = signer.sign(&unsigned.to_canonical_cbor().unwrap());
+        op["sig"] = URL_SAFE_NO_PAD.encode(sig.to_bytes()).into();
+        let text = op.to_string();
+        let cid = operation_cid(&serde_json::from_str(&text).unwrap()).unwrap();
+        (op, cid)
+    }
+
+    fn entry(did: &str, op: serde_json::Value, cid: &str) -> serde_json::Value {
+        serde_json::json!({
+            "did": did,
+            "operation": op,
+            "cid": cid,
+            "nullified": false,
+            "createdAt": "2024-03-01T12:00:00.000Z",
+        })
+    }
+
+    fn update(rotation_key: &str, prev: &str) -> serde_json::Value {
+        serde_json::json!({
+            "type": "plc_operation",
+            "rotationKeys": [rotation_key],
+            "verificationMethods": {"atproto": rotation_key},
+            "alsoKnownAs": ["at://alice.example.com"],
+            "services": {
+                "atproto_pds": {
+                    "type": "AtprotoPersonalDataServer",
+                    "endpoint": "https://pds.example.com"
+                }
+            },
+            "prev": prev,
+        })
+    }
+
+    /// A log whose rotation key is swapped once: genesis (key A), rotation to key B signed
+    /// by A, then an update signed by B. Returns the log and the DID the genesis creates.
+    fn rotated_log(last_signer: &SigningKey) -> (serde_json::Value, String) {
+        let a = SigningKey::from_slice(&[1; 32]).unwrap();
+        let b = SigningKey::from_slice(&[2; 32]).unwrap();
+        let mut genesis = update(&did_key(&a), "");
+        genesis["prev"] = serde_json::Value::Null;
+        let (genesis, genesis_cid) = sign(genesis, &a);
+        let text = entry("did:plc:placeholder", genesis.clone(), &genesis_cid).to_string();
+        let did = genesis_did(&serde_json::from_str(&text).unwrap()).unwrap();
+        let (rotate, rotate_cid) = sign(update(&did_key(&b), &genesis_cid), &a);
+        let (last, last_cid) = sign(update(&did_key(&b), &rotate_cid), last_signer);
+        let log = serde_json::json!([
+            entry(&did, genesis, &genesis_cid),
+            entry(&did, rotate, &rotate_cid),
+            entry(&did, last, &last_cid),
+        ]);
+        (log, did)
+    }
+
+    #[test]
+    fn verifies_log_with_rotated_key() {
+        let b = SigningKey::from_slice(&[2; 32]).unwrap();
+        let (log, did) = rotated_log(&b);
+        let buf = log.to_string();
+        let ops: Vec<PlcOperation> = serde_json::from_str(&buf).unwrap();
+        assert_eq!(ops.len(), 3);
+        assert_eq!(ops[0].op_type(), Some("plc_operation"));
+        assert_eq!(ops[0].prev(), None);
+        assert_eq!(ops[1].rotation_keys(), vec![did_key(&b).as_str()]);
+        assert!(ops[2].signature().is_some());
+        verify_plc_log(&Did::new(&did).unwrap(), &ops).unwrap();
+    }
+
+    #[test]
+    fn rejects_op_signed_by_rotated_out_key() {
+        let a = SigningKey::from_slice(&[1; 32]).unwrap();
+        let (log, did) = rotated_log(&a);
+        let buf = log.to_string();
+        let ops: Vec<PlcOperation> = serde_json::from_str(&buf).unwrap();
+        let err = verify_plc_log(&Did::new(&did).unwrap(), &ops).unwrap_err();
+        assert!(err.context().unwrap().starts_with("operation 2"));
+    }
+
+    #[test]
+    fn rejects_cid_not_matching_operation() {
+        let b = SigningKey::from_slice(&[2; 32]).unwrap();
+        let (mut log, did) = rotated_log(&b);
+        // Point the rotation at a CID it doesn't hash to, keeping the chain consistent
+        let forged = log[0]["cid"].clone();
+        log[1]["cid"] = forged.clone();
+        log[2]["operation"]["prev"] = forged;
+        let buf = log.to_string();
+        let ops: Vec<PlcOperation> = serde_json::from_str(&buf).unwrap();
+        let err = verify_plc_log(&Did::new(&did).unwrap(), &ops).unwrap_err();
+        assert!(err.context().unwrap().starts_with("operation 1"));
+    }
+
+    #[test]
+    fn rejects_self_consistent_log_for_another_did() {
+        // A well-formed log with the mirror's own keys, served under someone else's DID
+        let b = SigningKey::from_slice(&[2; 32]).unwrap();
+        let (mut log, _) = rotated_log(&b);
+        let victim = "did:plc:ewvi7nxzyoun6zhxrhs64oiz";
+        for entry in log.as_array_mut().unwrap() {
+            entry["did"] = victim.into();
+        }
+        let buf = log.to_string();
+        let ops: Vec<PlcOperation> = serde_json::from_str(&buf).unwrap();
+        let err = verify_plc_log(&Did::new(victim).unwrap(), &ops).unwrap_err();
+        assert!(err.context().unwrap().starts_with("operation 0"));
+    }
+
+    #[test]
+    fn rejects_entry_for_a_different_did() {
+        let b = SigningKey::from_slice(&[2; 32]).unwrap();
+        let (mut log, did) = rotated_log(&b);
+        log[2]["did"] = "did:plc:ewvi7nxzyoun6zhxrhs64oiz".into();
+        let buf = log.to_string();
+        let ops: Vec<PlcOperation> = serde_json::from_str(&buf).unwrap();
+        let err = verify_plc_log(&Did::new(&did).unwrap(), &ops).unwrap_err();
+        assert!(err.context().unwrap().starts_with("operation 2"));
+    }
+
+    /// Audit log checked in at `tests/fixtures/plc_audit_log.json`
+    ///
+    /// The checked-in log comes from `generate_audit_log_fixture`. Run
+    /// `cargo test -p jacquard-identity --features plc-audit --lib capture_audit_log_fixture -- --ignored`
+    /// to replace it with a live capture from plc.directory; these tests hold for either.
+    const AUDIT_LOG: &str = include_str!("../tests/fixtures/plc_audit_log.json");
+
+    const AUDIT_LOG_PATH: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/plc_audit_log.json"
+    );
+
+    #[test]
+    fn verifies_audit_log_fixture() {
+        let ops: Vec<PlcOperation> = serde_json::from_str(AUDIT_LOG).unwrap();
+        // The log rotates its keys at least once
+        let live: Vec<_> = ops.iter().filter(|op| !op.nullified).collect();
+        assert!(
+            live.windows(2)
+                .any(|pair| pair[0].rotation_keys() != pair[1].rotation_keys())
+        );
+        verify_plc_log(&ops[0].did, &ops).unwrap();
+    }
+
+    #[test]
+    fn rejects_audit_log_fixture_with_flipped_sig_byte() {
+        let mut log: serde_json::Value = serde_json::from_str(AUDIT_LOG).unwrap();
+        let entries = log.as_array_mut().unwrap();
+        let index = entries
+            .iter()
+            .rposition(|entry| entry["nullified"] != true)
+            .unwrap();
+        let sig = entries[index]["operation"]["sig"].as_str().unwrap();
+        let mut bytes = URL_SAFE_NO_PAD.decode(sig.trim_end_matches('=')).unwrap();
+        bytes[0] ^= 0x01;
+        entries[index]["operation"]["sig"] = URL_SAFE_NO_PAD.encode(bytes).into();
+        // Keep the CID consistent so the signature is the only thing that fails. Nothing
+        // live follows this operation, so no `prev` points at the old CID.
+        let text = entries[index]["operation"].to_string();
+        entries[index]["cid"] = operation_cid(&serde_json::from_str(&text).unwrap())
+            .unwrap()
+            .into();
+
+        let buf = log.to_string();
+        let ops: Vec<PlcOperation> = serde_json::from_str(&buf).unwrap();
+        let err = verify_plc_log(&ops[0].did, &ops).unwrap_err();
+        assert!(
+            err.context()
+                .unwrap()
+                .starts_with(&format!("operation {index}"))
+        );
+        assert!(err.to_string().contains("signature"), "{err}");
+    }
+
+    /// Writes the audit log fixture from fixed keys, in the directory's audit log format
+    #[test]
+    #[ignore] // Writes the fixture file
+    fn generate_audit_log_fixture() {
+        let b = SigningKey::from_slice(&[2; 32]).unwrap();
+        let (mut log, did) = rotated_log(&b);
+        let created = [
+            "2024-03-01T12:00:00.000Z",
+            "2024-05-14T09:31:12.418Z",
+            "2024-08-02T17:05:47.903Z",
+        ];
+        for (entry, created_at) in log.as_array_mut().unwrap().iter_mut().zip(created) {
+            entry["createdAt"] = created_at.into();
+        }
+        let buf = serde_json::to_string_pretty(&log).unwrap();
+        let ops: Vec<PlcOperation> = serde_json::from_str(&buf).unwrap();
+        verify_plc_log(&Did::new(&did).unwrap(), &ops).unwrap();
+        std::fs::write(AUDIT_LOG_PATH, format!("{buf}\n")).unwrap();
+    }
+
+    /// Replaces the audit log fixture with a live capture
+    #[cfg(all(not(target_family = "wasm"), feature = "reqwest-client"))]
+    #[tokio::test]
+    #[ignore] // Network: fetches a live audit log from plc.directory and writes the fixture file
+    async fn capture_audit_log_fixture() {
+        use crate::JacquardResolver;
+        use crate::resolver::ResolverOptions;
+
+        let resolver = JacquardResolver::new(reqwest::Client::new(), ResolverOptions::default());
+        let did = Did::new_static("did:plc:ewvi7nxzyoun6zhxrhs64oiz").unwrap();
+        let ops = resolver.fetch_plc_audit_log(&did).await.unwrap();
+        verify_plc_log(&did, &ops).unwrap();
+        let buf = serde_json::to_string_pretty(&ops).unwrap();
+        std::fs::write(AUDIT_LOG_PATH, format!("{buf}\n")).unwrap();
+    }
+}
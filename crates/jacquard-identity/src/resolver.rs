@@ -89,7 +89,7 @@ impl DidDocResponse {
             } else if let Ok(mini_doc) = serde_json::from_slice::<MiniDoc<'b>>(&self.buffer) {
                 Ok(DidDocument {
                     id: mini_doc.did,
-                    also_known_as: Some(vec![CowStr::from(mini_doc.handle)]),
+                    also_known_as: Some(vec![CowStr::from(format!("at://{}", mini_doc.handle))]),
                     verification_method: None,
                     service: Some(vec![Service {
                         id: CowStr::new_static("#atproto_pds"),
@@ -133,7 +133,7 @@ impl DidDocResponse {
             } else if let Ok(mini_doc) = serde_json::from_slice::<MiniDoc<'_>>(&self.buffer) {
                 Ok(DidDocument {
                     id: mini_doc.did,
-                    also_known_as: Some(vec![CowStr::from(mini_doc.handle)]),
+                    also_known_as: Some(vec![CowStr::from(format!("at://{}", mini_doc.handle))]),
                     verification_method: None,
                     service: Some(vec![Service {
                         id: CowStr::new_static("#atproto_pds"),
@@ -181,6 +181,91 @@ pub enum HandleStep {
     PdsResolveHandle,
 }
 
+/// Concrete source consulted while resolving a handle.
+///
+/// Finer grained than [`HandleStep`]: a single `PdsResolveHandle` step may consult the
+/// configured PDS, the public API, and Slingshot in turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandleSource {
+    /// DNS TXT _atproto.\<handle\>
+    DnsTxt,
+    /// HTTPS well-known atproto-did
+    HttpsWellKnown,
+    /// `resolveHandle` against the configured `pds_fallback`
+    Pds,
+    /// `resolveHandle` against the public Bluesky AppView
+    PublicApi,
+    /// `resolveHandle` against Slingshot
+    Slingshot,
+}
+
+/// Outcome of a single handle resolution attempt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttemptOutcome {
+    /// The source produced this DID
+    Resolved(Did<'static>),
+    /// The source was not consulted (not configured or not compiled in)
+    Skipped(SmolStr),
+    /// The source was consulted but did not produce a DID
+    Failed(SmolStr),
+}
+
+/// One entry in a [`ResolutionTrace`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandleAttempt {
+    /// Configured step this attempt belongs to
+    pub step: HandleStep,
+    /// Source consulted within the step
+    pub source: HandleSource,
+    /// What happened
+    pub outcome: AttemptOutcome,
+}
+
+/// Record of every source consulted during handle resolution, in order.
+///
+/// Returned by [`JacquardResolver::resolve_handle_traced`](crate::JacquardResolver::resolve_handle_traced).
+/// Resolution stops at the first success, so a successful trace always ends with the winning attempt.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResolutionTrace {
+    /// Attempts in the order they were made (or finished, when racing steps)
+    pub attempts: Vec<HandleAttempt>,
+}
+
+impl ResolutionTrace {
+    /// Record an attempt
+    pub fn push(&mut self, step: HandleStep, source: HandleSource, outcome: AttemptOutcome) {
+        self.attempts.push(HandleAttempt {
+            step,
+            source,
+            outcome,
+        });
+    }
+
+    /// The attempt that produced the resolved DID, if any
+    pub fn winner(&self) -> Option<&HandleAttempt> {
+        self.attempts
+            .iter()
+            .find(|a| matches!(a.outcome, AttemptOutcome::Resolved(_)))
+    }
+}
+
+impl std::fmt::Display for ResolutionTrace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, attempt) in self.attempts.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{:?}: ", attempt.source)?;
+            match &attempt.outcome {
+                AttemptOutcome::Resolved(did) => write!(f, "resolved {did}")?,
+                AttemptOutcome::Skipped(reason) => write!(f, "skipped ({reason})")?,
+                AttemptOutcome::Failed(reason) => write!(f, "failed ({reason})")?,
+            }
+        }
+        Ok(())
+    }
+}
+
 /// DID → Doc fallback step.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DidStep {
@@ -196,7 +281,7 @@ pub enum DidStep {
 ///
 /// - `plc_source`: where to fetch did:plc documents (PLC Directory or Slingshot).
 /// - `pds_fallback`: optional base URL of a PDS for XRPC fallbacks (stateless
-///   XRPC over reqwest; authentication can be layered as needed).
+///   XRPC over the resolver's HTTP client; authentication can be layered as needed).
 /// - `handle_order`/`did_order`: ordered strategies for resolution.
 /// - `validate_doc_id`: if true (default), convenience helpers validate doc `id` against the requested DID,
 ///   returning `DocIdMismatch` with the fetched document on mismatch.
@@ -204,6 +289,8 @@ pub enum DidStep {
 ///   `https://public.api.bsky.app/xrpc/com.atproto.identity.resolveHandle` as an unauth fallback.
 ///   There is no public fallback for DID documents; when `PdsResolveDid` is chosen and the PDS XRPC
 ///   client fails, the resolver falls back to Slingshot mini-doc (partial) if `PlcSource::Slingshot` is configured.
+/// - `race_handle_steps`: if true, start every handle step at once and use the first DID found
+///   rather than waiting on each step in turn (default false).
 #[derive(Debug, Clone, Builder)]
 #[builder(start_fn = new)]
 pub struct ResolverOptions {
@@ -219,6 +306,10 @@ pub struct ResolverOptions {
     pub validate_doc_id: bool,
     /// Allow public unauthenticated fallback for resolveHandle via public.api.bsky.app
     pub public_fallback_for_handle: bool,
+    /// Run all `handle_order` steps concurrently and take the first DID found, instead of
+    /// trying them one after another
+    #[builder(default)]
+    pub race_handle_steps: bool,
 }
 
 impl Default for ResolverOptions {
@@ -252,7 +343,7 @@ impl Default for ResolverOptions {
 /// - HTTPS well-known for handles and `did:web`
 /// - PLC directory or Slingshot for `did:plc`
 /// - Slingshot `resolveHandle` (unauthenticated) when configured as the PLC source
-/// - PDS fallbacks via helpers that use stateless XRPC on top of reqwest
+/// - PDS fallbacks via helpers that use stateless XRPC on top of its HTTP client
 
 #[cfg_attr(not(target_arch = "wasm32"), trait_variant::make(Send))]
 pub trait IdentityResolver {
@@ -572,6 +663,16 @@ pub enum IdentityErrorKind {
     )]
     InvalidDoc(SmolStr),
 
+    /// PLC operation log failed verification
+    #[error("invalid PLC operation log: {0}")]
+    #[diagnostic(
+        code(jacquard::identity::invalid_plc_log),
+        help(
+            "the directory served a log that doesn't form a validly signed chain; do not trust it"
+        )
+    )]
+    InvalidPlcLog(SmolStr),
+
     /// DID document id mismatch - includes the fetched document for inspection
     #[error("DID document id mismatch")]
     #[diagnostic(
@@ -675,6 +776,11 @@ impl IdentityError {
         Self::new(IdentityErrorKind::InvalidDoc(msg.into()), None)
     }
 
+    /// Create an invalid PLC log error
+    pub fn invalid_plc_log(msg: impl Into<SmolStr>) -> Self {
+        Self::new(IdentityErrorKind::InvalidPlcLog(msg.into()), None)
+    }
+
     /// Create a doc id mismatch error
     pub fn doc_id_mismatch(expected: Did<'static>, doc: DidDocument<'static>) -> Self {
         Self::new(IdentityErrorKind::DocIdMismatch { expected, doc }, None)
@@ -737,6 +843,7 @@ impl From<AtDataError> for IdentityError {
     }
 }
 
+#[cfg(feature = "reqwest-client")]
 impl From<reqwest::Error> for IdentityError {
     fn from(e: reqwest::Error) -> Self {
         Self::transport(e).with_context("HTTP request failed during identity resolution")
@@ -779,4 +886,35 @@ mod tests {
             other => panic!("unexpected result: {:?}", other),
         }
     }
+
+    #[test]
+    fn resolution_trace_winner_and_display() {
+        let did = Did::new_owned("did:plc:alice").unwrap();
+        let mut trace = ResolutionTrace::default();
+        trace.push(
+            HandleStep::DnsTxt,
+            HandleSource::DnsTxt,
+            AttemptOutcome::Skipped("no DNS resolver configured".into()),
+        );
+        trace.push(
+            HandleStep::HttpsWellKnown,
+            HandleSource::HttpsWellKnown,
+            AttemptOutcome::Failed("HTTP 404 Not Found".into()),
+        );
+        assert!(trace.winner().is_none());
+        trace.push(
+            HandleStep::PdsResolveHandle,
+            HandleSource::PublicApi,
+            AttemptOutcome::Resolved(did.clone()),
+        );
+        let winner = trace.winner().expect("winner");
+        assert_eq!(winner.source, HandleSource::PublicApi);
+        assert_eq!(winner.outcome, AttemptOutcome::Resolved(did));
+        assert_eq!(
+            trace.to_string(),
+            "DnsTxt: skipped (no DNS resolver configured); \
+             HttpsWellKnown: failed (HTTP 404 Not Found); \
+             PublicApi: resolved did:plc:alice"
+        );
+    }
 }
@@ -12,7 +12,14 @@ fn main() -> miette::Result<()> {
     println!("Loaded {} lexicon documents", corpus.iter().count());
 
     println!("Generating code...");
-    let codegen = CodeGenerator::new(&corpus, "crate".to_string());
+    let codegen = args
+        .external_crates
+        .iter()
+        .fold(
+            CodeGenerator::new(&corpus, "crate".to_string()),
+            |codegen, (prefix, krate)| codegen.with_external_crate(prefix, krate),
+        )
+        .with_server_endpoints(!args.no_server);
     codegen.write_to_disk(&args.output)?;
 
     println!("Generated code to {:?}", args.output);
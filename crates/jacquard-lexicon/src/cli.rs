@@ -28,8 +28,28 @@ pub struct CodegenArgs {
     #[arg(short = 'o', long)]
     pub output: PathBuf,
 
+    /// Resolve refs under an NSID prefix to an existing crate instead of generating
+    /// them, e.g. `--external-crate app.bsky=jacquard_api` (repeatable)
+    #[arg(long = "external-crate", value_name = "PREFIX=CRATE", value_parser = parse_external_crate)]
+    pub external_crates: Vec<(String, String)>,
+
+    /// Only emit client-side types; skip the `XrpcEndpoint`/`SubscriptionEndpoint` marker
+    /// types used by server frameworks such as `jacquard-axum`
+    #[arg(long)]
+    pub no_server: bool,
     // TODO: root_module causes issues when set to anything other than "crate", needs rework
     // /// Root module name (default: "crate")
     // #[arg(short = 'r', long, default_value = "crate")]
     // pub root_module: String,
 }
+
+/// Parse a `prefix=crate_path` mapping for `--external-crate`
+fn parse_external_crate(s: &str) -> Result<(String, String), String> {
+    let (prefix, crate_path) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected PREFIX=CRATE, got `{s}`"))?;
+    if prefix.is_empty() || crate_path.is_empty() {
+        return Err(format!("expected PREFIX=CRATE, got `{s}`"));
+    }
+    Ok((prefix.to_string(), crate_path.to_string()))
+}
@@ -21,6 +21,10 @@ pub struct CodeGenerator<'c> {
         std::cell::RefCell<std::collections::HashMap<String, std::collections::HashSet<String>>>,
     /// Track which file paths contain subscription endpoints
     subscription_files: std::cell::RefCell<std::collections::HashSet<std::path::PathBuf>>,
+    /// Namespace prefixes whose types live in another crate (prefix -> crate path)
+    external_crates: std::collections::BTreeMap<String, String>,
+    /// Whether to emit server-side `XrpcEndpoint`/`SubscriptionEndpoint` marker types
+    server_endpoints: bool,
 }
 
 impl<'c> CodeGenerator<'c> {
@@ -31,9 +35,53 @@ impl<'c> CodeGenerator<'c> {
             root_module: root_module.into(),
             namespace_deps: std::cell::RefCell::new(std::collections::HashMap::new()),
             subscription_files: std::cell::RefCell::new(std::collections::HashSet::new()),
+            external_crates: std::collections::BTreeMap::new(),
+            server_endpoints: true,
         }
     }
 
+    /// Set whether to emit server-side endpoint types (on by default)
+    ///
+    /// Each query and procedure gets a `...Request` marker implementing `XrpcEndpoint`,
+    /// and each subscription a `...Endpoint` marker implementing `SubscriptionEndpoint`,
+    /// which is what `jacquard-axum`'s extractors and router helpers build on. Turn this
+    /// off for client-only crates.
+    pub fn with_server_endpoints(mut self, emit: bool) -> Self {
+        self.server_endpoints = emit;
+        self
+    }
+
+    /// Resolve refs under an NSID prefix to an existing crate instead of generating them
+    ///
+    /// `with_external_crate("app.bsky", "jacquard_api")` makes a ref to
+    /// `app.bsky.feed.post` resolve to `jacquard_api::app_bsky::feed::post::Post`,
+    /// and skips generating code for any `app.bsky.*` lexicons in the corpus.
+    /// Keeping the external lexicons in the corpus is still worthwhile: it lets the
+    /// generator pick the right type names and lifetimes for them.
+    pub fn with_external_crate(
+        mut self,
+        prefix: impl Into<String>,
+        crate_path: impl Into<String>,
+    ) -> Self {
+        self.external_crates
+            .insert(prefix.into(), crate_path.into());
+        self
+    }
+
+    /// Crate path for an NSID covered by an external crate mapping, if any
+    ///
+    /// When several prefixes match, the longest one wins.
+    fn external_crate_for(&self, nsid: &str) -> Option<&str> {
+        self.external_crates
+            .iter()
+            .filter(|(prefix, _)| {
+                nsid.strip_prefix(prefix.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+            })
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, crate_path)| crate_path.as_str())
+    }
+
     /// Generate doc comment from optional description (wrapper for utils function)
     fn generate_doc_comment(&self, desc: Option<&jacquard_common::CowStr>) -> TokenStream {
         utils::generate_doc_comment(desc)
@@ -272,6 +320,25 @@ mod tests {
         assert!(formatted.contains("pub feed"));
         assert!(formatted.contains("BlockedActor"));
         assert!(formatted.contains("BlockedByActor"));
+        assert!(
+            formatted.contains("impl jacquard_common::xrpc::XrpcEndpoint for GetAuthorFeedRequest")
+        );
+        // cursor param + cursor/feed output makes it paginated
+        assert!(
+            formatted.contains("impl<'a> jacquard_common::xrpc::Paginated for GetAuthorFeed<'a>")
+        );
+        assert!(formatted.contains("output.feed"));
+
+        // Client-only output drops the server endpoint marker
+        let client_only = CodeGenerator::new(&corpus, "jacquard_api").with_server_endpoints(false);
+        let tokens = client_only
+            .generate_def("app.bsky.feed.getAuthorFeed", "main", def)
+            .expect("generate");
+        let file: syn::File = syn::parse2(tokens).expect("parse tokens");
+        let formatted = prettyplease::unparse(&file);
+        assert!(formatted.contains("XrpcRequest for GetAuthorFeed"));
+        assert!(!formatted.contains("XrpcEndpoint"));
+        assert!(!formatted.contains("GetAuthorFeedRequest"));
     }
 
     #[test]
@@ -304,6 +371,67 @@ mod tests {
         assert!(formatted.contains("fn as_str(&self)"));
     }
 
+    #[test]
+    fn test_generate_schema_defaults() {
+        let corpus = LexiconCorpus::new();
+        let codegen = CodeGenerator::new(&corpus, "jacquard_api");
+
+        use jacquard_common::IntoStatic;
+
+        let doc: crate::lexicon::LexiconDoc = serde_json::from_str(
+            r#"{
+              "lexicon": 1,
+              "id": "com.example.settings",
+              "defs": {
+                "main": {
+                  "type": "record",
+                  "record": {
+                    "type": "object",
+                    "required": ["visibility", "version"],
+                    "properties": {
+                      "visibility": { "type": "string", "default": "public" },
+                      "version": { "type": "integer", "const": 2 }
+                    }
+                  }
+                },
+                "prefs": {
+                  "type": "object",
+                  "required": ["mode"],
+                  "properties": {
+                    "mode": { "type": "string", "default": "auto" }
+                  }
+                }
+              }
+            }"#,
+        )
+        .expect("parse lexicon");
+        let doc = doc.into_static();
+
+        let tokens = codegen
+            .generate_def("com.example.settings", "main", &doc.defs["main"])
+            .expect("generate");
+        let file: syn::File = syn::parse2(tokens).expect("parse tokens");
+        let formatted = prettyplease::unparse(&file);
+        println!("\n{}\n", formatted);
+
+        assert!(formatted.contains("#[serde(default = \"_default_settings_visibility\")]"));
+        assert!(formatted.contains("fn _default_settings_version() -> i64"));
+        assert!(formatted.contains("#[builder(default = 2i64)]"));
+        assert!(formatted.contains("CowStr::new_static(\"public\")"));
+
+        // Default-able objects get a Default impl using the schema values
+        let tokens = codegen
+            .generate_def("com.example.settings", "prefs", &doc.defs["prefs"])
+            .expect("generate");
+        let file: syn::File = syn::parse2(tokens).expect("parse tokens");
+        let formatted = prettyplease::unparse(&file);
+        println!("\n{}\n", formatted);
+
+        assert!(formatted.contains("impl Default for Prefs<'_>"));
+        assert!(formatted.contains("mode: jacquard_common::CowStr::new_static(\"auto\")"));
+        assert!(!formatted.contains("Default)]"));
+    }
+
     #[test]
     fn test_nsid_to_file_path() {
         let corpus =
@@ -359,4 +487,53 @@ mod tests {
         assert!(post_content.contains("pub struct Post"));
         assert!(post_content.contains("jacquard_common"));
     }
+
+    #[test]
+    fn test_external_crate_refs() {
+        let corpus =
+            LexiconCorpus::load_from_dir("tests/fixtures/test_lexicons").expect("load corpus");
+        let codegen =
+            CodeGenerator::new(&corpus, "crate").with_external_crate("com.atproto", "jacquard_api");
+
+        assert_eq!(
+            codegen.external_crate_for("com.atproto.repo.strongRef"),
+            Some("jacquard_api")
+        );
+        assert_eq!(codegen.external_crate_for("com.atprotocol.thing"), None);
+
+        // Refs into the external namespace use the external crate path
+        let ty = codegen
+            .ref_to_rust_type("com.atproto.repo.strongRef")
+            .expect("ref type")
+            .to_string()
+            .replace(' ', "");
+        assert_eq!(
+            ty,
+            "jacquard_api::com_atproto::repo::strong_ref::StrongRef<'a>"
+        );
+
+        // Even when the corpus doesn't contain the referenced lexicon
+        let ty = codegen
+            .ref_to_rust_type("com.atproto.sync.subscribeRepos#commit")
+            .expect("ref type")
+            .to_string()
+            .replace(' ', "");
+        assert_eq!(
+            ty,
+            "jacquard_api::com_atproto::sync::subscribe_repos::Commit<'a>"
+        );
+
+        // Local refs are unaffected
+        let ty = codegen
+            .ref_to_rust_type("app.bsky.embed.images")
+            .expect("ref type")
+            .to_string()
+            .replace(' ', "");
+        assert_eq!(ty, "crate::app_bsky::embed::images::Images<'a>");
+
+        // External lexicons are not generated
+        let files = codegen.generate_all().expect("generate all");
+        assert!(files.contains_key(std::path::Path::new("app_bsky/feed/post.rs")));
+        assert!(files.keys().all(|path| !path.starts_with("com_atproto")));
+    }
 }
@@ -17,6 +17,10 @@ impl<'c> CodeGenerator<'c> {
 
         // Generate code for all lexicons
         for (nsid, doc) in self.corpus.iter() {
+            // Types for external namespaces already exist in another crate
+            if self.external_crate_for(nsid.as_ref()).is_some() {
+                continue;
+            }
             let file_path = self.nsid_to_file_path(nsid.as_ref());
 
             // Track which NSID this file is for
@@ -249,6 +253,9 @@ impl<'c> CodeGenerator<'c> {
 
         // Collect all namespaces from the corpus (first two segments of each NSID)
         for (nsid, _doc) in self.corpus.iter() {
+            if self.external_crate_for(nsid.as_str()).is_some() {
+                continue;
+            }
             let parts: Vec<_> = nsid.as_str().splitn(3, '.').collect();
             let namespace = if parts.len() >= 2 {
                 format!("{}.{}", parts[0], parts[1])
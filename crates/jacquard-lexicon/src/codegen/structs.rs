@@ -55,6 +55,68 @@ pub(crate) fn all_required_are_defaultable_strings(obj: &LexObject<'static>) ->
     })
 }
 
+/// Schema-provided value for a field, from its `const` or `default`.
+/// Returns the Rust type and an expression producing the value, for field types
+/// whose generated Rust type can hold it (booleans, integers and bare strings).
+pub(crate) fn schema_default(
+    prop: &LexObjectProperty<'static>,
+) -> Option<(TokenStream, TokenStream)> {
+    match prop {
+        LexObjectProperty::Boolean(b) => b
+            .r#const
+            .or(b.default)
+            .map(|v| (quote! { bool }, quote! { #v })),
+        LexObjectProperty::Integer(i) => i
+            .r#const
+            .or(i.default)
+            .map(|v| (quote! { i64 }, quote! { #v })),
+        LexObjectProperty::String(s) if s.format.is_none() => {
+            s.r#const.as_ref().or(s.default.as_ref()).map(|v| {
+                let v = v.as_ref();
+                (
+                    quote! { jacquard_common::CowStr<'static> },
+                    quote! { jacquard_common::CowStr::new_static(#v) },
+                )
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Render a field's schema default/const for doc comments.
+fn value_doc(prop: &LexObjectProperty<'static>) -> String {
+    match prop {
+        LexObjectProperty::Boolean(b) => b.r#const.or(b.default).unwrap_or_default().to_string(),
+        LexObjectProperty::Integer(i) => i.r#const.or(i.default).unwrap_or_default().to_string(),
+        LexObjectProperty::String(s) => {
+            let v = s.r#const.as_ref().or(s.default.as_ref());
+            format!("\"{}\"", v.map(|v| v.as_ref()).unwrap_or_default())
+        }
+        _ => String::new(),
+    }
+}
+
+/// Check if any required field in an object has a schema default or const.
+pub(crate) fn has_schema_defaults(obj: &LexObject<'static>) -> bool {
+    let required = obj.required.as_deref().unwrap_or(&[]);
+    required.iter().any(|field_name| {
+        let field_name_str: &str = field_name.as_ref();
+        obj.properties
+            .get(field_name_str)
+            .and_then(schema_default)
+            .is_some()
+    })
+}
+
+/// Name of the serde default function for a field with a schema default.
+fn default_fn_name(parent_type_name: &str, field_name: &str) -> String {
+    format!(
+        "_default_{}_{}",
+        parent_type_name.to_snake_case(),
+        field_name.to_snake_case()
+    )
+}
+
 impl<'c> CodeGenerator<'c> {
     pub(super) fn generate_record(
         &self,
@@ -74,6 +136,7 @@ impl<'c> CodeGenerator<'c> {
 
                 // Generate main struct fields
                 let fields = self.generate_object_fields(nsid, &type_name, obj, has_builder)?;
+                let default_fns = self.generate_schema_default_fns(&type_name, obj);
                 let doc = self.generate_doc_comment(record.description.as_ref());
 
                 let struct_def = if has_builder {
@@ -215,6 +278,7 @@ impl<'c> CodeGenerator<'c> {
                 };
 
                 Ok(quote! {
+                    #default_fns
                     #struct_def
 
                     impl<'a> #ident<'a> {
@@ -256,6 +320,7 @@ impl<'c> CodeGenerator<'c> {
             required_count >= 1 && !has_default && !conflicts_with_builder_macro(&type_name);
 
         let fields = self.generate_object_fields(nsid, &type_name, obj, has_builder)?;
+        let default_fns = self.generate_schema_default_fns(&type_name, obj);
         let doc = self.generate_doc_comment(obj.description.as_ref());
 
         let struct_def = if has_builder {
@@ -268,6 +333,19 @@ impl<'c> CodeGenerator<'c> {
                     #fields
                 }
             }
+        } else if has_default && has_schema_defaults(obj) {
+            let default_impl = self.generate_schema_default_impl(&ident, obj);
+            quote! {
+                #doc
+                #[jacquard_derive::lexicon]
+                #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq, jacquard_derive::IntoStatic)]
+                #[serde(rename_all = "camelCase")]
+                pub struct #ident<'a> {
+                    #fields
+                }
+
+                #default_impl
+            }
         } else if has_default {
             quote! {
                 #doc
@@ -332,6 +410,7 @@ impl<'c> CodeGenerator<'c> {
         }
 
         Ok(quote! {
+            #default_fns
             #struct_def
             #(#unions)*
         })
@@ -436,13 +515,84 @@ impl<'c> CodeGenerator<'c> {
             attrs.push(quote! { #[builder(into)] });
         }
 
+        // Required fields with a schema default/const fall back to it when missing
+        let mut default_doc = None;
+        if let Some((_, value)) = schema_default(field_type).filter(|_| is_required) {
+            let fn_name = default_fn_name(parent_type_name, field_name);
+            attrs.push(quote! { #[serde(default = #fn_name)] });
+            if is_builder {
+                attrs.push(quote! { #[builder(default = #value)] });
+            }
+            let note = format!("Defaults to `{}`", value_doc(field_type));
+            default_doc = Some(quote! { #[doc = #note] });
+        }
+
         Ok(quote! {
             #doc
+            #default_doc
             #(#attrs)*
             pub #field_ident: #rust_type,
         })
     }
 
+    /// Generate serde default functions for required fields with a schema default/const.
+    /// Must be emitted alongside any struct built from `generate_object_fields`.
+    pub(super) fn generate_schema_default_fns(
+        &self,
+        parent_type_name: &str,
+        obj: &LexObject<'static>,
+    ) -> TokenStream {
+        let required = obj.required.as_deref().unwrap_or(&[]);
+
+        let fns = obj
+            .properties
+            .iter()
+            .filter(|(field_name, _)| required.contains(field_name))
+            .filter_map(|(field_name, field_type)| {
+                let (ty, value) = schema_default(field_type)?;
+                let fn_ident = syn::Ident::new(
+                    &default_fn_name(parent_type_name, field_name),
+                    proc_macro2::Span::call_site(),
+                );
+                Some(quote! {
+                    fn #fn_ident() -> #ty { #value }
+                })
+            });
+
+        quote! { #(#fns)* }
+    }
+
+    /// Generate a `Default` impl that uses schema defaults/consts for required fields.
+    /// Used in place of `#[derive(Default)]` when `has_schema_defaults` is true.
+    pub(super) fn generate_schema_default_impl(
+        &self,
+        ident: &syn::Ident,
+        obj: &LexObject<'static>,
+    ) -> TokenStream {
+        let required = obj.required.as_deref().unwrap_or(&[]);
+
+        let fields = obj.properties.iter().map(|(field_name, field_type)| {
+            let field_ident = make_ident(&field_name.to_snake_case());
+            match schema_default(field_type) {
+                Some((_, value)) if required.contains(field_name) => {
+                    quote! { #field_ident: #value, }
+                }
+                _ => quote! { #field_ident: Default::default(), },
+            }
+        });
+
+        quote! {
+            impl Default for #ident<'_> {
+                fn default() -> Self {
+                    Self {
+                        #(#fields)*
+                        extra_data: Default::default(),
+                    }
+                }
+            }
+        }
+    }
+
     /// Generate a union enum for refs
     pub fn generate_union(
         &self,
@@ -489,7 +639,9 @@ impl<'c> CodeGenerator<'c> {
                 };
 
             // Skip unknown refs - they'll be handled by Unknown variant
-            if !self.corpus.ref_exists(&normalized_ref) {
+            if self.external_crate_for(ref_nsid_str).is_none()
+                && !self.corpus.ref_exists(&normalized_ref)
+            {
                 continue;
             }
 
@@ -541,8 +693,8 @@ impl<'c> CodeGenerator<'c> {
         for info in variant_infos {
             let has_collision = name_counts.get(&info.simple_name).copied().unwrap_or(0) > 1;
 
-            // Track namespace dependency for foreign refs
-            if !info.is_current_namespace {
+            // Track namespace dependency for foreign refs (external crates are not features)
+            if !info.is_current_namespace && self.external_crate_for(&info.ref_nsid).is_none() {
                 let parts: Vec<_> = info.ref_nsid.splitn(3, '.').collect();
                 let foreign_namespace = if parts.len() >= 2 {
                     format!("{}.{}", parts[0], parts[1])
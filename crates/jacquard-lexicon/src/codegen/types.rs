@@ -26,36 +26,9 @@ impl<'c> CodeGenerator<'c> {
             LexObjectProperty::Blob(_) => Ok(quote! { jacquard_common::types::blob::BlobRef<'a> }),
             LexObjectProperty::Unknown(_) => Ok(quote! { jacquard_common::types::value::Data<'a> }),
             LexObjectProperty::Array(array) => {
-                // For arrays with union items, check if multi-variant
-                if let LexArrayItem::Union(union) = &array.items {
-                    if union.refs.is_empty() {
-                        // Empty union: fall back to Data
-                        Ok(quote! { Vec<jacquard_common::types::value::Data<'a>> })
-                    } else if union.refs.len() == 1 {
-                        // Single-variant: use the ref type directly
-                        let ref_str = if union.refs[0].starts_with('#') {
-                            format!("{}{}", nsid, union.refs[0])
-                        } else {
-                            union.refs[0].to_string()
-                        };
-                        let ref_type = self.ref_to_rust_type(&ref_str)?;
-                        Ok(quote! { Vec<#ref_type> })
-                    } else {
-                        // Multi-variant: use generated union type
-                        let union_name = self.generate_field_type_name(
-                            nsid,
-                            parent_type_name,
-                            field_name,
-                            "Item",
-                        );
-                        let union_ident =
-                            syn::Ident::new(&union_name, proc_macro2::Span::call_site());
-                        Ok(quote! { Vec<#union_ident<'a>> })
-                    }
-                } else {
-                    let item_type = self.array_item_to_rust_type(nsid, &array.items)?;
-                    Ok(quote! { Vec<#item_type> })
-                }
+                let item_type =
+                    self.array_property_item_type(nsid, parent_type_name, field_name, array)?;
+                Ok(quote! { Vec<#item_type> })
             }
             LexObjectProperty::Object(object) => {
                 // Empty objects (no properties) are untyped data bags
@@ -120,6 +93,39 @@ impl<'c> CodeGenerator<'c> {
         }
     }
 
+    /// Element type of an array property, as used in the property's `Vec<...>`
+    pub(super) fn array_property_item_type(
+        &self,
+        nsid: &str,
+        parent_type_name: &str,
+        field_name: &str,
+        array: &crate::lexicon::LexArray<'static>,
+    ) -> Result<TokenStream> {
+        // For arrays with union items, check if multi-variant
+        if let LexArrayItem::Union(union) = &array.items {
+            if union.refs.is_empty() {
+                // Empty union: fall back to Data
+                Ok(quote! { jacquard_common::types::value::Data<'a> })
+            } else if union.refs.len() == 1 {
+                // Single-variant: use the ref type directly
+                let ref_str = if union.refs[0].starts_with('#') {
+                    format!("{}{}", nsid, union.refs[0])
+                } else {
+                    union.refs[0].to_string()
+                };
+                self.ref_to_rust_type(&ref_str)
+            } else {
+                // Multi-variant: use generated union type
+                let union_name =
+                    self.generate_field_type_name(nsid, parent_type_name, field_name, "Item");
+                let union_ident = syn::Ident::new(&union_name, proc_macro2::Span::call_site());
+                Ok(quote! { #union_ident<'a> })
+            }
+        } else {
+            self.array_item_to_rust_type(nsid, &array.items)
+        }
+    }
+
     /// Convert array item to Rust type
     pub(super) fn array_item_to_rust_type(
         &self,
@@ -192,8 +198,12 @@ impl<'c> CodeGenerator<'c> {
             (ref_str, "main")
         };
 
+        // Refs into an external crate resolve to that crate even if the corpus lacks them
+        let external_crate = self.external_crate_for(ref_nsid);
+        let root_module = external_crate.unwrap_or(&self.root_module);
+
         // Check if ref exists
-        if !self.corpus.ref_exists(ref_str) {
+        if external_crate.is_none() && !self.corpus.ref_exists(ref_str) {
             // Fallback to Data
             return Ok(quote! { jacquard_common::types::value::Data<'a> });
         }
@@ -212,7 +222,7 @@ impl<'c> CodeGenerator<'c> {
             let first_two = format!("{}_{}", sanitize_name(parts[0]), sanitize_name(parts[1]));
             if parts.len() == 3 {
                 // com.atproto.defs -> com_atproto::TypeName
-                format!("{}::{}::{}", self.root_module, first_two, type_name)
+                format!("{}::{}::{}", root_module, first_two, type_name)
             } else {
                 // app.bsky.actor.defs -> app_bsky::actor::TypeName
                 let middle: Vec<_> = parts[2..parts.len() - 1]
@@ -222,7 +232,7 @@ impl<'c> CodeGenerator<'c> {
                     .collect();
                 format!(
                     "{}::{}::{}::{}",
-                    self.root_module,
+                    root_module,
                     first_two,
                     middle.join("::"),
                     type_name
@@ -259,7 +269,7 @@ impl<'c> CodeGenerator<'c> {
 
             format!(
                 "{}::{}::{}::{}",
-                self.root_module, module_path, file_module, type_name
+                root_module, module_path, file_module, type_name
             )
         };
 
@@ -4,6 +4,7 @@ use crate::lexicon::{
     LexXrpcProcedure, LexXrpcQuery, LexXrpcSubscription, LexXrpcSubscriptionMessageSchema,
 };
 use heck::{ToPascalCase, ToSnakeCase};
+use jacquard_common::smol_str::SmolStr;
 use proc_macro2::TokenStream;
 use quote::quote;
 
@@ -69,11 +70,96 @@ impl<'c> CodeGenerator<'c> {
         )?;
         output.push(xrpc_impl);
 
+        if let Some(paginated_impl) = self.generate_paginated_impl(&type_base, query)? {
+            output.push(paginated_impl);
+        }
+
         Ok(quote! {
             #(#output)*
         })
     }
 
+    /// Generate a `Paginated` impl for cursor-paginated list queries
+    ///
+    /// Applies when the query takes an optional plain-string `cursor` parameter and its JSON
+    /// output object has an optional `cursor` string next to exactly one array property.
+    fn generate_paginated_impl(
+        &self,
+        type_base: &str,
+        query: &LexXrpcQuery<'static>,
+    ) -> Result<Option<TokenStream>> {
+        use crate::lexicon::{LexXrpcParametersProperty, LexXrpcQueryParameter};
+
+        let Some(LexXrpcQueryParameter::Params(params)) = &query.parameters else {
+            return Ok(None);
+        };
+        let names_cursor = |required: &Option<Vec<SmolStr>>| {
+            required.iter().flatten().any(|name| name == "cursor")
+        };
+        let cursor_param = matches!(
+            params.properties.get("cursor"),
+            Some(LexXrpcParametersProperty::String(s)) if s.format.is_none()
+        );
+        if !cursor_param || names_cursor(&params.required) {
+            return Ok(None);
+        }
+
+        let Some(body) = &query.output else {
+            return Ok(None);
+        };
+        let Some(LexXrpcBodySchema::Object(obj)) = &body.schema else {
+            return Ok(None);
+        };
+        let cursor_output = matches!(
+            obj.properties.get("cursor"),
+            Some(LexObjectProperty::String(s)) if s.format.is_none()
+        );
+        if body.encoding.as_ref() != "application/json"
+            || !cursor_output
+            || names_cursor(&obj.required)
+        {
+            return Ok(None);
+        }
+
+        let mut arrays = obj.properties.iter().filter_map(|(name, prop)| match prop {
+            LexObjectProperty::Array(array) => Some((name, array)),
+            _ => None,
+        });
+        let (Some((field_name, array)), None) = (arrays.next(), arrays.next()) else {
+            return Ok(None);
+        };
+
+        // Mirror the output struct's field generation so the item type matches exactly
+        let output_name = format!("{}Output", type_base);
+        let item_type = self.array_property_item_type("", &output_name, field_name, array)?;
+
+        let request_ident = syn::Ident::new(type_base, proc_macro2::Span::call_site());
+        let output_ident = syn::Ident::new(&output_name, proc_macro2::Span::call_site());
+        let items_ident = make_ident(&field_name.to_snake_case());
+
+        Ok(Some(quote! {
+            impl<'a> jacquard_common::xrpc::Paginated for #request_ident<'a> {
+                type Item = <#item_type as jacquard_common::IntoStatic>::Output;
+
+                fn cursor(&self) -> std::option::Option<&str> {
+                    self.cursor.as_deref()
+                }
+
+                fn set_cursor(&mut self, cursor: std::option::Option<jacquard_common::CowStr<'static>>) {
+                    self.cursor = cursor;
+                }
+
+                fn next_cursor<'o>(output: &'o #output_ident<'static>) -> std::option::Option<&'o str> {
+                    output.cursor.as_deref()
+                }
+
+                fn items(output: #output_ident<'static>) -> Vec<Self::Item> {
+                    output.#items_ident
+                }
+            }
+        }))
+    }
+
     /// Generate procedure type
     pub(super) fn generate_procedure(
         &self,
@@ -304,11 +390,13 @@ impl<'c> CodeGenerator<'c> {
                 let struct_ident = syn::Ident::new(&struct_name, proc_macro2::Span::call_site());
 
                 let fields = self.generate_object_fields("", &struct_name, obj, false)?;
+                let default_fns = self.generate_schema_default_fns(&struct_name, obj);
                 let doc = self.generate_doc_comment(obj.description.as_ref());
 
                 // Subscription message structs always get a lifetime since they have the #[lexicon] attribute
                 // which adds extra_data: BTreeMap<..., Data<'a>>
                 let struct_def = quote! {
+                    #default_fns
                     #doc
                     #[jacquard_derive::lexicon]
                     #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq, jacquard_derive::IntoStatic)]
@@ -523,6 +611,8 @@ impl<'c> CodeGenerator<'c> {
             }
         };
 
+        let (default_fns, default_impl) =
+            self.generate_body_defaults(&ident, type_base, body.schema.as_ref(), has_default);
         let doc = self.generate_doc_comment(body.description.as_ref());
 
         // Binary bodies don't need #[lexicon] attribute or lifetime
@@ -556,6 +646,18 @@ impl<'c> CodeGenerator<'c> {
                     >,
                 }
             }
+        } else if let Some(default_impl) = default_impl {
+            quote! {
+                #doc
+                #[jacquard_derive::lexicon]
+                #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq, jacquard_derive::IntoStatic)]
+                #[serde(rename_all = "camelCase")]
+                pub struct #ident<'a> {
+                    #fields
+                }
+
+                #default_impl
+            }
         } else if has_default {
             quote! {
                 #doc
@@ -621,6 +723,7 @@ impl<'c> CodeGenerator<'c> {
         }
 
         Ok(quote! {
+            #default_fns
             #struct_def
             #(#unions)*
         })
@@ -658,10 +761,24 @@ impl<'c> CodeGenerator<'c> {
         } else {
             false
         };
+        let (default_fns, default_impl) =
+            self.generate_body_defaults(&ident, &struct_name, body.schema.as_ref(), has_default);
 
         // Output structs always get a lifetime since they have the #[lexicon] attribute
         // which adds extra_data: BTreeMap<..., Data<'a>>
-        let struct_def = if has_default {
+        let struct_def = if let Some(default_impl) = default_impl {
+            quote! {
+                #doc
+                #[jacquard_derive::lexicon]
+                #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq, jacquard_derive::IntoStatic)]
+                #[serde(rename_all = "camelCase")]
+                pub struct #ident<'a> {
+                    #fields
+                }
+
+                #default_impl
+            }
+        } else if has_default {
             quote! {
                 #doc
                 #[jacquard_derive::lexicon]
@@ -738,11 +855,34 @@ impl<'c> CodeGenerator<'c> {
         }
 
         Ok(quote! {
+            #default_fns
             #struct_def
             #(#unions)*
         })
     }
 
+    /// Generate schema default functions for an XRPC body, plus a `Default` impl
+    /// replacing the derived one when any required field has a schema default/const
+    fn generate_body_defaults(
+        &self,
+        ident: &syn::Ident,
+        parent_type_name: &str,
+        schema: Option<&LexXrpcBodySchema<'static>>,
+        has_default: bool,
+    ) -> (TokenStream, Option<TokenStream>) {
+        use crate::codegen::structs::has_schema_defaults;
+
+        match schema {
+            Some(LexXrpcBodySchema::Object(obj)) => {
+                let default_fns = self.generate_schema_default_fns(parent_type_name, obj);
+                let default_impl = (has_default && has_schema_defaults(obj))
+                    .then(|| self.generate_schema_default_impl(ident, obj));
+                (default_fns, default_impl)
+            }
+            _ => (quote! {}, None),
+        }
+    }
+
     /// Generate fields from XRPC body schema
     pub(super) fn generate_body_fields(
         &self,
@@ -1149,9 +1289,7 @@ impl<'c> CodeGenerator<'c> {
         // Generate decode_body() method for binary inputs
         let decode_body_method = if is_binary_input {
             quote! {
-                fn decode_body<'de>(
-                    body: &'de [u8],
-                ) -> Result<Box<Self>, jacquard_common::error::DecodeError>
+                fn decode_body<'de>(body: &'de [u8]) -> jacquard_common::error::XrpcResult<Box<Self>>
                 where
                     Self: serde::Deserialize<'de>,
                 {
@@ -1184,6 +1322,22 @@ impl<'c> CodeGenerator<'c> {
                 )
             };
 
+            let endpoint_impl = self.server_endpoints.then(|| {
+                quote! {
+                    #[doc = " Endpoint type for "]
+                    #[doc = #nsid]
+                    pub struct #endpoint_ident;
+
+                    impl jacquard_common::xrpc::XrpcEndpoint for #endpoint_ident {
+                        const PATH: &'static str = #endpoint_path;
+                        const METHOD: jacquard_common::xrpc::XrpcMethod = #method;
+
+                        type Request<'de> = #endpoint_request_type;
+                        type Response = #response_ident;
+                    }
+                }
+            });
+
             Ok(quote! {
                 #response_type
 
@@ -1197,21 +1351,26 @@ impl<'c> CodeGenerator<'c> {
                     #decode_body_method
                 }
 
-                #[doc = " Endpoint type for "]
-                #[doc = #nsid]
-                pub struct #endpoint_ident;
-
-                impl jacquard_common::xrpc::XrpcEndpoint for #endpoint_ident {
-                    const PATH: &'static str = #endpoint_path;
-                    const METHOD: jacquard_common::xrpc::XrpcMethod = #method;
-
-                    type Request<'de> = #endpoint_request_type;
-                    type Response = #response_ident;
-                }
+                #endpoint_impl
             })
         } else {
             // No params - generate a marker struct
             let request_ident = syn::Ident::new(type_base, proc_macro2::Span::call_site());
+            let endpoint_impl = self.server_endpoints.then(|| {
+                quote! {
+                    #[doc = " Endpoint type for "]
+                    #[doc = #nsid]
+                    pub struct #endpoint_ident;
+
+                    impl jacquard_common::xrpc::XrpcEndpoint for #endpoint_ident {
+                        const PATH: &'static str = #endpoint_path;
+                        const METHOD: jacquard_common::xrpc::XrpcMethod = #method;
+
+                        type Request<'de> = #request_ident;
+                        type Response = #response_ident;
+                    }
+                }
+            });
 
             Ok(quote! {
                 /// XRPC request marker type
@@ -1227,17 +1386,7 @@ impl<'c> CodeGenerator<'c> {
                     type Response = #response_ident;
                 }
 
-                #[doc = " Endpoint type for "]
-                #[doc = #nsid]
-                pub struct #endpoint_ident;
-
-                impl jacquard_common::xrpc::XrpcEndpoint for #endpoint_ident {
-                    const PATH: &'static str = #endpoint_path;
-                    const METHOD: jacquard_common::xrpc::XrpcMethod = #method;
-
-                    type Request<'de> = #request_ident;
-                    type Response = #response_ident;
-                }
+                #endpoint_impl
             })
         }
     }
@@ -1329,6 +1478,19 @@ impl<'c> CodeGenerator<'c> {
                 proc_macro2::Span::call_site(),
             );
             let endpoint_path = format!("/xrpc/{}", nsid);
+            let endpoint_impl = self.server_endpoints.then(|| {
+                quote! {
+                    pub struct #endpoint_ident;
+
+                    impl jacquard_common::xrpc::SubscriptionEndpoint for #endpoint_ident {
+                        const PATH: &'static str = #endpoint_path;
+                        const ENCODING: jacquard_common::xrpc::MessageEncoding = #encoding;
+
+                        type Params<'de> = #marker;
+                        type Stream = #stream_ident;
+                    }
+                }
+            });
 
             return Ok(quote! {
                 #stream_resp_impl
@@ -1343,15 +1505,7 @@ impl<'c> CodeGenerator<'c> {
                     type Stream = #stream_ident;
                 }
 
-                pub struct #endpoint_ident;
-
-                impl jacquard_common::xrpc::SubscriptionEndpoint for #endpoint_ident {
-                    const PATH: &'static str = #endpoint_path;
-                    const ENCODING: jacquard_common::xrpc::MessageEncoding = #encoding;
-
-                    type Params<'de> = #marker;
-                    type Stream = #stream_ident;
-                }
+                #endpoint_impl
             });
         };
 
@@ -1376,6 +1530,19 @@ impl<'c> CodeGenerator<'c> {
         );
 
         let endpoint_path = format!("/xrpc/{}", nsid);
+        let endpoint_impl = self.server_endpoints.then(|| {
+            quote! {
+                pub struct #endpoint_ident;
+
+                impl jacquard_common::xrpc::SubscriptionEndpoint for #endpoint_ident {
+                    const PATH: &'static str = #endpoint_path;
+                    const ENCODING: jacquard_common::xrpc::MessageEncoding = #encoding;
+
+                    type Params<'de> = #endpoint_params_type;
+                    type Stream = #stream_ident;
+                }
+            }
+        });
 
         Ok(quote! {
             #stream_resp_impl
@@ -1387,15 +1554,7 @@ impl<'c> CodeGenerator<'c> {
                 type Stream = #stream_ident;
             }
 
-            pub struct #endpoint_ident;
-
-            impl jacquard_common::xrpc::SubscriptionEndpoint for #endpoint_ident {
-                const PATH: &'static str = #endpoint_path;
-                const ENCODING: jacquard_common::xrpc::MessageEncoding = #encoding;
-
-                type Params<'de> = #endpoint_params_type;
-                type Stream = #stream_ident;
-            }
+            #endpoint_impl
         })
     }
 }
@@ -6,6 +6,10 @@ use std::fs;
 use std::path::Path;
 
 /// Registry of all loaded lexicons for reference resolution
+///
+/// Codegen builds one of these up front, but nothing about it is codegen-specific: load one
+/// with [`load_from_dir`](Self::load_from_dir) at runtime to look up schemas by NSID and
+/// follow refs with [`resolve_ref_from`](Self::resolve_ref_from).
 #[derive(Debug, Clone)]
 pub struct LexiconCorpus {
     /// Map from NSID to lexicon document
@@ -75,6 +79,27 @@ impl LexiconCorpus {
         Some((doc, def))
     }
 
+    /// Resolve a reference as written inside the lexicon `from`
+    ///
+    /// Local refs (`#replyRef`) resolve against `from`; anything else resolves as in
+    /// [`resolve_ref`](Self::resolve_ref). `from` may carry a fragment of its own, which is
+    /// ignored, so the ref of the def currently being walked can be passed straight in.
+    pub fn resolve_ref_from(
+        &self,
+        from: &str,
+        ref_str: &str,
+    ) -> Option<(&LexiconDoc<'static>, &LexUserType<'static>)> {
+        match ref_str.strip_prefix('#') {
+            Some(def_name) => {
+                let nsid = from.split_once('#').map_or(from, |(nsid, _)| nsid);
+                let doc = self.get(nsid)?;
+                let def = doc.defs.get(def_name)?;
+                Some((doc, def))
+            }
+            None => self.resolve_ref(ref_str),
+        }
+    }
+
     /// Check if a reference exists
     pub fn ref_exists(&self, ref_str: &str) -> bool {
         self.resolve_ref(ref_str).is_some()
@@ -171,4 +196,40 @@ mod tests {
         assert!(!corpus.ref_exists("com.example.fake"));
         assert!(!corpus.ref_exists("app.bsky.feed.post#nonexistent"));
     }
+
+    #[test]
+    fn test_resolve_ref_from() {
+        let corpus = LexiconCorpus::load_from_dir("../jacquard-api/lexicons")
+            .expect("failed to load lexicons");
+
+        // Local refs resolve against the referring lexicon
+        let (doc, def) = corpus
+            .resolve_ref_from("app.bsky.feed.post", "#replyRef")
+            .expect("should resolve");
+        assert_eq!(doc.id.as_ref(), "app.bsky.feed.post");
+        assert!(matches!(def, LexUserType::Object(_)));
+
+        // The referring side may name a specific def
+        let (doc, _) = corpus
+            .resolve_ref_from("app.bsky.feed.post#replyRef", "#entity")
+            .expect("should resolve");
+        assert_eq!(doc.id.as_ref(), "app.bsky.feed.post");
+
+        // Cross-lexicon refs ignore `from`
+        let (doc, _) = corpus
+            .resolve_ref_from("app.bsky.feed.post", "app.bsky.richtext.facet#mention")
+            .expect("should resolve");
+        assert_eq!(doc.id.as_ref(), "app.bsky.richtext.facet");
+
+        assert!(
+            corpus
+                .resolve_ref_from("app.bsky.feed.post", "#nonexistent")
+                .is_none()
+        );
+        assert!(
+            corpus
+                .resolve_ref_from("com.example.fake", "#main")
+                .is_none()
+        );
+    }
 }
@@ -2,6 +2,9 @@
 // https://github.com/atrium-rs/atrium/blob/main/lexicon/atrium-lex/src/lexicon.rs
 // https://github.com/atrium-rs/atrium/blob/main/lexicon/atrium-lex/src/lib.rs
 
+use crate::error::CodegenError;
+use jacquard_common::types::string::AtprotoStr;
+use jacquard_common::types::value::{Array, Data, Object};
 use jacquard_common::{CowStr, into_static::IntoStatic, smol_str::SmolStr, types::blob::MimeType};
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
@@ -843,6 +846,116 @@ impl IntoStatic for LexUserType<'_> {
     }
 }
 
+/// Fill fields missing from `data` with their schema `default` or `const` values
+///
+/// `def` names an object or record def in `schema` (e.g. `"main"`). Nested objects, and
+/// refs/union members pointing at defs in the same lexicon, are filled recursively; refs
+/// into other lexicons are left alone. Fields already present are never overwritten, and
+/// data that isn't an object is ignored.
+pub fn apply_defaults(
+    schema: &LexiconDoc<'_>,
+    def: &str,
+    data: &mut Data<'_>,
+) -> Result<(), CodegenError> {
+    let def_type = schema.defs.get(def).ok_or_else(|| {
+        CodegenError::invalid_lexicon(format!("no def named `{def}`"), schema.id.as_ref())
+    })?;
+    match def_type {
+        LexUserType::Record(LexRecord {
+            record: LexRecordRecord::Object(obj),
+            ..
+        })
+        | LexUserType::Object(obj) => apply_object_defaults(schema, obj, data),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn apply_object_defaults(schema: &LexiconDoc<'_>, obj: &LexObject<'_>, data: &mut Data<'_>) {
+    let Data::Object(Object(fields)) = data else {
+        return;
+    };
+    for (name, prop) in &obj.properties {
+        match fields.get_mut(name) {
+            Some(value) => apply_property_defaults(schema, prop, value),
+            None => {
+                if let Some(value) = property_default(prop) {
+                    fields.insert(name.clone(), value);
+                }
+            }
+        }
+    }
+}
+
+fn apply_property_defaults(
+    schema: &LexiconDoc<'_>,
+    prop: &LexObjectProperty<'_>,
+    data: &mut Data<'_>,
+) {
+    match prop {
+        LexObjectProperty::Object(obj) => apply_object_defaults(schema, obj, data),
+        LexObjectProperty::Ref(r) => apply_ref_defaults(schema, &r.r#ref, data),
+        LexObjectProperty::Union(_) => apply_union_defaults(schema, data),
+        LexObjectProperty::Array(array) => {
+            let Data::Array(Array(items)) = data else {
+                return;
+            };
+            for item in items {
+                match &array.items {
+                    LexArrayItem::Object(obj) => apply_object_defaults(schema, obj, item),
+                    LexArrayItem::Ref(r) => apply_ref_defaults(schema, &r.r#ref, item),
+                    LexArrayItem::Union(_) => apply_union_defaults(schema, item),
+                    _ => {}
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn apply_ref_defaults(schema: &LexiconDoc<'_>, r#ref: &str, data: &mut Data<'_>) {
+    if let Some(def) = local_def_name(schema, r#ref) {
+        // Every ref in the doc points at an existing def or there's nothing to fill
+        let _ = apply_defaults(schema, def, data);
+    }
+}
+
+/// Union members carry their type in `$type`; fill them if it names a local def
+fn apply_union_defaults(schema: &LexiconDoc<'_>, data: &mut Data<'_>) {
+    let type_name = match data {
+        Data::Object(Object(fields)) => match fields.get("$type") {
+            Some(Data::String(s)) => s.as_str().to_string(),
+            _ => return,
+        },
+        _ => return,
+    };
+    apply_ref_defaults(schema, &type_name, data);
+}
+
+/// Def name for a ref within `schema`: `#name`, `<id>#name` or bare `<id>` (main)
+fn local_def_name<'r>(schema: &LexiconDoc<'_>, r#ref: &'r str) -> Option<&'r str> {
+    match r#ref.split_once('#') {
+        Some(("", name)) => Some(name),
+        Some((id, name)) if id == schema.id.as_ref() => Some(name),
+        None if r#ref == schema.id.as_ref() => Some("main"),
+        _ => None,
+    }
+}
+
+/// A property's `const` or `default` value, for primitive types that have one
+fn property_default(prop: &LexObjectProperty<'_>) -> Option<Data<'static>> {
+    match prop {
+        LexObjectProperty::Boolean(b) => b.r#const.or(b.default).map(Data::Boolean),
+        LexObjectProperty::Integer(i) => i.r#const.or(i.default).map(Data::Integer),
+        LexObjectProperty::String(s) => s
+            .r#const
+            .as_ref()
+            .or(s.default.as_ref())
+            .map(|v| Data::String(AtprotoStr::String(CowStr::copy_from_str(v)))),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -869,4 +982,58 @@ mod tests {
         assert_eq!(doc.description, None);
         assert_eq!(doc.defs.len(), 1);
     }
+
+    const LEXICON_EXAMPLE_DEFAULTS: &str = r##"
+{
+  "lexicon": 1,
+  "id": "com.example.settings",
+  "defs": {
+    "main": {
+      "type": "record",
+      "key": "literal:self",
+      "record": {
+        "type": "object",
+        "required": ["visibility"],
+        "properties": {
+          "visibility": { "type": "string", "default": "public" },
+          "limit": { "type": "integer", "default": 50 },
+          "version": { "type": "integer", "const": 2 },
+          "theme": { "type": "ref", "ref": "#theme" }
+        }
+      }
+    },
+    "theme": {
+      "type": "object",
+      "properties": {
+        "dark": { "type": "boolean", "default": false }
+      }
+    }
+  }
+}"##;
+
+    #[test]
+    fn apply_defaults_fills_missing_fields() {
+        let doc = serde_json::from_str::<LexiconDoc>(LEXICON_EXAMPLE_DEFAULTS)
+            .expect("failed to deserialize");
+        let json = serde_json::json!({ "limit": 10, "theme": {} });
+        let mut data = Data::from_json(&json).unwrap();
+
+        apply_defaults(&doc, "main", &mut data).unwrap();
+
+        let Data::Object(Object(fields)) = &data else {
+            panic!("expected object");
+        };
+        assert_eq!(
+            fields["visibility"],
+            Data::String(AtprotoStr::String("public".into()))
+        );
+        assert_eq!(fields["limit"], Data::Integer(10));
+        assert_eq!(fields["version"], Data::Integer(2));
+        let Data::Object(Object(theme)) = &fields["theme"] else {
+            panic!("expected object");
+        };
+        assert_eq!(theme["dark"], Data::Boolean(false));
+
+        assert!(apply_defaults(&doc, "missing", &mut data).is_err());
+    }
 }
@@ -26,6 +26,8 @@ pub enum Error {
     #[error(transparent)]
     SerdeHtmlForm(#[from] serde_html_form::ser::Error),
     #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+    #[error(transparent)]
     LocalhostClient(#[from] LocalhostClientError),
 }
 
@@ -158,6 +160,90 @@ impl<'m> AtprotoClientMetadata<'m> {
     }
 }
 
+impl AtprotoClientMetadata<'_> {
+    /// Render the client metadata document to host at `client_id`.
+    ///
+    /// Validates the metadata like [`atproto_client_metadata`], then fills in the
+    /// fields the atproto OAuth profile requires of a hosted document:
+    /// `response_types` is `["code"]`, `grant_types` is always listed, and
+    /// `dpop_bound_access_tokens` is always `true`. With a keyset the client is
+    /// confidential (`token_endpoint_auth_method: "private_key_jwt"` with `ES256`),
+    /// and publishes its public keys inline as `jwks` unless `jwks_uri` is set, in
+    /// which case serve [`Keyset::to_jwks`] from that URL instead. Without a keyset
+    /// the client is public (`token_endpoint_auth_method: "none"`).
+    pub fn to_metadata_json(&self, keyset: Option<&Keyset>) -> Result<serde_json::Value> {
+        let metadata = atproto_client_metadata(self.clone(), &keyset.cloned())?;
+        let mut value = serde_json::to_value(&metadata)?;
+        if let Some(doc) = value.as_object_mut() {
+            let grant_types: Vec<CowStr<'static>> =
+                self.grant_types.iter().cloned().map(Into::into).collect();
+            doc.insert("grant_types".into(), serde_json::to_value(grant_types)?);
+            doc.insert("response_types".into(), serde_json::json!(["code"]));
+            doc.insert("dpop_bound_access_tokens".into(), true.into());
+        }
+        Ok(value)
+    }
+
+    /// Check that this metadata can be published as a confidential client's document.
+    ///
+    /// A hosted client must have an `https:` `client_id` (loopback clients are never
+    /// fetched), at least one redirect URI, the `authorization_code` grant, the `atproto`
+    /// scope, and a keyset to authenticate with. Run this before serving
+    /// [`to_metadata_json`](Self::to_metadata_json) from the `client_id` URL.
+    pub fn validate_for_publication(&self, keyset: Option<&Keyset>) -> Result<()> {
+        if self.client_id.scheme() != "https" || self.client_id.host_str().is_none() {
+            return Err(Error::InvalidClientId);
+        }
+        if self.redirect_uris.is_empty() {
+            return Err(Error::EmptyRedirectUris);
+        }
+        if !self.grant_types.contains(&GrantType::AuthorizationCode) {
+            return Err(Error::InvalidGrantTypes);
+        }
+        if !self.scopes.contains(&Scope::Atproto) {
+            return Err(Error::InvalidScope);
+        }
+        if keyset.is_none() {
+            return Err(Error::EmptyJwks);
+        }
+        Ok(())
+    }
+
+    /// Render the client metadata document from the metadata alone, without a keyset.
+    ///
+    /// Emits `client_id`, `redirect_uris`, `scope`, `grant_types`, `response_types`,
+    /// `token_endpoint_auth_method` and `dpop_bound_access_tokens: true`. A client with a
+    /// `jwks_uri` is confidential (`private_key_jwt` with `ES256`, keys served from that
+    /// URL); otherwise it is public (`none`). No validation is done here; use
+    /// [`validate_for_publication`](Self::validate_for_publication) for that, or
+    /// [`to_metadata_json`](Self::to_metadata_json) to publish the keyset inline as `jwks`.
+    pub fn to_client_metadata_json(&self) -> serde_json::Value {
+        let confidential = self.jwks_uri.is_some();
+        let auth_method = if confidential {
+            AuthMethod::PrivateKeyJwt
+        } else {
+            AuthMethod::None
+        };
+        let metadata = OAuthClientMetadata {
+            client_id: self.client_id.clone(),
+            client_uri: self.client_uri.clone(),
+            redirect_uris: self.redirect_uris.clone(),
+            scope: Some(Scope::serialize_multiple(self.scopes.as_slice())),
+            grant_types: Some(self.grant_types.iter().cloned().map(Into::into).collect()),
+            token_endpoint_auth_method: Some(auth_method.into()),
+            dpop_bound_access_tokens: Some(true),
+            jwks_uri: self.jwks_uri.clone(),
+            jwks: None,
+            token_endpoint_auth_signing_alg: confidential.then(|| CowStr::new_static("ES256")),
+        };
+        let mut value = serde_json::to_value(&metadata).expect("client metadata serializes");
+        if let Some(doc) = value.as_object_mut() {
+            doc.insert("response_types".into(), serde_json::json!(["code"]));
+        }
+        value
+    }
+}
+
 pub fn atproto_client_metadata<'m>(
     metadata: AtprotoClientMetadata<'m>,
     keyset: &Option<Keyset>,
@@ -423,4 +509,193 @@ gbGGr0pN+oSing7cZ0169JaRHTNh+0LNQXrFobInX6cj95FzEdRyT4T3
             );
         }
     }
+
+    #[test]
+    fn test_client_metadata_json() {
+        let metadata = AtprotoClientMetadata {
+            client_id: Url::from_str("https://example.com/client_metadata.json").unwrap(),
+            client_uri: Some(Url::from_str("https://example.com").unwrap()),
+            redirect_uris: vec![Url::from_str("https://example.com/callback").unwrap()],
+            grant_types: vec![GrantType::AuthorizationCode, GrantType::RefreshToken],
+            scopes: vec![Scope::Atproto, Scope::Transition(TransitionScope::Generic)],
+            jwks_uri: None,
+        };
+        let secret_key = SecretKey::<p256::NistP256>::from_pkcs8_pem(PRIVATE_KEY)
+            .expect("failed to parse private key");
+        let keyset = Keyset::try_from(vec![Jwk {
+            key: Key::from(&secret_key.into()),
+            prm: Parameters {
+                kid: Some(String::from("kid00")),
+                ..Default::default()
+            },
+        }])
+        .expect("failed to create keyset");
+
+        let doc = metadata.to_metadata_json(Some(&keyset)).unwrap();
+        assert_eq!(doc["client_id"], "https://example.com/client_metadata.json");
+        assert_eq!(doc["client_uri"], "https://example.com/");
+        assert_eq!(doc["redirect_uris"], serde_json::json!(["https://example.com/callback"]));
+        assert_eq!(doc["scope"], "atproto transition:generic");
+        assert_eq!(
+            doc["grant_types"],
+            serde_json::json!(["authorization_code", "refresh_token"])
+        );
+        assert_eq!(doc["response_types"], serde_json::json!(["code"]));
+        assert_eq!(doc["token_endpoint_auth_method"], "private_key_jwt");
+        assert_eq!(doc["token_endpoint_auth_signing_alg"], "ES256");
+        assert_eq!(doc["dpop_bound_access_tokens"], true);
+        assert_eq!(doc["jwks"], keyset.to_jwks());
+        assert!(doc.get("jwks_uri").is_none());
+        assert!(doc["jwks"]["keys"][0].get("d").is_none());
+
+        // With a jwks_uri the keys are served separately
+        let metadata = AtprotoClientMetadata {
+            jwks_uri: Some(Url::from_str("https://example.com/jwks.json").unwrap()),
+            ..metadata
+        };
+        let doc = metadata.to_metadata_json(Some(&keyset)).unwrap();
+        assert_eq!(doc["jwks_uri"], "https://example.com/jwks.json");
+        assert!(doc.get("jwks").is_none());
+
+        // Public (loopback) clients still declare DPoP-bound tokens
+        let doc = AtprotoClientMetadata::default_localhost()
+            .to_metadata_json(None)
+            .unwrap();
+        assert_eq!(doc["token_endpoint_auth_method"], "none");
+        assert_eq!(doc["dpop_bound_access_tokens"], true);
+        assert_eq!(doc["response_types"], serde_json::json!(["code"]));
+        assert!(doc.get("jwks").is_none());
+        assert!(doc.get("token_endpoint_auth_signing_alg").is_none());
+    }
+
+    #[test]
+    fn test_client_metadata_json_document() {
+        let metadata = AtprotoClientMetadata {
+            client_id: Url::from_str("https://app.example.com/client-metadata.json").unwrap(),
+            client_uri: Some(Url::from_str("https://app.example.com").unwrap()),
+            redirect_uris: vec![Url::from_str("https://app.example.com/oauth/callback").unwrap()],
+            grant_types: vec![GrantType::AuthorizationCode, GrantType::RefreshToken],
+            scopes: vec![Scope::Atproto, Scope::Transition(TransitionScope::Generic)],
+            jwks_uri: Some(Url::from_str("https://app.example.com/jwks.json").unwrap()),
+        };
+        let secret_key = SecretKey::<p256::NistP256>::from_pkcs8_pem(PRIVATE_KEY)
+            .expect("failed to parse private key");
+        let keyset = Keyset::try_from(vec![Jwk {
+            key: Key::from(&secret_key.into()),
+            prm: Parameters {
+                kid: Some(String::from("kid00")),
+                ..Default::default()
+            },
+        }])
+        .expect("failed to create keyset");
+
+        metadata.validate_for_publication(Some(&keyset)).unwrap();
+        assert_eq!(
+            metadata.to_metadata_json(Some(&keyset)).unwrap(),
+            serde_json::json!({
+                "client_id": "https://app.example.com/client-metadata.json",
+                "client_uri": "https://app.example.com/",
+                "redirect_uris": ["https://app.example.com/oauth/callback"],
+                "scope": "atproto transition:generic",
+                "grant_types": ["authorization_code", "refresh_token"],
+                "response_types": ["code"],
+                "token_endpoint_auth_method": "private_key_jwt",
+                "token_endpoint_auth_signing_alg": "ES256",
+                "dpop_bound_access_tokens": true,
+                "jwks_uri": "https://app.example.com/jwks.json",
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_for_publication() {
+        let metadata = AtprotoClientMetadata {
+            client_id: Url::from_str("https://app.example.com/client-metadata.json").unwrap(),
+            client_uri: None,
+            redirect_uris: vec![Url::from_str("https://app.example.com/oauth/callback").unwrap()],
+            grant_types: vec![GrantType::AuthorizationCode],
+            scopes: vec![Scope::Atproto],
+            jwks_uri: None,
+        };
+        assert!(matches!(
+            metadata.validate_for_publication(None),
+            Err(Error::EmptyJwks)
+        ));
+        assert!(matches!(
+            AtprotoClientMetadata::default_localhost().validate_for_publication(None),
+            Err(Error::InvalidClientId)
+        ));
+        let no_redirects = AtprotoClientMetadata {
+            redirect_uris: vec![],
+            ..metadata.clone()
+        };
+        assert!(matches!(
+            no_redirects.validate_for_publication(None),
+            Err(Error::EmptyRedirectUris)
+        ));
+        let no_atproto = AtprotoClientMetadata {
+            scopes: vec![Scope::Transition(TransitionScope::Generic)],
+            ..metadata
+        };
+        assert!(matches!(
+            no_atproto.validate_for_publication(None),
+            Err(Error::InvalidScope)
+        ));
+    }
+
+    #[test]
+    fn test_to_client_metadata_json_round_trip() {
+        let metadata = AtprotoClientMetadata {
+            client_id: Url::from_str("https://app.example.com/client-metadata.json").unwrap(),
+            client_uri: Some(Url::from_str("https://app.example.com").unwrap()),
+            redirect_uris: vec![Url::from_str("https://app.example.com/oauth/callback").unwrap()],
+            grant_types: vec![GrantType::AuthorizationCode, GrantType::RefreshToken],
+            scopes: vec![Scope::Atproto, Scope::Transition(TransitionScope::Generic)],
+            jwks_uri: Some(Url::from_str("https://app.example.com/jwks.json").unwrap()),
+        };
+        let doc = metadata.to_client_metadata_json();
+        assert_eq!(
+            doc,
+            serde_json::json!({
+                "client_id": "https://app.example.com/client-metadata.json",
+                "client_uri": "https://app.example.com/",
+                "redirect_uris": ["https://app.example.com/oauth/callback"],
+                "scope": "atproto transition:generic",
+                "grant_types": ["authorization_code", "refresh_token"],
+                "response_types": ["code"],
+                "token_endpoint_auth_method": "private_key_jwt",
+                "token_endpoint_auth_signing_alg": "ES256",
+                "dpop_bound_access_tokens": true,
+                "jwks_uri": "https://app.example.com/jwks.json",
+            })
+        );
+
+        let json = serde_json::to_string(&doc).unwrap();
+        let parsed: OAuthClientMetadata = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.client_id, metadata.client_id);
+        assert_eq!(parsed.client_uri, metadata.client_uri);
+        assert_eq!(parsed.redirect_uris, metadata.redirect_uris);
+        assert_eq!(
+            Scope::parse_multiple(parsed.scope.as_deref().unwrap()).unwrap(),
+            metadata.scopes
+        );
+        assert_eq!(
+            parsed.grant_types.unwrap(),
+            vec!["authorization_code", "refresh_token"]
+        );
+        assert_eq!(
+            parsed.token_endpoint_auth_method.as_deref(),
+            Some("private_key_jwt")
+        );
+        assert_eq!(parsed.dpop_bound_access_tokens, Some(true));
+        assert_eq!(parsed.jwks_uri, metadata.jwks_uri);
+        assert!(parsed.jwks.is_none());
+
+        // Without a jwks_uri the client is public
+        let doc = AtprotoClientMetadata::default_localhost().to_client_metadata_json();
+        assert_eq!(doc["token_endpoint_auth_method"], "none");
+        assert_eq!(doc["dpop_bound_access_tokens"], true);
+        assert!(doc.get("jwks_uri").is_none());
+        assert!(doc.get("token_endpoint_auth_signing_alg").is_none());
+    }
 }
@@ -152,9 +152,10 @@ where
             &self.registry.client_data.keyset,
         )?;
 
-        let (server_metadata, identity) = self.client.resolve_oauth(input.as_ref()).await?;
+        let input = crate::resolver::parse_login_input(input.as_ref())?;
+        let (server_metadata, identity) = self.client.resolve_oauth(input.as_str()).await?;
         let login_hint = if identity.is_some() {
-            Some(input.as_ref().into())
+            Some(input.as_str().into())
         } else {
             None
         };
@@ -383,6 +384,28 @@ where
     S: ClientAuthStore + Send + Sync + 'static,
     T: OAuthResolver + DpopExt + Send + Sync + 'static,
 {
+    /// Revoke this session's tokens at the authorization server, then drop it from the store
+    ///
+    /// Sends the refresh token (or the access token, if there is no refresh token) to the
+    /// server's `revocation_endpoint`. Unlike [`logout`](Self::logout), a failed revocation
+    /// or a server without a revocation endpoint is an error and the stored session is kept.
+    pub async fn revoke(&self) -> Result<()> {
+        use crate::request::{OAuthMetadata, revoke};
+        let mut data = self.data.write().await;
+        let meta =
+            OAuthMetadata::new(self.client.as_ref(), &self.registry.client_data, &data).await?;
+        let token = data
+            .token_set
+            .refresh_token
+            .clone()
+            .unwrap_or_else(|| data.token_set.access_token.clone());
+        revoke(self.client.as_ref(), &mut data.dpop_data, &token, &meta).await?;
+        self.registry
+            .del(&data.account_did, &data.session_id)
+            .await?;
+        Ok(())
+    }
+
     pub async fn logout(&self) -> Result<()> {
         use crate::request::{OAuthMetadata, revoke};
         let mut data = self.data.write().await;
@@ -426,12 +449,12 @@ where
             let data = self.data.read().await;
             (data.account_did.clone(), data.session_id.clone())
         };
+        // The registry persists a rotated token set before handing it back, so a stored
+        // session never holds a refresh token the server has already retired
         let refreshed = self.registry.as_ref().get(&did, &sid, true).await?;
         let token = AuthorizationToken::Dpop(refreshed.token_set.access_token.clone());
         // Write back updated session
-        *self.data.write().await = refreshed.clone().into_static();
-        // Store in the registry
-        self.registry.set(refreshed).await?;
+        *self.data.write().await = refreshed.into_static();
         Ok(token)
     }
 }
@@ -669,7 +692,9 @@ where
             builder = builder.header(http::header::AUTHORIZATION, hv);
         }
 
-        if let Some(proxy) = &opts.atproto_proxy {
+        if let Some(proxy) =
+            opts.proxy_for(<Str::Request as jacquard_common::xrpc::XrpcRequest>::NSID)
+        {
             builder = builder.header("atproto-proxy", proxy.as_ref());
         }
         if let Some(labelers) = &opts.atproto_accept_labelers {
@@ -841,3 +866,151 @@ where
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::atproto::AtprotoClientMetadata;
+    use crate::authstore::MemoryAuthStore;
+    use http::StatusCode;
+
+    /// Authorization server serving its metadata and answering revocations with `revoke_status`
+    #[derive(Clone)]
+    struct MockServer {
+        revoke_status: StatusCode,
+    }
+
+    impl HttpClient for MockServer {
+        type Error = std::convert::Infallible;
+
+        async fn send_http(
+            &self,
+            request: http::Request<Vec<u8>>,
+        ) -> core::result::Result<http::Response<Vec<u8>>, Self::Error> {
+            let (status, body) = match request.uri().path() {
+                "/.well-known/oauth-authorization-server" => (
+                    StatusCode::OK,
+                    serde_json::to_vec(&serde_json::json!({
+                        "issuer": "https://issuer",
+                        "authorization_endpoint": "https://issuer/authorize",
+                        "token_endpoint": "https://issuer/token",
+                        "revocation_endpoint": "https://issuer/revoke",
+                        "token_endpoint_auth_methods_supported": ["none"],
+                        "scopes_supported": ["atproto"],
+                        "response_types_supported": ["code"],
+                    }))
+                    .unwrap(),
+                ),
+                "/revoke" if self.revoke_status.is_success() => (self.revoke_status, Vec::new()),
+                "/revoke" => (
+                    self.revoke_status,
+                    br#"{"error":"invalid_request"}"#.to_vec(),
+                ),
+                _ => (StatusCode::NOT_FOUND, Vec::new()),
+            };
+            Ok(http::Response::builder().status(status).body(body).unwrap())
+        }
+    }
+
+    impl IdentityResolver for MockServer {
+        fn options(&self) -> &ResolverOptions {
+            use std::sync::LazyLock;
+            static OPTS: LazyLock<ResolverOptions> = LazyLock::new(ResolverOptions::default);
+            &OPTS
+        }
+
+        async fn resolve_handle(
+            &self,
+            _handle: &Handle<'_>,
+        ) -> std::result::Result<Did<'static>, IdentityError> {
+            Err(IdentityError::invalid_well_known())
+        }
+
+        async fn resolve_did_doc(
+            &self,
+            _did: &Did<'_>,
+        ) -> std::result::Result<DidDocResponse, IdentityError> {
+            Err(IdentityError::invalid_well_known())
+        }
+    }
+
+    impl DpopExt for MockServer {}
+    impl OAuthResolver for MockServer {}
+
+    fn session_data() -> ClientSessionData<'static> {
+        ClientSessionData {
+            account_did: Did::new_static("did:plc:alice").unwrap(),
+            session_id: CowStr::from("state"),
+            host_url: Url::parse("https://pds").unwrap(),
+            authserver_url: Url::parse("https://issuer").unwrap(),
+            authserver_token_endpoint: CowStr::from("https://issuer/token"),
+            authserver_revocation_endpoint: Some(CowStr::from("https://issuer/revoke")),
+            scopes: vec![Scope::Atproto],
+            dpop_data: DpopClientData {
+                dpop_key: crate::utils::generate_key(&[CowStr::from("ES256")]).unwrap(),
+                dpop_authserver_nonce: CowStr::from(""),
+                dpop_host_nonce: CowStr::from(""),
+            },
+            token_set: crate::types::TokenSet {
+                iss: CowStr::from("https://issuer"),
+                sub: Did::new_static("did:plc:alice").unwrap(),
+                aud: CowStr::from("https://pds"),
+                scope: None,
+                refresh_token: Some(CowStr::from("refresh")),
+                access_token: CowStr::from("access"),
+                token_type: crate::types::OAuthTokenType::DPoP,
+                expires_at: None,
+            },
+        }
+    }
+
+    /// Revoke a stored session against a server answering with `revoke_status`
+    ///
+    /// Returns the result and whether the session is still in the store afterwards.
+    async fn revoke_with(revoke_status: StatusCode) -> (Result<()>, bool) {
+        let client = Arc::new(MockServer { revoke_status });
+        let client_data = ClientData {
+            keyset: None,
+            config: AtprotoClientMetadata::default_localhost(),
+        };
+        let registry = Arc::new(SessionRegistry::new(
+            MemoryAuthStore::new(),
+            client.clone(),
+            client_data,
+        ));
+        let data = session_data();
+        registry.store.upsert_session(data.clone()).await.unwrap();
+
+        let session = OAuthSession::new(registry.clone(), client, data);
+        let result = session.revoke().await;
+        let did = Did::new_static("did:plc:alice").unwrap();
+        let stored = registry.store.get_session(&did, "state").await.unwrap();
+        (result, stored.is_some())
+    }
+
+    #[tokio::test]
+    async fn revoke_removes_session_on_success() {
+        let (result, stored) = revoke_with(StatusCode::OK).await;
+        result.unwrap();
+        assert!(!stored);
+    }
+
+    #[tokio::test]
+    async fn revoke_keeps_session_on_failure() {
+        use crate::request::RequestErrorKind;
+
+        let (result, stored) = revoke_with(StatusCode::BAD_REQUEST).await;
+        let Err(crate::error::OAuthError::Request(err)) = result else {
+            panic!("expected a request error, got {result:?}");
+        };
+        assert!(matches!(
+            err.kind(),
+            RequestErrorKind::HttpStatus(StatusCode::BAD_REQUEST)
+                | RequestErrorKind::HttpStatusWithBody {
+                    status: StatusCode::BAD_REQUEST,
+                    ..
+                }
+        ));
+        assert!(stored);
+    }
+}
@@ -181,6 +181,14 @@ fn store_nonce<N: DpopDataSource>(
     }
 }
 
+/// Read the `DPoP-Nonce` header from a response
+fn response_nonce(headers: &http::HeaderMap) -> Option<CowStr<'static>> {
+    headers
+        .get("DPoP-Nonce")
+        .and_then(|v| v.to_str().ok())
+        .map(|c| CowStr::from(c.to_string()))
+}
+
 pub async fn wrap_request_with_dpop<T, N>(
     client: &T,
     data_source: &mut N,
@@ -210,11 +218,7 @@ where
         .await
         .map_err(|e| Error::Inner(e.into()))?;
 
-    let next_nonce = response
-        .headers()
-        .get("DPoP-Nonce")
-        .and_then(|v| v.to_str().ok())
-        .map(|c| CowStr::from(c.to_string()));
+    let next_nonce = response_nonce(response.headers());
     match &next_nonce {
         Some(s) if next_nonce != init_nonce => {
             store_nonce(data_source, is_to_auth_server, s.clone());
@@ -233,6 +237,10 @@ where
         .send_http(request)
         .await
         .map_err(|e| Error::Inner(e.into()))?;
+    // Servers may rotate the nonce on any response; keep the newest for the next call
+    if let Some(nonce) = response_nonce(response.headers()) {
+        store_nonce(data_source, is_to_auth_server, nonce);
+    }
     Ok(response)
 }
 
@@ -269,11 +277,7 @@ where
         .map_err(|e| Error::Inner(e.into()))?;
 
     let (parts, body) = http_response.into_parts();
-    let next_nonce = parts
-        .headers
-        .get("DPoP-Nonce")
-        .and_then(|v| v.to_str().ok())
-        .map(|c| CowStr::from(c.to_string()));
+    let next_nonce = response_nonce(&parts.headers);
     match &next_nonce {
         Some(s) if next_nonce != init_nonce => {
             store_nonce(data_source, is_to_auth_server, s.clone());
@@ -296,6 +300,9 @@ where
         .await
         .map_err(|e| Error::Inner(e.into()))?;
     let (parts, body) = http_response.into_parts();
+    if let Some(nonce) = response_nonce(&parts.headers) {
+        store_nonce(data_source, is_to_auth_server, nonce);
+    }
     Ok(StreamingResponse::new(parts, body))
 }
 
@@ -337,11 +344,7 @@ where
         .map_err(|e| Error::Inner(e.into()))?;
 
     let (resp_parts, resp_body) = http_response.into_parts();
-    let next_nonce = resp_parts
-        .headers
-        .get("DPoP-Nonce")
-        .and_then(|v| v.to_str().ok())
-        .map(|c| CowStr::from(c.to_string()));
+    let next_nonce = response_nonce(&resp_parts.headers);
     match &next_nonce {
         Some(s) if next_nonce != init_nonce => {
             store_nonce(data_source, is_to_auth_server, s.clone());
@@ -365,6 +368,9 @@ where
         .await
         .map_err(|e| Error::Inner(e.into()))?;
     let (parts, body) = http_response.into_parts();
+    if let Some(nonce) = response_nonce(&parts.headers) {
+        store_nonce(data_source, is_to_auth_server, nonce);
+    }
     Ok(StreamingResponse::new(parts, body))
 }
 
@@ -462,3 +468,110 @@ pub fn build_dpop_proof<'s>(
 }
 
 impl DpopExt for JacquardResolver {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::DpopClientData;
+    use std::sync::Mutex;
+
+    /// Resource server that demands a DPoP nonce before accepting a request
+    struct NonceServer {
+        /// Nonce required in the proof; `None` rejects every request with a fresh nonce
+        nonce: Option<&'static str>,
+        /// Nonce claim of each proof received
+        seen: Mutex<Vec<Option<String>>>,
+    }
+
+    impl NonceServer {
+        fn new(nonce: Option<&'static str>) -> Self {
+            Self {
+                nonce,
+                seen: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    fn proof_nonce(request: &Request<Vec<u8>>) -> Option<String> {
+        let proof = request.headers().get("DPoP")?.to_str().ok()?;
+        let payload = URL_SAFE_NO_PAD.decode(proof.split('.').nth(1)?).ok()?;
+        let claims: serde_json::Value = serde_json::from_slice(&payload).ok()?;
+        claims["nonce"].as_str().map(String::from)
+    }
+
+    impl HttpClient for NonceServer {
+        type Error = std::convert::Infallible;
+
+        async fn send_http(
+            &self,
+            request: Request<Vec<u8>>,
+        ) -> core::result::Result<Response<Vec<u8>>, Self::Error> {
+            let nonce = proof_nonce(&request);
+            let mut seen = self.seen.lock().unwrap();
+            seen.push(nonce.clone());
+            let response = match self.nonce {
+                Some(required) if nonce.as_deref() == Some(required) => Response::builder()
+                    .status(200)
+                    .header("DPoP-Nonce", required),
+                _ => Response::builder()
+                    .status(401)
+                    .header("WWW-Authenticate", r#"DPoP error="use_dpop_nonce""#)
+                    .header(
+                        "DPoP-Nonce",
+                        self.nonce
+                            .map(String::from)
+                            .unwrap_or_else(|| format!("nonce-{}", seen.len())),
+                    ),
+            };
+            Ok(response.body(Vec::new()).unwrap())
+        }
+    }
+
+    impl DpopExt for NonceServer {}
+
+    fn session_dpop() -> DpopClientData<'static> {
+        DpopClientData {
+            dpop_key: crate::utils::generate_key(&[CowStr::from("ES256")]).unwrap(),
+            dpop_authserver_nonce: CowStr::from(""),
+            dpop_host_nonce: CowStr::from(""),
+        }
+    }
+
+    fn request() -> Request<Vec<u8>> {
+        Request::get("https://pds.example.com/xrpc/com.atproto.server.getSession")
+            .header("Authorization", "DPoP token")
+            .body(Vec::new())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn retries_with_nonce_and_remembers_it() {
+        let server = NonceServer::new(Some("n1"));
+        let mut data = session_dpop();
+
+        let response = server.dpop_call(&mut data).send(request()).await.unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(data.dpop_host_nonce, "n1");
+        assert_eq!(
+            *server.seen.lock().unwrap(),
+            vec![None, Some("n1".to_string())]
+        );
+
+        // The remembered nonce is used straight away next time
+        let response = server.dpop_call(&mut data).send(request()).await.unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(server.seen.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn retries_at_most_once() {
+        let server = NonceServer::new(None);
+        let mut data = session_dpop();
+
+        let response = server.dpop_call(&mut data).send(request()).await.unwrap();
+        assert_eq!(response.status(), 401);
+        assert_eq!(server.seen.lock().unwrap().len(), 2);
+        // The nonce from the rejected retry is kept for the next attempt
+        assert_eq!(data.dpop_host_nonce, "nonce-2");
+    }
+}
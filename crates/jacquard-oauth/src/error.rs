@@ -67,6 +67,9 @@ pub enum CallbackError {
     #[error("timeout")]
     #[diagnostic(code(jacquard_oauth::callback::timeout))]
     Timeout,
+    #[error("login cancelled")]
+    #[diagnostic(code(jacquard_oauth::callback::login_cancelled))]
+    LoginCancelled,
 }
 
 pub type Result<T> = core::result::Result<T, OAuthError>;
@@ -35,6 +35,10 @@ impl Keyset {
     const PREFERRED_SIGNING_ALGORITHMS: [&'static str; 9] = [
         "EdDSA", "ES256K", "ES256", "PS256", "PS384", "PS512", "HS256", "HS384", "HS512",
     ];
+    /// The public halves of the keys, for publishing as the client's JWK set.
+    ///
+    /// Each key keeps its `kid`, which is the same `kid` [`Keyset::create_jwt`] puts in the
+    /// header of the JWTs it signs, so servers can look up the verifying key.
     pub fn public_jwks(&self) -> JwkSet {
         let mut keys = Vec::with_capacity(self.0.len());
         for mut key in self.0.clone() {
@@ -48,6 +52,12 @@ impl Keyset {
         }
         JwkSet { keys }
     }
+    /// The public JWK set as JSON, for serving at the client's `jwks_uri`.
+    ///
+    /// Private key material is stripped, as in [`Keyset::public_jwks`].
+    pub fn to_jwks(&self) -> serde_json::Value {
+        serde_json::to_value(self.public_jwks()).expect("JWK sets always serialize to JSON")
+    }
     pub fn create_jwt(&self, algs: &[CowStr], claims: Claims) -> Result<CowStr<'static>> {
         let Some(jwk) = self.find_key(algs, Class::Signing) else {
             return Err(Error::NotFound(algs.to_vec().into_static()));
@@ -125,3 +135,53 @@ impl TryFrom<Vec<Jwk>> for Keyset {
         Ok(Self(v))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jose::jwt::RegisteredClaims;
+    use base64::Engine;
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use jose_jwk::Parameters;
+
+    fn keyset() -> Keyset {
+        let keys = ["key-a", "key-b"]
+            .into_iter()
+            .map(|kid| Jwk {
+                key: crate::utils::generate_key(&[CowStr::from("ES256")]).unwrap(),
+                prm: Parameters {
+                    kid: Some(kid.into()),
+                    ..Default::default()
+                },
+            })
+            .collect::<Vec<_>>();
+        Keyset::try_from(keys).unwrap()
+    }
+
+    #[test]
+    fn jwks_resolves_signing_kid() {
+        let keyset = keyset();
+        let jwt = keyset
+            .create_jwt(
+                &[CowStr::from("ES256")],
+                RegisteredClaims {
+                    iss: Some(CowStr::from("https://client.example.com")),
+                    ..Default::default()
+                }
+                .into(),
+            )
+            .unwrap();
+        let header = jwt.split('.').next().unwrap();
+        let header: serde_json::Value =
+            serde_json::from_slice(&URL_SAFE_NO_PAD.decode(header).unwrap()).unwrap();
+        let kid = header["kid"].as_str().unwrap();
+
+        let jwks = keyset.to_jwks();
+        let keys = jwks["keys"].as_array().unwrap();
+        assert_eq!(keys.len(), 2);
+        let key = keys.iter().find(|key| key["kid"] == kid).unwrap();
+        assert_eq!(key["kty"], "EC");
+        assert_eq!(key["crv"], "P-256");
+        assert!(keys.iter().all(|key| key.get("d").is_none()));
+    }
+}
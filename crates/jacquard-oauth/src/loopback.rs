@@ -14,6 +14,7 @@ use jacquard_common::{IntoStatic, cowstr::ToCowStr};
 use rouille::Server;
 use std::net::SocketAddr;
 use tokio::sync::mpsc;
+pub use tokio_util::sync::CancellationToken;
 use url::Url;
 
 #[derive(Clone, Debug)]
@@ -72,12 +73,20 @@ pub fn create_callback_router(
 }
 
 struct CallbackHandle {
-    #[allow(dead_code)]
     server_handle: std::thread::JoinHandle<()>,
     server_stop: std::sync::mpsc::Sender<()>,
     callback_rx: mpsc::Receiver<CallbackParams<'static>>,
 }
 
+/// Stop the callback server and wait for its thread to exit, so the port is released
+async fn shutdown_server(
+    server_handle: std::thread::JoinHandle<()>,
+    server_stop: std::sync::mpsc::Sender<()>,
+) {
+    let _ = server_stop.send(());
+    let _ = tokio::task::spawn_blocking(move || server_handle.join()).await;
+}
+
 fn one_shot_server(addr: SocketAddr) -> (SocketAddr, CallbackHandle) {
     let (tx, callback_rx) = mpsc::channel(5);
     let server = Server::new(addr, move |request| {
@@ -99,12 +108,35 @@ where
     S: ClientAuthStore + Send + Sync + 'static,
 {
     /// Drive the full OAuth flow using a local loopback server.
+    ///
+    /// Fails with [`CallbackError::Timeout`] if no callback arrives within
+    /// `cfg.timeout_ms`. See
+    /// [`login_with_local_server_cancellable`](Self::login_with_local_server_cancellable)
+    /// to also abort on demand.
     pub async fn login_with_local_server(
         &self,
         input: impl AsRef<str>,
         opts: AuthorizeOptions<'_>,
         cfg: LoopbackConfig,
     ) -> crate::error::Result<super::client::OAuthSession<T, S>> {
+        self.login_with_local_server_cancellable(input, opts, cfg, CancellationToken::new())
+            .await
+    }
+
+    /// Drive the full OAuth flow using a local loopback server, aborting when `cancel` fires.
+    ///
+    /// Cancelling (e.g. from a "cancel login" button) stops the callback server, waits for
+    /// it to release its port, and returns [`CallbackError::LoginCancelled`]. The
+    /// `cfg.timeout_ms` limit still applies.
+    pub async fn login_with_local_server_cancellable(
+        &self,
+        input: impl AsRef<str>,
+        opts: AuthorizeOptions<'_>,
+        cfg: LoopbackConfig,
+        cancel: CancellationToken,
+    ) -> crate::error::Result<super::client::OAuthSession<T, S>> {
+        // Reject malformed input before binding the callback server
+        let input = crate::resolver::parse_login_input(input.as_ref())?;
         let port = match cfg.port {
             LoopbackPort::Fixed(p) => p,
             LoopbackPort::Ephemeral => 0,
@@ -140,29 +172,38 @@ where
             client_data.clone(),
         );
 
-        // Start auth and get authorization URL
-        let auth_url = flow_client.start_auth(input.as_ref(), opts).await?;
-        // Print URL for copy/paste
-        println!("To authenticate with your PDS, visit:\n{}\n", auth_url);
-        // Optionally open browser
-        if cfg.open_browser {
-            let _ = try_open_in_browser(&auth_url);
-        }
+        let CallbackHandle {
+            server_handle,
+            server_stop,
+            mut callback_rx,
+        } = handle;
+        let await_callback = async {
+            // Start auth and get authorization URL
+            let auth_url = flow_client.start_auth(input.as_str(), opts).await?;
+            // Print URL for copy/paste
+            println!("To authenticate with your PDS, visit:\n{}\n", auth_url);
+            // Optionally open browser
+            if cfg.open_browser {
+                let _ = try_open_in_browser(&auth_url);
+            }
 
-        // Await callback or timeout
-        let mut callback_rx = handle.callback_rx;
-        let cb = tokio::time::timeout(
-            std::time::Duration::from_millis(cfg.timeout_ms),
-            callback_rx.recv(),
-        )
-        .await;
-        // trigger shutdown
-        let _ = handle.server_stop.send(());
-        if let Ok(Some(cb)) = cb {
-            // Handle callback and create a session
-            Ok(flow_client.callback(cb).await?)
-        } else {
-            Err(OAuthError::Callback(CallbackError::Timeout))
-        }
+            // Await callback or timeout
+            let cb = tokio::time::timeout(
+                std::time::Duration::from_millis(cfg.timeout_ms),
+                callback_rx.recv(),
+            )
+            .await;
+            match cb {
+                Ok(Some(cb)) => Ok(cb),
+                _ => Err(OAuthError::Callback(CallbackError::Timeout)),
+            }
+        };
+        let cb = cancel.run_until_cancelled(await_callback).await;
+        // Shut down on every path, including errors and cancellation
+        shutdown_server(server_handle, server_stop).await;
+
+        let cb = cb.ok_or(OAuthError::Callback(CallbackError::LoginCancelled))??;
+        // Handle callback and create a session
+        flow_client.callback(cb).await
     }
 }
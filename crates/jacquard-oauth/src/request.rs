@@ -90,6 +90,14 @@ pub enum RequestErrorKind {
     #[diagnostic(code(jacquard_oauth::request::no_refresh_token))]
     NoRefreshToken,
 
+    /// The refresh token was rejected as expired or revoked
+    #[error("refresh token expired or revoked")]
+    #[diagnostic(
+        code(jacquard_oauth::request::refresh_token_expired),
+        help("the session can't be refreshed; start a new authorization flow")
+    )]
+    RefreshTokenExpired,
+
     /// Invalid DID
     #[error("failed to parse DID")]
     #[diagnostic(code(jacquard_oauth::request::invalid_did))]
@@ -253,6 +261,11 @@ impl RequestError {
         Self::new(RequestErrorKind::NoRefreshToken, None)
     }
 
+    /// Create a refresh token expired error
+    pub fn refresh_token_expired() -> Self {
+        Self::new(RequestErrorKind::RefreshTokenExpired, None)
+    }
+
     /// Create an invalid DID error
     pub fn invalid_did(source: impl std::error::Error + Send + Sync + 'static) -> Self {
         Self::new(RequestErrorKind::InvalidDid, Some(Box::new(source)))
@@ -426,6 +439,16 @@ impl OAuthRequest<'_> {
             _ => unimplemented!(),
         }
     }
+
+    /// Whether `status` counts as success for this request
+    pub fn accepts_status(&self, status: StatusCode) -> bool {
+        match self {
+            // Revocation accepts `200` and `204`: RFC 7009 says servers answer `200`,
+            // but oauth-provider answers `204`.
+            Self::Revocation(_) => status == StatusCode::OK || status == StatusCode::NO_CONTENT,
+            _ => status == self.expected_status(),
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -576,7 +599,19 @@ where
         }),
         metadata,
     )
-    .await?;
+    .await
+    .map_err(|e| {
+        // https://datatracker.ietf.org/doc/html/rfc6749#section-5.2
+        let invalid_grant = matches!(
+            e.kind(),
+            RequestErrorKind::HttpStatusWithBody { body, .. } if body["error"] == "invalid_grant"
+        );
+        if invalid_grant {
+            RequestError::refresh_token_expired()
+        } else {
+            e
+        }
+    })?;
 
     let expires_at = response.expires_in.and_then(|expires_in| {
         let now = Datetime::now();
@@ -715,7 +750,7 @@ where
         .header("Content-Type", "application/x-www-form-urlencoded")
         .body(body.into_bytes())?;
     let res = client.dpop_server_call(data_source).send(req).await?;
-    if res.status() == request.expected_status() {
+    if request.accepts_status(res.status()) {
         let body = res.body();
         if body.is_empty() {
             // since an empty body cannot be deserialized, use “null” temporarily to allow deserialization to `()`.
@@ -858,18 +893,23 @@ mod tests {
     #[derive(Clone, Default)]
     struct MockClient {
         resp: Arc<Mutex<Option<HttpResponse<Vec<u8>>>>>,
+        sent: Arc<Mutex<Option<http::Request<Vec<u8>>>>>,
     }
 
     impl HttpClient for MockClient {
         type Error = std::convert::Infallible;
         fn send_http(
             &self,
-            _request: http::Request<Vec<u8>>,
+            request: http::Request<Vec<u8>>,
         ) -> impl core::future::Future<
             Output = core::result::Result<http::Response<Vec<u8>>, Self::Error>,
         > + Send {
             let resp = self.resp.clone();
-            async move { Ok(resp.lock().await.take().unwrap()) }
+            let sent = self.sent.clone();
+            async move {
+                *sent.lock().await = Some(request);
+                Ok(resp.lock().await.take().unwrap())
+            }
         }
     }
 
@@ -900,7 +940,7 @@ mod tests {
             let doc = serde_json::json!({
                 "id": "did:plc:alice",
                 "service": [{
-                    "id": "#pds",
+                    "id": "#atproto_pds",
                     "type": "AtprotoPersonalDataServer",
                     "serviceEndpoint": "https://pds"
                 }]
@@ -1015,4 +1055,44 @@ mod tests {
             .unwrap_err();
         assert!(matches!(err.kind(), RequestErrorKind::TokenVerification));
     }
+
+    #[tokio::test]
+    async fn revoke_accepts_empty_ok() {
+        let client = MockClient::default();
+        *client.resp.lock().await = Some(
+            HttpResponse::builder()
+                .status(StatusCode::OK)
+                .body(Vec::new())
+                .unwrap(),
+        );
+        let mut meta = base_metadata();
+        meta.server_metadata.revocation_endpoint = Some(CowStr::from("https://issuer/revoke"));
+        let mut dpop = DpopReqData {
+            dpop_key: crate::utils::generate_key(&[CowStr::from("ES256")]).unwrap(),
+            dpop_authserver_nonce: None,
+        };
+        super::revoke(&client, &mut dpop, "refresh-tok", &meta)
+            .await
+            .unwrap();
+
+        let sent = client.sent.lock().await.take().unwrap();
+        assert_eq!(sent.uri(), "https://issuer/revoke");
+        assert_eq!(sent.method(), Method::POST);
+        assert!(sent.headers().contains_key("DPoP"));
+        let body = String::from_utf8(sent.into_body()).unwrap();
+        assert!(body.contains("token=refresh-tok"));
+    }
+
+    #[tokio::test]
+    async fn revoke_missing_endpoint() {
+        let meta = base_metadata();
+        let mut dpop = DpopReqData {
+            dpop_key: crate::utils::generate_key(&[CowStr::from("ES256")]).unwrap(),
+            dpop_authserver_nonce: None,
+        };
+        let err = super::revoke(&MockClient::default(), &mut dpop, "refresh-tok", &meta)
+            .await
+            .unwrap_err();
+        assert!(matches!(err.kind(), RequestErrorKind::NoEndpoint(name) if name == "revocation"));
+    }
 }
@@ -6,6 +6,7 @@ use http::{Request, StatusCode};
 use jacquard_common::CowStr;
 use jacquard_common::IntoStatic;
 use jacquard_common::types::did_doc::DidDocument;
+use jacquard_common::types::handle::Handle;
 use jacquard_common::types::ident::AtIdentifier;
 use jacquard_common::{http_client::HttpClient, types::did::Did};
 use jacquard_identity::resolver::{IdentityError, IdentityResolver};
@@ -388,6 +389,85 @@ async fn verify_issuer_impl<T: OAuthResolver + ?Sized>(
         .ok_or_else(|| ResolverError::did_document(smol_str::format_smolstr!("{:?}", identity)))?)
 }
 
+/// What a user typed into a login box, classified and validated
+///
+/// Produced by [`parse_login_input`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoginInput {
+    /// An account handle, lowercased and without any leading `@`
+    Handle(Handle<'static>),
+    /// An account DID
+    Did(Did<'static>),
+    /// A PDS or entryway URL, for when the user's identity doesn't resolve (or they've
+    /// forgotten their handle)
+    PdsUrl(Url),
+}
+
+impl LoginInput {
+    /// The normalized input as a string
+    pub fn as_str(&self) -> &str {
+        match self {
+            LoginInput::Handle(handle) => handle.as_str(),
+            LoginInput::Did(did) => did.as_str(),
+            LoginInput::PdsUrl(url) => url.as_str(),
+        }
+    }
+
+    /// The account identifier, or `None` for a server URL
+    pub fn as_identifier(&self) -> Option<AtIdentifier<'_>> {
+        match self {
+            LoginInput::Handle(handle) => Some(AtIdentifier::Handle(handle.clone())),
+            LoginInput::Did(did) => Some(AtIdentifier::Did(did.clone())),
+            LoginInput::PdsUrl(_) => None,
+        }
+    }
+}
+
+/// Classify and validate login input without touching the network
+///
+/// Accepts a handle (optionally prefixed with `@`), a DID, or an `https://` PDS/entryway
+/// URL. Surrounding whitespace is ignored. This is the same parsing the login flow does
+/// before resolving anything, so a UI can use it to reject bad input up front.
+///
+/// ```
+/// # use jacquard_oauth::resolver::{LoginInput, parse_login_input};
+/// let input = parse_login_input(" @Alice.bsky.social ").unwrap();
+/// assert_eq!(input.as_str(), "alice.bsky.social");
+/// assert!(matches!(input, LoginInput::Handle(_)));
+///
+/// assert!(matches!(
+///     parse_login_input("https://bsky.social").unwrap(),
+///     LoginInput::PdsUrl(_)
+/// ));
+/// assert!(parse_login_input("not a handle").is_err());
+/// ```
+pub fn parse_login_input(s: &str) -> Result<LoginInput> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err(ResolverError::at_identifier("empty login input")
+            .with_help("enter a handle, DID, or PDS URL"));
+    }
+
+    if s.starts_with("https://") {
+        let url = Url::parse(s).map_err(|e| {
+            ResolverError::new(ResolverErrorKind::Uri, Some(Box::new(e))).with_context(s)
+        })?;
+        Ok(LoginInput::PdsUrl(url))
+    } else if s.starts_with("http://") {
+        Err(ResolverError::new(ResolverErrorKind::Uri, None)
+            .with_context(s)
+            .with_help("PDS URLs must use https"))
+    } else if s.starts_with("did:") {
+        let did = Did::new_owned(s)
+            .map_err(|e| ResolverError::did(smol_str::format_smolstr!("{}", e)))?;
+        Ok(LoginInput::Did(did))
+    } else {
+        let handle = Handle::new_owned(s.to_lowercase())
+            .map_err(|e| ResolverError::at_identifier(smol_str::format_smolstr!("{}", e)))?;
+        Ok(LoginInput::Handle(handle))
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 async fn resolve_oauth_impl<T: OAuthResolver + Sync + ?Sized>(
     resolver: &T,
@@ -399,12 +479,12 @@ async fn resolve_oauth_impl<T: OAuthResolver + Sync + ?Sized>(
     // Allow using an entryway, or PDS url, directly as login input (e.g.
     // when the user forgot their handle, or when the handle does not
     // resolve to a DID)
-    Ok(if input.starts_with("https://") {
-        let url = Url::parse(input).map_err(|_| ResolverError::not_found())?;
-        (resolver.resolve_from_service(&url).await?, None)
-    } else {
-        let (metadata, identity) = resolver.resolve_from_identity(input).await?;
-        (metadata, Some(identity))
+    Ok(match parse_login_input(input)? {
+        LoginInput::PdsUrl(url) => (resolver.resolve_from_service(&url).await?, None),
+        ident => {
+            let (metadata, identity) = resolver.resolve_from_identity(ident.as_str()).await?;
+            (metadata, Some(identity))
+        }
     })
 }
 
@@ -419,12 +499,12 @@ async fn resolve_oauth_impl<T: OAuthResolver + ?Sized>(
     // Allow using an entryway, or PDS url, directly as login input (e.g.
     // when the user forgot their handle, or when the handle does not
     // resolve to a DID)
-    Ok(if input.starts_with("https://") {
-        let url = Url::parse(input).map_err(|_| ResolverError::not_found())?;
-        (resolver.resolve_from_service(&url).await?, None)
-    } else {
-        let (metadata, identity) = resolver.resolve_from_identity(input).await?;
-        (metadata, Some(identity))
+    Ok(match parse_login_input(input)? {
+        LoginInput::PdsUrl(url) => (resolver.resolve_from_service(&url).await?, None),
+        ident => {
+            let (metadata, identity) = resolver.resolve_from_identity(ident.as_str()).await?;
+            (metadata, Some(identity))
+        }
     })
 }
 
@@ -922,4 +1002,40 @@ mod tests {
             "https://issuer/"
         ));
     }
+
+    #[test]
+    fn login_input_parsing() {
+        let handle = parse_login_input("@Alice.Example.com").unwrap();
+        assert_eq!(
+            handle,
+            LoginInput::Handle(Handle::new_static("alice.example.com").unwrap())
+        );
+        assert!(handle.as_identifier().is_some());
+
+        let did = parse_login_input(" did:plc:z72i7hdynmk6r22z27h6tvur\n").unwrap();
+        assert_eq!(did.as_str(), "did:plc:z72i7hdynmk6r22z27h6tvur");
+        assert!(matches!(did, LoginInput::Did(_)));
+
+        let url = parse_login_input("https://pds.example.com").unwrap();
+        assert!(matches!(url, LoginInput::PdsUrl(_)));
+        assert!(url.as_identifier().is_none());
+
+        for bad in [
+            "",
+            "   ",
+            "not a handle",
+            "did:nope",
+            "http://pds.example.com",
+            "https://",
+        ] {
+            assert!(
+                parse_login_input(bad).is_err(),
+                "{bad:?} should be rejected"
+            );
+        }
+        assert!(matches!(
+            parse_login_input("bad..handle").unwrap_err().kind(),
+            ResolverErrorKind::AtIdentifier(_)
+        ));
+    }
 }
@@ -307,6 +307,28 @@ impl IntoStatic for RpcAudience<'_> {
     }
 }
 
+/// Service the XRPC calls under [`Scope::preset_bluesky_client`] are proxied to
+///
+/// The Bluesky AppView, with the `#bsky_appview` fragment percent-encoded as the scope
+/// grammar requires.
+pub const BLUESKY_APPVIEW_AUDIENCE: &str = "did:web:api.bsky.app%23bsky_appview";
+
+/// Record collections writable under [`Scope::preset_bluesky_client`]
+pub const BLUESKY_CLIENT_COLLECTIONS: &[&str] = &[
+    "app.bsky.actor.profile",
+    "app.bsky.feed.post",
+    "app.bsky.feed.like",
+    "app.bsky.feed.repost",
+    "app.bsky.feed.threadgate",
+    "app.bsky.feed.postgate",
+    "app.bsky.graph.follow",
+    "app.bsky.graph.block",
+    "app.bsky.graph.list",
+    "app.bsky.graph.listitem",
+    "app.bsky.graph.listblock",
+    "app.bsky.graph.starterpack",
+];
+
 impl<'s> Scope<'s> {
     /// Parse multiple space-separated scopes from a string
     ///
@@ -342,12 +364,11 @@ impl<'s> Scope<'s> {
     /// assert_eq!(scopes.len(), 2); // atproto and repo:*
     /// ```
     pub fn parse_multiple_reduced(s: &'s str) -> Result<Vec<Self>, ParseError> {
-        let all_scopes = Self::parse_multiple(s)?;
-
-        if all_scopes.is_empty() {
-            return Ok(Vec::new());
-        }
+        Ok(Self::reduce(Self::parse_multiple(s)?))
+    }
 
+    /// Drop duplicate scopes and scopes granted by another scope in the list
+    fn reduce(all_scopes: Vec<Self>) -> Vec<Self> {
         let mut result: Vec<Self> = Vec::new();
 
         for scope in all_scopes {
@@ -383,7 +404,47 @@ impl<'s> Scope<'s> {
             }
         }
 
-        Ok(result)
+        result
+    }
+
+    /// Reduce a list of scopes to its canonical form
+    ///
+    /// Duplicates and scopes granted by another scope in the list are removed
+    /// (as in [`Scope::parse_multiple_reduced`]), and the remainder is sorted by
+    /// its normalized string representation. Two lists granting the same
+    /// permissions produce identical output regardless of ordering or whether
+    /// default parameters were spelled out.
+    ///
+    /// # Examples
+    /// ```
+    /// # use jacquard_oauth::scopes::Scope;
+    /// let a = Scope::parse_multiple("repo:* atproto repo:app.bsky.feed.post").unwrap();
+    /// let normalized = Scope::normalize_set(&a);
+    /// assert_eq!(Scope::serialize_multiple(&normalized), "atproto repo:*");
+    /// ```
+    pub fn normalize_set(scopes: &[Self]) -> Vec<Self> {
+        let mut result = Self::reduce(scopes.to_vec());
+        result.sort_by_cached_key(|scope| scope.to_string_normalized());
+        result
+    }
+
+    /// Check whether two lists of scopes grant the same permissions
+    ///
+    /// Useful for deciding whether a new scope request differs from one the
+    /// user already approved.
+    ///
+    /// # Examples
+    /// ```
+    /// # use jacquard_oauth::scopes::Scope;
+    /// let approved = Scope::parse_multiple("atproto repo:*").unwrap();
+    /// let requested = Scope::parse_multiple(
+    ///     "repo:*?action=create&action=update&action=delete atproto",
+    /// )
+    /// .unwrap();
+    /// assert!(Scope::set_eq(&approved, &requested));
+    /// ```
+    pub fn set_eq(a: &[Self], b: &[Self]) -> bool {
+        Self::normalize_set(a) == Self::normalize_set(b)
     }
 
     /// Serialize a list of scopes into a space-separated OAuth scopes string
@@ -442,6 +503,123 @@ impl<'s> Scope<'s> {
             .collect()
     }
 
+    /// Scopes granted by both `a` and `b`
+    ///
+    /// Coverage is decided by [`grants`](Self::grants), so the narrower of two overlapping
+    /// scopes is kept: `repo:*` against `repo:app.bsky.feed.post` yields the latter.
+    ///
+    /// # Examples
+    /// ```
+    /// # use jacquard_oauth::scopes::Scope;
+    /// let a = Scope::parse_multiple("atproto repo:*").unwrap();
+    /// let b = Scope::parse_multiple("atproto repo:app.bsky.feed.post account:email").unwrap();
+    /// assert!(Scope::set_eq(
+    ///     &Scope::intersect(&a, &b),
+    ///     &Scope::parse_multiple("atproto repo:app.bsky.feed.post").unwrap(),
+    /// ));
+    /// ```
+    pub fn intersect(a: &[Self], b: &[Self]) -> Vec<Self> {
+        let covered = |scope: &Self, by: &[Self]| by.iter().any(|s| s.grants(scope));
+        let mut result: Vec<Self> = Vec::new();
+        let candidates = a
+            .iter()
+            .filter(|s| covered(s, b))
+            .chain(b.iter().filter(|s| covered(s, a)));
+        for scope in candidates {
+            if !result.contains(scope) {
+                result.push(scope.clone());
+            }
+        }
+        result
+    }
+
+    /// Scopes in `requested` that nothing in `granted` covers
+    ///
+    /// Coverage is decided by [`grants`](Self::grants) rather than equality, so a granted
+    /// `repo:*` covers a requested `repo:app.bsky.feed.post`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use jacquard_oauth::scopes::Scope;
+    /// let requested = Scope::parse_multiple("repo:app.bsky.feed.post account:email").unwrap();
+    /// let granted = Scope::parse_multiple("repo:*").unwrap();
+    /// assert_eq!(
+    ///     Scope::difference(&requested, &granted),
+    ///     vec![Scope::parse("account:email").unwrap()]
+    /// );
+    /// ```
+    pub fn difference(requested: &[Self], granted: &[Self]) -> Vec<Self> {
+        requested
+            .iter()
+            .filter(|scope| !granted.iter().any(|g| g.grants(scope)))
+            .cloned()
+            .collect()
+    }
+
+    /// Scopes for a typical Bluesky client using granular permissions
+    ///
+    /// Grants:
+    /// - `atproto`
+    /// - `repo:<collection>` with create, update and delete for each collection in
+    ///   [`BLUESKY_CLIENT_COLLECTIONS`]: posts, likes, reposts, thread/post gates,
+    ///   follows, blocks, lists, starter packs and the profile record
+    /// - `blob:image/* video/*` for uploading media
+    /// - `rpc:*` proxied to the Bluesky AppView ([`BLUESKY_APPVIEW_AUDIENCE`]) - timelines,
+    ///   notifications, etc. A wildcard audience isn't allowed together with `lxm=*`.
+    ///
+    /// It does not grant writes to collections outside that list, account email or status
+    /// access, handle changes, or chat. Use [`preset_full_account`](Self::preset_full_account)
+    /// if the server doesn't support granular scopes yet.
+    ///
+    /// # Examples
+    /// ```
+    /// # use jacquard_oauth::scopes::Scope;
+    /// let scopes = Scope::preset_bluesky_client();
+    /// let post = Scope::parse("repo:app.bsky.feed.post?action=create").unwrap();
+    /// assert!(scopes.iter().any(|s| s.grants(&post)));
+    /// ```
+    pub fn preset_bluesky_client() -> Vec<Self> {
+        let all_actions =
+            BTreeSet::from([RepoAction::Create, RepoAction::Update, RepoAction::Delete]);
+        let mut scopes = vec![Scope::Atproto];
+        scopes.extend(BLUESKY_CLIENT_COLLECTIONS.iter().map(|nsid| {
+            Scope::Repo(RepoScope {
+                collection: RepoCollection::Nsid(Nsid::raw(nsid)),
+                actions: all_actions.clone(),
+            })
+        }));
+        scopes.push(Scope::Blob(BlobScope {
+            accept: BTreeSet::from([
+                MimePattern::TypeWildcard(CowStr::new_static("image")),
+                MimePattern::TypeWildcard(CowStr::new_static("video")),
+            ]),
+        }));
+        scopes.push(Scope::Rpc(RpcScope {
+            lxm: BTreeSet::from([RpcLexicon::All]),
+            aud: BTreeSet::from([RpcAudience::Did(Did::raw(BLUESKY_APPVIEW_AUDIENCE))]),
+        }));
+        scopes
+    }
+
+    /// Scopes equivalent to an app password: `atproto transition:generic`
+    ///
+    /// Grants write access to every collection in the repo, blob uploads of any type, and
+    /// any XRPC method except account-management ones (email, password, deactivation,
+    /// handle and PLC changes) and chat. This is what most existing Bluesky clients
+    /// request, and works on servers that predate granular scopes.
+    ///
+    /// # Examples
+    /// ```
+    /// # use jacquard_oauth::scopes::Scope;
+    /// assert_eq!(
+    ///     Scope::serialize_multiple(&Scope::preset_full_account()),
+    ///     "atproto transition:generic"
+    /// );
+    /// ```
+    pub fn preset_full_account() -> Vec<Self> {
+        vec![Scope::Atproto, Scope::Transition(TransitionScope::Generic)]
+    }
+
     /// Parse a scope from a string
     pub fn parse(s: &'s str) -> Result<Self, ParseError> {
         // Determine the prefix first by checking for known prefixes
@@ -952,6 +1130,132 @@ impl<'s> Scope<'s> {
     }
 }
 
+/// Fluent construction of `repo` and `rpc` scopes
+///
+/// Unset actions and audiences default to the same wildcards [`Scope::parse`] uses, so a
+/// built scope compares equal to the parsed form of the equivalent string.
+///
+/// # Examples
+/// ```
+/// # use jacquard_oauth::scopes::{RepoAction, Scope, ScopeBuilder};
+/// # use jacquard_common::types::{did::Did, nsid::Nsid};
+/// let post = ScopeBuilder::repo(Nsid::new_static("app.bsky.feed.post").unwrap())
+///     .actions([RepoAction::Create, RepoAction::Update])
+///     .build();
+/// assert_eq!(
+///     post,
+///     Scope::parse("repo:app.bsky.feed.post?action=create&action=update").unwrap()
+/// );
+///
+/// let timeline = ScopeBuilder::rpc(Nsid::new_static("app.bsky.feed.getTimeline").unwrap())
+///     .aud(Did::new_static("did:web:api.bsky.app").unwrap())
+///     .build();
+/// assert_eq!(
+///     timeline,
+///     Scope::parse("rpc:app.bsky.feed.getTimeline?aud=did:web:api.bsky.app").unwrap()
+/// );
+/// ```
+pub struct ScopeBuilder;
+
+impl ScopeBuilder {
+    /// Start a `repo` scope for one collection
+    pub fn repo<'s>(collection: Nsid<'s>) -> RepoScopeBuilder<'s> {
+        RepoScopeBuilder {
+            collection: RepoCollection::Nsid(collection),
+            actions: BTreeSet::new(),
+        }
+    }
+
+    /// Start a `repo` scope for every collection (`repo:*`)
+    pub fn repo_all<'s>() -> RepoScopeBuilder<'s> {
+        RepoScopeBuilder {
+            collection: RepoCollection::All,
+            actions: BTreeSet::new(),
+        }
+    }
+
+    /// Start an `rpc` scope for one lexicon method
+    pub fn rpc<'s>(lxm: Nsid<'s>) -> RpcScopeBuilder<'s> {
+        RpcScopeBuilder {
+            lxm: BTreeSet::from([RpcLexicon::Nsid(lxm)]),
+            aud: BTreeSet::new(),
+        }
+    }
+
+    /// Start an `rpc` scope for every lexicon method
+    pub fn rpc_all<'s>() -> RpcScopeBuilder<'s> {
+        RpcScopeBuilder {
+            lxm: BTreeSet::from([RpcLexicon::All]),
+            aud: BTreeSet::new(),
+        }
+    }
+}
+
+/// Builder for [`Scope::Repo`], created by [`ScopeBuilder::repo`]
+#[derive(Debug, Clone)]
+pub struct RepoScopeBuilder<'s> {
+    collection: RepoCollection<'s>,
+    actions: BTreeSet<RepoAction>,
+}
+
+impl<'s> RepoScopeBuilder<'s> {
+    /// Allow `actions`; with none, all actions are allowed
+    pub fn actions(mut self, actions: impl IntoIterator<Item = RepoAction>) -> Self {
+        self.actions.extend(actions);
+        self
+    }
+
+    /// Finish the scope
+    pub fn build(self) -> Scope<'s> {
+        let actions = if self.actions.is_empty() {
+            BTreeSet::from([RepoAction::Create, RepoAction::Update, RepoAction::Delete])
+        } else {
+            self.actions
+        };
+        Scope::Repo(RepoScope {
+            collection: self.collection,
+            actions,
+        })
+    }
+}
+
+/// Builder for [`Scope::Rpc`], created by [`ScopeBuilder::rpc`]
+#[derive(Debug, Clone)]
+pub struct RpcScopeBuilder<'s> {
+    lxm: BTreeSet<RpcLexicon<'s>>,
+    aud: BTreeSet<RpcAudience<'s>>,
+}
+
+impl<'s> RpcScopeBuilder<'s> {
+    /// Also allow the `lxm` method
+    pub fn lxm(mut self, lxm: Nsid<'s>) -> Self {
+        self.lxm.insert(RpcLexicon::Nsid(lxm));
+        self
+    }
+
+    /// Allow calls proxied to `aud`; with no audience set, any audience is allowed
+    pub fn aud(mut self, aud: Did<'s>) -> Self {
+        self.aud.insert(RpcAudience::Did(aud));
+        self
+    }
+
+    /// Allow calls proxied to any audience
+    pub fn aud_all(mut self) -> Self {
+        self.aud.insert(RpcAudience::All);
+        self
+    }
+
+    /// Finish the scope
+    pub fn build(self) -> Scope<'s> {
+        let aud = if self.aud.is_empty() {
+            BTreeSet::from([RpcAudience::All])
+        } else {
+            self.aud
+        };
+        Scope::Rpc(RpcScope { lxm: self.lxm, aud })
+    }
+}
+
 impl MimePattern<'_> {
     fn grants(&self, other: &MimePattern) -> bool {
         match (self, other) {
@@ -1543,6 +1847,33 @@ mod tests {
         assert!(Scope::parse_multiple("account:invalid repo:*").is_err());
     }
 
+    #[test]
+    fn test_normalize_set_and_set_eq() {
+        let a = Scope::parse_multiple("atproto repo:* account:email").unwrap();
+        let b = Scope::parse_multiple(
+            "account:email?action=read repo:app.bsky.feed.post \
+             repo:*?action=create&action=update&action=delete atproto atproto",
+        )
+        .unwrap();
+        assert_eq!(Scope::normalize_set(&a), Scope::normalize_set(&b));
+        assert!(Scope::set_eq(&a, &b));
+        assert_eq!(
+            Scope::serialize_multiple(&Scope::normalize_set(&b)),
+            "account:email atproto repo:*"
+        );
+
+        // Narrower permissions are a different set
+        let c = Scope::parse_multiple("atproto repo:*?action=create account:email").unwrap();
+        assert!(!Scope::set_eq(&a, &c));
+
+        // Broader permissions are a different set
+        let d = Scope::parse_multiple("atproto repo:* account:email?action=manage").unwrap();
+        assert!(!Scope::set_eq(&a, &d));
+
+        assert!(Scope::set_eq(&[], &[]));
+        assert!(Scope::normalize_set(&[]).is_empty());
+    }
+
     #[test]
     fn test_parse_multiple_reduced() {
         // Test repo scope reduction - wildcard grants specific
@@ -2006,4 +2337,127 @@ mod tests {
         assert!(result.contains(&Scope::parse("account:email?action=manage").unwrap()));
         assert!(result.contains(&Scope::parse("account:repo").unwrap()));
     }
+
+    #[test]
+    fn test_presets() {
+        let full = Scope::preset_full_account();
+        assert_eq!(
+            full,
+            vec![Scope::Atproto, Scope::Transition(TransitionScope::Generic)]
+        );
+
+        let client = Scope::preset_bluesky_client();
+        assert!(client.contains(&Scope::Atproto));
+        let serialized = Scope::serialize_multiple(&client);
+        assert!(serialized.contains("repo:app.bsky.feed.post"));
+        assert!(serialized.contains("blob?accept=image/*&accept=video/*"));
+        assert!(serialized.contains("aud=did:web:api.bsky.app%23bsky_appview&lxm=*"));
+        assert!(!serialized.contains("aud=*"));
+
+        // Presets round-trip through the scope grammar
+        let reparsed = Scope::parse_multiple(&serialized).unwrap();
+        assert!(Scope::set_eq(&reparsed, &client));
+
+        let grants = |scope: &str| {
+            let scope = Scope::parse(scope).unwrap();
+            client.iter().any(|s| s.grants(&scope))
+        };
+        assert!(grants("repo:app.bsky.graph.follow?action=delete"));
+        assert!(grants("blob:image/png"));
+        assert!(grants(
+            "rpc:app.bsky.feed.getTimeline?aud=did:web:api.bsky.app%23bsky_appview"
+        ));
+        assert!(!grants(
+            "rpc:app.bsky.feed.getTimeline?aud=did:web:example.com"
+        ));
+        assert!(!grants("repo:com.example.record"));
+        assert!(!grants("blob:application/pdf"));
+        assert!(!grants("account:email"));
+        assert!(!grants("identity:handle"));
+    }
+
+    #[test]
+    fn test_scope_builder() {
+        let post = Nsid::new_static("app.bsky.feed.post").unwrap();
+        assert_eq!(
+            ScopeBuilder::repo(post.clone())
+                .actions([RepoAction::Create, RepoAction::Update])
+                .build(),
+            Scope::parse("repo:app.bsky.feed.post?action=create&action=update").unwrap()
+        );
+        // All three actions collapse to the same scope as no actions at all
+        assert_eq!(
+            ScopeBuilder::repo(post.clone())
+                .actions([RepoAction::Create, RepoAction::Update, RepoAction::Delete])
+                .build(),
+            Scope::parse("repo:app.bsky.feed.post").unwrap()
+        );
+        assert_eq!(
+            ScopeBuilder::repo(post).build().to_string_normalized(),
+            "repo:app.bsky.feed.post"
+        );
+        assert_eq!(
+            ScopeBuilder::repo_all()
+                .actions([RepoAction::Delete])
+                .build(),
+            Scope::parse("repo:*?action=delete").unwrap()
+        );
+
+        let timeline = Nsid::new_static("app.bsky.feed.getTimeline").unwrap();
+        assert_eq!(
+            ScopeBuilder::rpc(timeline.clone())
+                .aud(Did::new_static("did:web:api.bsky.app").unwrap())
+                .build(),
+            Scope::parse("rpc:app.bsky.feed.getTimeline?aud=did:web:api.bsky.app").unwrap()
+        );
+        assert_eq!(
+            ScopeBuilder::rpc(timeline).build(),
+            Scope::parse("rpc:app.bsky.feed.getTimeline").unwrap()
+        );
+        assert_eq!(
+            ScopeBuilder::rpc_all().build(),
+            Scope::parse("rpc:*").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_intersect_and_difference() {
+        let parse = |s| Scope::parse_multiple(s).unwrap();
+
+        // A wildcard grant covers specific requests, but not the reverse
+        let granted = parse("atproto repo:* blob:image/*");
+        let requested = parse("atproto repo:app.bsky.feed.post blob:image/png blob:video/mp4");
+        assert_eq!(
+            Scope::difference(&requested, &granted),
+            parse("blob:video/mp4")
+        );
+        assert_eq!(
+            Scope::difference(&granted, &requested),
+            parse("repo:* blob:image/*")
+        );
+        assert!(Scope::set_eq(
+            &Scope::intersect(&granted, &requested),
+            &parse("atproto repo:app.bsky.feed.post blob:image/png"),
+        ));
+        assert!(Scope::set_eq(
+            &Scope::intersect(&requested, &granted),
+            &Scope::intersect(&granted, &requested),
+        ));
+
+        // Action subsets are covered by the full action set
+        let granted = parse("repo:app.bsky.feed.post rpc:*");
+        let requested =
+            parse("repo:app.bsky.feed.post?action=create rpc:app.bsky.feed.getTimeline");
+        assert!(Scope::difference(&requested, &granted).is_empty());
+        assert!(Scope::set_eq(
+            &Scope::intersect(&granted, &requested),
+            &requested
+        ));
+
+        // Disjoint sets
+        let a = parse("account:email");
+        let b = parse("identity:handle");
+        assert!(Scope::intersect(&a, &b).is_empty());
+        assert_eq!(Scope::difference(&a, &b), a);
+    }
 }
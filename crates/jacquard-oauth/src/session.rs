@@ -124,12 +124,13 @@ impl DpopDataSource for DpopClientData<'_> {
     fn key(&self) -> &Key {
         &self.dpop_key
     }
+    // An empty nonce means none has been issued yet
     fn authserver_nonce(&self) -> Option<CowStr<'_>> {
-        Some(self.dpop_authserver_nonce.clone())
+        Some(self.dpop_authserver_nonce.clone()).filter(|nonce| !nonce.is_empty())
     }
 
     fn host_nonce(&self) -> Option<CowStr<'_>> {
-        Some(self.dpop_host_nonce.clone())
+        Some(self.dpop_host_nonce.clone()).filter(|nonce| !nonce.is_empty())
     }
 
     fn set_authserver_nonce(&mut self, nonce: CowStr<'_>) {
@@ -19,10 +19,35 @@
 //! let roots = vec![commit_cid];
 //! write_car("repo.car", roots, blocks).await?;
 //! ```
+//!
+//! Reading a CARv2 file and its index:
+//! ```ignore
+//! use jacquard_repo::car::reader::read_car_v2;
+//!
+//! let parsed = read_car_v2("repo.car").await?;
+//! let offsets = parsed.index.unwrap_or_default();
+//! ```
+//!
+//! Appending the blocks of a newer commit to an existing backup:
+//! ```ignore
+//! use jacquard_repo::car::writer::{append_car, read_car_roots};
+//!
+//! let appended = append_car("repo.car", new_commit_cid, new_blocks).await?;
+//! let latest = read_car_roots("repo.car").await?[0];
+//! ```
 
 pub mod reader;
 pub mod writer;
 
 // Re-export commonly used functions and types
-pub use reader::{parse_car_bytes, read_car, read_car_header, stream_car, ParsedCar};
-pub use writer::{export_repo_car, write_car, write_car_bytes};
+pub use reader::{
+    parse_car_bytes, parse_car_v2_bytes, validate_car_bytes, validate_car_framing, CarV2Header,
+    CarValidation, ParsedCar, ParsedCarV2,
+};
+#[cfg(not(target_family = "wasm"))]
+pub use reader::{read_car, read_car_header, read_car_v2, stream_car, validate_car};
+#[cfg(feature = "streaming")]
+pub use writer::stream_export_repo_car;
+pub use writer::{append_blocks, export_diff_car, write_car_bytes};
+#[cfg(not(target_family = "wasm"))]
+pub use writer::{append_car, export_repo_car, read_car_roots, write_car};
@@ -2,16 +2,25 @@
 //!
 //! Provides functions for reading CAR (Content Addressable aRchive) files into memory
 //! or streaming them for large repositories.
+//!
+//! Both CARv1 and CARv2 are accepted. A CARv2 file wraps a CARv1 data payload with a
+//! fixed-size header and an optional index; the general readers unwrap the payload
+//! transparently, and [`read_car_v2`] / [`parse_car_v2_bytes`] also expose the header and
+//! index.
 
-use crate::error::{RepoError, Result};
+use crate::error::{CarError, RepoError, Result};
 use bytes::Bytes;
 use cid::Cid as IpldCid;
 use iroh_car::CarReader;
 use n0_future::stream::{Stream, StreamExt};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+#[cfg(not(target_family = "wasm"))]
 use std::path::Path;
 use std::pin::Pin;
+#[cfg(not(target_family = "wasm"))]
 use tokio::fs::File;
+#[cfg(not(target_family = "wasm"))]
+use tokio::io::{AsyncReadExt, AsyncSeekExt, Take};
 
 /// Parsed CAR file data
 #[derive(Debug, Clone)]
@@ -22,17 +31,339 @@ pub struct ParsedCar {
     pub blocks: BTreeMap<IpldCid, Bytes>,
 }
 
+/// Parsed CARv2 file data
+#[derive(Debug, Clone)]
+pub struct ParsedCarV2 {
+    /// The CARv2 header
+    pub header: CarV2Header,
+    /// Root and blocks of the CARv1 data payload
+    pub car: ParsedCar,
+    /// Offset of each block's section, relative to the start of the data payload
+    ///
+    /// `None` if the file has no index.
+    pub index: Option<HashMap<IpldCid, u64>>,
+}
+
+/// Bytes opening every CARv2 file: a CARv1-style header section holding `{"version": 2}`
+pub const CAR_V2_PRAGMA: [u8; 11] = [
+    0x0a, 0xa1, 0x67, b'v', b'e', b'r', b's', b'i', b'o', b'n', 0x02,
+];
+
+/// Length of the fixed CARv2 header following the pragma
+const CAR_V2_HEADER_LEN: usize = 40;
+
+/// Multicodec code of the IndexSorted CARv2 index format
+const INDEX_SORTED: u64 = 0x0400;
+
+/// Multicodec code of the MultihashIndexSorted CARv2 index format
+const MULTIHASH_INDEX_SORTED: u64 = 0x0401;
+
+/// Fixed-size CARv2 header
+///
+/// Locates the CARv1 data payload and the optional index within the file. Offsets are
+/// from the start of the file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CarV2Header {
+    /// Characteristics bitfield
+    pub characteristics: [u8; 16],
+    /// Offset of the CARv1 data payload
+    pub data_offset: u64,
+    /// Length of the CARv1 data payload
+    pub data_size: u64,
+    /// Offset of the index, or 0 if there is none
+    pub index_offset: u64,
+}
+
+impl CarV2Header {
+    /// Whether the index covers every block, including identity CIDs and duplicates
+    pub fn is_fully_indexed(&self) -> bool {
+        self.characteristics[0] & 0x80 != 0
+    }
+
+    /// Whether the file has an index
+    pub fn has_index(&self) -> bool {
+        self.index_offset != 0
+    }
+
+    /// Decode the pragma and header at the start of `data`
+    ///
+    /// Returns `None` if `data` doesn't start with the CARv2 pragma, i.e. is a CARv1 file.
+    fn parse(data: &[u8]) -> std::result::Result<Option<Self>, CarError> {
+        let Some(rest) = data.strip_prefix(&CAR_V2_PRAGMA[..]) else {
+            return Ok(None);
+        };
+        let Some(header) = rest.get(..CAR_V2_HEADER_LEN) else {
+            return Err(CarError::Truncated {
+                offset: CAR_V2_PRAGMA.len(),
+                needed: CAR_V2_HEADER_LEN,
+                remaining: rest.len(),
+            });
+        };
+
+        let u64_at = |at: usize| u64::from_le_bytes(header[at..at + 8].try_into().unwrap());
+        Ok(Some(Self {
+            characteristics: header[..16].try_into().unwrap(),
+            data_offset: u64_at(16),
+            data_size: u64_at(24),
+            index_offset: u64_at(32),
+        }))
+    }
+
+    /// Check that the payload and index lie within a file of `len` bytes
+    fn validate(&self, len: u64) -> std::result::Result<(), CarError> {
+        let header_end = (CAR_V2_PRAGMA.len() + CAR_V2_HEADER_LEN) as u64;
+        if self.data_offset < header_end {
+            return Err(CarError::InvalidV2Header(format!(
+                "data offset {} overlaps the header",
+                self.data_offset
+            )));
+        }
+        let data_end = self
+            .data_offset
+            .checked_add(self.data_size)
+            .filter(|&end| end <= len)
+            .ok_or_else(|| {
+                CarError::InvalidV2Header(format!(
+                    "data payload of {} bytes at offset {} runs past the end of the {}-byte file",
+                    self.data_size, self.data_offset, len
+                ))
+            })?;
+        if self.has_index() && (self.index_offset < data_end || self.index_offset >= len) {
+            return Err(CarError::InvalidV2Header(format!(
+                "index offset {} is not between the end of the data payload ({}) and the end of the file ({})",
+                self.index_offset, data_end, len
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Split in-memory CAR data into its CARv2 header, if any, and CARv1 data payload
+fn split_car_payload(data: &[u8]) -> std::result::Result<(Option<CarV2Header>, &[u8]), CarError> {
+    match CarV2Header::parse(data)? {
+        Some(header) => {
+            header.validate(data.len() as u64)?;
+            // Both bounds were checked against the slice length above
+            let start = header.data_offset as usize;
+            let end = start + header.data_size as usize;
+            Ok((Some(header), &data[start..end]))
+        }
+        None => Ok((None, data)),
+    }
+}
+
+/// Sequential little-endian reader over CARv2 index bytes
+struct IndexReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> IndexReader<'a> {
+    fn take(&mut self, len: usize) -> std::result::Result<&'a [u8], CarError> {
+        let bytes = self
+            .offset
+            .checked_add(len)
+            .and_then(|end| self.data.get(self.offset..end))
+            .ok_or_else(|| {
+                CarError::InvalidIndex(format!(
+                    "truncated at offset {}: needed {} bytes",
+                    self.offset, len
+                ))
+            })?;
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    fn u32(&mut self) -> std::result::Result<u32, CarError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> std::result::Result<u64, CarError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    /// An `i32` count, which must not be negative
+    fn count(&mut self) -> std::result::Result<usize, CarError> {
+        let count = self.u32()? as i32;
+        usize::try_from(count)
+            .map_err(|_| CarError::InvalidIndex(format!("negative count {}", count)))
+    }
+
+    /// An `i64` byte length, which must not be negative
+    fn length(&mut self) -> std::result::Result<usize, CarError> {
+        let len = self.u64()? as i64;
+        usize::try_from(len).map_err(|_| CarError::InvalidIndex(format!("negative length {}", len)))
+    }
+}
+
+/// Decode a CARv2 index, resolving each entry to a CID via the data payload
+///
+/// The index formats only store multihash digests, so each entry's CID is read from the
+/// section it points at and checked against the digest.
+fn parse_car_v2_index(
+    index: &[u8],
+    payload: &[u8],
+) -> std::result::Result<HashMap<IpldCid, u64>, CarError> {
+    let (codec, varint_len) = read_varint(index, 0)
+        .map_err(|_| CarError::InvalidIndex("malformed index codec".to_string()))?;
+    let mut reader = IndexReader {
+        data: index,
+        offset: varint_len,
+    };
+
+    let mut offsets = HashMap::new();
+    match codec {
+        INDEX_SORTED => read_sorted_buckets(&mut reader, None, payload, &mut offsets)?,
+        MULTIHASH_INDEX_SORTED => {
+            for _ in 0..reader.count()? {
+                let code = reader.u64()?;
+                read_sorted_buckets(&mut reader, Some(code), payload, &mut offsets)?;
+            }
+        }
+        codec => return Err(CarError::UnsupportedIndex { codec }),
+    }
+    Ok(offsets)
+}
+
+/// Read the width buckets of an IndexSorted index
+///
+/// `code` is the multihash code the entries must use, if the index records one.
+fn read_sorted_buckets(
+    reader: &mut IndexReader<'_>,
+    code: Option<u64>,
+    payload: &[u8],
+    offsets: &mut HashMap<IpldCid, u64>,
+) -> std::result::Result<(), CarError> {
+    for _ in 0..reader.count()? {
+        let width = reader.u32()? as usize;
+        let len = reader.length()?;
+        if width <= 8 || len % width != 0 {
+            return Err(CarError::InvalidIndex(format!(
+                "bucket of {} bytes doesn't hold whole {}-byte entries",
+                len, width
+            )));
+        }
+
+        let entries = reader.take(len)?;
+        for entry in entries.chunks_exact(width) {
+            let (digest, offset) = entry.split_at(width - 8);
+            let offset = u64::from_le_bytes(offset.try_into().unwrap());
+            let cid = cid_at(payload, offset)?;
+
+            let hash = cid.hash();
+            if hash.digest() != digest || code.is_some_and(|code| hash.code() != code) {
+                return Err(CarError::InvalidIndex(format!(
+                    "entry at payload offset {} doesn't match the block there ({})",
+                    offset, cid
+                )));
+            }
+            offsets.insert(cid, offset);
+        }
+    }
+    Ok(())
+}
+
+/// Read the CID of the section at `offset` in a CARv1 payload
+fn cid_at(payload: &[u8], offset: u64) -> std::result::Result<IpldCid, CarError> {
+    let invalid = || {
+        CarError::InvalidIndex(format!(
+            "offset {} doesn't point at a block section",
+            offset
+        ))
+    };
+    let offset = usize::try_from(offset)
+        .ok()
+        .filter(|&offset| offset < payload.len())
+        .ok_or_else(invalid)?;
+    let (len, varint_len) = read_varint(payload, offset).map_err(|_| invalid())?;
+    let section = payload
+        .get(offset + varint_len..)
+        .and_then(|rest| rest.get(..usize::try_from(len).ok()?))
+        .ok_or_else(invalid)?;
+    IpldCid::read_bytes(section).map_err(|_| invalid())
+}
+
+/// Maximum length of a CAR section length prefix, per the multiformats unsigned-varint spec
+const MAX_VARINT_LEN: usize = 9;
+
+/// Decode the unsigned varint at `offset`, returning the value and its encoded length
+fn read_varint(data: &[u8], offset: usize) -> std::result::Result<(u64, usize), CarError> {
+    let mut value = 0u64;
+    for i in 0..MAX_VARINT_LEN {
+        let Some(&byte) = data.get(offset + i) else {
+            return Err(CarError::Truncated {
+                offset,
+                needed: i + 1,
+                remaining: data.len() - offset,
+            });
+        };
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok((value, i + 1));
+        }
+    }
+    Err(CarError::InvalidVarint { offset })
+}
+
+/// Check the section framing of in-memory CAR data
+///
+/// Walks the varint length prefixes of the header and every block section without
+/// decoding their contents, rejecting overlong varints, zero-length sections, and
+/// declared lengths which run past the end of the input. Returns the number of
+/// block sections.
+///
+/// This is cheap and allocation-free, so it is worth running on untrusted input
+/// before handing it to a decoder. [`parse_car_bytes`] and [`read_car`] call it
+/// automatically. `data` must be CARv1; for a CARv2 file, pass its data payload.
+pub fn validate_car_framing(data: &[u8]) -> std::result::Result<usize, CarError> {
+    let mut offset = 0;
+    let mut sections = 0usize;
+
+    while offset < data.len() {
+        let (len, varint_len) = read_varint(data, offset)?;
+        let remaining = data.len() - offset - varint_len;
+        if len == 0 {
+            return Err(CarError::EmptySection { offset });
+        }
+        if len > remaining as u64 {
+            return Err(CarError::Truncated {
+                offset,
+                needed: usize::try_from(len).unwrap_or(usize::MAX),
+                remaining,
+            });
+        }
+        offset += varint_len + len as usize;
+        sections += 1;
+    }
+
+    if sections == 0 {
+        // No header at all
+        return Err(CarError::Truncated {
+            offset: 0,
+            needed: 1,
+            remaining: 0,
+        });
+    }
+
+    Ok(sections - 1)
+}
+
 /// Read entire CAR file into memory
 ///
 /// Returns BTreeMap of CID -> block data (sorted order for determinism).
+/// Accepts CARv1 and CARv2 files. The framing is checked with [`validate_car_framing`] before decoding.
 /// For large CAR files, consider using `stream_car()` instead.
+#[cfg(not(target_family = "wasm"))]
 pub async fn read_car(path: impl AsRef<Path>) -> Result<BTreeMap<IpldCid, Bytes>> {
     let path = path.as_ref();
-    let file = File::open(path)
+    let data = tokio::fs::read(path)
         .await
-        .map_err(|e| RepoError::io(e).with_context(format!("opening CAR file: {}", path.display())))?;
+        .map_err(|e| RepoError::io(e).with_context(format!("reading CAR file: {}", path.display())))?;
 
-    let reader = CarReader::new(file).await.map_err(|e| RepoError::car(e))?;
+    let (_, payload) = split_car_payload(&data)?;
+    validate_car_framing(payload)?;
+
+    let reader = CarReader::new(payload).await.map_err(RepoError::car)?;
 
     let mut blocks = BTreeMap::new();
     let stream = reader.stream();
@@ -48,12 +379,11 @@ pub async fn read_car(path: impl AsRef<Path>) -> Result<BTreeMap<IpldCid, Bytes>
 
 /// Read CAR file header (roots only)
 ///
-/// Useful for checking roots without loading all blocks.
+/// Useful for checking roots without loading all blocks. For a CARv2 file, these are
+/// the roots of its data payload.
+#[cfg(not(target_family = "wasm"))]
 pub async fn read_car_header(path: impl AsRef<Path>) -> Result<Vec<IpldCid>> {
-    let path = path.as_ref();
-    let file = File::open(path)
-        .await
-        .map_err(|e| RepoError::io(e).with_context(format!("opening CAR file: {}", path.display())))?;
+    let file = open_car_payload(path.as_ref()).await?;
 
     let reader = CarReader::new(file).await.map_err(|e| RepoError::car(e))?;
 
@@ -63,8 +393,57 @@ pub async fn read_car_header(path: impl AsRef<Path>) -> Result<Vec<IpldCid>> {
 /// Parse CAR bytes into root and block map
 ///
 /// For in-memory CAR data (e.g., from firehose commit messages, merkle proofs).
-/// Returns the first root CID and all blocks.
+/// Returns the first root CID and all blocks. Accepts CARv1 and CARv2; for CARv2 the
+/// header is only used to locate the data payload. The framing is checked with
+/// [`validate_car_framing`] before decoding.
 pub async fn parse_car_bytes(data: &[u8]) -> Result<ParsedCar> {
+    let (_, payload) = split_car_payload(data)?;
+    parse_car_payload(payload).await
+}
+
+/// Parse a CARv2 file held in memory, including its index
+///
+/// Unlike [`parse_car_bytes`], CARv1 input is rejected. The index, if present, is
+/// checked against the data payload: every entry must point at a block section whose CID
+/// has the indexed digest.
+pub async fn parse_car_v2_bytes(data: &[u8]) -> Result<ParsedCarV2> {
+    let (header, payload) = split_car_payload(data)?;
+    let header = header.ok_or_else(|| {
+        CarError::InvalidV2Header("data doesn't start with the CARv2 pragma".to_string())
+    })?;
+
+    let car = parse_car_payload(payload).await?;
+    let index = if header.has_index() {
+        Some(parse_car_v2_index(
+            &data[header.index_offset as usize..],
+            payload,
+        )?)
+    } else {
+        None
+    };
+
+    Ok(ParsedCarV2 { header, car, index })
+}
+
+/// Read a CARv2 file into memory, including its index
+///
+/// See [`parse_car_v2_bytes`].
+#[cfg(not(target_family = "wasm"))]
+pub async fn read_car_v2(path: impl AsRef<Path>) -> Result<ParsedCarV2> {
+    let path = path.as_ref();
+    let data = tokio::fs::read(path).await.map_err(|e| {
+        RepoError::io(e).with_context(format!("reading CAR file: {}", path.display()))
+    })?;
+
+    parse_car_v2_bytes(&data)
+        .await
+        .map_err(|e| e.with_context(format!("reading CARv2 file: {}", path.display())))
+}
+
+/// Decode a CARv1 payload into root and block map
+async fn parse_car_payload(data: &[u8]) -> Result<ParsedCar> {
+    validate_car_framing(data)?;
+
     let reader = CarReader::new(data)
         .await
         .map_err(|e| RepoError::car_parse(e))?;
@@ -87,14 +466,134 @@ pub async fn parse_car_bytes(data: &[u8]) -> Result<ParsedCar> {
     Ok(ParsedCar { root, blocks })
 }
 
+/// Multihash code of the identity "hash", whose digest is the data itself
+const IDENTITY_HASH: u64 = 0x00;
+
+/// Multihash code of SHA2-512
+const SHA2_512_HASH: u64 = 0x13;
+
+/// Result of checking every block of a CAR against its CID
+///
+/// See [`validate_car`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CarValidation {
+    /// Roots declared in the CAR header
+    pub roots: Vec<IpldCid>,
+    /// Number of blocks whose hash was recomputed
+    pub blocks_checked: usize,
+    /// Blocks whose bytes don't hash to their CID
+    pub mismatched: Vec<IpldCid>,
+    /// Blocks with a codec or hash function that can't be checked
+    pub unsupported: Vec<IpldCid>,
+    /// Declared roots with no block in the archive
+    pub missing_roots: Vec<IpldCid>,
+}
+
+impl CarValidation {
+    /// Whether every declared root has a block in the archive
+    pub fn all_roots_present(&self) -> bool {
+        self.missing_roots.is_empty()
+    }
+
+    /// Whether every block was checked and matched, and every root is present
+    pub fn is_valid(&self) -> bool {
+        self.mismatched.is_empty() && self.unsupported.is_empty() && self.all_roots_present()
+    }
+}
+
+/// Check that every block in a CAR file matches its CID
+///
+/// Reads the file into memory and runs [`validate_car_bytes`] on it.
+#[cfg(not(target_family = "wasm"))]
+pub async fn validate_car(path: impl AsRef<Path>) -> Result<CarValidation> {
+    let path = path.as_ref();
+    let data = tokio::fs::read(path).await.map_err(|e| {
+        RepoError::io(e).with_context(format!("reading CAR file: {}", path.display()))
+    })?;
+
+    validate_car_bytes(&data)
+        .map_err(|e| e.with_context(format!("validating CAR file: {}", path.display())))
+}
+
+/// Check that every block in in-memory CAR data matches its CID
+///
+/// Each block is rehashed with the hash function named in its CID (identity, SHA2-256 or
+/// SHA2-512) rather than assuming SHA-256. Blocks with any other hash function, or with a
+/// codec other than DAG-CBOR or raw, are reported as unsupported instead of being trusted.
+///
+/// Broken framing, such as a final section whose length prefix runs past the end of the
+/// data, is an error rather than part of the report, since nothing after that point can
+/// be read. Accepts CARv1 and CARv2.
+pub fn validate_car_bytes(data: &[u8]) -> Result<CarValidation> {
+    let (_, payload) = split_car_payload(data)?;
+    validate_car_framing(payload)?;
+
+    // Framing was validated above, so every section is in bounds and non-empty
+    let (header_len, varint_len) = read_varint(payload, 0)?;
+    let mut offset = varint_len + header_len as usize;
+    let header =
+        iroh_car::CarHeader::decode(&payload[varint_len..offset]).map_err(RepoError::car_parse)?;
+
+    let mut report = CarValidation {
+        roots: header.roots().to_vec(),
+        ..Default::default()
+    };
+    let mut seen = HashSet::new();
+    while offset < payload.len() {
+        let (len, varint_len) = read_varint(payload, offset)?;
+        let mut section = &payload[offset + varint_len..offset + varint_len + len as usize];
+        let cid = IpldCid::read_bytes(&mut section).map_err(|e| {
+            RepoError::car_parse(e).with_context(format!("reading block CID at offset {}", offset))
+        })?;
+        offset += varint_len + len as usize;
+        seen.insert(cid);
+
+        match block_matches_cid(&cid, section) {
+            Some(true) => report.blocks_checked += 1,
+            Some(false) => {
+                report.blocks_checked += 1;
+                report.mismatched.push(cid);
+            }
+            None => report.unsupported.push(cid),
+        }
+    }
+
+    report.missing_roots = report
+        .roots
+        .iter()
+        .filter(|root| !seen.contains(*root))
+        .copied()
+        .collect();
+    Ok(report)
+}
+
+/// Rehash a block with its CID's hash function
+///
+/// Returns `None` if the codec or hash function isn't supported.
+pub(crate) fn block_matches_cid(cid: &IpldCid, data: &[u8]) -> Option<bool> {
+    use sha2::{Digest, Sha256, Sha512};
+
+    if !matches!(cid.codec(), crate::DAG_CBOR_CID_CODEC | crate::RAW_CID_CODEC) {
+        return None;
+    }
+    let hash = cid.hash();
+    let matches = match hash.code() {
+        IDENTITY_HASH => hash.digest() == data,
+        jacquard_common::types::crypto::SHA2_256 => hash.digest() == &Sha256::digest(data)[..],
+        SHA2_512_HASH => hash.digest() == &Sha512::digest(data)[..],
+        _ => return None,
+    };
+    Some(matches)
+}
+
 /// Stream CAR blocks without loading entire file into memory
 ///
-/// Useful for processing large CAR files incrementally.
+/// Useful for processing large CAR files incrementally. Since the file isn't held in
+/// memory, framing can't be checked up front; individual sections are still capped
+/// at 4 MiB by the underlying reader. CARv2 files are streamed from their data payload.
+#[cfg(not(target_family = "wasm"))]
 pub async fn stream_car(path: impl AsRef<Path>) -> Result<CarBlockStream> {
-    let path = path.as_ref();
-    let file = File::open(path)
-        .await
-        .map_err(|e| RepoError::io(e).with_context(format!("opening CAR file: {}", path.display())))?;
+    let file = open_car_payload(path.as_ref()).await?;
 
     let reader = CarReader::new(file).await.map_err(|e| RepoError::car(e))?;
 
@@ -104,6 +603,35 @@ pub async fn stream_car(path: impl AsRef<Path>) -> Result<CarBlockStream> {
     Ok(CarBlockStream { stream, roots })
 }
 
+/// Open a CAR file positioned at its CARv1 data payload
+///
+/// CARv1 files are read from the start; CARv2 files are limited to their data payload.
+#[cfg(not(target_family = "wasm"))]
+async fn open_car_payload(path: &Path) -> Result<Take<File>> {
+    let io_err = |e| RepoError::io(e).with_context(format!("opening CAR file: {}", path.display()));
+    let mut file = File::open(path).await.map_err(io_err)?;
+
+    let mut prefix = Vec::with_capacity(CAR_V2_PRAGMA.len() + CAR_V2_HEADER_LEN);
+    (&mut file)
+        .take((CAR_V2_PRAGMA.len() + CAR_V2_HEADER_LEN) as u64)
+        .read_to_end(&mut prefix)
+        .await
+        .map_err(io_err)?;
+
+    let (start, limit) = match CarV2Header::parse(&prefix)? {
+        Some(header) => {
+            let len = file.metadata().await.map_err(io_err)?.len();
+            header.validate(len)?;
+            (header.data_offset, header.data_size)
+        }
+        None => (0, u64::MAX),
+    };
+    file.seek(std::io::SeekFrom::Start(start))
+        .await
+        .map_err(io_err)?;
+    Ok(file.take(limit))
+}
+
 /// Streaming CAR block reader
 ///
 /// Iterates through CAR blocks without loading entire file into memory.
@@ -265,4 +793,337 @@ mod tests {
         // Stream exhausted
         assert!(stream.next().await.unwrap().is_none());
     }
+
+    // Regression inputs for malformed framing; none of these may panic or over-allocate
+
+    #[tokio::test]
+    async fn test_validate_framing_counts_blocks() {
+        let cid1 = make_test_cid(1);
+        let cid2 = make_test_cid(2);
+        let car_bytes =
+            make_test_car(vec![cid1], vec![(cid1, vec![1, 2, 3]), (cid2, vec![4])]).await;
+
+        assert_eq!(validate_car_framing(&car_bytes).unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_empty_input() {
+        assert!(matches!(
+            validate_car_framing(&[]),
+            Err(CarError::Truncated { offset: 0, .. })
+        ));
+        let err = parse_car_bytes(&[]).await.unwrap_err();
+        assert_eq!(*err.kind(), crate::error::RepoErrorKind::Car);
+    }
+
+    #[tokio::test]
+    async fn test_truncated_header() {
+        let cid1 = make_test_cid(1);
+        let car_bytes = make_test_car(vec![cid1], vec![(cid1, vec![1, 2, 3])]).await;
+
+        // Cut off partway through the header
+        let truncated = &car_bytes[..10];
+        assert!(matches!(
+            validate_car_framing(truncated),
+            Err(CarError::Truncated { offset: 0, .. })
+        ));
+        assert!(parse_car_bytes(truncated).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_truncated_block() {
+        let cid1 = make_test_cid(1);
+        let car_bytes = make_test_car(vec![cid1], vec![(cid1, vec![1, 2, 3])]).await;
+
+        let truncated = &car_bytes[..car_bytes.len() - 1];
+        assert!(matches!(
+            validate_car_framing(truncated),
+            Err(CarError::Truncated { remaining, .. }) if remaining > 0
+        ));
+        assert!(parse_car_bytes(truncated).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_truncated_varint() {
+        let cid1 = make_test_cid(1);
+        let mut car_bytes = make_test_car(vec![cid1], vec![(cid1, vec![1, 2, 3])]).await;
+
+        // Dangling continuation byte where the next section's length should start
+        car_bytes.push(0x80);
+        assert!(matches!(
+            validate_car_framing(&car_bytes),
+            Err(CarError::Truncated { needed: 2, remaining: 1, .. })
+        ));
+        assert!(parse_car_bytes(&car_bytes).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_huge_length_prefix() {
+        // Header claims u64::MAX - 1 bytes (a maximal 9-byte varint)
+        let data = [0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f, 0xa1];
+        assert!(matches!(
+            validate_car_framing(&data),
+            Err(CarError::Truncated { remaining: 1, .. })
+        ));
+        assert!(parse_car_bytes(&data).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_overlong_varint() {
+        // Ten continuation bytes - longer than any valid length prefix
+        let data = [0xff; 10];
+        assert!(matches!(
+            validate_car_framing(&data),
+            Err(CarError::InvalidVarint { offset: 0 })
+        ));
+        assert!(parse_car_bytes(&data).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_zero_length_block() {
+        let cid1 = make_test_cid(1);
+        let mut car_bytes = make_test_car(vec![cid1], vec![(cid1, vec![1, 2, 3])]).await;
+
+        let offset = car_bytes.len();
+        car_bytes.push(0x00);
+        assert!(matches!(
+            validate_car_framing(&car_bytes),
+            Err(CarError::EmptySection { offset: o }) if o == offset
+        ));
+        assert!(parse_car_bytes(&car_bytes).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_read_car_rejects_truncated_file() {
+        let cid1 = make_test_cid(1);
+        let car_bytes = make_test_car(vec![cid1], vec![(cid1, vec![1, 2, 3])]).await;
+
+        let temp_file = NamedTempFile::new().unwrap();
+        tokio::io::AsyncWriteExt::write_all(
+            &mut tokio::fs::File::from_std(temp_file.reopen().unwrap()),
+            &car_bytes[..car_bytes.len() - 2],
+        )
+        .await
+        .unwrap();
+
+        let err = read_car(temp_file.path()).await.unwrap_err();
+        assert_eq!(*err.kind(), crate::error::RepoErrorKind::Car);
+    }
+
+    // CARv2 fixtures from tests/fixtures/gen_car_v2.py: a payload with two dag-cbor
+    // string blocks, "hello" (the root) and "world"
+
+    const CAR_V2_INDEXED: &[u8] = include_bytes!("../../tests/fixtures/car_v2_indexed.car");
+    const CAR_V2_NO_INDEX: &[u8] = include_bytes!("../../tests/fixtures/car_v2_no_index.car");
+
+    fn fixture_cid(text: &str) -> IpldCid {
+        let mut data = vec![0x60 | text.len() as u8];
+        data.extend_from_slice(text.as_bytes());
+        crate::mst::util::compute_cid(&data).unwrap()
+    }
+
+    async fn write_temp(data: &[u8]) -> NamedTempFile {
+        let temp_file = NamedTempFile::new().unwrap();
+        tokio::io::AsyncWriteExt::write_all(
+            &mut tokio::fs::File::from_std(temp_file.reopen().unwrap()),
+            data,
+        )
+        .await
+        .unwrap();
+        temp_file
+    }
+
+    #[tokio::test]
+    async fn test_parse_car_v2_with_index() {
+        let hello = fixture_cid("hello");
+        let world = fixture_cid("world");
+
+        let parsed = parse_car_v2_bytes(CAR_V2_INDEXED).await.unwrap();
+        assert_eq!(parsed.header.data_offset, 51);
+        assert!(parsed.header.has_index());
+        assert!(!parsed.header.is_fully_indexed());
+        assert_eq!(parsed.car.root, hello);
+        let mut cids = vec![hello, world];
+        cids.sort();
+        assert_eq!(parsed.car.blocks.keys().copied().collect::<Vec<_>>(), cids);
+
+        // Each offset points at the block's section in the payload
+        let index = parsed.index.unwrap();
+        assert_eq!(index.len(), 2);
+        let start = parsed.header.data_offset as usize;
+        let payload = &CAR_V2_INDEXED[start..start + parsed.header.data_size as usize];
+        for (cid, offset) in &index {
+            assert_eq!(cid_at(payload, *offset).unwrap(), *cid);
+        }
+        assert!(index[&hello] < index[&world]);
+    }
+
+    #[tokio::test]
+    async fn test_parse_car_v2_without_index() {
+        let parsed = parse_car_v2_bytes(CAR_V2_NO_INDEX).await.unwrap();
+        assert!(!parsed.header.has_index());
+        assert!(parsed.index.is_none());
+        assert_eq!(parsed.car.root, fixture_cid("hello"));
+        assert_eq!(parsed.car.blocks.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_parse_car_bytes_accepts_v2() {
+        let v2 = parse_car_bytes(CAR_V2_INDEXED).await.unwrap();
+        let v2_no_index = parse_car_bytes(CAR_V2_NO_INDEX).await.unwrap();
+        assert_eq!(v2.root, v2_no_index.root);
+        assert_eq!(v2.blocks, v2_no_index.blocks);
+
+        // The same payload as plain CARv1
+        let payload = &CAR_V2_NO_INDEX[51..];
+        let v1 = parse_car_bytes(payload).await.unwrap();
+        assert_eq!(v1.root, v2.root);
+        assert_eq!(v1.blocks, v2.blocks);
+
+        // CARv1 isn't CARv2
+        assert!(parse_car_v2_bytes(payload).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_read_v2_from_file() {
+        let hello = fixture_cid("hello");
+        let temp_file = write_temp(CAR_V2_INDEXED).await;
+
+        let parsed = read_car_v2(temp_file.path()).await.unwrap();
+        assert_eq!(parsed.car.root, hello);
+        assert_eq!(parsed.index.unwrap().len(), 2);
+
+        assert_eq!(read_car(temp_file.path()).await.unwrap().len(), 2);
+        assert_eq!(
+            read_car_header(temp_file.path()).await.unwrap(),
+            vec![hello]
+        );
+
+        // Streaming stops at the end of the payload rather than reading into the index
+        let mut stream = stream_car(temp_file.path()).await.unwrap();
+        assert_eq!(stream.roots(), &[hello]);
+        let (cid, _) = stream.next().await.unwrap().unwrap();
+        assert_eq!(cid, hello);
+        let (cid, _) = stream.next().await.unwrap().unwrap();
+        assert_eq!(cid, fixture_cid("world"));
+        assert!(stream.next().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_v2_header() {
+        // Cut off inside the fixed header
+        let truncated = &CAR_V2_INDEXED[..30];
+        assert!(matches!(
+            split_car_payload(truncated),
+            Err(CarError::Truncated { offset: 11, .. })
+        ));
+        assert!(parse_car_bytes(truncated).await.is_err());
+        let temp_file = write_temp(truncated).await;
+        assert!(read_car_header(temp_file.path()).await.is_err());
+
+        // Data payload runs past the end of the file
+        let short = &CAR_V2_NO_INDEX[..CAR_V2_NO_INDEX.len() - 1];
+        assert!(matches!(
+            split_car_payload(short),
+            Err(CarError::InvalidV2Header(_))
+        ));
+        let temp_file = write_temp(short).await;
+        assert!(stream_car(temp_file.path()).await.is_err());
+
+        // Index offset inside the data payload
+        let mut bad_index = CAR_V2_INDEXED.to_vec();
+        bad_index[43..51].copy_from_slice(&60u64.to_le_bytes());
+        let err = parse_car_v2_bytes(&bad_index).await.unwrap_err();
+        assert_eq!(*err.kind(), crate::error::RepoErrorKind::Car);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_v2_index() {
+        let index_offset = u64::from_le_bytes(CAR_V2_INDEXED[43..51].try_into().unwrap());
+        let index = &CAR_V2_INDEXED[index_offset as usize..];
+        let payload = &CAR_V2_INDEXED[51..index_offset as usize];
+        assert_eq!(parse_car_v2_index(index, payload).unwrap().len(), 2);
+
+        // Truncated bucket
+        assert!(matches!(
+            parse_car_v2_index(&index[..index.len() - 1], payload),
+            Err(CarError::InvalidIndex(_))
+        ));
+
+        // Corrupted digest no longer matches the block it points at
+        let mut corrupted = index.to_vec();
+        let last = corrupted.len() - 9;
+        corrupted[last] ^= 0xff;
+        assert!(matches!(
+            parse_car_v2_index(&corrupted, payload),
+            Err(CarError::InvalidIndex(_))
+        ));
+
+        // Unknown index codec
+        assert!(matches!(
+            parse_car_v2_index(&[0x80, 0x24], payload),
+            Err(CarError::UnsupportedIndex { codec: 0x1200 })
+        ));
+    }
+
+    fn raw_cid(code: u64, digest: &[u8]) -> IpldCid {
+        IpldCid::new_v1(0x55, multihash::Multihash::wrap(code, digest).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_validate_car_bytes() {
+        use sha2::{Digest, Sha512};
+
+        let good = b"\x63abc".to_vec();
+        let good_cid = crate::mst::util::compute_cid(&good).unwrap();
+        let blob = b"blob bytes".to_vec();
+        let blob_cid = raw_cid(0x13, &Sha512::digest(&blob));
+        let inline = raw_cid(0x00, b"inline");
+        // make_test_cid hashes a different byte than the block holds
+        let bad_cid = make_test_cid(1);
+        // blake3 isn't supported
+        let blake3 = raw_cid(0x1e, &[0; 32]);
+        let missing_root = make_test_cid(2);
+
+        let car_bytes = make_test_car(
+            vec![good_cid, missing_root],
+            vec![
+                (good_cid, good),
+                (blob_cid, blob),
+                (inline, b"inline".to_vec()),
+                (bad_cid, vec![9, 9, 9]),
+                (blake3, vec![1]),
+            ],
+        )
+        .await;
+
+        let report = validate_car_bytes(&car_bytes).unwrap();
+        assert_eq!(report.roots, vec![good_cid, missing_root]);
+        assert_eq!(report.blocks_checked, 4);
+        assert_eq!(report.mismatched, vec![bad_cid]);
+        assert_eq!(report.unsupported, vec![blake3]);
+        assert_eq!(report.missing_roots, vec![missing_root]);
+        assert!(!report.all_roots_present());
+        assert!(!report.is_valid());
+
+        // CARv2 fixture is intact
+        let report = validate_car_bytes(CAR_V2_INDEXED).unwrap();
+        assert_eq!(report.blocks_checked, 2);
+        assert!(report.is_valid());
+    }
+
+    #[tokio::test]
+    async fn test_validate_car_rejects_truncated_file() {
+        let data = b"\x63abc".to_vec();
+        let cid = crate::mst::util::compute_cid(&data).unwrap();
+        let car_bytes = make_test_car(vec![cid], vec![(cid, data)]).await;
+
+        let temp_file = write_temp(&car_bytes).await;
+        assert!(validate_car(temp_file.path()).await.unwrap().is_valid());
+
+        // Last frame's length prefix runs past the end of the file
+        let temp_file = write_temp(&car_bytes[..car_bytes.len() - 1]).await;
+        let err = validate_car(temp_file.path()).await.unwrap_err();
+        assert_eq!(*err.kind(), crate::error::RepoErrorKind::Car);
+    }
 }
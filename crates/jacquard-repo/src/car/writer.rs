@@ -1,22 +1,33 @@
 //! CAR file writing utilities
 //!
-//! Provides functions for writing blocks to CAR (Content Addressable aRchive) files.
+//! Provides functions for writing blocks to CAR (Content Addressable aRchive) files,
+//! either from scratch or by appending to an existing archive.
 
-use crate::error::{RepoError, Result};
+use crate::commit::Commit;
+use crate::error::{CarError, RepoError, Result};
+#[cfg(feature = "streaming")]
+use crate::mst::node::NodeData;
 use crate::mst::tree::Mst;
 use crate::storage::BlockStore;
 use bytes::Bytes;
 use cid::Cid as IpldCid;
 use iroh_car::CarWriter;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
+use std::io::SeekFrom;
+#[cfg(not(target_family = "wasm"))]
 use std::path::Path;
+use std::sync::Arc;
+#[cfg(not(target_family = "wasm"))]
 use tokio::fs::File;
-use tokio::io::AsyncWriteExt;
+use tokio::io::{
+    AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, AsyncWrite, AsyncWriteExt, BufReader,
+};
 
 /// Write blocks to CAR file
 ///
 /// Roots should contain commit CID(s).
 /// Blocks are written in sorted CID order (BTreeMap) for determinism.
+#[cfg(not(target_family = "wasm"))]
 pub async fn write_car(
     path: impl AsRef<Path>,
     roots: Vec<IpldCid>,
@@ -26,9 +37,19 @@ pub async fn write_car(
     let file = File::create(path).await.map_err(|e| {
         RepoError::io(e).with_context(format!("creating CAR file: {}", path.display()))
     })?;
+    write_car_to(file, roots, blocks).await
+}
 
+/// Write blocks as a CAR to an already-open writer
+///
+/// Same layout as [`write_car`]; used where the caller needs to manage the file itself.
+pub(crate) async fn write_car_to<W: AsyncWrite + Send + Unpin>(
+    out: W,
+    roots: Vec<IpldCid>,
+    blocks: BTreeMap<IpldCid, Bytes>,
+) -> Result<()> {
     let header = iroh_car::CarHeader::new_v1(roots);
-    let mut writer = CarWriter::new(header, file);
+    let mut writer = CarWriter::new(header, out);
 
     for (cid, data) in blocks {
         writer
@@ -74,6 +95,381 @@ pub async fn write_car_bytes(root: IpldCid, blocks: BTreeMap<IpldCid, Bytes>) ->
     Ok(buffer)
 }
 
+/// Longest CID prefix read while indexing an existing CAR (CIDs are well under this)
+const MAX_CID_LEN: usize = 128;
+
+/// Maximum length of an unsigned varint section prefix
+const MAX_VARINT_LEN: usize = 9;
+
+/// Append blocks to an existing CAR file for incremental backups
+///
+/// Scans the existing archive to index the CIDs it already holds and appends only the
+/// blocks from `new_blocks` which are not yet present. Bytes already in the file,
+/// including the header, are never rewritten, so an append costs one pass over the
+/// archive plus the new blocks, and an interrupted append can at worst leave a partial
+/// block at the end. The result is a plain CARv1 archive which
+/// [`read_car`](super::reader::read_car) loads as usual.
+///
+/// An empty `file` is started as a fresh archive with `root` as its header root.
+/// Otherwise the header keeps the roots it was first written with; use [`append_car`]
+/// to track the latest commit alongside the file. The root block must be present
+/// either in the existing file or in `new_blocks`.
+///
+/// Returns the number of blocks appended.
+pub async fn append_blocks<F>(
+    file: &mut F,
+    root: IpldCid,
+    new_blocks: BTreeMap<IpldCid, Bytes>,
+) -> Result<usize>
+where
+    F: AsyncRead + AsyncWrite + AsyncSeek + Unpin,
+{
+    let file_len = file
+        .seek(SeekFrom::End(0))
+        .await
+        .map_err(|e| RepoError::io(e).with_context("seeking to end of CAR file"))?;
+
+    let seen = if file_len == 0 {
+        HashSet::new()
+    } else {
+        file.seek(SeekFrom::Start(0))
+            .await
+            .map_err(|e| RepoError::io(e).with_context("seeking to start of CAR file"))?;
+        let (_, seen) = index_car(&mut BufReader::new(&mut *file), file_len).await?;
+        file.seek(SeekFrom::End(0))
+            .await
+            .map_err(|e| RepoError::io(e).with_context("seeking to end of CAR file"))?;
+        seen
+    };
+
+    if !seen.contains(&root) && !new_blocks.contains_key(&root) {
+        return Err(RepoError::not_found("root block", root)
+            .with_help("Include the commit block in new_blocks when appending a new root"));
+    }
+
+    if file_len == 0 {
+        let header = iroh_car::CarHeader::new_v1(vec![root])
+            .encode()
+            .map_err(|e| RepoError::car(e).with_context("encoding CAR header"))?;
+        file.write_all(&encode_section(&[], &header))
+            .await
+            .map_err(|e| RepoError::io(e).with_context("writing CAR header"))?;
+    }
+
+    let mut appended = 0;
+    for (cid, data) in new_blocks.iter().filter(|(cid, _)| !seen.contains(*cid)) {
+        file.write_all(&encode_section(&cid.to_bytes(), data))
+            .await
+            .map_err(|e| RepoError::io(e).with_context(format!("appending block {}", cid)))?;
+        appended += 1;
+    }
+    file.flush()
+        .await
+        .map_err(|e| RepoError::io(e).with_context("flushing CAR file"))?;
+
+    Ok(appended)
+}
+
+/// Sidecar manifest kept next to a CAR file by [`append_car`]
+#[cfg(not(target_family = "wasm"))]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct RootsManifest {
+    /// Length of the CAR file after the last completed append
+    len: u64,
+    /// Commit roots, latest first
+    roots: Vec<IpldCid>,
+}
+
+/// Path of the roots manifest for the CAR file at `path` (`<path>.roots`)
+#[cfg(not(target_family = "wasm"))]
+fn manifest_path(path: &Path) -> std::path::PathBuf {
+    let mut manifest = path.as_os_str().to_owned();
+    manifest.push(".roots");
+    manifest.into()
+}
+
+#[cfg(not(target_family = "wasm"))]
+async fn read_manifest(path: &Path) -> Result<Option<RootsManifest>> {
+    let manifest = manifest_path(path);
+    match tokio::fs::read(&manifest).await {
+        Ok(bytes) => serde_ipld_dagcbor::from_slice(&bytes)
+            .map(Some)
+            .map_err(|e| {
+                RepoError::serialization(e).with_context(format!(
+                    "decoding CAR roots manifest: {}",
+                    manifest.display()
+                ))
+            }),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(RepoError::io(e).with_context(format!(
+            "reading CAR roots manifest: {}",
+            manifest.display()
+        ))),
+    }
+}
+
+/// Append a commit's blocks to a CAR file on disk, recording it as the latest root
+///
+/// Appends with [`append_blocks`] (creating the file if needed), then records `root`
+/// in a sidecar manifest at `<path>.roots`, which [`read_car_roots`] reads back. The
+/// CAR header is left as first written, so the list of roots can grow without
+/// touching the archive.
+///
+/// The manifest also records the length of the archive after each append. It is only
+/// replaced, via a synced temporary file and a rename, once the appended blocks are
+/// synced to disk. If the file no longer has that length, its framing is checked before
+/// appending again: complete blocks past the recorded length are kept, a partial block
+/// left by an interrupted append is truncated, and an archive the manifest doesn't
+/// describe (shorter than recorded, or with no section ending at the recorded length) is
+/// rejected rather than modified.
+///
+/// Returns the number of blocks appended.
+#[cfg(not(target_family = "wasm"))]
+pub async fn append_car(
+    path: impl AsRef<Path>,
+    root: IpldCid,
+    new_blocks: BTreeMap<IpldCid, Bytes>,
+) -> Result<usize> {
+    let path = path.as_ref();
+    let mut file = tokio::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+        .await
+        .map_err(|e| {
+            RepoError::io(e).with_context(format!("opening CAR file: {}", path.display()))
+        })?;
+
+    let file_len = file.metadata().await.map_err(RepoError::io)?.len();
+    let existing_roots = match read_manifest(path).await? {
+        Some(manifest) => {
+            if file_len != manifest.len {
+                recover_interrupted_append(&mut file, file_len, manifest.len).await?;
+            }
+            manifest.roots
+        }
+        None if file_len > 0 => super::reader::read_car_header(path).await?,
+        None => Vec::new(),
+    };
+
+    let appended = append_blocks(&mut file, root, new_blocks).await?;
+    file.sync_all()
+        .await
+        .map_err(|e| RepoError::io(e).with_context("syncing CAR file"))?;
+    let len = file.metadata().await.map_err(RepoError::io)?.len();
+
+    let mut roots = vec![root];
+    roots.extend(existing_roots.into_iter().filter(|r| *r != root));
+    let encoded = serde_ipld_dagcbor::to_vec(&RootsManifest { len, roots })
+        .map_err(|e| RepoError::serialization(e).with_context("encoding CAR roots manifest"))?;
+
+    let manifest = manifest_path(path);
+    let mut tmp_path = manifest.clone().into_os_string();
+    tmp_path.push(".tmp");
+    let tmp_path = std::path::PathBuf::from(tmp_path);
+    let written = async {
+        let mut tmp = File::create(&tmp_path)
+            .await
+            .map_err(|e| RepoError::io(e).with_context("creating CAR roots manifest"))?;
+        tmp.write_all(&encoded)
+            .await
+            .map_err(|e| RepoError::io(e).with_context("writing CAR roots manifest"))?;
+        tmp.sync_all()
+            .await
+            .map_err(|e| RepoError::io(e).with_context("syncing CAR roots manifest"))?;
+        tokio::fs::rename(&tmp_path, &manifest).await.map_err(|e| {
+            RepoError::io(e).with_context(format!(
+                "replacing CAR roots manifest: {}",
+                manifest.display()
+            ))
+        })
+    }
+    .await;
+    if written.is_err() {
+        let _ = tokio::fs::remove_file(&tmp_path).await;
+    }
+    written?;
+
+    Ok(appended)
+}
+
+/// Reconcile a CAR file with a roots manifest that recorded a different length
+///
+/// Truncates only a partial section at the end of the file, and only when the recorded
+/// length falls on a section boundary before it.
+#[cfg(not(target_family = "wasm"))]
+async fn recover_interrupted_append(file: &mut File, file_len: u64, recorded: u64) -> Result<()> {
+    file.seek(SeekFrom::Start(0))
+        .await
+        .map_err(|e| RepoError::io(e).with_context("seeking to start of CAR file"))?;
+    let (complete, on_boundary) =
+        complete_sections_len(&mut BufReader::new(&mut *file), file_len, recorded).await?;
+    if !on_boundary || complete < recorded {
+        return Err(RepoError::new(crate::error::RepoErrorKind::Car, None)
+            .with_context(format!(
+                "CAR file ({file_len} bytes, {complete} in complete sections) does not match \
+                 its roots manifest ({recorded} bytes)"
+            ))
+            .with_help(
+                "The file was changed outside append_car; check it and remove the .roots \
+                 manifest to start tracking it afresh",
+            ));
+    }
+    if complete < file_len {
+        file.set_len(complete)
+            .await
+            .map_err(|e| RepoError::io(e).with_context("truncating interrupted CAR append"))?;
+    }
+    Ok(())
+}
+
+/// Read the roots of a CAR file, latest first
+///
+/// For archives maintained with [`append_car`] this returns the roots recorded in the
+/// sidecar manifest, so the first root is the most recently appended commit. Otherwise
+/// it falls back to the roots in the CAR header.
+#[cfg(not(target_family = "wasm"))]
+pub async fn read_car_roots(path: impl AsRef<Path>) -> Result<Vec<IpldCid>> {
+    let path = path.as_ref();
+    match read_manifest(path).await? {
+        Some(manifest) => Ok(manifest.roots),
+        None => super::reader::read_car_header(path).await,
+    }
+}
+
+/// Read the header and index the block CIDs of an existing CAR
+///
+/// Returns the header roots and every CID in the file. Block data is read through but
+/// not kept.
+async fn index_car<R>(reader: &mut R, file_len: u64) -> Result<(Vec<IpldCid>, HashSet<IpldCid>)>
+where
+    R: AsyncRead + Unpin,
+{
+    let (header_len, prefix_len) = read_section_len(reader, 0, file_len)
+        .await?
+        .ok_or(CarError::EmptySection { offset: 0 })?;
+    let mut header = vec![0u8; header_len as usize];
+    reader
+        .read_exact(&mut header)
+        .await
+        .map_err(|e| RepoError::io(e).with_context("reading CAR header"))?;
+    let roots = iroh_car::CarHeader::decode(&header)
+        .map_err(RepoError::car_parse)?
+        .roots()
+        .to_vec();
+
+    let mut offset = prefix_len as u64 + header_len;
+    let mut seen = HashSet::new();
+    let mut cid_buf = [0u8; MAX_CID_LEN];
+    while let Some((len, prefix_len)) = read_section_len(reader, offset, file_len).await? {
+        let prefix = (len as usize).min(MAX_CID_LEN);
+        reader
+            .read_exact(&mut cid_buf[..prefix])
+            .await
+            .map_err(|e| RepoError::io(e).with_context("reading block CID"))?;
+        let cid = IpldCid::read_bytes(&cid_buf[..prefix]).map_err(RepoError::car_parse)?;
+        seen.insert(cid);
+
+        // Read through rather than seek, so small blocks are served from the buffer
+        let rest = len - prefix as u64;
+        tokio::io::copy(&mut (&mut *reader).take(rest), &mut tokio::io::sink())
+            .await
+            .map_err(|e| RepoError::io(e).with_context("skipping block data"))?;
+        offset += prefix_len as u64 + len;
+    }
+
+    Ok((roots, seen))
+}
+
+/// Walk the section framing of a CAR, stopping at a partial section at the end
+///
+/// Returns the length covered by complete sections (header included) and whether one of
+/// them ends exactly at `mark`. Malformed framing is an error; only a section cut short
+/// by the end of the file is tolerated.
+#[cfg(not(target_family = "wasm"))]
+async fn complete_sections_len<R>(reader: &mut R, file_len: u64, mark: u64) -> Result<(u64, bool)>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut offset = 0;
+    let mut on_boundary = mark == 0;
+    loop {
+        let (len, prefix_len) = match read_section_len(reader, offset, file_len).await {
+            Ok(Some(section)) => section,
+            Ok(None) | Err(CarError::Truncated { .. }) => break,
+            Err(e) => return Err(e.into()),
+        };
+        tokio::io::copy(&mut (&mut *reader).take(len), &mut tokio::io::sink())
+            .await
+            .map_err(|e| RepoError::io(e).with_context("skipping CAR section"))?;
+        offset += prefix_len as u64 + len;
+        on_boundary |= offset == mark;
+    }
+    Ok((offset, on_boundary))
+}
+
+/// Read the varint length prefix of the section at `offset`
+///
+/// Returns `None` at a clean end of file, otherwise the section length and the
+/// length of the prefix. Rejects sections which are empty or run past `file_len`.
+async fn read_section_len<R>(
+    reader: &mut R,
+    offset: u64,
+    file_len: u64,
+) -> std::result::Result<Option<(u64, usize)>, CarError>
+where
+    R: AsyncRead + Unpin,
+{
+    if offset >= file_len {
+        return Ok(None);
+    }
+    let offset_usize = offset as usize;
+    let mut value = 0u64;
+    for i in 0..MAX_VARINT_LEN {
+        let byte = reader.read_u8().await.map_err(|_| CarError::Truncated {
+            offset: offset_usize,
+            needed: i + 1,
+            remaining: (file_len - offset) as usize,
+        })?;
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            let remaining = file_len - offset - (i as u64 + 1);
+            if value == 0 {
+                return Err(CarError::EmptySection {
+                    offset: offset_usize,
+                });
+            }
+            if value > remaining {
+                return Err(CarError::Truncated {
+                    offset: offset_usize,
+                    needed: usize::try_from(value).unwrap_or(usize::MAX),
+                    remaining: remaining as usize,
+                });
+            }
+            return Ok(Some((value, i + 1)));
+        }
+    }
+    Err(CarError::InvalidVarint {
+        offset: offset_usize,
+    })
+}
+
+/// Encode a length-prefixed CAR section from a CID prefix and payload
+fn encode_section(cid_bytes: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut len = (cid_bytes.len() + data.len()) as u64;
+    let mut out = Vec::with_capacity(MAX_VARINT_LEN + len as usize);
+    while len >= 0x80 {
+        out.push((len as u8 & 0x7f) | 0x80);
+        len >>= 7;
+    }
+    out.push(len as u8);
+    out.extend_from_slice(cid_bytes);
+    out.extend_from_slice(data);
+    out
+}
+
 /// Write MST + commit to CAR file
 ///
 /// Streams blocks directly to CAR file:
@@ -84,6 +480,7 @@ pub async fn write_car_bytes(root: IpldCid, blocks: BTreeMap<IpldCid, Bytes>) ->
 /// Uses streaming to avoid loading all blocks into memory.
 ///
 /// Should write in the correct order for [streaming car processing](https://github.com/bluesky-social/proposals/blob/main/0006-sync-iteration/README.md#streaming-car-processing) from sync v1.1
+#[cfg(not(target_family = "wasm"))]
 pub async fn export_repo_car<S: BlockStore + Sync + 'static>(
     path: impl AsRef<Path>,
     commit_cid: IpldCid,
@@ -124,6 +521,221 @@ pub async fn export_repo_car<S: BlockStore + Sync + 'static>(
     Ok(())
 }
 
+/// Export the blocks a commit adds on top of an earlier one as CAR bytes
+///
+/// Diffs the MSTs of the commits at `from_root` and `to_root` with [`Mst::diff`] and writes
+/// a CAR with `to_root` as its only root, holding the `to_root` commit block, the MST nodes
+/// the new tree adds and the newly referenced record blocks. Applying it to a store which
+/// already holds `from_root` gives a complete copy of `to_root`, as with a firehose
+/// `#commit` event.
+///
+/// Nodes and records the old tree already holds are left out: the diff only visits
+/// subtrees whose CIDs differ, and new record CIDs are checked against every leaf of the
+/// old tree, so a record the old tree references under a different key isn't sent again.
+/// Missing record blocks are skipped, as in [`export_repo_car`].
+pub async fn export_diff_car<S: BlockStore + Sync + 'static>(
+    from_root: IpldCid,
+    to_root: IpldCid,
+    store: Arc<S>,
+) -> Result<Vec<u8>> {
+    let load_commit = |cid: IpldCid| {
+        let store = store.clone();
+        async move {
+            let bytes = store.get(&cid).await?.ok_or_else(|| {
+                RepoError::not_found("commit", cid)
+                    .with_help("Both commits must be persisted to storage before exporting a diff")
+            })?;
+            let data = *Commit::from_cbor_versioned(&bytes)?.data();
+            Ok::<_, RepoError>((bytes, data))
+        }
+    };
+    let (_, from_data) = load_commit(from_root).await?;
+    let (to_bytes, to_data) = load_commit(to_root).await?;
+
+    let old = Mst::load(store.clone(), from_data, None);
+    let new = Mst::load(store.clone(), to_data, None);
+    let diff = old.diff(&new).await?;
+
+    let mut blocks = diff.new_mst_blocks;
+    blocks.insert(to_root, to_bytes);
+    let removed: HashSet<IpldCid> = diff.removed_cids.into_iter().collect();
+    let mut new_leaves: Vec<IpldCid> = diff
+        .new_leaf_cids
+        .into_iter()
+        .filter(|cid| !removed.contains(cid))
+        .collect();
+    // A new key can point at a record the old tree still holds under another key
+    if !new_leaves.is_empty() {
+        let old_leaves: HashSet<IpldCid> = old
+            .leaves()
+            .await?
+            .into_iter()
+            .map(|(_, cid)| cid)
+            .collect();
+        new_leaves.retain(|cid| !old_leaves.contains(cid));
+    }
+    let leaf_blocks = store.get_many(&new_leaves).await?;
+    for (cid, data) in new_leaves.into_iter().zip(leaf_blocks) {
+        if let Some(data) = data {
+            blocks.insert(cid, data);
+        }
+    }
+
+    write_car_bytes(to_root, blocks).await
+}
+
+/// Stream a repository export as CAR bytes
+///
+/// Yields the CAR header first, then one length-prefixed section per block as it is read
+/// from `store`, so the archive can be sent or written out without holding the repo in
+/// memory. Concatenating the items gives a CARv1 file with `roots` in its header.
+///
+/// Blocks come out in a fixed order: the root blocks, in the order given, then for each
+/// root a depth-first walk of its MST. A root which decodes as a commit contributes its
+/// `data` tree; any other root is walked as an MST node itself. Within the walk, each node
+/// is followed by its left subtree, then each entry's record and right subtree in key
+/// order, so two exports of the same roots produce identical bytes.
+///
+/// Blocks shared between trees are only emitted once; the CIDs already sent are the only
+/// state that grows with the size of the repo. Missing root or MST node blocks end the
+/// stream with an error, while missing record blocks are skipped, as in
+/// [`export_repo_car`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use jacquard_repo::car::writer::stream_export_repo_car;
+/// use n0_future::StreamExt;
+///
+/// let mut frames = stream_export_repo_car(vec![commit_cid], storage.clone());
+/// while let Some(frame) = frames.next().await {
+///     body.write_all(&frame?).await?;
+/// }
+/// ```
+#[cfg(feature = "streaming")]
+pub fn stream_export_repo_car<S: BlockStore + Sync + 'static>(
+    roots: Vec<IpldCid>,
+    store: Arc<S>,
+) -> impl n0_future::Stream<Item = Result<Bytes>> + Send + 'static {
+    let state = ExportState {
+        store,
+        header: Some(roots.clone()),
+        roots: roots.into(),
+        pending: Vec::new(),
+        sent: HashSet::new(),
+    };
+    n0_future::stream::unfold(Some(state), |state| async move {
+        let mut state = state?;
+        match state.next_frame().await {
+            Ok(Some(frame)) => Some((Ok(frame), Some(state))),
+            Ok(None) => None,
+            // Nothing sensible can follow a failed block, so end the stream
+            Err(e) => Some((Err(e), None)),
+        }
+    })
+}
+
+/// Block still to be emitted by [`stream_export_repo_car`]
+#[cfg(feature = "streaming")]
+enum ExportBlock {
+    /// MST node, followed by its subtrees and records
+    Node(IpldCid),
+    /// Record block, skipped if missing from storage
+    Record(IpldCid),
+    /// Subtrees and records of an MST node which was already emitted as a root
+    Children(IpldCid),
+}
+
+#[cfg(feature = "streaming")]
+struct ExportState<S> {
+    store: Arc<S>,
+    header: Option<Vec<IpldCid>>,
+    roots: std::collections::VecDeque<IpldCid>,
+    /// Depth-first work stack; the next block is at the end
+    pending: Vec<ExportBlock>,
+    sent: HashSet<IpldCid>,
+}
+
+#[cfg(feature = "streaming")]
+impl<S: BlockStore + Sync + 'static> ExportState<S> {
+    /// Produce the next CAR section, or `None` once every block has been sent
+    async fn next_frame(&mut self) -> Result<Option<Bytes>> {
+        if let Some(roots) = self.header.take() {
+            let header = iroh_car::CarHeader::new_v1(roots)
+                .encode()
+                .map_err(|e| RepoError::car(e).with_context("encoding CAR header"))?;
+            return Ok(Some(encode_section(&[], &header).into()));
+        }
+
+        while let Some(root) = self.roots.pop_front() {
+            if !self.sent.insert(root) {
+                continue;
+            }
+            let data = self.store.get(&root).await?.ok_or_else(|| {
+                RepoError::not_found("root block", root)
+                    .with_help("Every root must be persisted to storage before exporting")
+            })?;
+            // Walk the trees in root order, once all the roots are out
+            let tree = match Commit::from_cbor_versioned(&data) {
+                Ok(commit) => ExportBlock::Node(*commit.data()),
+                // Not a commit, so it should be an MST node
+                Err(_) => ExportBlock::Children(root),
+            };
+            self.pending.insert(0, tree);
+            return Ok(Some(encode_section(&root.to_bytes(), &data).into()));
+        }
+
+        while let Some(next) = self.pending.pop() {
+            match next {
+                ExportBlock::Node(cid) if !self.sent.contains(&cid) => {
+                    let data = self.node(cid).await?;
+                    self.sent.insert(cid);
+                    self.expand_node(cid, &data)?;
+                    return Ok(Some(encode_section(&cid.to_bytes(), &data).into()));
+                }
+                ExportBlock::Record(cid) if !self.sent.contains(&cid) => {
+                    if let Some(data) = self.store.get(&cid).await? {
+                        self.sent.insert(cid);
+                        return Ok(Some(encode_section(&cid.to_bytes(), &data).into()));
+                    }
+                }
+                ExportBlock::Children(cid) => {
+                    let data = self.node(cid).await?;
+                    self.expand_node(cid, &data)?;
+                }
+                ExportBlock::Node(_) | ExportBlock::Record(_) => {}
+            }
+        }
+
+        Ok(None)
+    }
+
+    async fn node(&self, cid: IpldCid) -> Result<Bytes> {
+        self.store.get(&cid).await?.ok_or_else(|| {
+            RepoError::not_found("MST node", cid)
+                .with_help("MST node missing from storage - ensure all blocks were properly persisted or that the tree CID is correct")
+        })
+    }
+
+    /// Queue an MST node's subtrees and records, in the order they are emitted
+    fn expand_node(&mut self, cid: IpldCid, data: &[u8]) -> Result<()> {
+        let node: NodeData = serde_ipld_dagcbor::from_slice(data).map_err(|e| {
+            RepoError::serialization(e)
+                .with_context(format!("deserializing MST node from storage: {}", cid))
+        })?;
+        for entry in node.entries.iter().rev() {
+            if let Some(tree) = entry.tree {
+                self.pending.push(ExportBlock::Node(tree));
+            }
+            self.pending.push(ExportBlock::Record(entry.value));
+        }
+        if let Some(left) = node.left {
+            self.pending.push(ExportBlock::Node(left));
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -222,4 +834,369 @@ mod tests {
         assert!(blocks.contains_key(&cid1));
         assert!(blocks.contains_key(&cid2));
     }
+
+    fn blocks_for(values: &[u8]) -> BTreeMap<IpldCid, Bytes> {
+        values
+            .iter()
+            .map(|v| (make_test_cid(*v), Bytes::from(vec![*v; 3])))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_append_blocks_skips_existing_and_keeps_header() {
+        use crate::car::reader::{parse_car_bytes, validate_car_framing};
+
+        let old_root = make_test_cid(1);
+        let initial = write_car_bytes(old_root, blocks_for(&[1, 2, 3]))
+            .await
+            .unwrap();
+        let mut cursor = std::io::Cursor::new(initial.clone());
+
+        let new_root = make_test_cid(4);
+        let appended = append_blocks(&mut cursor, new_root, blocks_for(&[2, 3, 4, 5]))
+            .await
+            .unwrap();
+        assert_eq!(appended, 2);
+
+        // Existing bytes are untouched; only new sections are added
+        let bytes = cursor.into_inner();
+        assert_eq!(&bytes[..initial.len()], &initial[..]);
+        assert_eq!(validate_car_framing(&bytes).unwrap(), 5);
+
+        let parsed = parse_car_bytes(&bytes).await.unwrap();
+        assert_eq!(parsed.root, old_root);
+        assert_eq!(parsed.blocks, blocks_for(&[1, 2, 3, 4, 5]));
+    }
+
+    #[tokio::test]
+    async fn test_append_blocks_to_empty_creates_car() {
+        use crate::car::reader::parse_car_bytes;
+
+        let root = make_test_cid(1);
+        let mut cursor = std::io::Cursor::new(Vec::new());
+        let appended = append_blocks(&mut cursor, root, blocks_for(&[1, 2]))
+            .await
+            .unwrap();
+        assert_eq!(appended, 2);
+
+        let parsed = parse_car_bytes(&cursor.into_inner()).await.unwrap();
+        assert_eq!(parsed.root, root);
+        assert_eq!(parsed.blocks, blocks_for(&[1, 2]));
+    }
+
+    #[tokio::test]
+    async fn test_append_car_tracks_roots_in_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("backup.car");
+        let root1 = make_test_cid(1);
+        write_car(&path, vec![root1], blocks_for(&[1]))
+            .await
+            .unwrap();
+        assert_eq!(read_car_roots(&path).await.unwrap(), vec![root1]);
+
+        let root2 = make_test_cid(2);
+        assert_eq!(
+            append_car(&path, root2, blocks_for(&[1, 2])).await.unwrap(),
+            1
+        );
+        // Re-appending the same commit is a no-op for blocks and roots
+        assert_eq!(
+            append_car(&path, root2, blocks_for(&[1, 2])).await.unwrap(),
+            0
+        );
+        let root3 = make_test_cid(3);
+        append_car(&path, root3, blocks_for(&[3])).await.unwrap();
+
+        assert_eq!(
+            read_car_roots(&path).await.unwrap(),
+            vec![root3, root2, root1]
+        );
+        // The header still names the original commit
+        assert_eq!(
+            crate::car::reader::read_car_header(&path).await.unwrap(),
+            vec![root1]
+        );
+        assert_eq!(read_car(&path).await.unwrap(), blocks_for(&[1, 2, 3]));
+    }
+
+    #[tokio::test]
+    async fn test_append_car_recovers_interrupted_append() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("backup.car");
+        append_car(&path, make_test_cid(1), blocks_for(&[1]))
+            .await
+            .unwrap();
+        let committed = tokio::fs::read(&path).await.unwrap();
+
+        // Simulate a crash part-way through writing a block section
+        let mut torn = committed.clone();
+        torn.extend_from_slice(&[0x40, 0x01, 0x71]);
+        tokio::fs::write(&path, &torn).await.unwrap();
+        assert!(read_car(&path).await.is_err());
+
+        append_car(&path, make_test_cid(2), blocks_for(&[2]))
+            .await
+            .unwrap();
+        let bytes = tokio::fs::read(&path).await.unwrap();
+        assert_eq!(&bytes[..committed.len()], &committed[..]);
+        assert_eq!(read_car(&path).await.unwrap(), blocks_for(&[1, 2]));
+        assert_eq!(
+            read_car_roots(&path).await.unwrap(),
+            vec![make_test_cid(2), make_test_cid(1)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_append_car_keeps_complete_blocks_past_stale_manifest() {
+        use crate::car::reader::validate_car_framing;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("backup.car");
+        append_car(&path, make_test_cid(1), blocks_for(&[1]))
+            .await
+            .unwrap();
+
+        // Another tool appends a block without updating the manifest
+        let mut file = tokio::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&path)
+            .await
+            .unwrap();
+        append_blocks(&mut file, make_test_cid(1), blocks_for(&[2]))
+            .await
+            .unwrap();
+        drop(file);
+
+        append_car(&path, make_test_cid(3), blocks_for(&[3]))
+            .await
+            .unwrap();
+        let bytes = tokio::fs::read(&path).await.unwrap();
+        assert_eq!(validate_car_framing(&bytes).unwrap(), 3);
+        assert_eq!(read_car(&path).await.unwrap(), blocks_for(&[1, 2, 3]));
+    }
+
+    #[tokio::test]
+    async fn test_append_car_rejects_file_not_matching_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("backup.car");
+        append_car(&path, make_test_cid(1), blocks_for(&[1, 2]))
+            .await
+            .unwrap();
+
+        // Replaced by a smaller archive: the recorded length is past its end
+        let replaced = write_car_bytes(make_test_cid(1), blocks_for(&[1]))
+            .await
+            .unwrap();
+        tokio::fs::write(&path, &replaced).await.unwrap();
+        let err = append_car(&path, make_test_cid(3), blocks_for(&[3]))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), &crate::error::RepoErrorKind::Car);
+        assert_eq!(tokio::fs::read(&path).await.unwrap(), replaced);
+
+        // Replaced by a larger archive whose sections don't line up with the recorded length
+        let big = BTreeMap::from([(make_test_cid(4), Bytes::from(vec![0u8; 1000]))]);
+        let replaced = write_car_bytes(make_test_cid(4), big).await.unwrap();
+        tokio::fs::write(&path, &replaced).await.unwrap();
+        let err = append_car(&path, make_test_cid(3), blocks_for(&[3]))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), &crate::error::RepoErrorKind::Car);
+        assert_eq!(tokio::fs::read(&path).await.unwrap(), replaced);
+    }
+
+    #[tokio::test]
+    async fn test_append_blocks_requires_root_block() {
+        let initial = write_car_bytes(make_test_cid(1), blocks_for(&[1]))
+            .await
+            .unwrap();
+        let mut cursor = std::io::Cursor::new(initial.clone());
+        let err = append_blocks(&mut cursor, make_test_cid(9), blocks_for(&[2]))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), &crate::error::RepoErrorKind::NotFound);
+        assert_eq!(cursor.into_inner(), initial);
+    }
+
+    #[tokio::test]
+    async fn test_append_blocks_rejects_truncated_car() {
+        let mut initial = write_car_bytes(make_test_cid(1), blocks_for(&[1, 2]))
+            .await
+            .unwrap();
+        initial.truncate(initial.len() - 1);
+        let mut cursor = std::io::Cursor::new(initial);
+        let err = append_blocks(&mut cursor, make_test_cid(1), BTreeMap::new())
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), &crate::error::RepoErrorKind::Car);
+    }
+
+    #[cfg(feature = "streaming")]
+    #[tokio::test]
+    async fn test_stream_export_round_trips() {
+        use crate::commit::Commit;
+        use jacquard_common::types::string::Did;
+        use jacquard_common::types::tid::Ticker;
+        use n0_future::StreamExt;
+
+        let storage = Arc::new(MemoryBlockStore::new());
+        let mut mst = Mst::new(storage.clone());
+        let mut expected = BTreeMap::new();
+        for n in 0..100u32 {
+            let record = serde_ipld_dagcbor::to_vec(&format!("record {}", n)).unwrap();
+            let cid = storage.put(&record).await.unwrap();
+            expected.insert(cid, Bytes::from(record));
+            mst = mst
+                .add(&format!("com.example.test/{:04}", n), cid)
+                .await
+                .unwrap();
+        }
+        let root = mst.persist().await.unwrap();
+        for cid in mst.collect_node_cids().await.unwrap() {
+            expected.insert(cid, storage.get(&cid).await.unwrap().unwrap());
+        }
+        let commit = Commit::new_unsigned(
+            Did::new("did:plc:test").unwrap(),
+            root,
+            Ticker::new().next(None),
+            None,
+        )
+        .to_cbor()
+        .unwrap();
+        let commit_cid = storage.put(&commit).await.unwrap();
+        expected.insert(commit_cid, Bytes::from(commit));
+
+        let export = |roots: Vec<IpldCid>| {
+            let frames = stream_export_repo_car(roots, storage.clone());
+            async move {
+                let frames: Vec<Result<Bytes>> = frames.collect().await;
+                let frames: Vec<Bytes> = frames.into_iter().collect::<Result<_>>().unwrap();
+                frames.concat()
+            }
+        };
+        let bytes = export(vec![commit_cid]).await;
+        assert_eq!(bytes, export(vec![commit_cid]).await);
+
+        let temp_file = NamedTempFile::new().unwrap();
+        tokio::fs::write(temp_file.path(), &bytes).await.unwrap();
+        assert_eq!(read_car(temp_file.path()).await.unwrap(), expected);
+
+        // Commit first, then the MST root node
+        let mut reader = iroh_car::CarReader::new(&bytes[..]).await.unwrap();
+        assert_eq!(reader.header().roots(), &[commit_cid]);
+        assert_eq!(reader.next_block().await.unwrap().unwrap().0, commit_cid);
+        assert_eq!(reader.next_block().await.unwrap().unwrap().0, root);
+
+        // A bare MST root exports the same tree, without the commit
+        let parsed = crate::car::reader::parse_car_bytes(&export(vec![root]).await)
+            .await
+            .unwrap();
+        assert_eq!(parsed.root, root);
+        expected.remove(&commit_cid);
+        assert_eq!(parsed.blocks, expected);
+
+        // A missing root ends the stream with an error
+        let results: Vec<Result<Bytes>> =
+            stream_export_repo_car(vec![make_test_cid(1)], storage.clone())
+                .collect()
+                .await;
+        assert_eq!(results.len(), 2);
+        assert!(results[1].is_err());
+    }
+
+    #[tokio::test]
+    async fn test_export_diff_car_applies_on_base() {
+        use crate::car::reader::parse_car_bytes;
+        use crate::commit::Commit;
+        use crate::storage::BlockStore;
+        use jacquard_common::types::string::Did;
+        use jacquard_common::types::tid::Ticker;
+
+        let source = Arc::new(MemoryBlockStore::new());
+        let did = Did::new("did:plc:test").unwrap();
+        let mut ticker = Ticker::new();
+        let record = |text: String| serde_ipld_dagcbor::to_vec(&text).unwrap();
+
+        let mut mst1 = Mst::new(source.clone());
+        for n in 0..50 {
+            let cid = source.put(&record(format!("record {}", n))).await.unwrap();
+            mst1 = mst1
+                .add(&format!("com.example.test/{:04}", n), cid)
+                .await
+                .unwrap();
+        }
+        let commit1 = Commit::new_unsigned(
+            did.clone(),
+            mst1.persist().await.unwrap(),
+            ticker.next(None),
+            None,
+        );
+        let commit1_cid = source.put(&commit1.to_cbor().unwrap()).await.unwrap();
+
+        // Update, create and delete, plus a new key for a record the base already holds
+        let updated = source.put(&record("updated".into())).await.unwrap();
+        let created = source.put(&record("created".into())).await.unwrap();
+        let existing = mst1.get("com.example.test/0003").await.unwrap().unwrap();
+        let mst2 = mst1
+            .update("com.example.test/0007", updated)
+            .await
+            .unwrap()
+            .add("com.example.test/0100", created)
+            .await
+            .unwrap()
+            .add("com.example.test/0101", existing)
+            .await
+            .unwrap()
+            .delete("com.example.test/0020")
+            .await
+            .unwrap();
+        let commit2 = Commit::new_unsigned(
+            did,
+            mst2.persist().await.unwrap(),
+            ticker.next(None),
+            Some(commit1_cid),
+        );
+        let commit2_cid = source.put(&commit2.to_cbor().unwrap()).await.unwrap();
+
+        // Import the base repo from a full export
+        let export = |commit_cid: IpldCid, mst: Mst<MemoryBlockStore>| async move {
+            let temp_file = NamedTempFile::new().unwrap();
+            export_repo_car(temp_file.path(), commit_cid, &mst)
+                .await
+                .unwrap();
+            read_car(temp_file.path()).await.unwrap()
+        };
+        let base_blocks = export(commit1_cid, mst1.clone()).await;
+        let target = Arc::new(MemoryBlockStore::new());
+        target.put_many(base_blocks.clone()).await.unwrap();
+
+        let diff_car = export_diff_car(commit1_cid, commit2_cid, source.clone())
+            .await
+            .unwrap();
+        let parsed = parse_car_bytes(&diff_car).await.unwrap();
+        assert_eq!(parsed.root, commit2_cid);
+        assert!(parsed.blocks.contains_key(&commit2_cid));
+        assert!(parsed.blocks.contains_key(&updated));
+        assert!(parsed.blocks.contains_key(&created));
+        assert!(!parsed.blocks.contains_key(&existing));
+        assert!(
+            parsed
+                .blocks
+                .keys()
+                .all(|cid| !base_blocks.contains_key(cid))
+        );
+        target.put_many(parsed.blocks).await.unwrap();
+
+        // The patched store exports the same repo as the source
+        let patched = Mst::load(target.clone(), *commit2.data(), None);
+        assert_eq!(
+            patched.leaves().await.unwrap(),
+            mst2.leaves().await.unwrap()
+        );
+        assert_eq!(
+            export(commit2_cid, patched).await,
+            export(commit2_cid, mst2).await
+        );
+    }
 }
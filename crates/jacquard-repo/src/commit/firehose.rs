@@ -214,10 +214,15 @@ use crate::car::parse_car_bytes;
 /// Validation functions for firehose commit messages
 ///
 /// These functions validate commits from the `com.atproto.sync.subscribeRepos` firehose.
-use crate::error::{RepoError, Result};
-use crate::mst::{Mst, VerifiedWriteOp};
+use crate::error::{BoxError, FirehoseError, RepoError, Result};
+use crate::mst::{Mst, RecordWriteOp, VerifiedWriteOp};
 use crate::storage::{BlockStore, LayeredBlockStore, MemoryBlockStore};
 use cid::Cid as IpldCid;
+use jacquard_common::types::recordkey::{RecordKey, Rkey};
+use jacquard_common::types::string::Nsid;
+use jacquard_common::types::value::RawData;
+use smol_str::SmolStr;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 impl<'a> FirehoseCommit<'a> {
@@ -412,6 +417,177 @@ impl<'a> FirehoseCommit<'a> {
     }
 }
 
+/// Header of an event stream frame
+#[derive(serde::Deserialize)]
+struct FrameHeader {
+    op: i64,
+    t: Option<smol_str::SmolStr>,
+}
+
+/// Body of an error frame (`op == -1`)
+#[derive(serde::Deserialize)]
+struct ErrorBody {
+    error: String,
+    message: Option<String>,
+}
+
+/// A `#commit` firehose event with its ops resolved against the embedded blocks
+///
+/// Produced by [`parse_commit_event`]. The raw message stays available as `message`; `ops`
+/// carries the same operations as typed [`RecordWriteOp`]s with their records decoded from
+/// `blocks`.
+#[derive(Debug, Clone)]
+pub struct ParsedCommitEvent<'a> {
+    /// The decoded event body
+    pub message: FirehoseCommit<'a>,
+    /// Operations, in event order
+    pub ops: Vec<RecordWriteOp<'static>>,
+    /// Blocks from the embedded CAR
+    pub blocks: BTreeMap<IpldCid, Bytes>,
+    /// Previous MST root (sync v1.1 only)
+    pub prev_data: Option<IpldCid>,
+}
+
+impl<'a> ParsedCommitEvent<'a> {
+    /// Stream sequence number
+    pub fn seq(&self) -> i64 {
+        self.message.seq
+    }
+
+    /// The repo this event comes from
+    pub fn repo(&self) -> &Did<'a> {
+        &self.message.repo
+    }
+
+    /// Rev of the emitted commit
+    pub fn rev(&self) -> &Tid {
+        &self.message.rev
+    }
+}
+
+/// Parse a `com.atproto.sync.subscribeRepos` frame holding a `#commit` event
+///
+/// A frame is a DAG-CBOR header (`{op, t}`) followed by a DAG-CBOR body. Error frames
+/// (`op == -1`) come back as [`FirehoseError::ErrorFrame`], and other event types as
+/// [`FirehoseError::UnexpectedEvent`].
+///
+/// Every create and update must have its record block in the embedded CAR, otherwise this
+/// fails with [`FirehoseError::MissingBlock`]. This only checks that the event is
+/// self-consistent; use [`FirehoseCommit::validate_v1_1`] to check the commit signature and
+/// MST.
+pub async fn parse_commit_event(bytes: &[u8]) -> Result<ParsedCommitEvent<'_>> {
+    let invalid_frame = |e: BoxError| FirehoseError::InvalidFrame { source: e };
+
+    let mut cursor = std::io::Cursor::new(bytes);
+    let header: FrameHeader = serde_ipld_dagcbor::de::from_reader_once(&mut cursor)
+        .map_err(|e| invalid_frame(Box::new(e)))?;
+    let body = &bytes[cursor.position() as usize..];
+
+    if header.op == -1 {
+        let body: ErrorBody =
+            serde_ipld_dagcbor::from_slice(body).map_err(|e| invalid_frame(Box::new(e)))?;
+        return Err(FirehoseError::ErrorFrame {
+            error: body.error,
+            message: body.message,
+        }
+        .into());
+    }
+    match header.t.as_deref() {
+        Some("#commit") if header.op == 1 => {}
+        t => {
+            return Err(FirehoseError::UnexpectedEvent(format!(
+                "op {} with type {}",
+                header.op,
+                t.unwrap_or("<none>")
+            ))
+            .into());
+        }
+    }
+
+    let message: FirehoseCommit<'_> =
+        serde_ipld_dagcbor::from_slice(body).map_err(|e| invalid_frame(Box::new(e)))?;
+    let blocks = parse_car_bytes(&message.blocks).await?.blocks;
+
+    let mut ops = Vec::with_capacity(message.ops.len());
+    for op in &message.ops {
+        ops.push(resolve_op(op, &blocks)?);
+    }
+
+    let prev_data = message
+        .prev_data
+        .as_ref()
+        .map(|cid| cid.to_ipld())
+        .transpose()
+        .map_err(|e| RepoError::invalid_cid_conversion(e, "prev_data CID"))?;
+
+    Ok(ParsedCommitEvent {
+        message,
+        ops,
+        blocks,
+        prev_data,
+    })
+}
+
+/// Turn a firehose op into a write op, decoding its record from the event's blocks
+fn resolve_op(
+    op: &RepoOp<'_>,
+    blocks: &BTreeMap<IpldCid, Bytes>,
+) -> std::result::Result<RecordWriteOp<'static>, FirehoseError> {
+    let invalid = |reason: String| FirehoseError::InvalidOp {
+        path: op.path.to_string(),
+        reason,
+    };
+
+    let (collection, rkey) = op
+        .path
+        .split_once('/')
+        .ok_or_else(|| invalid("path must be collection/rkey".into()))?;
+    let collection = Nsid::new_owned(collection).map_err(|e| invalid(e.to_string()))?;
+    let rkey = RecordKey(Rkey::new_owned(rkey).map_err(|e| invalid(e.to_string()))?);
+
+    let to_cid = |cid: &Option<CidLink<'_>>, field: &str| {
+        cid.as_ref()
+            .map(|cid| cid.to_ipld())
+            .transpose()
+            .map_err(|e| invalid(format!("bad {} CID: {}", field, e)))
+    };
+    let prev = to_cid(&op.prev, "prev")?;
+
+    let record = || {
+        let cid = to_cid(&op.cid, "record")?
+            .ok_or_else(|| invalid(format!("{} op missing cid", op.action)))?;
+        let data = blocks
+            .get(&cid)
+            .ok_or_else(|| FirehoseError::MissingBlock {
+                path: op.path.to_string(),
+                cid: cid.to_string(),
+            })?;
+        serde_ipld_dagcbor::from_slice::<BTreeMap<SmolStr, RawData<'_>>>(data)
+            .map(|record| record.into_static())
+            .map_err(|e| invalid(format!("record isn't a DAG-CBOR map: {}", e)))
+    };
+
+    match op.action.as_ref() {
+        "create" => Ok(RecordWriteOp::Create {
+            collection,
+            rkey,
+            record: record()?,
+        }),
+        "update" => Ok(RecordWriteOp::Update {
+            collection,
+            rkey,
+            record: record()?,
+            prev,
+        }),
+        "delete" => Ok(RecordWriteOp::Delete {
+            collection,
+            rkey,
+            prev,
+        }),
+        action => Err(invalid(format!("unknown action type: {}", action))),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1046,4 +1222,235 @@ mod tests {
             "Validation should fail with wrong prev_data CID"
         );
     }
+
+    /// Encode an event stream frame: DAG-CBOR header followed by DAG-CBOR body
+    fn encode_frame(header: &BTreeMap<&str, RawData<'_>>, body: &impl serde::Serialize) -> Vec<u8> {
+        let mut frame = serde_ipld_dagcbor::to_vec(header).unwrap();
+        frame.extend(serde_ipld_dagcbor::to_vec(body).unwrap());
+        frame
+    }
+
+    fn commit_header() -> BTreeMap<&'static str, RawData<'static>> {
+        BTreeMap::from([
+            ("op", RawData::SignedInt(1)),
+            ("t", RawData::String("#commit".into())),
+        ])
+    }
+
+    async fn make_commit_event() -> FirehoseCommit<'static> {
+        let storage = Arc::new(MemoryBlockStore::new());
+        let mut repo = create_test_repo(storage.clone()).await;
+        let did = Did::new("did:plc:test").unwrap();
+        let signing_key = k256::ecdsa::SigningKey::random(&mut rand::rngs::OsRng);
+        let collection = Nsid::new("app.bsky.feed.post").unwrap();
+
+        let ops = vec![
+            RecordWriteOp::Create {
+                collection: collection.clone(),
+                rkey: RecordKey(Rkey::new("test1").unwrap()),
+                record: make_test_record(1),
+            },
+            RecordWriteOp::Create {
+                collection: collection.clone(),
+                rkey: RecordKey(Rkey::new("test2").unwrap()),
+                record: make_test_record(2),
+            },
+        ];
+        let (repo_ops, commit_data) = repo
+            .create_commit(&ops, &did, Some(*repo.current_commit_cid()), &signing_key)
+            .await
+            .unwrap();
+        commit_data
+            .to_firehose_commit(&did, 42, Datetime::now(), repo_ops, vec![])
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_parse_commit_event() {
+        let message = make_commit_event().await;
+        let frame = encode_frame(&commit_header(), &message);
+
+        let event = parse_commit_event(&frame).await.unwrap();
+        assert_eq!(event.seq(), 42);
+        assert_eq!(event.repo().as_str(), "did:plc:test");
+        assert_eq!(event.rev(), &message.rev);
+        assert_eq!(event.message, message);
+        assert_eq!(
+            event.prev_data,
+            Some(message.prev_data.as_ref().unwrap().to_ipld().unwrap())
+        );
+
+        assert_eq!(event.ops.len(), 2);
+        for (op, n) in event.ops.iter().zip(1..) {
+            match op {
+                RecordWriteOp::Create {
+                    collection,
+                    rkey,
+                    record,
+                } => {
+                    assert_eq!(collection.as_str(), "app.bsky.feed.post");
+                    assert_eq!(rkey.as_ref(), format!("test{}", n));
+                    assert_eq!(record, &make_test_record(n));
+                }
+                other => panic!("expected create, got {:?}", other),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parse_commit_event_fixture() {
+        // Frame for seq 7 from did:plc:test: creates app.bsky.feed.post/test1, updates
+        // test2 and deletes test3
+        let frame = include_bytes!("../../tests/fixtures/firehose_commit_frame.bin");
+
+        let event = parse_commit_event(frame).await.unwrap();
+        assert_eq!(event.seq(), 7);
+        assert_eq!(event.repo().as_str(), "did:plc:test");
+        assert!(event.prev_data.is_some());
+
+        let actions: Vec<_> = event
+            .ops
+            .iter()
+            .map(|op| match op {
+                RecordWriteOp::Create { rkey, record, .. } => {
+                    assert_eq!(record, &make_test_record(1));
+                    format!("create {}", rkey.as_ref())
+                }
+                RecordWriteOp::Update {
+                    rkey, record, prev, ..
+                } => {
+                    assert_eq!(record, &make_test_record(20));
+                    assert!(prev.is_some());
+                    format!("update {}", rkey.as_ref())
+                }
+                RecordWriteOp::Delete { rkey, prev, .. } => {
+                    assert!(prev.is_some());
+                    format!("delete {}", rkey.as_ref())
+                }
+            })
+            .collect();
+        assert_eq!(actions, ["create test1", "update test2", "delete test3"]);
+    }
+
+    /// Writes `tests/fixtures/firehose_commit_frame.bin`, as read by
+    /// `test_parse_commit_event_fixture`
+    ///
+    /// Regenerate with
+    /// `cargo test -p jacquard-repo --lib generate_commit_frame_fixture -- --ignored`.
+    /// The signing key and commit rev are fresh on every run, so the bytes change while the
+    /// events they describe stay the same.
+    #[tokio::test]
+    #[ignore] // Writes the fixture file
+    async fn generate_commit_frame_fixture() {
+        let storage = Arc::new(MemoryBlockStore::new());
+        let mut repo = create_test_repo(storage.clone()).await;
+        let did = Did::new("did:plc:test").unwrap();
+        let signing_key = k256::ecdsa::SigningKey::random(&mut rand::rngs::OsRng);
+        let collection = Nsid::new("app.bsky.feed.post").unwrap();
+        let rkey = |rkey: &'static str| RecordKey(Rkey::new(rkey).unwrap());
+
+        // Base commit holding test2 and test3
+        let base_ops = vec![
+            RecordWriteOp::Create {
+                collection: collection.clone(),
+                rkey: rkey("test2"),
+                record: make_test_record(2),
+            },
+            RecordWriteOp::Create {
+                collection: collection.clone(),
+                rkey: rkey("test3"),
+                record: make_test_record(3),
+            },
+        ];
+        let (_, commit_data) = repo
+            .create_commit(
+                &base_ops,
+                &did,
+                Some(*repo.current_commit_cid()),
+                &signing_key,
+            )
+            .await
+            .unwrap();
+        repo.apply_commit(commit_data).await.unwrap();
+
+        let ops = vec![
+            RecordWriteOp::Create {
+                collection: collection.clone(),
+                rkey: rkey("test1"),
+                record: make_test_record(1),
+            },
+            RecordWriteOp::Update {
+                collection: collection.clone(),
+                rkey: rkey("test2"),
+                record: make_test_record(20),
+                prev: None,
+            },
+            RecordWriteOp::Delete {
+                collection: collection.clone(),
+                rkey: rkey("test3"),
+                prev: None,
+            },
+        ];
+        let (repo_ops, commit_data) = repo
+            .create_commit(&ops, &did, Some(*repo.current_commit_cid()), &signing_key)
+            .await
+            .unwrap();
+        let message = commit_data
+            .to_firehose_commit(&did, 7, Datetime::now(), repo_ops, vec![])
+            .await
+            .unwrap();
+        message
+            .validate_v1_1(&get_public_key(&signing_key))
+            .await
+            .unwrap();
+
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/firehose_commit_frame.bin"
+        );
+        std::fs::write(path, encode_frame(&commit_header(), &message)).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_parse_commit_event_missing_block() {
+        let mut message = make_commit_event().await;
+        let missing = message.ops[1].cid.as_ref().unwrap().to_ipld().unwrap();
+
+        let mut car = parse_car_bytes(&message.blocks).await.unwrap();
+        car.blocks.remove(&missing);
+        message.blocks = crate::car::write_car_bytes(car.root, car.blocks)
+            .await
+            .unwrap()
+            .into();
+
+        let err = parse_commit_event(&encode_frame(&commit_header(), &message))
+            .await
+            .unwrap_err();
+        assert!(matches!(err.kind(), crate::error::RepoErrorKind::NotFound));
+        assert!(err.to_string().contains(&missing.to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_parse_error_and_other_frames() {
+        let header = BTreeMap::from([("op", RawData::SignedInt(-1))]);
+        let body = BTreeMap::from([
+            ("error", RawData::String("FutureCursor".into())),
+            ("message", RawData::String("cursor in the future".into())),
+        ]);
+        let err = parse_commit_event(&encode_frame(&header, &body))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("FutureCursor"));
+
+        let header = BTreeMap::from([
+            ("op", RawData::SignedInt(1)),
+            ("t", RawData::String("#identity".into())),
+        ]);
+        let body = BTreeMap::from([("seq", RawData::SignedInt(1))]);
+        let err = parse_commit_event(&encode_frame(&header, &body))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("#identity"));
+    }
 }
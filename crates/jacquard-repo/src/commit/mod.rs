@@ -9,18 +9,25 @@ use crate::error::{CommitError, Result};
 use bytes::Bytes;
 use cid::Cid as IpldCid;
 use jacquard_common::IntoStatic;
-use jacquard_common::types::crypto::PublicKey;
+use jacquard_common::types::crypto::{KeyCodec, PublicKey};
+use jacquard_common::types::did_doc::DidDocument;
 use jacquard_common::types::string::Did;
 use jacquard_common::types::tid::Tid;
+#[cfg(feature = "identity")]
+use jacquard_identity::resolver::{IdentityError, IdentityResolver};
+use serde::Deserialize;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
 /// Repository commit object
 ///
 /// This structure represents a signed commit in an AT Protocol repository.
 /// Stored as a block in CAR files, identified by its CID.
 ///
 /// **Version compatibility**: v2 and v3 commits differ only in how `prev` is
-/// serialized (v2 uses it, v3 must include it even if null). This struct
-/// handles both by always including `prev` in serialization.
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+/// serialized: v3 must include it even if null, while v2 writers could omit it
+/// entirely. `prev` is always serialized unless the commit was decoded with
+/// [`from_cbor_versioned`](Self::from_cbor_versioned) from v2 bytes that omitted
+/// it, so legacy commits re-encode to the same bytes they were signed over.
+#[derive(Debug, Clone, Deserialize)]
 pub struct Commit<'a> {
     /// Repository DID
     #[serde(borrow)]
@@ -41,6 +48,14 @@ pub struct Commit<'a> {
     /// Signature bytes
     #[serde(with = "serde_bytes_helper")]
     pub sig: Bytes,
+
+    /// Whether the source was a v2 commit with no `prev` field at all
+    ///
+    /// Set by [`from_cbor_versioned`](Self::from_cbor_versioned). While it is set on a v2
+    /// commit that still has no `prev`, the field is left out when serializing (see
+    /// [`omits_prev`](Self::omits_prev)). Leave it `false` when building commits.
+    #[serde(skip)]
+    pub prev_omitted: bool,
 }
 
 impl<'a> Commit<'a> {
@@ -53,6 +68,7 @@ impl<'a> Commit<'a> {
             rev,
             prev,
             sig: Bytes::new(),
+            prev_omitted: false,
         }
     }
 
@@ -103,9 +119,62 @@ impl<'a> Commit<'a> {
     }
 
     /// Deserialize from DAG-CBOR
+    ///
+    /// Commits with a version other than 2 or 3 are rejected; see
+    /// [`validate_version`](Self::validate_version).
+    ///
+    /// A missing `prev` is treated as null, so a v2 commit that omitted it re-encodes
+    /// differently. Use [`from_cbor_versioned`](Self::from_cbor_versioned) for legacy repos.
     pub fn from_cbor(data: &'a [u8]) -> Result<Self> {
-        serde_ipld_dagcbor::from_slice(data)
-            .map_err(|e| CommitError::Serialization(Box::new(e)).into())
+        let commit: Self = serde_ipld_dagcbor::from_slice(data)
+            .map_err(|e| CommitError::Serialization(Box::new(e)))?;
+        commit.validate_version()?;
+        Ok(commit)
+    }
+
+    /// Check that this commit's version is one whose serialization rules are known (2 or 3)
+    ///
+    /// Later versions may sign a different payload, so verifying them as if they were v3
+    /// could accept or reject signatures for the wrong reasons.
+    pub fn validate_version(&self) -> std::result::Result<(), CommitError> {
+        match self.version {
+            2 | 3 => Ok(()),
+            v => Err(CommitError::InvalidVersion(v)),
+        }
+    }
+
+    /// Deserialize from DAG-CBOR, preserving the serialization shape of v2 commits
+    ///
+    /// Records whether a v2 commit had a `prev` field, and omits it again when
+    /// re-encoding if it was absent, so [`to_cid`](Self::to_cid) and signature
+    /// verification match the original bytes. v3 commits always serialize `prev`.
+    pub fn from_cbor_versioned(data: &'a [u8]) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct PrevField {
+            #[serde(default, deserialize_with = "field_present")]
+            prev: bool,
+        }
+
+        fn field_present<'de, D: serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> std::result::Result<bool, D::Error> {
+            serde::de::IgnoredAny::deserialize(deserializer).map(|_| true)
+        }
+
+        let mut commit = Self::from_cbor(data)?;
+        if commit.version < 3 {
+            let field: PrevField = serde_ipld_dagcbor::from_slice(data)
+                .map_err(|e| CommitError::Serialization(Box::new(e)))?;
+            commit.prev_omitted = !field.prev;
+        }
+        Ok(commit)
+    }
+
+    /// Whether `prev` is left out when serializing
+    ///
+    /// Only true for a v2 commit decoded without a `prev` field that still has none.
+    pub fn omits_prev(&self) -> bool {
+        self.prev_omitted && self.version < 3 && self.prev.is_none()
     }
 
     /// Compute CID of this commit
@@ -116,14 +185,15 @@ impl<'a> Commit<'a> {
 
     /// Verify signature against a public key from a DID document.
     ///
-    /// The key type is inferred from the PublicKey codec.
+    /// The key type is inferred from the PublicKey codec. Fails with
+    /// [`CommitError::InvalidVersion`] for commits of an unknown version.
     pub fn verify(&self, pubkey: &PublicKey) -> std::result::Result<(), CommitError> {
+        self.validate_version()?;
         let unsigned = self
             .unsigned_bytes()
             .map_err(|e| CommitError::Serialization(e.into()))?;
         let signature = self.sig();
 
-        use jacquard_common::types::crypto::KeyCodec;
         match pubkey.codec {
             KeyCodec::Ed25519 => {
                 let vk = pubkey
@@ -165,6 +235,135 @@ impl<'a> Commit<'a> {
 
         Ok(())
     }
+
+    /// Resolve the repo DID and verify the signature against its atproto signing key.
+    ///
+    /// Always checks against the key currently published in the DID document, so a commit
+    /// signed before a key rotation fails with [`CommitError::KeyMismatch`].
+    #[cfg(feature = "identity")]
+    pub async fn verify_with_resolver<R: IdentityResolver + Sync + ?Sized>(
+        &self,
+        resolver: &R,
+    ) -> std::result::Result<(), CommitError> {
+        let resolution_err = |e: IdentityError| CommitError::Resolution {
+            did: self.did.to_string(),
+            source: Box::new(e),
+        };
+        let response = resolver
+            .resolve_did_doc(&self.did)
+            .await
+            .map_err(resolution_err)?;
+        let doc = response.parse().map_err(resolution_err)?;
+        if doc.id.as_str() != self.did.as_str() {
+            return Err(resolution_err(IdentityError::doc_id_mismatch(
+                self.did.clone().into_static(),
+                doc.into_static(),
+            )));
+        }
+
+        self.verify_with_did_doc(&doc)
+    }
+
+    /// Verify the signature against the atproto signing keys in a DID document.
+    ///
+    /// Tries every `Multikey` verification method whose id is `#atproto` (relative or
+    /// qualified with the DID) and succeeds if any of them verifies. Fails with
+    /// [`CommitError::MissingSigningKey`] if the document has no such key that decodes to a
+    /// supported key type, with [`CommitError::KeyMismatch`] if there are keys but none
+    /// verifies, and with [`CommitError::DidMismatch`] if the document is for another DID.
+    pub fn verify_with_did_doc(
+        &self,
+        doc: &DidDocument<'_>,
+    ) -> std::result::Result<(), CommitError> {
+        if doc.id.as_str() != self.did.as_str() {
+            return Err(CommitError::DidMismatch {
+                commit: self.did.to_string(),
+                doc: doc.id.to_string(),
+            });
+        }
+        self.validate_version()?;
+
+        let keys: Vec<(&str, PublicKey<'static>)> = doc
+            .verification_method
+            .iter()
+            .flatten()
+            .filter(|method| {
+                method.r#type.as_ref() == "Multikey"
+                    && method
+                        .id
+                        .strip_suffix("#atproto")
+                        .is_some_and(|did| did.is_empty() || did == self.did.as_str())
+            })
+            .filter_map(|method| {
+                let multibase = method.public_key_multibase.as_deref()?;
+                let key = PublicKey::decode(multibase).ok()?;
+                (!matches!(key.codec, KeyCodec::Unknown(_))).then_some((multibase, key))
+            })
+            .collect();
+        if keys.is_empty() {
+            return Err(CommitError::MissingSigningKey(self.did.to_string()));
+        }
+
+        let mut tried = Vec::with_capacity(keys.len());
+        for (multibase, key) in &keys {
+            match self.verify(key) {
+                Ok(()) => return Ok(()),
+                Err(
+                    CommitError::SignatureVerificationFailed | CommitError::InvalidSignature(_),
+                ) => tried.push(*multibase),
+                Err(e) => return Err(e),
+            }
+        }
+        Err(CommitError::KeyMismatch {
+            did: self.did.to_string(),
+            key: tried.join(", "),
+        })
+    }
+}
+
+/// Verify a chain of commits signed by the same key
+///
+/// `commits` runs oldest first. Every commit's signature is checked against `pubkey`, and
+/// each commit after the first must have the previous commit's CID as its `prev` and a
+/// later `rev`. The first commit's `prev` isn't checked, so a chain can start anywhere in
+/// the repo history.
+///
+/// Commits are checked in order, and the error for the first one that fails is wrapped in
+/// [`CommitError::Chain`] with its index and CID.
+pub fn verify_chain(
+    commits: &[Commit<'_>],
+    pubkey: &PublicKey,
+) -> std::result::Result<(), CommitError> {
+    let mut previous: Option<(IpldCid, &Tid)> = None;
+    for (index, commit) in commits.iter().enumerate() {
+        let cid = commit
+            .to_cid()
+            .map_err(|e| CommitError::Serialization(Box::new(e)))?;
+        let check = || {
+            if let Some((prev_cid, prev_rev)) = previous {
+                if commit.prev() != Some(&prev_cid) {
+                    return Err(CommitError::PrevMismatch {
+                        expected: prev_cid.to_string(),
+                        found: commit.prev().map(|cid| cid.to_string()),
+                    });
+                }
+                if commit.rev() <= prev_rev {
+                    return Err(CommitError::RevNotIncreasing {
+                        prev: prev_rev.to_string(),
+                        rev: commit.rev().to_string(),
+                    });
+                }
+            }
+            commit.verify(pubkey)
+        };
+        check().map_err(|e| CommitError::Chain {
+            index,
+            cid: cid.to_string(),
+            source: Box::new(e),
+        })?;
+        previous = Some((cid, commit.rev()));
+    }
+    Ok(())
 }
 
 impl IntoStatic for Commit<'_> {
@@ -178,7 +377,30 @@ impl IntoStatic for Commit<'_> {
             rev: self.rev,
             prev: self.prev,
             sig: self.sig,
+            prev_omitted: self.prev_omitted,
+        }
+    }
+}
+
+impl Serialize for Commit<'_> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let omit_prev = self.omits_prev();
+        let len = if omit_prev { 5 } else { 6 };
+        let mut state = serializer.serialize_struct("Commit", len)?;
+        state.serialize_field("did", &self.did)?;
+        state.serialize_field("version", &self.version)?;
+        state.serialize_field("data", &self.data)?;
+        state.serialize_field("rev", &self.rev)?;
+        if omit_prev {
+            state.skip_field("prev")?;
+        } else {
+            state.serialize_field("prev", &self.prev)?;
         }
+        state.serialize_field("sig", serde_bytes::Bytes::new(&self.sig))?;
+        state.end()
     }
 }
 
@@ -237,3 +459,302 @@ impl SigningKey for p256::ecdsa::SigningKey {
             .to_vec()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "identity")]
+    use jacquard_common::types::crypto::multikey;
+    #[cfg(feature = "identity")]
+    use jacquard_identity::resolver::{DidDocResponse, ResolverOptions};
+
+    /// Resolver that serves a single fixed DID document
+    #[cfg(feature = "identity")]
+    struct StaticResolver {
+        options: ResolverOptions,
+        doc: serde_json::Value,
+    }
+
+    #[cfg(feature = "identity")]
+    impl StaticResolver {
+        fn new(did: &str, key: &k256::ecdsa::SigningKey) -> Self {
+            let multibase = multikey(0xE7, &key.public_key());
+            Self {
+                options: ResolverOptions::default(),
+                doc: serde_json::json!({
+                    "id": did,
+                    "verificationMethod": [{
+                        "id": format!("{did}#atproto"),
+                        "type": "Multikey",
+                        "controller": did,
+                        "publicKeyMultibase": multibase,
+                    }],
+                }),
+            }
+        }
+    }
+
+    #[cfg(feature = "identity")]
+    impl IdentityResolver for StaticResolver {
+        fn options(&self) -> &ResolverOptions {
+            &self.options
+        }
+
+        async fn resolve_handle(
+            &self,
+            _handle: &jacquard_common::types::string::Handle<'_>,
+        ) -> jacquard_identity::resolver::Result<Did<'static>> {
+            // Commit verification only looks up DID documents
+            Err(jacquard_identity::resolver::IdentityError::invalid_well_known())
+        }
+
+        async fn resolve_did_doc(
+            &self,
+            did: &Did<'_>,
+        ) -> jacquard_identity::resolver::Result<DidDocResponse> {
+            Ok(DidDocResponse {
+                buffer: Bytes::from(serde_json::to_vec(&self.doc).unwrap()),
+                status: Default::default(),
+                requested: Some(did.clone().into_static()),
+            })
+        }
+    }
+
+    fn signed_commit(did: &str, key: &k256::ecdsa::SigningKey) -> Commit<'static> {
+        let data = crate::mst::util::compute_cid(b"\xa0").unwrap();
+        Commit::new_unsigned(
+            Did::new_owned(did).unwrap(),
+            data,
+            jacquard_common::types::tid::Ticker::new().next(None),
+            None,
+        )
+        .sign(key)
+        .unwrap()
+    }
+
+    fn random_key() -> k256::ecdsa::SigningKey {
+        k256::ecdsa::SigningKey::random(&mut rand::rngs::OsRng)
+    }
+
+    #[cfg(feature = "identity")]
+    #[tokio::test]
+    async fn verify_with_resolver_uses_published_key() {
+        let did = "did:plc:verifytest";
+        let key = random_key();
+        let commit = signed_commit(did, &key);
+
+        commit
+            .verify_with_resolver(&StaticResolver::new(did, &key))
+            .await
+            .unwrap();
+    }
+
+    #[cfg(feature = "identity")]
+    #[tokio::test]
+    async fn verify_with_resolver_reports_rotated_key() {
+        let did = "did:plc:verifytest";
+        let old_key = random_key();
+        let commit = signed_commit(did, &old_key);
+
+        let rotated = StaticResolver::new(did, &random_key());
+        let err = commit.verify_with_resolver(&rotated).await.unwrap_err();
+        assert!(matches!(err, CommitError::KeyMismatch { did: d, .. } if d == did));
+
+        let mut no_key = rotated;
+        no_key.doc = serde_json::json!({ "id": did });
+        let err = commit.verify_with_resolver(&no_key).await.unwrap_err();
+        assert!(matches!(err, CommitError::MissingSigningKey(_)));
+    }
+
+    #[test]
+    fn verify_with_did_doc_tries_atproto_keys() {
+        use jacquard_common::types::crypto::multikey;
+
+        let did = "did:plc:doctest";
+        let key = random_key();
+        let other = random_key();
+        let commit = signed_commit(did, &key);
+        let method = |id: &str, key: &k256::ecdsa::SigningKey| {
+            serde_json::json!({
+                "id": id,
+                "type": "Multikey",
+                "controller": did,
+                "publicKeyMultibase": multikey(0xE7, &key.public_key()),
+            })
+        };
+        let verify = |doc: serde_json::Value| {
+            let json = doc.to_string();
+            let doc: DidDocument<'_> = serde_json::from_str(&json).unwrap();
+            commit.verify_with_did_doc(&doc)
+        };
+
+        verify(serde_json::json!({
+            "id": did,
+            "verificationMethod": [method(&format!("{did}#atproto"), &key)],
+        }))
+        .unwrap();
+        verify(serde_json::json!({
+            "id": did,
+            "verificationMethod": [method("#atproto", &other), method("#atproto", &key)],
+        }))
+        .unwrap();
+
+        // The right key under another id doesn't count
+        let err = verify(serde_json::json!({
+            "id": did,
+            "verificationMethod": [method("#atproto", &other), method("#backup", &key)],
+        }))
+        .unwrap_err();
+        assert!(matches!(err, CommitError::KeyMismatch { did: d, .. } if d == did));
+
+        for methods in [
+            serde_json::json!([]),
+            serde_json::json!([method("#backup", &key)]),
+            serde_json::json!([{
+                "id": "#atproto",
+                "type": "Multikey",
+                "publicKeyMultibase": "zNotAKey",
+            }]),
+        ] {
+            let err = verify(serde_json::json!({ "id": did, "verificationMethod": methods }))
+                .unwrap_err();
+            assert!(matches!(err, CommitError::MissingSigningKey(_)), "{err}");
+        }
+
+        let err = verify(serde_json::json!({
+            "id": "did:plc:someoneelse",
+            "verificationMethod": [method("#atproto", &key)],
+        }))
+        .unwrap_err();
+        assert!(matches!(err, CommitError::DidMismatch { .. }));
+    }
+
+    #[test]
+    fn verify_chain_reports_first_bad_commit() {
+        let did = Did::new_static("did:plc:chaintest").unwrap();
+        let key = random_key();
+        let pubkey = PublicKey {
+            codec: jacquard_common::types::crypto::KeyCodec::Secp256k1,
+            bytes: key.public_key().into(),
+        };
+        let data = crate::mst::util::compute_cid(b"\xa0").unwrap();
+        let mut ticker = jacquard_common::types::tid::Ticker::new();
+
+        let mut chain: Vec<Commit<'static>> = Vec::new();
+        for _ in 0..3 {
+            let prev = chain.last().map(|c| c.to_cid().unwrap());
+            let commit = Commit::new_unsigned(did.clone(), data, ticker.next(None), prev)
+                .sign(&key)
+                .unwrap();
+            chain.push(commit);
+        }
+        verify_chain(&chain, &pubkey).unwrap();
+
+        let mut corrupted = chain.clone();
+        let mut sig = corrupted[1].sig.to_vec();
+        sig[0] ^= 0xff;
+        corrupted[1].sig = Bytes::from(sig);
+        let err = verify_chain(&corrupted, &pubkey).unwrap_err();
+        let CommitError::Chain { index, cid, source } = err else {
+            panic!("expected a chain error, got {err}");
+        };
+        assert_eq!(index, 1);
+        assert_eq!(cid, corrupted[1].to_cid().unwrap().to_string());
+        assert!(matches!(
+            *source,
+            CommitError::SignatureVerificationFailed | CommitError::InvalidSignature(_)
+        ));
+
+        // Out of order: prev no longer links up
+        let swapped = [chain[0].clone(), chain[2].clone(), chain[1].clone()];
+        let err = verify_chain(&swapped, &pubkey).unwrap_err();
+        assert!(matches!(
+            err,
+            CommitError::Chain { index: 1, ref source, .. }
+                if matches!(**source, CommitError::PrevMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn unknown_versions_are_rejected() {
+        let key = random_key();
+        let mut commit = signed_commit("did:plc:future", &key);
+        let pubkey = PublicKey {
+            codec: jacquard_common::types::crypto::KeyCodec::Secp256k1,
+            bytes: key.public_key().into(),
+        };
+        commit.verify(&pubkey).unwrap();
+
+        commit.version = 4;
+        assert!(matches!(
+            commit.validate_version(),
+            Err(CommitError::InvalidVersion(4))
+        ));
+        assert!(matches!(
+            commit.verify(&pubkey),
+            Err(CommitError::InvalidVersion(4))
+        ));
+        let bytes = commit.to_cbor().unwrap();
+        let err = Commit::from_cbor(&bytes).unwrap_err();
+        assert!(err.to_string().contains("unsupported version 4"), "{err}");
+        assert!(Commit::from_cbor_versioned(&bytes).is_err());
+
+        commit.version = 2;
+        commit.validate_version().unwrap();
+    }
+
+    #[test]
+    fn v2_commit_without_prev_round_trips() {
+        /// v2 commit as written by PDSes that left out `prev` entirely
+        #[derive(serde::Serialize)]
+        struct LegacyCommit<'a> {
+            did: &'a str,
+            version: i64,
+            data: IpldCid,
+            rev: Tid,
+            #[serde(with = "serde_bytes")]
+            sig: &'a [u8],
+        }
+
+        let key = random_key();
+        let legacy = LegacyCommit {
+            did: "did:plc:legacy",
+            version: 2,
+            data: crate::mst::util::compute_cid(b"\xa0").unwrap(),
+            rev: jacquard_common::types::tid::Ticker::new().next(None),
+            sig: &[],
+        };
+        let unsigned = serde_ipld_dagcbor::to_vec(&legacy).unwrap();
+        let sig = key.sign_bytes(&unsigned).unwrap();
+        let bytes = serde_ipld_dagcbor::to_vec(&LegacyCommit {
+            sig: &sig,
+            ..legacy
+        })
+        .unwrap();
+        let pubkey = PublicKey {
+            codec: jacquard_common::types::crypto::KeyCodec::Secp256k1,
+            bytes: key.public_key().into(),
+        };
+
+        let commit = Commit::from_cbor_versioned(&bytes).unwrap();
+        assert!(commit.omits_prev());
+        assert_eq!(commit.to_cbor().unwrap(), bytes);
+        commit.verify(&pubkey).unwrap();
+
+        // Plain decoding normalizes to `prev: null`, which no longer matches the signature
+        let normalized = Commit::from_cbor(&bytes).unwrap();
+        assert!(!normalized.omits_prev());
+        assert_ne!(normalized.to_cbor().unwrap(), bytes);
+        assert!(matches!(
+            normalized.verify(&pubkey),
+            Err(CommitError::SignatureVerificationFailed)
+        ));
+
+        // v3 always carries `prev`, even when decoded through the versioned path
+        let v3 = signed_commit("did:plc:legacy", &key);
+        let v3_bytes = v3.to_cbor().unwrap();
+        let decoded = Commit::from_cbor_versioned(&v3_bytes).unwrap();
+        assert!(!decoded.omits_prev());
+        assert_eq!(decoded.to_cbor().unwrap(), v3_bytes);
+    }
+}
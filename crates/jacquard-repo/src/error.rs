@@ -277,11 +277,87 @@ pub enum CommitError {
     /// Serialization failed
     #[error("Serialization failed")]
     Serialization(#[source] BoxError),
+
+    /// Resolving the repo DID's document failed
+    #[error("Failed to resolve DID document for {did}")]
+    Resolution {
+        /// The repo DID
+        did: String,
+        /// Underlying resolver error
+        #[source]
+        source: BoxError,
+    },
+
+    /// DID document has no atproto signing key
+    #[error("DID document for {0} has no atproto signing key")]
+    MissingSigningKey(String),
+
+    /// Signature does not verify against the DID's currently published signing key
+    #[error("Commit signature does not match current signing key {key} for {did}")]
+    #[diagnostic(help(
+        "The commit may have been signed before the account rotated its signing key, or it was not signed by this repo"
+    ))]
+    KeyMismatch {
+        /// The repo DID
+        did: String,
+        /// Multibase-encoded key(s) the signature was checked against, comma-separated
+        key: String,
+    },
+
+    /// Commit's `prev` doesn't point at the commit before it
+    #[error("Commit prev is {found:?}, expected {expected}")]
+    PrevMismatch {
+        /// CID of the preceding commit
+        expected: String,
+        /// The commit's `prev`
+        found: Option<String>,
+    },
+
+    /// Commit's rev is not after the rev of the commit before it
+    #[error("Commit rev {rev} is not after previous rev {prev}")]
+    RevNotIncreasing {
+        /// Rev of the preceding commit
+        prev: String,
+        /// The commit's rev
+        rev: String,
+    },
+
+    /// A commit in a chain failed verification
+    #[error("Commit {index} ({cid}) in chain failed verification")]
+    Chain {
+        /// Position of the commit in the chain
+        index: usize,
+        /// CID of the commit
+        cid: String,
+        /// Why the commit failed
+        #[source]
+        source: Box<CommitError>,
+    },
+
+    /// DID document belongs to a different DID than the commit
+    #[error("DID document for {doc} does not match commit DID {commit}")]
+    DidMismatch {
+        /// The repo DID from the commit
+        commit: String,
+        /// The `id` of the DID document
+        doc: String,
+    },
 }
 
 impl From<CommitError> for RepoError {
     fn from(e: CommitError) -> Self {
         match e {
+            CommitError::Resolution { .. } => RepoError::new(RepoErrorKind::Io, Some(Box::new(e))),
+            CommitError::MissingSigningKey(_)
+            | CommitError::KeyMismatch { .. }
+            | CommitError::DidMismatch { .. } => {
+                RepoError::new(RepoErrorKind::Crypto, Some(Box::new(e)))
+            }
+            CommitError::PrevMismatch { .. }
+            | CommitError::RevNotIncreasing { .. }
+            | CommitError::Chain { .. } => {
+                RepoError::new(RepoErrorKind::InvalidCommit, Some(Box::new(e)))
+            }
             CommitError::InvalidVersion(v) => {
                 RepoError::invalid_commit(format!("unsupported version {}", v))
             }
@@ -333,6 +409,149 @@ impl From<DiffError> for RepoError {
     }
 }
 
+/// CAR framing errors
+///
+/// Produced when the varint-length-prefixed section structure of a CAR file, or the
+/// header and index of a CARv2 file, is malformed, before any header or block contents
+/// are decoded.
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+pub enum CarError {
+    /// Input ended before a complete varint or section
+    #[error("CAR data truncated at offset {offset}: needed {needed} bytes, {remaining} remaining")]
+    #[diagnostic(
+        code(car::truncated),
+        help(
+            "The CAR data ends partway through a section - check that it was transferred completely"
+        )
+    )]
+    Truncated {
+        /// Byte offset of the incomplete varint or section
+        offset: usize,
+        /// Number of bytes the varint or section requires
+        needed: usize,
+        /// Number of bytes left in the input
+        remaining: usize,
+    },
+
+    /// Malformed varint length prefix
+    #[error("Invalid varint length prefix at offset {offset}")]
+    #[diagnostic(
+        code(car::invalid_varint),
+        help("Section length prefixes must be unsigned varints of at most 9 bytes")
+    )]
+    InvalidVarint {
+        /// Byte offset of the varint
+        offset: usize,
+    },
+
+    /// Zero-length section
+    #[error("Empty CAR section at offset {offset}")]
+    #[diagnostic(
+        code(car::empty_section),
+        help("Every CAR section must hold a header or a block CID, so cannot be empty")
+    )]
+    EmptySection {
+        /// Byte offset of the section's length prefix
+        offset: usize,
+    },
+
+    /// CARv2 header is inconsistent with the file
+    #[error("Invalid CARv2 header: {0}")]
+    #[diagnostic(
+        code(car::invalid_v2_header),
+        help("The data payload and index offsets in the CARv2 header must lie within the file")
+    )]
+    InvalidV2Header(String),
+
+    /// CARv2 index is malformed or doesn't match the data payload
+    #[error("Invalid CARv2 index: {0}")]
+    #[diagnostic(code(car::invalid_index))]
+    InvalidIndex(String),
+
+    /// CARv2 index in a format other than IndexSorted or MultihashIndexSorted
+    #[error("Unsupported CARv2 index codec 0x{codec:x}")]
+    #[diagnostic(
+        code(car::unsupported_index),
+        help("Only IndexSorted (0x0400) and MultihashIndexSorted (0x0401) indexes can be read")
+    )]
+    UnsupportedIndex {
+        /// Multicodec code of the index format
+        codec: u64,
+    },
+}
+
+impl From<CarError> for RepoError {
+    fn from(e: CarError) -> Self {
+        RepoError::new(RepoErrorKind::Car, Some(Box::new(e)))
+    }
+}
+
+/// Block ingestion errors
+///
+/// Produced by strict block validation (see [`crate::storage::canonical`]) when
+/// a block's bytes don't hash to its CID or are not canonical DAG-CBOR.
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+pub enum BlockError {
+    /// Block does not decode as DAG-CBOR
+    #[error("Block {cid} is not valid DAG-CBOR")]
+    #[diagnostic(code(block::invalid_cbor))]
+    InvalidCbor {
+        /// CID of the offending block
+        cid: cid::Cid,
+        /// Underlying decode error
+        #[source]
+        source: BoxError,
+    },
+
+    /// Block decodes, but re-encoding it produces different bytes
+    #[error("Block {cid} is not canonical DAG-CBOR")]
+    #[diagnostic(
+        code(block::non_canonical_cbor),
+        help(
+            "Other implementations will compute a different CID for this data - re-encode it canonically before storing"
+        )
+    )]
+    NonCanonicalCbor {
+        /// CID of the offending block
+        cid: cid::Cid,
+    },
+
+    /// Block bytes don't hash to the CID they were given with
+    #[error("Block data does not match CID {cid}")]
+    #[diagnostic(
+        code(block::cid_mismatch),
+        help("The block was corrupted or paired with the wrong CID")
+    )]
+    CidMismatch {
+        /// CID the block was given with
+        cid: cid::Cid,
+    },
+
+    /// CID uses a codec or hash function the block can't be checked against
+    #[error("Cannot verify block {cid}: unsupported codec or hash function")]
+    #[diagnostic(
+        code(block::unsupported_cid),
+        help(
+            "Only DAG-CBOR and raw blocks hashed with SHA-256, SHA-512 or identity can be verified"
+        )
+    )]
+    UnsupportedCid {
+        /// CID of the offending block
+        cid: cid::Cid,
+    },
+}
+
+impl From<BlockError> for RepoError {
+    fn from(e: BlockError) -> Self {
+        let kind = match e {
+            BlockError::CidMismatch { .. } => RepoErrorKind::CidMismatch,
+            BlockError::UnsupportedCid { .. } => RepoErrorKind::InvalidCid,
+            _ => RepoErrorKind::Serialization,
+        };
+        RepoError::new(kind, Some(Box::new(e)))
+    }
+}
+
 /// Proof verification errors
 #[derive(Debug, thiserror::Error, miette::Diagnostic)]
 pub enum ProofError {
@@ -440,3 +659,219 @@ impl From<ProofError> for RepoError {
         }
     }
 }
+
+/// Repository import verification errors
+///
+/// Returned by [`Repository::verify_import`](crate::Repository::verify_import); each
+/// variant names the check that failed.
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+pub enum ImportError {
+    /// CAR framing or header couldn't be parsed
+    #[error("CAR parsing failed")]
+    #[diagnostic(code(import::car_parse_failed))]
+    CarParseFailed {
+        /// Underlying error
+        #[source]
+        source: BoxError,
+    },
+
+    /// A block's bytes don't hash to its CID
+    #[error("Block {cid} does not match its CID")]
+    #[diagnostic(
+        code(import::block_mismatch),
+        help("The export was modified or corrupted after it was written")
+    )]
+    BlockMismatch {
+        /// CID of the offending block
+        cid: String,
+    },
+
+    /// A block uses a codec or hash function that can't be checked
+    #[error("Block {cid} uses an unsupported codec or hash function")]
+    #[diagnostic(code(import::unsupported_block))]
+    UnsupportedBlock {
+        /// CID of the offending block
+        cid: String,
+    },
+
+    /// Commit block named by the CAR root not found
+    #[error("Commit block {cid} not found in CAR")]
+    #[diagnostic(
+        code(import::commit_not_found),
+        help("The CAR root CID must point to the repo's commit block")
+    )]
+    CommitNotFound {
+        /// The CAR root CID
+        cid: String,
+    },
+
+    /// Commit deserialization failed
+    #[error("Commit deserialization failed")]
+    #[diagnostic(code(import::commit_deserialize_failed))]
+    CommitDeserializeFailed {
+        /// Underlying error
+        #[source]
+        source: BoxError,
+    },
+
+    /// DID mismatch between commit and expected
+    #[error("DID mismatch: commit has {commit_did}, expected {expected_did}")]
+    #[diagnostic(code(import::did_mismatch))]
+    DidMismatch {
+        /// DID in the commit
+        commit_did: String,
+        /// Expected DID
+        expected_did: String,
+    },
+
+    /// Signature verification failed
+    #[error("Signature verification failed")]
+    #[diagnostic(
+        code(import::signature_failed),
+        help("The commit signature must be valid for the provided public key")
+    )]
+    SignatureVerificationFailed {
+        /// Underlying crypto error
+        #[source]
+        source: CommitError,
+    },
+
+    /// The MST couldn't be walked from the commit's `data` CID
+    #[error("Failed to load MST rooted at {root}")]
+    #[diagnostic(
+        code(import::invalid_mst),
+        help("Every MST node reachable from the commit must be included in the CAR")
+    )]
+    InvalidMst {
+        /// The commit's `data` CID
+        root: String,
+        /// Underlying error
+        #[source]
+        source: BoxError,
+    },
+
+    /// MST rebuilt from the included leaves has a different root than the commit
+    #[error("Commit data is {expected}, but the included MST hashes to {computed}")]
+    #[diagnostic(
+        code(import::data_mismatch),
+        help("The MST nodes in the CAR aren't the canonical tree for their keys")
+    )]
+    DataMismatch {
+        /// The commit's `data` CID
+        expected: String,
+        /// Root of the MST rebuilt from the leaves
+        computed: String,
+    },
+
+    /// A record the MST points at is missing from the CAR
+    #[error("Record block {cid} for {key} missing from CAR")]
+    #[diagnostic(code(import::missing_record))]
+    MissingRecord {
+        /// MST key (collection/rkey)
+        key: String,
+        /// The missing CID
+        cid: String,
+    },
+}
+
+impl From<ImportError> for RepoError {
+    fn from(e: ImportError) -> Self {
+        match &e {
+            ImportError::CarParseFailed { .. } => {
+                RepoError::new(RepoErrorKind::Car, Some(Box::new(e)))
+            }
+            ImportError::BlockMismatch { .. }
+            | ImportError::UnsupportedBlock { .. }
+            | ImportError::DataMismatch { .. } => {
+                RepoError::new(RepoErrorKind::CidMismatch, Some(Box::new(e)))
+            }
+            ImportError::CommitNotFound { .. } | ImportError::MissingRecord { .. } => {
+                RepoError::new(RepoErrorKind::NotFound, Some(Box::new(e)))
+            }
+            ImportError::CommitDeserializeFailed { .. } => {
+                RepoError::new(RepoErrorKind::Serialization, Some(Box::new(e)))
+            }
+            ImportError::DidMismatch { .. } => {
+                RepoError::new(RepoErrorKind::InvalidCommit, Some(Box::new(e)))
+            }
+            ImportError::SignatureVerificationFailed { .. } => {
+                RepoError::new(RepoErrorKind::Crypto, Some(Box::new(e)))
+            }
+            ImportError::InvalidMst { .. } => {
+                RepoError::new(RepoErrorKind::InvalidMst, Some(Box::new(e)))
+            }
+        }
+    }
+}
+
+/// Firehose event parsing errors
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+pub enum FirehoseError {
+    /// The relay sent an error frame instead of an event
+    #[error("Firehose error frame: {error}")]
+    #[diagnostic(code(firehose::error_frame))]
+    ErrorFrame {
+        /// Error name
+        error: String,
+        /// Human-readable description
+        message: Option<String>,
+    },
+
+    /// Frame holds a different event type
+    #[error("Expected a #commit event, got {0}")]
+    #[diagnostic(
+        code(firehose::unexpected_event),
+        help("Dispatch on the frame header's `t` field before parsing the body")
+    )]
+    UnexpectedEvent(String),
+
+    /// Frame header or body isn't valid DAG-CBOR for a commit event
+    #[error("Invalid firehose frame")]
+    #[diagnostic(code(firehose::invalid_frame))]
+    InvalidFrame {
+        /// Underlying error
+        #[source]
+        source: BoxError,
+    },
+
+    /// An op's record block isn't in the embedded CAR
+    #[error("Record block {cid} for {path} missing from commit blocks")]
+    #[diagnostic(
+        code(firehose::missing_block),
+        help("Creates and updates must ship their record block in the event's CAR")
+    )]
+    MissingBlock {
+        /// Op path (collection/rkey)
+        path: String,
+        /// The missing CID
+        cid: String,
+    },
+
+    /// Op has a malformed path or is missing a field its action requires
+    #[error("Invalid op for {path}: {reason}")]
+    #[diagnostic(code(firehose::invalid_op))]
+    InvalidOp {
+        /// Op path (collection/rkey)
+        path: String,
+        /// What's wrong with it
+        reason: String,
+    },
+}
+
+impl From<FirehoseError> for RepoError {
+    fn from(e: FirehoseError) -> Self {
+        match &e {
+            FirehoseError::MissingBlock { .. } => {
+                RepoError::new(RepoErrorKind::NotFound, Some(Box::new(e)))
+            }
+            FirehoseError::InvalidOp { .. } => {
+                RepoError::new(RepoErrorKind::InvalidCommit, Some(Box::new(e)))
+            }
+            FirehoseError::ErrorFrame { .. }
+            | FirehoseError::UnexpectedEvent(_)
+            | FirehoseError::InvalidFrame { .. } => {
+                RepoError::new(RepoErrorKind::Serialization, Some(Box::new(e)))
+            }
+        }
+    }
+}
@@ -57,8 +57,27 @@ pub mod storage;
 
 pub use error::{RepoError, RepoErrorKind, Result};
 pub use mst::{Mst, MstDiff, WriteOp};
-pub use repo::{CommitData, Repository};
-pub use storage::{BlockStore, FileBlockStore, LayeredBlockStore, MemoryBlockStore};
+pub use repo::{CommitData, Repository, VerifiedRepo, VerifyReport};
+#[cfg(not(target_family = "wasm"))]
+pub use storage::FileBlockStore;
+pub use storage::{BlockStore, LayeredBlockStore, MemoryBlockStore};
 
 /// DAG-CBOR codec identifier for CIDs (0x71)
 pub const DAG_CBOR_CID_CODEC: u64 = 0x71;
+
+/// Raw codec identifier for CIDs (0x55), used for blobs
+pub const RAW_CID_CODEC: u64 = 0x55;
+
+/// CID of a DAG-CBOR block: CIDv1 with a SHA-256 multihash and the DAG-CBOR codec
+///
+/// This is the addressing [`BlockStore::put`] and the MST use, so it gives the same CID a
+/// store returns for the same bytes. The bytes are hashed as given; encode records with
+/// `serde_ipld_dagcbor` first so they're canonical.
+pub fn cid_for_dag_cbor(bytes: &[u8]) -> Result<cid::Cid> {
+    mst::util::compute_cid_with_codec(bytes, DAG_CBOR_CID_CODEC)
+}
+
+/// CID of a blob: CIDv1 with a SHA-256 multihash and the raw codec
+pub fn cid_for_raw(bytes: &[u8]) -> Result<cid::Cid> {
+    mst::util::compute_cid_with_codec(bytes, RAW_CID_CODEC)
+}
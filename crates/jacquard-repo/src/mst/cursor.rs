@@ -34,6 +34,7 @@ pub enum CursorPosition<S: BlockStore> {
 /// - `advance()`: Move to next item in sorted order
 /// - `skip_subtree()`: Skip entire subtree at current position
 /// - `current()`: Get current position without moving
+/// - `seek_prefix()` / `next_in_prefix()`: Visit only keys under a prefix
 ///
 /// # Example traversal
 ///
@@ -65,6 +66,9 @@ pub struct MstCursor<S: BlockStore> {
 
     /// Current position in traversal
     current: CursorPosition<S>,
+
+    /// Prefix that `next_in_prefix()` is limited to (empty matches every key)
+    prefix: SmolStr,
 }
 
 impl<S: BlockStore + Sync + 'static> MstCursor<S> {
@@ -76,6 +80,7 @@ impl<S: BlockStore + Sync + 'static> MstCursor<S> {
         Self {
             path: Vec::new(),
             current: CursorPosition::Tree { mst: root },
+            prefix: SmolStr::default(),
         }
     }
 
@@ -97,6 +102,24 @@ impl<S: BlockStore + Sync + 'static> MstCursor<S> {
         }
     }
 
+    /// Exclusive upper bound on every key at or below the current position
+    ///
+    /// Found by looking for a following leaf sibling at each level of the path, innermost
+    /// first. When positioned on a subtree this is the key of the next leaf in sorted order.
+    /// Returns `None` if no leaf follows (the position extends to the end of the tree).
+    ///
+    /// Combined with `skip_subtree()`, lets range scans skip subtrees that lie entirely
+    /// below the start of the range.
+    pub fn upper_bound(&self) -> Option<&str> {
+        self.path
+            .iter()
+            .rev()
+            .find_map(|(_node, entries, index)| match entries.get(*index + 1) {
+                Some(NodeEntry::Leaf { key, .. }) => Some(key.as_str()),
+                _ => None,
+            })
+    }
+
     /// Get the layer we're currently walking at
     ///
     /// Returns the layer of the node we're traversing within.
@@ -152,6 +175,58 @@ impl<S: BlockStore + Sync + 'static> MstCursor<S> {
         self.step_over().await
     }
 
+    /// Position the cursor at the first key that is not below `prefix`
+    ///
+    /// Subtrees that sort entirely before the prefix are skipped without being loaded, so
+    /// only the nodes along the left edge of the prefix range are read. Afterwards the cursor
+    /// points at a leaf or is at the end, and `next_in_prefix()` yields keys starting with
+    /// `prefix`. An empty prefix matches every key.
+    ///
+    /// Seeking only moves forward; a cursor already past the prefix range stays where it is.
+    pub async fn seek_prefix(&mut self, prefix: &str) -> Result<()> {
+        self.prefix = SmolStr::new(prefix);
+        loop {
+            let skip = match &self.current {
+                CursorPosition::End => return Ok(()),
+                CursorPosition::Leaf { key, .. } => {
+                    if key.as_str() >= prefix {
+                        return Ok(());
+                    }
+                    false
+                }
+                // Every key in the subtree sorts below its upper bound
+                CursorPosition::Tree { .. } => {
+                    self.upper_bound().is_some_and(|bound| bound <= prefix)
+                }
+            };
+
+            if skip {
+                self.skip_subtree().await?;
+            } else {
+                self.advance().await?;
+            }
+        }
+    }
+
+    /// Return the next leaf whose key starts with the prefix given to `seek_prefix()`
+    ///
+    /// Returns `None` once a key past the prefix is reached, without loading the rest of
+    /// the tree. Without a prior `seek_prefix()` every leaf is yielded.
+    pub async fn next_in_prefix(&mut self) -> Result<Option<(SmolStr, IpldCid)>> {
+        let prefix = self.prefix.clone();
+        self.seek_prefix(&prefix).await?;
+
+        match &self.current {
+            CursorPosition::Leaf { key, cid } if key.starts_with(prefix.as_str()) => {
+                let leaf = (key.clone(), *cid);
+                self.advance().await?;
+                Ok(Some(leaf))
+            }
+            // Keys sharing the prefix are contiguous, so we're past them
+            _ => Ok(None),
+        }
+    }
+
     /// Move to next sibling or pop up
     fn step_over<'a>(
         &'a mut self,
@@ -317,4 +392,77 @@ mod tests {
         // We should have encountered some leaves
         assert!(leaf_count > 0);
     }
+
+    async fn collect_prefix<S: BlockStore + Sync + 'static>(
+        tree: &Mst<S>,
+        prefix: &str,
+    ) -> Vec<String> {
+        let mut cursor = MstCursor::new(tree.clone());
+        cursor.seek_prefix(prefix).await.unwrap();
+        let mut keys = Vec::new();
+        while let Some((key, _cid)) = cursor.next_in_prefix().await.unwrap() {
+            keys.push(key.to_string());
+        }
+        keys
+    }
+
+    #[tokio::test]
+    async fn test_cursor_prefix() {
+        let storage = Arc::new(MemoryBlockStore::new());
+        let mut tree = Mst::new(storage);
+        let mut all = Vec::new();
+        for collection in [
+            "app.bsky.feed.like",
+            "app.bsky.feed.post",
+            "app.bsky.graph.follow",
+        ] {
+            for i in 0..50u8 {
+                let key = format!("{}/{:04}", collection, i);
+                tree = tree.add(&key, test_cid(i)).await.unwrap();
+                all.push(key);
+            }
+        }
+        all.sort();
+
+        let posts = collect_prefix(&tree, "app.bsky.feed.post/").await;
+        assert_eq!(posts.len(), 50);
+        assert!(posts.iter().all(|k| k.starts_with("app.bsky.feed.post/")));
+        assert!(posts.is_sorted());
+
+        let feed = collect_prefix(&tree, "app.bsky.feed.").await;
+        assert_eq!(feed, all[..100]);
+
+        // Empty prefix iterates everything
+        assert_eq!(collect_prefix(&tree, "").await, all);
+
+        // Prefixes that match nothing, inside, before, and after the key range
+        let reposts = collect_prefix(&tree, "app.bsky.feed.repost/").await;
+        assert!(reposts.is_empty());
+        assert!(collect_prefix(&tree, "aaa").await.is_empty());
+        assert!(collect_prefix(&tree, "zzz").await.is_empty());
+
+        // A full key matches itself
+        assert_eq!(
+            collect_prefix(&tree, "app.bsky.feed.post/0007").await,
+            vec!["app.bsky.feed.post/0007"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cursor_prefix_without_seek() {
+        let storage = Arc::new(MemoryBlockStore::new());
+        let tree = Mst::new(storage.clone());
+        let mut cursor = MstCursor::new(tree);
+        assert_eq!(cursor.next_in_prefix().await.unwrap(), None);
+
+        let tree = Mst::new(storage);
+        let tree = tree.add("com.example.test/a", test_cid(1)).await.unwrap();
+        let tree = tree.add("com.example.test/b", test_cid(2)).await.unwrap();
+        let mut cursor = MstCursor::new(tree);
+        let mut keys = Vec::new();
+        while let Some((key, _cid)) = cursor.next_in_prefix().await.unwrap() {
+            keys.push(key);
+        }
+        assert_eq!(keys, vec!["com.example.test/a", "com.example.test/b"]);
+    }
 }
@@ -5,8 +5,9 @@ pub mod tree;
 pub mod util;
 pub mod diff;
 pub mod cursor;
+pub mod proof;
 
 pub use node::{NodeData, NodeEntry, TreeEntry};
-pub use tree::{Mst, WriteOp, RecordWriteOp, VerifiedWriteOp};
+pub use tree::{Mst, MstSnapshot, WriteOp, RecordWriteOp, VerifiedWriteOp};
 pub use diff::MstDiff;
 pub use cursor::{MstCursor, CursorPosition};
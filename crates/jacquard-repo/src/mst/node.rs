@@ -6,6 +6,7 @@ use bytes::Bytes;
 use cid::Cid as IpldCid;
 use smol_str::SmolStr;
 
+use crate::error::{MstError, Result};
 use crate::{mst::Mst, storage::BlockStore};
 
 /// Entry in an MST node - either a subtree or a leaf
@@ -137,3 +138,79 @@ pub struct NodeData {
     #[serde(rename = "e")]
     pub entries: Vec<TreeEntry>,
 }
+
+impl NodeData {
+    /// Build node data from full keys, recomputing the prefix compression
+    ///
+    /// `entries` are `(key, value, tree)` in key order, where `tree` is the subtree
+    /// to the right of that key. Each key is stored as the suffix after the bytes it
+    /// shares with the previous key in the node (the first key shares nothing), which
+    /// is the canonical encoding used by the reference implementation. Keys are
+    /// ASCII per the MST key rules, so byte and character prefixes coincide.
+    ///
+    /// Fails if keys are out of order or a shared prefix exceeds `u8::MAX`.
+    pub fn from_entries(
+        left: Option<IpldCid>,
+        entries: impl IntoIterator<Item = (SmolStr, IpldCid, Option<IpldCid>)>,
+    ) -> Result<Self> {
+        let mut data = NodeData {
+            left,
+            entries: Vec::new(),
+        };
+        let mut last_key = SmolStr::default();
+        for (key, value, tree) in entries {
+            if !data.entries.is_empty() && key <= last_key {
+                return Err(MstError::InvalidNode(format!(
+                    "Keys out of order in node: {} after {}",
+                    key, last_key
+                ))
+                .into());
+            }
+            let prefix_len = last_key
+                .bytes()
+                .zip(key.bytes())
+                .take_while(|(a, b)| a == b)
+                .count();
+            data.entries.push(TreeEntry {
+                key_suffix: Bytes::copy_from_slice(&key.as_bytes()[prefix_len..]),
+                prefix_len: prefix_len.try_into().map_err(|_| {
+                    MstError::InvalidNode(format!("Prefix length {} exceeds u8::MAX", prefix_len))
+                })?,
+                tree,
+                value,
+            });
+            last_key = key;
+        }
+        Ok(data)
+    }
+
+    /// Entries with their full keys reconstructed from the prefix compression
+    ///
+    /// Returns `(key, value, tree)` for each entry, the inverse of
+    /// [`from_entries`](Self::from_entries). The `left` pointer is not included.
+    ///
+    /// Fails if a prefix length runs past the previous key or a key is not UTF-8.
+    pub fn entries_decoded(&self) -> Result<Vec<(SmolStr, IpldCid, Option<IpldCid>)>> {
+        let mut out = Vec::with_capacity(self.entries.len());
+        let mut last_key = String::new();
+        for entry in &self.entries {
+            let prefix = last_key
+                .as_bytes()
+                .get(..entry.prefix_len as usize)
+                .ok_or_else(|| {
+                    MstError::InvalidNode(format!(
+                        "Prefix length {} exceeds previous key length {}",
+                        entry.prefix_len,
+                        last_key.len()
+                    ))
+                })?;
+            let mut key = prefix.to_vec();
+            key.extend_from_slice(&entry.key_suffix);
+            let key = String::from_utf8(key)
+                .map_err(|e| MstError::InvalidNode(format!("Invalid UTF-8 in key: {}", e)))?;
+            out.push((SmolStr::new(&key), entry.value, entry.tree));
+            last_key = key;
+        }
+        Ok(out)
+    }
+}
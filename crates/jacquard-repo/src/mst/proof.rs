@@ -0,0 +1,184 @@
+//! Store-free verification of single-key MST proofs
+//!
+//! A proof is the chain of MST node blocks from the root down to the node that holds the key,
+//! or, for absence, down to the node where the key would have to be. Each node block carries
+//! the CIDs of its sibling subtrees, so the chain alone is enough to rehash the path back up
+//! to a trusted root (e.g. the `data` CID of a verified commit).
+//!
+//! Proofs are produced by [`Mst::inclusion_proof`](super::Mst::inclusion_proof) and
+//! [`Mst::exclusion_proof`](super::Mst::exclusion_proof).
+
+use super::node::NodeData;
+use super::util::{compute_cid, validate_key};
+use crate::error::{RepoError, Result};
+use bytes::Bytes;
+use cid::Cid as IpldCid;
+
+/// Check that `proof` shows `key` mapping to `value` under `root`
+///
+/// Returns `Ok(false)` if the proof instead shows the key absent or mapped to another CID.
+/// Fails if the proof is malformed: a block on the path is missing, a block doesn't hash to
+/// its CID, or a node doesn't decode.
+pub fn verify_inclusion(
+    root: &IpldCid,
+    key: &str,
+    value: &IpldCid,
+    proof: &[(IpldCid, Bytes)],
+) -> Result<bool> {
+    Ok(lookup(root, key, proof)? == Some(*value))
+}
+
+/// Check that `proof` shows `key` absent under `root`
+///
+/// Returns `Ok(false)` if the proof instead shows the key present. Fails under the same
+/// conditions as [`verify_inclusion`].
+pub fn verify_exclusion(root: &IpldCid, key: &str, proof: &[(IpldCid, Bytes)]) -> Result<bool> {
+    Ok(lookup(root, key, proof)?.is_none())
+}
+
+/// Walk the proof from `root` towards `key`, returning the value it maps to, if any
+fn lookup(root: &IpldCid, key: &str, proof: &[(IpldCid, Bytes)]) -> Result<Option<IpldCid>> {
+    validate_key(key)?;
+
+    let mut cid = *root;
+    loop {
+        let (_, bytes) = proof
+            .iter()
+            .find(|(block_cid, _)| *block_cid == cid)
+            .ok_or_else(|| RepoError::not_found("proof block", cid))?;
+        if compute_cid(bytes)? != cid {
+            return Err(RepoError::cid_mismatch(format!(
+                "proof block does not hash to {}",
+                cid
+            )));
+        }
+
+        let node: NodeData =
+            serde_ipld_dagcbor::from_slice(bytes).map_err(RepoError::serialization)?;
+
+        let entries = node.entries_decoded()?;
+        if entries.windows(2).any(|pair| pair[0].0 >= pair[1].0) {
+            return Err(RepoError::invalid_mst(format!(
+                "Keys out of order in proof node {}",
+                cid
+            )));
+        }
+
+        // The key is either a leaf here or inside the subtree covering the gap it falls into
+        let mut gap = node.left;
+        for (entry_key, entry_value, tree) in entries {
+            if entry_key.as_str() == key {
+                return Ok(Some(entry_value));
+            }
+            if entry_key.as_str() > key {
+                break;
+            }
+            gap = tree;
+        }
+
+        match gap {
+            Some(subtree) => cid = subtree,
+            None => return Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mst::Mst;
+    use crate::storage::memory::MemoryBlockStore;
+    use std::sync::Arc;
+
+    fn test_cid(n: u32) -> IpldCid {
+        compute_cid(&n.to_be_bytes()).unwrap()
+    }
+
+    fn key(n: u32) -> String {
+        format!("com.example.record/{:05}", n)
+    }
+
+    /// Tree holding the even keys 0..400, so odd keys fall between existing leaves
+    async fn test_tree() -> Mst<MemoryBlockStore> {
+        let mut tree = Mst::new(Arc::new(MemoryBlockStore::new()));
+        for n in (0..400).step_by(2) {
+            tree = tree.add(&key(n), test_cid(n)).await.unwrap();
+        }
+        tree
+    }
+
+    #[tokio::test]
+    async fn test_inclusion_proofs() {
+        let tree = test_tree().await;
+        let root = tree.get_pointer().await.unwrap();
+
+        let mut max_depth = 0;
+        for n in (0..400).step_by(2) {
+            let proof = tree.inclusion_proof(&key(n)).await.unwrap();
+            assert_eq!(proof[0].0, root);
+            max_depth = max_depth.max(proof.len());
+
+            assert!(verify_inclusion(&root, &key(n), &test_cid(n), &proof).unwrap());
+            assert!(!verify_inclusion(&root, &key(n), &test_cid(n + 1), &proof).unwrap());
+            assert!(!verify_exclusion(&root, &key(n), &proof).unwrap());
+        }
+        // Make sure the tree was deep enough to exercise subtree descent
+        assert!(max_depth > 1);
+
+        assert!(tree.inclusion_proof(&key(1)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_exclusion_proofs() {
+        let tree = test_tree().await;
+        let root = tree.get_pointer().await.unwrap();
+
+        // Between every pair of leaves, so every node boundary is covered, plus both ends
+        let absent = (1..400)
+            .step_by(2)
+            .map(key)
+            .chain(["a/before".to_string(), "zzz/after".to_string()]);
+        for k in absent {
+            let proof = tree.exclusion_proof(&k).await.unwrap();
+            assert_eq!(proof[0].0, root);
+            assert!(verify_exclusion(&root, &k, &proof).unwrap());
+            assert!(!verify_inclusion(&root, &k, &test_cid(0), &proof).unwrap());
+        }
+
+        assert!(tree.exclusion_proof(&key(2)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_exclusion_proof_empty_tree() {
+        let tree = Mst::new(Arc::new(MemoryBlockStore::new()));
+        let root = tree.get_pointer().await.unwrap();
+
+        let proof = tree.exclusion_proof(&key(0)).await.unwrap();
+        assert_eq!(proof.len(), 1);
+        assert!(verify_exclusion(&root, &key(0), &proof).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_bad_proofs() {
+        let tree = test_tree().await;
+        let root = tree.get_pointer().await.unwrap();
+        let proof = tree.inclusion_proof(&key(100)).await.unwrap();
+
+        // Proof for a different root
+        let other = compute_cid(b"other").unwrap();
+        assert!(verify_inclusion(&other, &key(100), &test_cid(100), &proof).is_err());
+
+        // Missing block along the path
+        if proof.len() > 1 {
+            let truncated = &proof[..proof.len() - 1];
+            assert!(verify_inclusion(&root, &key(100), &test_cid(100), truncated).is_err());
+        }
+
+        // Block whose bytes don't match its CID
+        let mut tampered = proof.clone();
+        let mut bytes = tampered[0].1.to_vec();
+        *bytes.last_mut().unwrap() ^= 1;
+        tampered[0].1 = Bytes::from(bytes);
+        assert!(verify_inclusion(&root, &key(100), &test_cid(100), &tampered).is_err());
+    }
+}
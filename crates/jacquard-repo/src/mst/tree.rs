@@ -1,5 +1,6 @@
 //! Immutable Merkle Search Tree operations
 
+use super::cursor::MstCursor;
 use super::node::NodeEntry;
 use super::util;
 use crate::error::{RepoError, Result};
@@ -11,7 +12,7 @@ use core::fmt;
 use jacquard_common::types::recordkey::Rkey;
 use jacquard_common::types::string::{Nsid, RecordKey};
 use jacquard_common::types::value::RawData;
-use n0_future::try_join_all;
+use n0_future::{Stream, try_join_all};
 use smol_str::SmolStr;
 use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
@@ -57,6 +58,17 @@ pub enum WriteOp {
     },
 }
 
+impl WriteOp {
+    /// Get the record key (collection/rkey) this operation applies to
+    pub fn key(&self) -> &str {
+        match self {
+            WriteOp::Create { key, .. } => key,
+            WriteOp::Update { key, .. } => key,
+            WriteOp::Delete { key, .. } => key,
+        }
+    }
+}
+
 /// Record write operation with inline data
 ///
 /// Used for high-level record operations where the actual record data
@@ -199,6 +211,45 @@ pub struct Mst<S> {
     layer: Option<usize>,
 }
 
+/// Saved MST state for cheap copy-on-write branching
+///
+/// Just a root CID and the store holding its blocks. Taking a snapshot copies no blocks,
+/// and every tree restored from it shares the same underlying nodes: writes to a branch
+/// only add new nodes for the changed paths. Discarding a branch is free - its new
+/// blocks are simply unreferenced, and can be garbage collected later.
+///
+/// ```rust,ignore
+/// let snapshot = mst.snapshot().await?;
+/// let speculative = mst.add("app.bsky.feed.post/3k2a", cid).await?;
+/// // ... decide the write was a mistake ...
+/// let mst = Mst::from_snapshot(snapshot.storage().clone(), &snapshot);
+/// ```
+#[derive(Clone)]
+pub struct MstSnapshot<S> {
+    root: IpldCid,
+    storage: Arc<S>,
+}
+
+impl<S> MstSnapshot<S> {
+    /// Root CID of the saved tree
+    pub fn root(&self) -> IpldCid {
+        self.root
+    }
+
+    /// Store the snapshot's blocks were persisted to
+    pub fn storage(&self) -> &Arc<S> {
+        &self.storage
+    }
+}
+
+impl<S> fmt::Debug for MstSnapshot<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MstSnapshot")
+            .field("root", &self.root)
+            .finish_non_exhaustive()
+    }
+}
+
 impl<S: BlockStore + Sync + 'static> Mst<S> {
     /// Create empty MST
     pub fn new(storage: Arc<S>) -> Self {
@@ -238,6 +289,20 @@ impl<S: BlockStore + Sync + 'static> Mst<S> {
         Ok(mst)
     }
 
+    /// Create MST with existing entries, without computing its CID
+    ///
+    /// The pointer starts out stale, like a tree returned by [`new_tree`](Self::new_tree),
+    /// so the node is only hashed once [`get_pointer`](Self::get_pointer) is called.
+    fn create_lazy(storage: Arc<S>, entries: Vec<NodeEntry<S>>, layer: Option<usize>) -> Self {
+        Self {
+            storage,
+            entries: Arc::new(RwLock::new(Some(entries))),
+            pointer: Arc::new(RwLock::new(IpldCid::default())),
+            outdated_pointer: Arc::new(RwLock::new(true)),
+            layer,
+        }
+    }
+
     /// Load MST from CID (lazy)
     ///
     /// Doesn't actually load from storage until entries are accessed.
@@ -359,10 +424,25 @@ impl<S: BlockStore + Sync + 'static> Mst<S> {
     }
 
     /// Get root CID (alias for get_pointer)
+    ///
+    /// The CID is computed lazily and cached: the first call after a modification hashes
+    /// the changed nodes, later calls on the same tree return the cached value. Tree
+    /// operations return a new `Mst` whose pointer is stale until this is called.
     pub async fn root(&self) -> Result<IpldCid> {
         self.get_pointer().await
     }
 
+    /// Get the root CID for a commit's `data` field
+    ///
+    /// A tree loaded from a CID, or whose root was already computed, returns the known CID
+    /// without serializing or hashing anything. After writes, only the changed nodes are
+    /// serialized and hashed, and the result is cached on the tree, so later calls are
+    /// free until the next modification. Errors come only from serializing changed nodes.
+    /// Nothing is written to storage; use [`persist`](Self::persist) for that.
+    pub async fn root_cid(&self) -> Result<IpldCid> {
+        self.get_pointer().await
+    }
+
     /// Get shared reference to the block storage
     ///
     /// Useful for CAR export and other operations that need direct storage access.
@@ -370,6 +450,28 @@ impl<S: BlockStore + Sync + 'static> Mst<S> {
         &self.storage
     }
 
+    /// Snapshot the current tree state
+    ///
+    /// Persists any nodes not yet in storage (so the snapshot can always be restored),
+    /// then records the root CID alongside the shared store. No existing blocks are
+    /// copied. See [`MstSnapshot`].
+    pub async fn snapshot(&self) -> Result<MstSnapshot<S>> {
+        let root = self.persist().await?;
+        Ok(MstSnapshot {
+            root,
+            storage: self.storage.clone(),
+        })
+    }
+
+    /// Restore a tree from a snapshot
+    ///
+    /// `storage` is normally the snapshot's own store (`snapshot.storage().clone()`), but
+    /// any store holding the snapshot's blocks works. Loading is lazy, as with
+    /// [`load`](Self::load).
+    pub fn from_snapshot(storage: Arc<S>, snapshot: &MstSnapshot<S>) -> Self {
+        Self::load(storage, snapshot.root, None)
+    }
+
     /// Get the layer of this node
     ///
     /// Layer is the maximum layer of any leaf key in this node.
@@ -581,7 +683,11 @@ impl<S: BlockStore + Sync + 'static> Mst<S> {
                     new_entries.push(NodeEntry::Tree(r));
                 }
 
-                Mst::create(self.storage.clone(), new_entries, Some(key_layer)).await
+                Ok(Mst::create_lazy(
+                    self.storage.clone(),
+                    new_entries,
+                    Some(key_layer),
+                ))
             }
         })
     }
@@ -882,12 +988,11 @@ impl<S: BlockStore + Sync + 'static> Mst<S> {
     pub async fn create_parent(self) -> Result<Mst<S>> {
         let layer = self.get_layer().await?;
 
-        Mst::create(
+        Ok(Mst::create_lazy(
             self.storage.clone(),
             vec![NodeEntry::Tree(self)],
             Some(layer + 1),
-        )
-        .await
+        ))
     }
 
     /// Replace entry at index with [left?, leaf, right?]
@@ -964,6 +1069,120 @@ impl<S: BlockStore + Sync + 'static> Mst<S> {
         })
     }
 
+    /// Total number of leaf entries in the tree
+    ///
+    /// Counts node by node like [`layer_histogram`](Self::layer_histogram), without
+    /// collecting the leaves.
+    pub async fn len(&self) -> Result<usize> {
+        Ok(self.layer_histogram().await?.values().sum())
+    }
+
+    /// Number of leaf entries at each MST layer
+    ///
+    /// Nodes already in memory are read from their cached entries; everything else is
+    /// decoded straight from the block store and dropped once counted, without being cached
+    /// on the tree. Memory use is bounded by the subtrees waiting to be visited rather than
+    /// the number of records.
+    pub async fn layer_histogram(&self) -> Result<BTreeMap<u8, usize>> {
+        enum Pending<S> {
+            Node(Mst<S>),
+            Block(IpldCid),
+        }
+
+        let mut histogram = BTreeMap::new();
+        let mut pending = vec![Pending::Node(self.clone())];
+        while let Some(next) = pending.pop() {
+            let (first_key, leaves) = match next {
+                Pending::Node(node) => {
+                    let entries = node.entries.read().await.clone();
+                    let Some(entries) = entries else {
+                        pending.push(Pending::Block(*node.pointer.read().await));
+                        continue;
+                    };
+                    let mut first_key = None;
+                    let mut leaves = 0;
+                    for entry in entries {
+                        match entry {
+                            NodeEntry::Tree(subtree) => pending.push(Pending::Node(subtree)),
+                            NodeEntry::Leaf { key, .. } => {
+                                first_key.get_or_insert(key);
+                                leaves += 1;
+                            }
+                        }
+                    }
+                    (first_key, leaves)
+                }
+                Pending::Block(cid) => {
+                    let bytes = self.storage.get(&cid).await?.ok_or_else(|| {
+                        RepoError::not_found("MST node", cid)
+                            .with_help("MST node missing from storage - ensure all blocks were properly persisted or that the tree CID is correct")
+                    })?;
+                    let node: super::node::NodeData = serde_ipld_dagcbor::from_slice(&bytes)
+                        .map_err(|e| {
+                            RepoError::serialization(e).with_context(format!(
+                                "deserializing MST node from storage: {}",
+                                cid
+                            ))
+                        })?;
+                    pending.extend(
+                        node.left
+                            .into_iter()
+                            .chain(node.entries.iter().filter_map(|entry| entry.tree))
+                            .map(Pending::Block),
+                    );
+                    // The first key in a node is stored whole
+                    let first_key = node
+                        .entries
+                        .first()
+                        .map(|entry| {
+                            std::str::from_utf8(&entry.key_suffix)
+                                .map(SmolStr::new)
+                                .map_err(|e| {
+                                    RepoError::invalid_mst(format!("Invalid UTF-8 in key: {}", e))
+                                })
+                        })
+                        .transpose()?;
+                    (first_key, node.entries.len())
+                }
+            };
+
+            // Every key in a node shares the node's layer
+            if let Some(key) = first_key {
+                let layer = util::layer_for_key(&key) as u8;
+                *histogram.entry(layer).or_default() += leaves;
+            }
+        }
+
+        Ok(histogram)
+    }
+
+    /// Stream leaf entries whose keys start with `prefix`, in lexicographic order
+    ///
+    /// Walks the tree lazily with an [`MstCursor`], skipping subtrees that sort entirely
+    /// before the prefix and stopping at the first key past it. An empty prefix streams
+    /// every leaf.
+    pub fn leaves_with_prefix(
+        &self,
+        prefix: impl Into<SmolStr>,
+    ) -> impl Stream<Item = Result<(SmolStr, IpldCid)>> + Send + 'static {
+        let prefix = prefix.into();
+        let cursor = MstCursor::new(self.clone());
+        n0_future::stream::unfold((Some(cursor), Some(prefix)), |(cursor, seek)| async move {
+            let mut cursor = cursor?;
+            let next = async {
+                if let Some(prefix) = seek {
+                    cursor.seek_prefix(&prefix).await?;
+                }
+                cursor.next_in_prefix().await
+            };
+            match next.await {
+                Ok(Some(leaf)) => Some((Ok(leaf), (Some(cursor), None))),
+                Ok(None) => None,
+                Err(e) => Some((Err(e), (None, None))),
+            }
+        })
+    }
+
     /// Copy tree with same entries (marking pointer as outdated)
     ///
     /// Internal helper for creating modified tree copies.
@@ -1082,6 +1301,71 @@ impl<S: BlockStore + Sync + 'static> Mst<S> {
         Ok(tree)
     }
 
+    /// Apply a batch of write operations, hashing each changed node once (returns new tree)
+    ///
+    /// Operations are sorted by key (keeping the given order for repeated keys) and applied
+    /// to an in-memory copy of the tree. Nodes built along the way aren't hashed; the new
+    /// root CID is computed once at the end, which serializes every node touched by the
+    /// batch exactly once and leaves untouched subtrees alone. The result is identical to
+    /// applying the operations one at a time.
+    ///
+    /// # Validation
+    ///
+    /// - Create: errors if key already exists
+    /// - Update: errors if key doesn't exist, or if `prev` is given and doesn't match
+    /// - Delete: errors if key doesn't exist, or if `prev` is given and doesn't match
+    ///
+    /// Checks run against the tree as updated by the operations before them, so a batch
+    /// can create a key and then update it.
+    pub async fn apply_batch(&self, mut ops: Vec<WriteOp>) -> Result<Mst<S>> {
+        ops.sort_by(|a, b| a.key().cmp(b.key()));
+
+        let mut tree = self.clone();
+        for op in &ops {
+            let current = tree.get(op.key()).await?;
+            tree = match op {
+                WriteOp::Create { key, cid } => {
+                    if current.is_some() {
+                        return Err(RepoError::invalid_mst(format!(
+                            "Cannot create: key already exists: {}",
+                            key
+                        )));
+                    }
+                    tree.add(key.as_str(), *cid).await?
+                }
+                WriteOp::Update { key, cid, prev } => {
+                    let current =
+                        current.ok_or_else(|| RepoError::not_found("key", key.as_str()))?;
+                    if let Some(prev) = prev
+                        && *prev != current
+                    {
+                        return Err(RepoError::cid_mismatch(format!(
+                            "Update prev CID mismatch for key {}: expected {}, got {}",
+                            key, prev, current
+                        )));
+                    }
+                    tree.add(key.as_str(), *cid).await?
+                }
+                WriteOp::Delete { key, prev } => {
+                    let current =
+                        current.ok_or_else(|| RepoError::not_found("key", key.as_str()))?;
+                    if let Some(prev) = prev
+                        && *prev != current
+                    {
+                        return Err(RepoError::cid_mismatch(format!(
+                            "Delete prev CID mismatch for key {}: expected {}, got {}",
+                            key, prev, current
+                        )));
+                    }
+                    tree.delete(key.as_str()).await?
+                }
+            };
+        }
+
+        tree.get_pointer().await?;
+        Ok(tree)
+    }
+
     /// Collect all blocks that need persisting
     ///
     /// Recursively walks the tree, serializing nodes and collecting blocks
@@ -1311,6 +1595,66 @@ impl<S: BlockStore + Sync + 'static> Mst<S> {
         })
     }
 
+    /// Blocks proving that `key` is in the tree
+    ///
+    /// Returns the serialized MST nodes on the path from the root down to the node holding
+    /// `key`, root first. Each node carries its sibling entries and subtree CIDs, so the chain
+    /// is all [`proof::verify_inclusion`](super::proof::verify_inclusion) needs to rehash the
+    /// path up to the root. Record blocks are not included.
+    ///
+    /// Fails with a not-found error if `key` is absent; prove that with
+    /// [`exclusion_proof`](Self::exclusion_proof) instead.
+    pub async fn inclusion_proof(&self, key: &str) -> Result<Vec<(IpldCid, Bytes)>> {
+        let (proof, value) = self.proof_path(key).await?;
+        if value.is_none() {
+            return Err(RepoError::not_found("key", key));
+        }
+        Ok(proof)
+    }
+
+    /// Blocks proving that `key` is not in the tree
+    ///
+    /// Returns the serialized MST nodes from the root down to the node where `key` would have
+    /// to be, root first: the last node has no subtree covering the gap the key falls into.
+    /// Check with [`proof::verify_exclusion`](super::proof::verify_exclusion).
+    ///
+    /// Fails if `key` is present.
+    pub async fn exclusion_proof(&self, key: &str) -> Result<Vec<(IpldCid, Bytes)>> {
+        let (proof, value) = self.proof_path(key).await?;
+        if value.is_some() {
+            return Err(RepoError::already_exists("key", key));
+        }
+        Ok(proof)
+    }
+
+    /// Serialized nodes along the path to `key`, plus the key's value if it was found
+    async fn proof_path(&self, key: &str) -> Result<(Vec<(IpldCid, Bytes)>, Option<IpldCid>)> {
+        validate_key(key)?;
+
+        let mut proof = Vec::new();
+        let mut node = self.clone();
+        loop {
+            proof.push(node.serialize_tree().await?);
+
+            let entries = node.get_entries().await?;
+            let index = Self::find_gt_or_equal_leaf_index_in(&entries, key);
+            if let Some(NodeEntry::Leaf {
+                key: leaf_key,
+                value,
+            }) = entries.get(index)
+                && leaf_key.as_str() == key
+            {
+                return Ok((proof, Some(*value)));
+            }
+
+            // Otherwise the key can only be in the subtree just before that index
+            match index.checked_sub(1).and_then(|i| entries.get(i)) {
+                Some(NodeEntry::Tree(subtree)) => node = subtree.clone(),
+                _ => return Ok((proof, None)),
+            }
+        }
+    }
+
     /// Write all MST and record blocks to CAR writer
     ///
     /// Streams blocks directly to the writer as the tree is walked:
@@ -1773,6 +2117,57 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_root_cid_cached() {
+        let storage = Arc::new(MemoryBlockStore::new());
+        let mst = Mst::new(storage.clone());
+        let updated = mst.add("com.example.test/key", test_cid(1)).await.unwrap();
+
+        // A write leaves the root stale; root_cid computes it and caches the result
+        assert!(*updated.outdated_pointer.read().await);
+        let root = updated.root_cid().await.unwrap();
+        assert!(!*updated.outdated_pointer.read().await);
+        assert_eq!(root, updated.root().await.unwrap());
+        assert_eq!(updated.root_cid().await.unwrap(), root);
+
+        // Matches the persisted root, and a tree loaded from it knows it already
+        assert_eq!(updated.persist().await.unwrap(), root);
+        let loaded = Mst::load(storage, root, None);
+        assert!(!*loaded.outdated_pointer.read().await);
+        assert_eq!(loaded.root_cid().await.unwrap(), root);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_restore() {
+        let storage = Arc::new(MemoryBlockStore::new());
+        let mst = Mst::new(storage.clone());
+        let mst = mst.add("com.example.test/a", test_cid(1)).await.unwrap();
+        let mst = mst.add("com.example.test/b", test_cid(2)).await.unwrap();
+
+        let snapshot = mst.snapshot().await.unwrap();
+        let blocks_before = storage.len();
+
+        // Speculative writes on a branch leave the snapshot untouched
+        let branch = mst.add("com.example.test/c", test_cid(3)).await.unwrap();
+        let branch = branch.delete("com.example.test/a").await.unwrap();
+        branch.persist().await.unwrap();
+        assert!(storage.len() > blocks_before);
+        assert_ne!(branch.root().await.unwrap(), snapshot.root());
+
+        let restored = Mst::from_snapshot(snapshot.storage().clone(), &snapshot);
+        assert_eq!(restored.root().await.unwrap(), snapshot.root());
+        assert_eq!(
+            restored.get("com.example.test/a").await.unwrap(),
+            Some(test_cid(1))
+        );
+        assert_eq!(restored.get("com.example.test/c").await.unwrap(), None);
+
+        // Snapshotting an already-stored tree writes nothing
+        let len = storage.len();
+        restored.snapshot().await.unwrap();
+        assert_eq!(storage.len(), len);
+    }
+
     #[tokio::test]
     async fn test_add_multiple_keys() {
         let storage = Arc::new(MemoryBlockStore::new());
@@ -2250,4 +2645,156 @@ mod tests {
             );
         }
     }
+
+    #[tokio::test]
+    async fn test_apply_batch_matches_sequential() {
+        use rand::{Rng, SeedableRng, rngs::StdRng};
+
+        let mut rng = StdRng::seed_from_u64(0x6d7374);
+        let storage = Arc::new(MemoryBlockStore::new());
+        let key = |n: u32| SmolStr::new(format!("com.example.test/{:06}", n));
+
+        // Start from a persisted tree so the batch touches loaded and unloaded nodes
+        let mut base = Mst::new(storage.clone());
+        for n in 0..500 {
+            base = base
+                .add(&key(n * 2), test_cid((n % 250) as u8))
+                .await
+                .unwrap();
+        }
+        let root = base.persist().await.unwrap();
+        let base = Mst::load(storage.clone(), root, None);
+
+        // 1000 valid ops over existing and new keys, at most one per key
+        let mut existing: Vec<u32> = (0..500).map(|n| n * 2).collect();
+        let mut fresh: Vec<u32> = (0..1500).filter(|n| n % 2 == 1 || *n >= 1000).collect();
+        let mut ops = Vec::new();
+        while ops.len() < 1000 {
+            let cid = test_cid(rng.r#gen());
+            match rng.gen_range(0..3) {
+                0 if !fresh.is_empty() => {
+                    let n = fresh.swap_remove(rng.gen_range(0..fresh.len()));
+                    ops.push(WriteOp::Create { key: key(n), cid });
+                }
+                1 if !existing.is_empty() => {
+                    let n = existing.swap_remove(rng.gen_range(0..existing.len()));
+                    ops.push(WriteOp::Update {
+                        key: key(n),
+                        cid,
+                        prev: None,
+                    });
+                }
+                2 if !existing.is_empty() => {
+                    let n = existing.swap_remove(rng.gen_range(0..existing.len()));
+                    ops.push(WriteOp::Delete {
+                        key: key(n),
+                        prev: Some(test_cid(((n / 2) % 250) as u8)),
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        let mut sequential = base.clone();
+        for op in &ops {
+            sequential = match op {
+                WriteOp::Create { key, cid } | WriteOp::Update { key, cid, .. } => {
+                    sequential.add(key, *cid).await.unwrap()
+                }
+                WriteOp::Delete { key, .. } => sequential.delete(key).await.unwrap(),
+            };
+        }
+
+        let batched = base.apply_batch(ops).await.unwrap();
+        assert_eq!(
+            batched.root().await.unwrap(),
+            sequential.root().await.unwrap()
+        );
+        assert_eq!(
+            batched.leaves().await.unwrap(),
+            sequential.leaves().await.unwrap()
+        );
+        // The base tree is untouched
+        assert_eq!(base.root().await.unwrap(), root);
+    }
+
+    #[tokio::test]
+    async fn test_apply_batch_validates_ops() {
+        let storage = Arc::new(MemoryBlockStore::new());
+        let mst = Mst::new(storage)
+            .add("com.example.test/a", test_cid(1))
+            .await
+            .unwrap();
+
+        let create_existing = vec![WriteOp::Create {
+            key: SmolStr::new("com.example.test/a"),
+            cid: test_cid(2),
+        }];
+        assert!(mst.apply_batch(create_existing).await.is_err());
+
+        let update_missing = vec![WriteOp::Update {
+            key: SmolStr::new("com.example.test/b"),
+            cid: test_cid(2),
+            prev: None,
+        }];
+        assert!(mst.apply_batch(update_missing).await.is_err());
+
+        let wrong_prev = vec![WriteOp::Delete {
+            key: SmolStr::new("com.example.test/a"),
+            prev: Some(test_cid(9)),
+        }];
+        assert!(mst.apply_batch(wrong_prev).await.is_err());
+
+        // Later ops see earlier ones, including for the same key
+        let create_then_update = vec![
+            WriteOp::Create {
+                key: SmolStr::new("com.example.test/b"),
+                cid: test_cid(2),
+            },
+            WriteOp::Update {
+                key: SmolStr::new("com.example.test/b"),
+                cid: test_cid(3),
+                prev: Some(test_cid(2)),
+            },
+        ];
+        let mst = mst.apply_batch(create_then_update).await.unwrap();
+        assert_eq!(
+            mst.get("com.example.test/b").await.unwrap(),
+            Some(test_cid(3))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_len_and_layer_histogram() {
+        let storage = Arc::new(MemoryBlockStore::new());
+        let empty = Mst::new(storage.clone());
+        assert_eq!(empty.len().await.unwrap(), 0);
+        assert!(empty.layer_histogram().await.unwrap().is_empty());
+
+        let mut tree = Mst::new(storage.clone());
+        let mut expected = BTreeMap::new();
+        for n in 0..1000u32 {
+            let key = format!("com.example.test/{:06}", n);
+            tree = tree.add(&key, test_cid((n % 250) as u8)).await.unwrap();
+            *expected
+                .entry(util::layer_for_key(&key) as u8)
+                .or_insert(0usize) += 1;
+        }
+        // Known keys spread over several layers
+        assert!(expected.len() > 1);
+
+        // Unpersisted tree, counted from memory
+        assert_eq!(tree.len().await.unwrap(), 1000);
+        assert_eq!(tree.layer_histogram().await.unwrap(), expected);
+
+        // Freshly loaded tree, counted from the block store
+        let root = tree.persist().await.unwrap();
+        let loaded = Mst::load(storage.clone(), root, None);
+        let histogram = loaded.layer_histogram().await.unwrap();
+        assert_eq!(histogram, expected);
+        assert_eq!(histogram.values().sum::<usize>(), 1000);
+        assert_eq!(loaded.len().await.unwrap(), 1000);
+        // Counting doesn't cache the tree's nodes
+        assert!(loaded.entries.read().await.is_none());
+    }
 }
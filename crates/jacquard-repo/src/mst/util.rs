@@ -16,7 +16,12 @@ use sha2::{Digest, Sha256};
 ///
 /// Uses SHA-256 hash and DAG-CBOR codec. Assumes data is already DAG-CBOR encoded.
 pub fn compute_cid(data: &[u8]) -> Result<IpldCid> {
-    use jacquard_common::types::crypto::{DAG_CBOR, SHA2_256};
+    compute_cid_with_codec(data, crate::DAG_CBOR_CID_CODEC)
+}
+
+/// Compute a SHA-256 CIDv1 over `data` with the given multicodec
+pub(crate) fn compute_cid_with_codec(data: &[u8], codec: u64) -> Result<IpldCid> {
+    use jacquard_common::types::crypto::SHA2_256;
 
     // SHA-256 hash
     let mut sha = Sha256::new();
@@ -26,8 +31,7 @@ pub fn compute_cid(data: &[u8]) -> Result<IpldCid> {
     let mh = multihash::Multihash::<64>::wrap(SHA2_256, hash.as_slice())
         .map_err(|e| MstError::InvalidNode(e.to_string()))?;
 
-    // Build CID with DAG-CBOR codec
-    Ok(IpldCid::new_v1(DAG_CBOR, mh))
+    Ok(IpldCid::new_v1(codec, mh))
 }
 
 /// Serialize node to DAG-CBOR and compute CID
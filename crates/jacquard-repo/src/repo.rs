@@ -5,21 +5,27 @@
 
 use crate::commit::firehose::{FirehoseCommit, RepoOp};
 use crate::commit::{Commit, SigningKey};
-use crate::error::{RepoError, Result};
+use crate::error::{ImportError, RepoError, Result};
 use crate::mst::{Mst, RecordWriteOp};
-use crate::storage::BlockStore;
+use crate::storage::{BlockStore, MemoryBlockStore};
 use bytes::Bytes;
 use cid::Cid as IpldCid;
+use ipld_core::ipld::Ipld;
 use jacquard_common::IntoStatic;
 use jacquard_common::types::cid::CidLink;
-use jacquard_common::types::recordkey::RecordKeyType;
-use jacquard_common::types::string::{Datetime, Did, Nsid, RecordKey, Tid};
+use jacquard_common::types::crypto::PublicKey;
+use jacquard_common::types::recordkey::{RecordKeyType, Rkey};
+use jacquard_common::types::string::{AtUri, Datetime, Did, Nsid, RecordKey, Tid};
 use jacquard_common::types::tid::Ticker;
-use smol_str::format_smolstr;
+use jacquard_common::types::value::{Data, RawData};
+use n0_future::{Stream, StreamExt};
+use smol_str::{SmolStr, format_smolstr};
 use std::collections::BTreeMap;
 use std::fmt::{self, Display, Formatter};
+#[cfg(not(target_family = "wasm"))]
 use std::path::Path;
 use std::sync::Arc;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 /// Commit data for repository updates
 ///
@@ -95,6 +101,45 @@ impl CommitData {
     }
 }
 
+/// Result of [`Repository::verify_parallel`]
+///
+/// Lists every block the repo references but storage doesn't have, rather than stopping at
+/// the first one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerifyReport {
+    /// Number of MST nodes found and walked
+    pub nodes_checked: usize,
+    /// Number of record blocks checked
+    pub records_checked: usize,
+    /// MST nodes referenced but missing from storage
+    ///
+    /// Subtrees below a missing node can't be walked, so their own missing blocks aren't
+    /// reported.
+    pub missing_nodes: Vec<IpldCid>,
+    /// Records whose block is missing from storage, as `(key, cid)`
+    pub missing_records: Vec<(SmolStr, IpldCid)>,
+}
+
+impl VerifyReport {
+    /// Whether every referenced block was found
+    pub fn is_complete(&self) -> bool {
+        self.missing_nodes.is_empty() && self.missing_records.is_empty()
+    }
+}
+
+/// Result of [`Repository::verify_import`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifiedRepo {
+    /// The repo DID, as named in the commit
+    pub did: Did<'static>,
+    /// CID of the root commit
+    pub commit_cid: IpldCid,
+    /// Revision of the root commit
+    pub rev: Tid,
+    /// Number of records in the MST
+    pub records: usize,
+}
+
 /// High-level repository operations
 ///
 /// Provides a convenient API over MST primitives for common repository workflows.
@@ -326,25 +371,326 @@ impl<S: BlockStore + Sync + 'static> Repository<S> {
         Ok(old_cid)
     }
 
+    /// Fetch and decode the record at `<collection>/<rkey>`
+    ///
+    /// Returns `None` if the MST has no such key. Unlike [`get_record`](Self::get_record),
+    /// which only looks up the CID, this reads the record block and decodes it, failing if
+    /// the block is missing from storage.
+    pub async fn read_record(&self, collection: &str, rkey: &str) -> Result<Option<Data<'static>>> {
+        let key = format_smolstr!("{}/{}", collection, rkey);
+        let Some(cid) = self.mst.get(&key).await? else {
+            return Ok(None);
+        };
+        let block = self.storage.get(&cid).await?.ok_or_else(|| {
+            RepoError::not_found("record block", cid)
+                .with_context(format!("record block not found: {} ({})", cid, key))
+        })?;
+        decode_record(&block, &key).map(Some)
+    }
+
+    /// Write a record and commit the change
+    ///
+    /// Creates the record if `<collection>/<rkey>` is free, otherwise replaces it. The write
+    /// goes through [`create_commit`](Self::create_commit) as a single op, signed with
+    /// `signing_key` and chained to the current commit, then is applied to storage.
+    ///
+    /// Returns the new commit CID.
+    pub async fn put_record<K>(
+        &mut self,
+        collection: &str,
+        rkey: &str,
+        record: BTreeMap<SmolStr, RawData<'_>>,
+        signing_key: &K,
+    ) -> Result<IpldCid>
+    where
+        K: SigningKey,
+    {
+        let (collection, rkey) = parse_record_path(collection, rkey)?;
+        let key = format_smolstr!("{}/{}", collection, rkey.as_ref());
+        let op = match self.mst.get(&key).await? {
+            Some(_) => RecordWriteOp::Update {
+                collection,
+                rkey,
+                record,
+                prev: None,
+            },
+            None => RecordWriteOp::Create {
+                collection,
+                rkey,
+                record,
+            },
+        };
+        self.commit_ops(&[op], signing_key).await
+    }
+
+    /// Delete a record and commit the change
+    ///
+    /// Returns the new commit CID, or `None` without committing anything if there was no
+    /// record at `<collection>/<rkey>`.
+    pub async fn remove_record<K>(
+        &mut self,
+        collection: &str,
+        rkey: &str,
+        signing_key: &K,
+    ) -> Result<Option<IpldCid>>
+    where
+        K: SigningKey,
+    {
+        let (collection, rkey) = parse_record_path(collection, rkey)?;
+        let key = format_smolstr!("{}/{}", collection, rkey.as_ref());
+        let Some(prev) = self.mst.get(&key).await? else {
+            return Ok(None);
+        };
+        let op = RecordWriteOp::Delete {
+            collection,
+            rkey,
+            prev: Some(prev),
+        };
+        self.commit_ops(&[op], signing_key).await.map(Some)
+    }
+
+    /// Sign a commit of `ops` on top of the current one and apply it
+    async fn commit_ops<K>(&mut self, ops: &[RecordWriteOp<'_>], signing_key: &K) -> Result<IpldCid>
+    where
+        K: SigningKey,
+    {
+        let did = self.did().clone().into_static();
+        let prev = Some(self.commit_cid);
+        let (_ops, commit_data) = self.create_commit(ops, &did, prev, signing_key).await?;
+        self.apply_commit(commit_data).await
+    }
+
+    /// Count the records in each collection
+    ///
+    /// Walks the MST once. Keys are sorted, so each collection's records are contiguous
+    /// and every collection name is validated as an NSID only once.
+    pub async fn collections(&self) -> Result<BTreeMap<Nsid<'static>, usize>> {
+        let mut counts = BTreeMap::new();
+        let mut current: Option<(Nsid<'static>, usize)> = None;
+
+        let leaves = self.mst.leaves_with_prefix("");
+        n0_future::pin!(leaves);
+        while let Some(leaf) = leaves.next().await {
+            let (key, _cid) = leaf?;
+            let (collection, _rkey) = key
+                .split_once('/')
+                .ok_or_else(|| RepoError::invalid_key(key.as_str()))?;
+
+            match &mut current {
+                Some((nsid, count)) if nsid.as_str() == collection => *count += 1,
+                _ => {
+                    let nsid = Nsid::new_owned(collection)
+                        .map_err(|_| RepoError::invalid_key(key.as_str()))?;
+                    if let Some((nsid, count)) = current.replace((nsid, 1)) {
+                        counts.insert(nsid, count);
+                    }
+                }
+            }
+        }
+        if let Some((nsid, count)) = current {
+            counts.insert(nsid, count);
+        }
+
+        Ok(counts)
+    }
+
+    /// Stream the records in a collection in rkey order
+    ///
+    /// Lazily walks only the part of the MST under `<collection>/`.
+    pub fn list_collection(
+        &self,
+        collection: &Nsid<'_>,
+    ) -> impl Stream<Item = Result<(RecordKey<Rkey<'static>>, IpldCid)>> + Send + 'static {
+        let prefix = format_smolstr!("{}/", collection);
+        let prefix_len = prefix.len();
+        self.mst.leaves_with_prefix(prefix).map(move |leaf| {
+            let (key, cid) = leaf?;
+            let rkey = Rkey::new_owned(&key[prefix_len..])
+                .map_err(|_| RepoError::invalid_key(key.as_str()))?;
+            Ok((RecordKey(rkey), cid))
+        })
+    }
+
+    /// Stream every record in the repo as `(at-uri, cid, value)`, in key order
+    ///
+    /// The at-URI is built from the repo DID and the MST key. Each record block is fetched
+    /// and decoded as it's reached, so memory use stays flat for large repos. A record
+    /// that's missing or fails to decode yields an error item carrying its at-URI, and
+    /// the stream carries on with the next record.
+    pub fn iter_records(
+        &self,
+    ) -> impl Stream<Item = Result<(AtUri<'static>, IpldCid, Data<'static>)>> + Send + 'static {
+        let did = self.did().clone().into_static();
+        self.record_leaves().map(move |leaf| {
+            let leaf = leaf?;
+            let uri = AtUri::builder()
+                .authority(did.clone())
+                .collection(leaf.collection)
+                .rkey(leaf.rkey.0)
+                .build()
+                .map_err(|e| RepoError::invalid_key(e.to_string()))?;
+            Ok((uri, leaf.cid, leaf.value))
+        })
+    }
+
+    /// Stream every record as `(collection/rkey, value)`, in key order
+    ///
+    /// Walks the MST with a cursor and reads each record block only when the consumer asks
+    /// for the next item, so a slow consumer holds back the walk instead of letting blocks
+    /// pile up in memory. Suited to exports such as NDJSON dumps for migrations. A record
+    /// that's missing or fails to decode yields an error item naming it, and the stream
+    /// carries on with the next record.
+    #[cfg(feature = "streaming")]
+    pub fn stream_records(
+        &self,
+    ) -> impl Stream<Item = Result<(SmolStr, Data<'static>)>> + Send + 'static {
+        self.record_leaves().map(|leaf| {
+            let leaf = leaf?;
+            let key = format_smolstr!("{}/{}", leaf.collection, leaf.rkey.as_ref());
+            Ok((key, leaf.value))
+        })
+    }
+
+    /// Walk the MST in key order, splitting each key and reading its record as it's reached
+    ///
+    /// Shared by [`iter_records`](Self::iter_records) and `stream_records`. Errors name the
+    /// record by its at-URI.
+    fn record_leaves(&self) -> impl Stream<Item = Result<RecordLeaf>> + Send + 'static {
+        let storage = self.storage.clone();
+        let did = self.did().clone().into_static();
+        self.mst.leaves_with_prefix("").then(move |leaf| {
+            let storage = storage.clone();
+            let did = did.clone();
+            async move {
+                let (key, cid) = leaf?;
+                let (collection, rkey) = key
+                    .split_once('/')
+                    .ok_or_else(|| RepoError::invalid_key(key.as_str()))?;
+                let (collection, rkey) = parse_record_path(collection, rkey)?;
+
+                let uri = format_smolstr!("at://{}/{}", did, key);
+                let block = storage.get(&cid).await?.ok_or_else(|| {
+                    RepoError::not_found("record block", cid)
+                        .with_context(format!("record block not found: {} ({})", cid, uri))
+                })?;
+                let value = decode_record(&block, &uri)?;
+                Ok(RecordLeaf {
+                    collection: collection.into_static(),
+                    rkey: rkey.into_static(),
+                    cid,
+                    value,
+                })
+            }
+        })
+    }
+
+    /// Rewrite every record through `f`, producing a new repository and one commit
+    ///
+    /// `f` is called with each record's at-URI and decoded value, in key order. Returning
+    /// `None` deletes the record; returning a value equal to the one passed in leaves the
+    /// record untouched, so its CID stays exactly the same. Changed records are encoded as
+    /// canonical DAG-CBOR and written to storage, and the MST is rebuilt with all changes
+    /// applied at once.
+    ///
+    /// The returned commit is **unsigned**, and `self` is left as it was. Sign the new
+    /// repository's commit before publishing it; signing changes the commit CID, so the
+    /// returned `CommitData` is mainly useful for its blocks and `deleted_cids`.
+    pub async fn map_records<F>(&self, mut f: F) -> Result<(Repository<S>, CommitData)>
+    where
+        F: FnMut(&AtUri<'_>, Data<'static>) -> Option<Data<'static>>,
+    {
+        // Step 1: Run every record through `f`, storing any rewritten blocks
+        let mut changes = Vec::new();
+        let mut leaf_blocks = BTreeMap::new();
+
+        let records = self.iter_records();
+        n0_future::pin!(records);
+        while let Some(record) = records.next().await {
+            let (uri, _cid, value) = record?;
+            let key = self.record_key(&uri)?;
+            match f(&uri, value.clone()) {
+                Some(new_value) if new_value == value => {}
+                Some(new_value) => {
+                    let cbor = new_value.to_canonical_dag_cbor().map_err(|e| {
+                        RepoError::serialization(e).with_context(format!("encoding record {}", uri))
+                    })?;
+                    let cid = self.storage.put(&cbor).await?;
+                    leaf_blocks.insert(cid, Bytes::from(cbor));
+                    changes.push((key, Some(cid)));
+                }
+                None => changes.push((key, None)),
+            }
+        }
+
+        // Step 2: Apply the changes to a copy of the MST
+        let mut updated_tree = self.mst.clone();
+        for (key, cid) in &changes {
+            updated_tree = match cid {
+                Some(cid) => updated_tree.update(key, *cid).await?,
+                None => updated_tree.delete(key).await?,
+            };
+        }
+
+        // Step 3: Collect blocks the same way create_commit does
+        let data = updated_tree.root().await?;
+        let prev_data = *self.commit.data();
+        let diff = self.mst.diff(&updated_tree).await?;
+
+        let mut blocks = diff.new_mst_blocks;
+        blocks.extend(leaf_blocks.clone());
+        let mut relevant_blocks = leaf_blocks;
+        for (key, _) in &changes {
+            updated_tree
+                .blocks_for_path(key, &mut relevant_blocks)
+                .await?;
+            self.mst.blocks_for_path(key, &mut relevant_blocks).await?;
+        }
+        let removed_set: std::collections::HashSet<_> =
+            diff.removed_mst_blocks.iter().copied().collect();
+        relevant_blocks.retain(|cid, _| !removed_set.contains(cid));
+
+        // Step 4: Create the unsigned commit
+        let rev = Ticker::new().next(Some(self.commit.rev.clone()));
+        let commit = Commit::new_unsigned(self.commit.did().clone(), data, rev.clone(), None);
+        let commit_cbor = commit.to_cbor()?;
+        let commit_cid = crate::mst::util::compute_cid(&commit_cbor)?;
+        let commit_bytes = Bytes::from(commit_cbor);
+        blocks.insert(commit_cid, commit_bytes.clone());
+        relevant_blocks.insert(commit_cid, commit_bytes);
+
+        let repo = Repository::new(self.storage.clone(), updated_tree, commit, commit_cid);
+        Ok((
+            repo,
+            CommitData {
+                cid: commit_cid,
+                rev,
+                since: Some(self.commit.rev.clone()),
+                prev: None,
+                data,
+                prev_data: Some(prev_data),
+                blocks,
+                relevant_blocks,
+                deleted_cids: diff.removed_cids,
+            },
+        ))
+    }
+
+    /// MST key (`<collection>/<rkey>`) for a record at-URI in this repo
+    fn record_key(&self, uri: &AtUri<'_>) -> Result<SmolStr> {
+        let did = self.did();
+        uri.as_str()
+            .strip_prefix("at://")
+            .and_then(|rest| rest.strip_prefix(did.as_str()))
+            .and_then(|rest| rest.strip_prefix('/'))
+            .map(SmolStr::new)
+            .ok_or_else(|| RepoError::invalid_key(uri.as_str()))
+    }
+
     // TODO(cursor-based queries): Potential future API additions
     //
-    // The current API is purely single-record CRUD. Cursor-based traversal (see mst/cursor.rs)
-    // would enable efficient collection/range queries:
-    //
-    // - list_collection(collection: &Nsid) -> Vec<(RecordKey, IpldCid)>
-    //   Enumerate all records in a collection via prefix search on "collection/"
-    //   Uses cursor.advance() + cursor.skip_subtree() to skip irrelevant branches
-    //
     // - list_collection_range(collection: &Nsid, start: &Rkey, end: &Rkey) -> Vec<...>
     //   Range query: advance to start key, collect until > end, skip subtrees outside range
     //   Useful for pagination / time-bounded queries (since Rkeys are often TIDs)
-    //
-    // - list_all_collections() -> Vec<Nsid>
-    //   Walk tree, track collection prefixes, skip subtrees once prefix changes
-    //
-    // Current single-key get() is already optimal (O(log n) targeted lookup).
-    // But these bulk operations would benefit significantly from cursor's skip_subtree()
-    // to avoid traversing unrelated branches when searching lexicographically-organized data.
 
     /// Create a commit from record write operations
     ///
@@ -584,10 +930,111 @@ impl<S: BlockStore + Sync + 'static> Repository<S> {
     }
 
     /// Export repository to CAR file
+    #[cfg(not(target_family = "wasm"))]
     pub async fn export_car(&self, path: impl AsRef<Path>, commit_cid: IpldCid) -> Result<()> {
         crate::car::export_repo_car(path, commit_cid, &self.mst).await
     }
 
+    /// Export every record as newline-delimited JSON
+    ///
+    /// Writes one `{"uri": ..., "cid": ..., "value": ...}` object per line in key order,
+    /// streaming records one at a time so memory use stays flat for large repos. Record
+    /// values use the AT Protocol JSON encoding (`$link`, `$bytes`, blob refs).
+    ///
+    /// Returns the number of records written.
+    pub async fn export_jsonl<W: AsyncWrite + Unpin>(&self, mut writer: W) -> Result<usize> {
+        #[derive(serde::Serialize)]
+        struct JsonlRecord<'a> {
+            uri: &'a str,
+            cid: &'a str,
+            value: &'a Data<'a>,
+        }
+
+        let mut count = 0;
+        let mut line = Vec::new();
+
+        let records = self.iter_records();
+        n0_future::pin!(records);
+        while let Some(record) = records.next().await {
+            let (uri, cid, value) = record?;
+            line.clear();
+            serde_json::to_writer(
+                &mut line,
+                &JsonlRecord {
+                    uri: uri.as_str(),
+                    cid: &cid.to_string(),
+                    value: &value,
+                },
+            )
+            .map_err(RepoError::serialization)?;
+            line.push(b'\n');
+            writer.write_all(&line).await.map_err(RepoError::io)?;
+            count += 1;
+        }
+
+        writer.flush().await.map_err(RepoError::io)?;
+        Ok(count)
+    }
+
+    /// Check that every MST node and record block in the repo exists in storage
+    ///
+    /// Walks the MST breadth-first, fetching each layer's nodes and then checking record
+    /// blocks with up to `concurrency` storage requests in flight. Missing blocks are
+    /// collected into the report instead of failing the walk. Doesn't check the commit
+    /// signature; do that once up front with [`Commit::verify`].
+    ///
+    /// Errors only if storage fails or an MST node can't be decoded.
+    pub async fn verify_parallel(&self, concurrency: usize) -> Result<VerifyReport> {
+        use crate::mst::NodeData;
+
+        let concurrency = concurrency.max(1);
+        let mut report = VerifyReport::default();
+        let mut layer = vec![self.mst.root().await?];
+        let mut records = Vec::new();
+
+        while !layer.is_empty() {
+            let mut next = Vec::new();
+            for wave in layer.chunks(concurrency) {
+                let blocks =
+                    n0_future::try_join_all(wave.iter().map(|cid| self.storage.get(cid))).await?;
+                for (cid, block) in wave.iter().zip(blocks) {
+                    let Some(block) = block else {
+                        report.missing_nodes.push(*cid);
+                        continue;
+                    };
+                    let node: NodeData = serde_ipld_dagcbor::from_slice(&block).map_err(|e| {
+                        RepoError::serialization(e)
+                            .with_context(format!("deserializing MST node {}", cid))
+                    })?;
+                    report.nodes_checked += 1;
+
+                    next.extend(node.left);
+                    let mut key = Vec::new();
+                    for entry in node.entries {
+                        key.truncate(entry.prefix_len as usize);
+                        key.extend_from_slice(&entry.key_suffix);
+                        records.push((SmolStr::new(String::from_utf8_lossy(&key)), entry.value));
+                        next.extend(entry.tree);
+                    }
+                }
+            }
+            layer = next;
+        }
+
+        for wave in records.chunks(concurrency) {
+            let found =
+                n0_future::try_join_all(wave.iter().map(|(_, cid)| self.storage.has(cid))).await?;
+            for (record, found) in wave.iter().zip(found) {
+                if !found {
+                    report.missing_records.push(record.clone());
+                }
+            }
+        }
+        report.records_checked = records.len();
+
+        Ok(report)
+    }
+
     /// Get the underlying MST
     pub fn mst(&self) -> &Mst<S> {
         &self.mst
@@ -614,6 +1061,141 @@ impl<S: BlockStore + Sync + 'static> Repository<S> {
     }
 }
 
+/// Validate a collection and rkey pair as given to the record-level methods
+fn parse_record_path<'a>(
+    collection: &'a str,
+    rkey: &'a str,
+) -> Result<(Nsid<'a>, RecordKey<Rkey<'a>>)> {
+    let key = || format!("{}/{}", collection, rkey);
+    let collection = Nsid::new(collection).map_err(|_| RepoError::invalid_key(key()))?;
+    let rkey = Rkey::new(rkey).map_err(|_| RepoError::invalid_key(key()))?;
+    Ok((collection, RecordKey(rkey)))
+}
+
+/// A record read from an MST leaf by `Repository::record_leaves`
+struct RecordLeaf {
+    collection: Nsid<'static>,
+    rkey: RecordKey<Rkey<'static>>,
+    cid: IpldCid,
+    value: Data<'static>,
+}
+
+/// Decode a DAG-CBOR record block, naming the record in errors
+fn decode_record(block: &[u8], record: impl Display) -> Result<Data<'static>> {
+    let ipld: Ipld = serde_ipld_dagcbor::from_slice(block).map_err(|e| {
+        RepoError::serialization(e).with_context(format!("decoding record {}", record))
+    })?;
+    let value = Data::from_cbor(&ipld).map_err(|e| {
+        RepoError::serialization(e).with_context(format!("decoding record {}", record))
+    })?;
+    Ok(value.into_static())
+}
+
+impl Repository<MemoryBlockStore> {
+    /// Check a full repo export before importing it
+    ///
+    /// Runs every check in order and stops at the first failure, which the returned
+    /// [`ImportError`] variant names:
+    ///
+    /// 1. Every block in the CAR hashes to its CID
+    /// 2. The CAR root is a commit block, for `expected_did`
+    /// 3. The commit is signed by `pubkey`
+    /// 4. The MST under the commit's `data` can be walked using only blocks from the CAR,
+    ///    and every record it points at is included
+    /// 5. Rebuilding the MST from its leaves gives back the commit's `data` CID
+    ///
+    /// Nothing is written anywhere; load the blocks into real storage once this passes.
+    pub async fn verify_import(
+        car_bytes: &[u8],
+        expected_did: &Did<'_>,
+        pubkey: &PublicKey<'_>,
+    ) -> std::result::Result<VerifiedRepo, ImportError> {
+        let car_failed = |e: RepoError| ImportError::CarParseFailed {
+            source: Box::new(e),
+        };
+
+        // 1. Block hashes
+        let validation = crate::car::validate_car_bytes(car_bytes).map_err(car_failed)?;
+        if let Some(cid) = validation.mismatched.first() {
+            return Err(ImportError::BlockMismatch {
+                cid: cid.to_string(),
+            });
+        }
+        if let Some(cid) = validation.unsupported.first() {
+            return Err(ImportError::UnsupportedBlock {
+                cid: cid.to_string(),
+            });
+        }
+        let parsed = crate::car::parse_car_bytes(car_bytes)
+            .await
+            .map_err(car_failed)?;
+        let storage = Arc::new(MemoryBlockStore::new_from_blocks(parsed.blocks));
+
+        // 2. Commit and DID
+        let commit_cid = parsed.root;
+        let commit_bytes = storage
+            .get(&commit_cid)
+            .await
+            .ok()
+            .flatten()
+            .ok_or_else(|| ImportError::CommitNotFound {
+                cid: commit_cid.to_string(),
+            })?;
+        let commit =
+            Commit::from_cbor(&commit_bytes).map_err(|e| ImportError::CommitDeserializeFailed {
+                source: Box::new(e),
+            })?;
+        if commit.did().as_str() != expected_did.as_str() {
+            return Err(ImportError::DidMismatch {
+                commit_did: commit.did().to_string(),
+                expected_did: expected_did.to_string(),
+            });
+        }
+
+        // 3. Signature
+        commit
+            .verify(pubkey)
+            .map_err(|source| ImportError::SignatureVerificationFailed { source })?;
+
+        // 4. MST and records
+        let data = *commit.data();
+        let invalid_mst = |e: RepoError| ImportError::InvalidMst {
+            root: data.to_string(),
+            source: Box::new(e),
+        };
+        let mst = Mst::load(storage.clone(), data, None);
+        let leaves = mst.leaves().await.map_err(invalid_mst)?;
+        for (key, cid) in &leaves {
+            if !storage.has(cid).await.map_err(invalid_mst)? {
+                return Err(ImportError::MissingRecord {
+                    key: key.to_string(),
+                    cid: cid.to_string(),
+                });
+            }
+        }
+
+        // 5. Recompute the root from the leaves alone
+        let mut rebuilt = Mst::new(Arc::new(MemoryBlockStore::new()));
+        for (key, cid) in &leaves {
+            rebuilt = rebuilt.add(key, *cid).await.map_err(invalid_mst)?;
+        }
+        let computed = rebuilt.root().await.map_err(invalid_mst)?;
+        if computed != data {
+            return Err(ImportError::DataMismatch {
+                expected: data.to_string(),
+                computed: computed.to_string(),
+            });
+        }
+
+        Ok(VerifiedRepo {
+            did: commit.did().clone().into_static(),
+            commit_cid,
+            rev: commit.rev().clone(),
+            records: leaves.len(),
+        })
+    }
+}
+
 impl<S: BlockStore> Display for Repository<S> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         use crate::mst::tree::short_cid;
@@ -644,6 +1226,7 @@ mod tests {
     use jacquard_common::types::{
         crypto::{KeyCodec, PublicKey},
         recordkey::Rkey,
+        string::AtprotoStr,
         value::RawData,
     };
     use smol_str::SmolStr;
@@ -811,6 +1394,244 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_put_read_remove_record_cycle() {
+        let storage = Arc::new(MemoryBlockStore::new());
+        let did = Did::new_static("did:plc:test").unwrap();
+        let signing_key = k256::ecdsa::SigningKey::random(&mut rand::rngs::OsRng);
+        let mut repo = Repository::create(storage.clone(), did, &signing_key, None)
+            .await
+            .unwrap();
+        let text = |value: Option<Data<'static>>| match value {
+            Some(Data::Object(obj)) => match obj.0.get("text") {
+                Some(Data::String(text)) => text.as_str().to_string(),
+                other => panic!("unexpected text field: {:?}", other),
+            },
+            other => panic!("unexpected record: {:?}", other),
+        };
+
+        let initial = *repo.current_commit_cid();
+        let created = repo
+            .put_record(
+                "app.bsky.feed.post",
+                "abc123",
+                make_test_record(1),
+                &signing_key,
+            )
+            .await
+            .unwrap();
+        assert_ne!(created, initial);
+        assert_eq!(repo.current_commit().prev, Some(initial));
+        let pubkey = signing_key.verifying_key().to_encoded_point(true);
+        let pubkey = PublicKey {
+            codec: KeyCodec::Secp256k1,
+            bytes: pubkey.as_bytes().to_vec().into(),
+        };
+        repo.current_commit().verify(&pubkey).unwrap();
+
+        let record = repo
+            .read_record("app.bsky.feed.post", "abc123")
+            .await
+            .unwrap();
+        assert_eq!(text(record), "Test post #1");
+
+        // Putting over an existing record updates it
+        repo.put_record(
+            "app.bsky.feed.post",
+            "abc123",
+            make_test_record(2),
+            &signing_key,
+        )
+        .await
+        .unwrap();
+        let record = repo
+            .read_record("app.bsky.feed.post", "abc123")
+            .await
+            .unwrap();
+        assert_eq!(text(record), "Test post #2");
+
+        // Reloading from storage sees the committed record
+        let loaded = Repository::from_commit(storage.clone(), repo.current_commit_cid())
+            .await
+            .unwrap();
+        let record = loaded
+            .read_record("app.bsky.feed.post", "abc123")
+            .await
+            .unwrap();
+        assert_eq!(text(record), "Test post #2");
+
+        let removed = repo
+            .remove_record("app.bsky.feed.post", "abc123", &signing_key)
+            .await
+            .unwrap();
+        assert_eq!(removed, Some(*repo.current_commit_cid()));
+        assert!(
+            repo.read_record("app.bsky.feed.post", "abc123")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        // Nothing to remove, so no new commit
+        let head = *repo.current_commit_cid();
+        let removed = repo
+            .remove_record("app.bsky.feed.post", "abc123", &signing_key)
+            .await
+            .unwrap();
+        assert_eq!(removed, None);
+        assert_eq!(*repo.current_commit_cid(), head);
+
+        assert!(
+            repo.put_record("not an nsid", "abc123", make_test_record(3), &signing_key)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_import() {
+        use crate::mst::RecordWriteOp;
+
+        let did = Did::new_static("did:plc:test").unwrap();
+        let signing_key = k256::ecdsa::SigningKey::random(&mut rand::rngs::OsRng);
+        let pubkey = PublicKey {
+            codec: KeyCodec::Secp256k1,
+            bytes: signing_key
+                .verifying_key()
+                .to_encoded_point(true)
+                .as_bytes()
+                .to_vec()
+                .into(),
+        };
+
+        let collection = Nsid::new_static("app.bsky.feed.post").unwrap();
+        let ops: Vec<_> = (0..20)
+            .map(|n| RecordWriteOp::Create {
+                collection: collection.clone(),
+                rkey: RecordKey(Rkey::new_owned(format!("rec{:03}", n)).unwrap()),
+                record: make_test_record(n),
+            })
+            .collect();
+        let storage = Arc::new(MemoryBlockStore::new());
+        let commit = Repository::format_init_commit(storage, did.clone(), &signing_key, Some(&ops))
+            .await
+            .unwrap();
+        let export = |blocks| crate::car::write_car_bytes(commit.cid, blocks);
+
+        let car = export(commit.blocks.clone()).await.unwrap();
+        let verified = Repository::verify_import(&car, &did, &pubkey)
+            .await
+            .unwrap();
+        assert_eq!(verified.records, 20);
+        assert_eq!(verified.rev, commit.rev);
+        assert_eq!(verified.commit_cid, commit.cid);
+
+        // Swap one record block's bytes for another record's
+        let record_cid = crate::mst::util::compute_cid(
+            &serde_ipld_dagcbor::to_vec(&make_test_record(7)).unwrap(),
+        )
+        .unwrap();
+        let mut tampered = commit.blocks.clone();
+        tampered.insert(
+            record_cid,
+            Bytes::from(serde_ipld_dagcbor::to_vec(&make_test_record(99)).unwrap()),
+        );
+        let car = export(tampered).await.unwrap();
+        let err = Repository::verify_import(&car, &did, &pubkey)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, ImportError::BlockMismatch { cid } if *cid == record_cid.to_string()),
+            "{err:?}"
+        );
+
+        // Dropping it instead leaves the MST pointing at nothing
+        let mut missing = commit.blocks.clone();
+        missing.remove(&record_cid);
+        let car = export(missing).await.unwrap();
+        let err = Repository::verify_import(&car, &did, &pubkey)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, ImportError::MissingRecord { key, .. } if key == "app.bsky.feed.post/rec007"),
+            "{err:?}"
+        );
+
+        let car = export(commit.blocks.clone()).await.unwrap();
+        let other = Did::new_static("did:plc:other").unwrap();
+        let err = Repository::verify_import(&car, &other, &pubkey)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ImportError::DidMismatch { .. }), "{err:?}");
+
+        let other_key = k256::ecdsa::SigningKey::random(&mut rand::rngs::OsRng);
+        let other_pubkey = PublicKey {
+            codec: KeyCodec::Secp256k1,
+            bytes: other_key
+                .verifying_key()
+                .to_encoded_point(true)
+                .as_bytes()
+                .to_vec()
+                .into(),
+        };
+        let err = Repository::verify_import(&car, &did, &other_pubkey)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, ImportError::SignatureVerificationFailed { .. }),
+            "{err:?}"
+        );
+    }
+
+    #[cfg(feature = "streaming")]
+    #[tokio::test]
+    async fn test_stream_records_matches_mst() {
+        use crate::mst::RecordWriteOp;
+
+        let storage = Arc::new(MemoryBlockStore::new());
+        let did = Did::new_static("did:plc:test").unwrap();
+        let signing_key = k256::ecdsa::SigningKey::random(&mut rand::rngs::OsRng);
+        let ops: Vec<_> = [
+            "app.bsky.feed.like",
+            "app.bsky.feed.post",
+            "app.bsky.graph.follow",
+        ]
+        .into_iter()
+        .flat_map(|collection| {
+            (0..30).map(move |n| RecordWriteOp::Create {
+                collection: Nsid::new_static(collection).unwrap(),
+                rkey: RecordKey(Rkey::new_owned(format!("rec{:03}", n)).unwrap()),
+                record: make_test_record(n),
+            })
+        })
+        .collect();
+        let repo = Repository::create(storage, did, &signing_key, Some(&ops))
+            .await
+            .unwrap();
+
+        let records: Vec<_> = repo.stream_records().try_collect().await.unwrap();
+        let streamed: Vec<_> = records.iter().map(|(key, _)| key.clone()).collect();
+        let leaves: Vec<_> = repo
+            .mst()
+            .leaves()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect();
+        assert_eq!(streamed.len(), 90);
+        assert_eq!(streamed, leaves);
+
+        let (key, value) = &records[31];
+        assert_eq!(key, "app.bsky.feed.post/rec001");
+        let Data::Object(obj) = value else {
+            panic!("record is not an object: {:?}", value);
+        };
+        assert!(
+            matches!(obj.0.get("text"), Some(Data::String(text)) if text.as_str() == "Test post #1")
+        );
+    }
+
     #[tokio::test]
     async fn test_from_commit() {
         let storage = Arc::new(MemoryBlockStore::new());
@@ -893,6 +1714,327 @@ mod tests {
         assert!(got.is_none());
     }
 
+    #[tokio::test]
+    async fn test_collections_and_list_collection() {
+        let storage = Arc::new(MemoryBlockStore::new());
+        let mut repo = create_test_repo(storage).await;
+
+        // "post" and "postgate" share a string prefix but are distinct collections
+        let sizes = [
+            ("app.bsky.feed.like", 150),
+            ("app.bsky.feed.post", 80),
+            ("app.bsky.feed.postgate", 5),
+            ("com.example.record", 20),
+        ];
+        let mut ticker = Ticker::new();
+        let mut expected_posts = Vec::new();
+        for (collection, count) in sizes {
+            let collection = Nsid::new(collection).unwrap();
+            for i in 0..count {
+                let tid = ticker.next(None);
+                let rkey = RecordKey(Rkey::from_str(tid.as_str()).unwrap().into_static());
+                let cid = make_test_cid(i as u8);
+                repo.create_record(&collection, &rkey, cid).await.unwrap();
+                if collection.as_str() == "app.bsky.feed.post" {
+                    expected_posts.push((rkey, cid));
+                }
+            }
+        }
+
+        let counts = repo.collections().await.unwrap();
+        let counts: Vec<_> = counts.iter().map(|(k, v)| (k.as_str(), *v)).collect();
+        assert_eq!(counts, sizes);
+
+        let posts = Nsid::new("app.bsky.feed.post").unwrap();
+        let listed: Vec<_> = repo
+            .list_collection(&posts)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(listed, expected_posts);
+
+        let missing = Nsid::new("app.bsky.graph.follow").unwrap();
+        assert_eq!(repo.list_collection(&missing).count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_export_jsonl() {
+        let storage = Arc::new(MemoryBlockStore::new());
+        let mut repo = create_test_repo(storage).await;
+
+        let collection = Nsid::new("app.bsky.feed.post").unwrap();
+        let ops: Vec<_> = (1..=3)
+            .map(|n| RecordWriteOp::Create {
+                collection: collection.clone().into_static(),
+                rkey: RecordKey(Rkey::new_owned(format!("rkey{}", n)).unwrap()),
+                record: make_test_record(n),
+            })
+            .collect();
+        let did = Did::new("did:plc:test").unwrap();
+        let signing_key = k256::ecdsa::SigningKey::random(&mut rand::rngs::OsRng);
+        let prev = Some(*repo.current_commit_cid());
+        repo.create_commit(&ops, &did, prev, &signing_key)
+            .await
+            .unwrap();
+
+        let mut out = Vec::new();
+        let count = repo.export_jsonl(&mut out).await.unwrap();
+        assert_eq!(count, 3);
+
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<serde_json::Value> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        for (n, line) in (1..=3).zip(&lines) {
+            let rkey = RecordKey(Rkey::new_owned(format!("rkey{}", n)).unwrap());
+            let cid = repo.get_record(&collection, &rkey).await.unwrap().unwrap();
+            assert_eq!(
+                line["uri"],
+                format!("at://did:plc:test/app.bsky.feed.post/rkey{}", n)
+            );
+            assert_eq!(line["cid"], cid.to_string());
+            assert_eq!(line["value"]["$type"], "app.bsky.feed.post");
+            assert_eq!(line["value"]["text"], format!("Test post #{}", n));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_iter_records() {
+        let storage = Arc::new(MemoryBlockStore::new());
+        let mut repo = create_test_repo(storage).await;
+
+        let collection = Nsid::new("app.bsky.feed.post").unwrap();
+        let ops: Vec<_> = (1..=3)
+            .map(|n| RecordWriteOp::Create {
+                collection: collection.clone().into_static(),
+                rkey: RecordKey(Rkey::new_owned(format!("rkey{}", n)).unwrap()),
+                record: make_test_record(n),
+            })
+            .collect();
+        let did = Did::new("did:plc:test").unwrap();
+        let signing_key = k256::ecdsa::SigningKey::random(&mut rand::rngs::OsRng);
+        let prev = Some(*repo.current_commit_cid());
+        let (_, commit_data) = repo
+            .create_commit(&ops, &did, prev, &signing_key)
+            .await
+            .unwrap();
+        repo.apply_commit(commit_data).await.unwrap();
+
+        let records: Vec<_> = repo.iter_records().collect().await;
+        assert_eq!(records.len(), 3);
+        for (n, record) in (1..=3).zip(&records) {
+            let (uri, cid, value) = record.as_ref().unwrap();
+            assert_eq!(
+                uri.as_str(),
+                format!("at://did:plc:test/app.bsky.feed.post/rkey{}", n)
+            );
+            let rkey = RecordKey(Rkey::new_owned(format!("rkey{}", n)).unwrap());
+            assert_eq!(
+                repo.get_record(&collection, &rkey).await.unwrap(),
+                Some(*cid)
+            );
+            let Data::Object(obj) = value else {
+                panic!("record should be an object");
+            };
+            assert!(
+                matches!(obj.0.get("text"), Some(Data::String(text)) if text.as_str() == format!("Test post #{}", n))
+            );
+        }
+
+        // A missing record block is reported with its URI, and the stream continues
+        let root = repo.mst().root().await.unwrap();
+        let leaves = repo.mst().leaves().await.unwrap();
+        let mut blocks = BTreeMap::new();
+        for cid in repo.mst().collect_node_cids().await.unwrap() {
+            blocks.insert(cid, repo.storage().get(&cid).await.unwrap().unwrap());
+        }
+        for (_, cid) in [&leaves[0], &leaves[2]] {
+            blocks.insert(*cid, repo.storage().get(cid).await.unwrap().unwrap());
+        }
+        let storage = Arc::new(MemoryBlockStore::new_from_blocks(blocks));
+        let broken = Repository::new(
+            storage.clone(),
+            Mst::load(storage, root, None),
+            repo.current_commit().clone(),
+            *repo.current_commit_cid(),
+        );
+        let records: Vec<_> = broken.iter_records().collect().await;
+        assert_eq!(records.len(), 3);
+        assert!(records[0].is_ok());
+        let err = records[1].as_ref().unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("at://did:plc:test/app.bsky.feed.post/rkey2")
+        );
+        assert!(records[2].is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_map_records() {
+        let storage = Arc::new(MemoryBlockStore::new());
+        let mut repo = create_test_repo(storage).await;
+
+        let collection = Nsid::new("app.bsky.feed.post").unwrap();
+        let ops: Vec<_> = (1..=3)
+            .map(|n| RecordWriteOp::Create {
+                collection: collection.clone().into_static(),
+                rkey: RecordKey(Rkey::new_owned(format!("rkey{}", n)).unwrap()),
+                record: make_test_record(n),
+            })
+            .collect();
+        let did = Did::new("did:plc:test").unwrap();
+        let signing_key = k256::ecdsa::SigningKey::random(&mut rand::rngs::OsRng);
+        let (_, commit_data) = repo
+            .create_commit(&ops, &did, None, &signing_key)
+            .await
+            .unwrap();
+        repo.apply_commit(commit_data).await.unwrap();
+
+        let rkey = |n: u32| RecordKey(Rkey::new_owned(format!("rkey{}", n)).unwrap());
+        let old_cids = [
+            repo.get_record(&collection, &rkey(1)).await.unwrap(),
+            repo.get_record(&collection, &rkey(2)).await.unwrap(),
+        ];
+
+        // Keep rkey1 as-is, rewrite rkey2's text, delete rkey3
+        let mut seen = Vec::new();
+        let (mapped, commit_data) = repo
+            .map_records(|uri, value| {
+                seen.push(uri.as_str().to_string());
+                let rkey = uri.rkey().unwrap();
+                match rkey.as_ref() {
+                    "rkey1" => Some(value),
+                    "rkey2" => {
+                        let Data::Object(mut obj) = value else {
+                            panic!("record should be an object");
+                        };
+                        obj.0.insert(
+                            SmolStr::new_static("text"),
+                            Data::String(AtprotoStr::String("fixed".into())),
+                        );
+                        Some(Data::Object(obj))
+                    }
+                    _ => None,
+                }
+            })
+            .await
+            .unwrap();
+        assert_eq!(seen.len(), 3);
+
+        assert_eq!(
+            mapped.get_record(&collection, &rkey(1)).await.unwrap(),
+            old_cids[0]
+        );
+        let new_cid = mapped.get_record(&collection, &rkey(2)).await.unwrap();
+        assert!(new_cid.is_some());
+        assert_ne!(new_cid, old_cids[1]);
+        assert_eq!(
+            mapped.get_record(&collection, &rkey(3)).await.unwrap(),
+            None
+        );
+
+        // The source repo is untouched
+        assert_eq!(
+            repo.get_record(&collection, &rkey(2)).await.unwrap(),
+            old_cids[1]
+        );
+        assert!(
+            repo.get_record(&collection, &rkey(3))
+                .await
+                .unwrap()
+                .is_some()
+        );
+
+        // One unsigned commit covering the new tree
+        assert_eq!(commit_data.cid, *mapped.current_commit_cid());
+        assert_eq!(commit_data.data, mapped.mst().root().await.unwrap());
+        assert_eq!(
+            commit_data.since.as_ref(),
+            Some(repo.current_commit().rev())
+        );
+        assert!(mapped.current_commit().sig().is_empty());
+        assert!(commit_data.blocks.contains_key(&new_cid.unwrap()));
+
+        // Mapping with the identity function changes nothing
+        let (same, _) = repo.map_records(|_, value| Some(value)).await.unwrap();
+        assert_eq!(
+            same.mst().root().await.unwrap(),
+            repo.mst().root().await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_parallel() {
+        let storage = Arc::new(MemoryBlockStore::new());
+        let mut repo = create_test_repo(storage).await;
+
+        let collection = Nsid::new("app.bsky.feed.post").unwrap();
+        let ops: Vec<_> = (1..=200)
+            .map(|n| RecordWriteOp::Create {
+                collection: collection.clone().into_static(),
+                rkey: RecordKey(Rkey::new_owned(format!("rkey{}", n)).unwrap()),
+                record: make_test_record(n),
+            })
+            .collect();
+        let did = Did::new("did:plc:test").unwrap();
+        let signing_key = k256::ecdsa::SigningKey::random(&mut rand::rngs::OsRng);
+        let prev = Some(*repo.current_commit_cid());
+        let (_, commit_data) = repo
+            .create_commit(&ops, &did, prev, &signing_key)
+            .await
+            .unwrap();
+        repo.apply_commit(commit_data).await.unwrap();
+
+        let report = repo.verify_parallel(8).await.unwrap();
+        assert!(report.is_complete());
+        assert_eq!(report.records_checked, 200);
+        assert!(report.nodes_checked > 1);
+
+        // Copy the repo into stores missing one block each
+        let root = repo.mst().root().await.unwrap();
+        let nodes = repo.mst().collect_node_cids().await.unwrap();
+        let leaves = repo.mst().leaves().await.unwrap();
+        let all_cids: Vec<_> = nodes
+            .iter()
+            .copied()
+            .chain(leaves.iter().map(|(_, cid)| *cid))
+            .collect();
+        let copy_without = async |missing: IpldCid| {
+            let mut blocks = BTreeMap::new();
+            for cid in all_cids.iter().filter(|cid| **cid != missing) {
+                blocks.insert(*cid, repo.storage().get(cid).await.unwrap().unwrap());
+            }
+            let storage = Arc::new(MemoryBlockStore::new_from_blocks(blocks));
+            Repository::new(
+                storage.clone(),
+                Mst::load(storage, root, None),
+                repo.current_commit().clone(),
+                *repo.current_commit_cid(),
+            )
+        };
+
+        let (key, record) = leaves[0].clone();
+        let report = copy_without(record).await.verify_parallel(4).await.unwrap();
+        assert!(report.missing_nodes.is_empty());
+        assert_eq!(report.missing_records, vec![(key, record)]);
+        assert_eq!(report.records_checked, 200);
+
+        let subtree = *nodes.iter().find(|cid| **cid != root).unwrap();
+        let report = copy_without(subtree)
+            .await
+            .verify_parallel(4)
+            .await
+            .unwrap();
+        assert!(!report.is_complete());
+        assert_eq!(report.missing_nodes, vec![subtree]);
+        assert!(report.missing_records.is_empty());
+        assert!(report.records_checked < 200);
+    }
+
     #[tokio::test]
     async fn test_large_scale_operations() {
         let storage = Arc::new(MemoryBlockStore::new());
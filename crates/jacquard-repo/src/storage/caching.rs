@@ -0,0 +1,365 @@
+//! Read-through block cache
+//!
+//! Wraps a slow (e.g. remote) block store with an in-memory LRU cache, so repeated MST
+//! traversals only fetch each node once.
+
+use crate::error::Result;
+use crate::repo::CommitData;
+use crate::storage::BlockStore;
+use bytes::Bytes;
+use cid::Cid as IpldCid;
+use lru::LruCache;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Block bytes kept in least-recently-used order, bounded by total size
+#[derive(Debug)]
+struct Cache {
+    blocks: LruCache<IpldCid, Bytes>,
+    size: usize,
+    capacity: usize,
+}
+
+impl Cache {
+    fn get(&mut self, cid: &IpldCid) -> Option<Bytes> {
+        self.blocks.get(cid).cloned()
+    }
+
+    fn insert(&mut self, cid: IpldCid, data: Bytes) {
+        if data.len() > self.capacity {
+            return;
+        }
+        if let Some(old) = self.blocks.put(cid, data.clone()) {
+            self.size -= old.len();
+        }
+        self.size += data.len();
+        while self.size > self.capacity {
+            match self.blocks.pop_lru() {
+                Some((_, evicted)) => self.size -= evicted.len(),
+                None => break,
+            }
+        }
+    }
+
+    fn remove(&mut self, cid: &IpldCid) {
+        if let Some(old) = self.blocks.pop(cid) {
+            self.size -= old.len();
+        }
+    }
+}
+
+/// Block store wrapper with a read-through LRU cache
+///
+/// `get` and `get_many` serve blocks from the cache when they can and cache whatever they
+/// fetch from the inner store; `has` answers from the cache before asking the inner store.
+/// Writes go straight to the inner store without being cached. Since blocks are
+/// content-addressed a cached block never goes stale, but `apply_commit` still drops its
+/// `deleted_cids` so the cache doesn't keep serving garbage-collected blocks.
+///
+/// The cache is bounded by the total size of the cached blocks; blocks larger than the whole
+/// capacity are never cached. Clones share the cache and counters.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use jacquard_repo::storage::{CachingBlockStore, MemoryBlockStore};
+///
+/// // 64 MiB of hot MST nodes in front of a remote store
+/// let storage = CachingBlockStore::new(remote_store, 64 * 1024 * 1024);
+/// let mst = Mst::load(Arc::new(storage.clone()), root, None);
+/// // ... traverse ...
+/// println!("{} hits, {} misses", storage.hits(), storage.misses());
+/// ```
+#[derive(Debug, Clone)]
+pub struct CachingBlockStore<S> {
+    inner: S,
+    cache: Arc<Mutex<Cache>>,
+    hits: Arc<AtomicU64>,
+    misses: Arc<AtomicU64>,
+}
+
+impl<S> CachingBlockStore<S> {
+    /// Wrap `inner` with a cache holding up to `capacity` bytes of blocks
+    pub fn new(inner: S, capacity: usize) -> Self {
+        Self {
+            inner,
+            cache: Arc::new(Mutex::new(Cache {
+                blocks: LruCache::unbounded(),
+                size: 0,
+                capacity,
+            })),
+            hits: Arc::new(AtomicU64::new(0)),
+            misses: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// The wrapped store
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Number of block reads served from the cache
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of block reads that went to the inner store
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Total size of the blocks currently cached, in bytes
+    pub fn cached_bytes(&self) -> usize {
+        self.cache.lock().unwrap().size
+    }
+
+    /// Drop every cached block
+    pub fn clear(&self) {
+        let mut cache = self.cache.lock().unwrap();
+        cache.blocks.clear();
+        cache.size = 0;
+    }
+}
+
+impl<S: BlockStore + Sync + 'static> BlockStore for CachingBlockStore<S> {
+    async fn get(&self, cid: &IpldCid) -> Result<Option<Bytes>> {
+        let cached = self.cache.lock().unwrap().get(cid);
+        if let Some(data) = cached {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(Some(data));
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let data = self.inner.get(cid).await?;
+        if let Some(data) = &data {
+            self.cache.lock().unwrap().insert(*cid, data.clone());
+        }
+        Ok(data)
+    }
+
+    async fn put(&self, data: &[u8]) -> Result<IpldCid> {
+        self.inner.put(data).await
+    }
+
+    async fn has(&self, cid: &IpldCid) -> Result<bool> {
+        if self.cache.lock().unwrap().blocks.contains(cid) {
+            return Ok(true);
+        }
+        self.inner.has(cid).await
+    }
+
+    async fn put_many(
+        &self,
+        blocks: impl IntoIterator<Item = (IpldCid, Bytes)> + Send,
+    ) -> Result<()> {
+        self.inner.put_many(blocks).await
+    }
+
+    async fn get_many(&self, cids: &[IpldCid]) -> Result<Vec<Option<Bytes>>> {
+        let mut results: Vec<Option<Bytes>> = {
+            let mut cache = self.cache.lock().unwrap();
+            cids.iter().map(|cid| cache.get(cid)).collect()
+        };
+
+        let missing: Vec<IpldCid> = cids
+            .iter()
+            .zip(&results)
+            .filter(|(_, cached)| cached.is_none())
+            .map(|(cid, _)| *cid)
+            .collect();
+        self.hits
+            .fetch_add((cids.len() - missing.len()) as u64, Ordering::Relaxed);
+        if missing.is_empty() {
+            return Ok(results);
+        }
+
+        self.misses
+            .fetch_add(missing.len() as u64, Ordering::Relaxed);
+        let mut fetched = self.inner.get_many(&missing).await?.into_iter();
+        let mut cache = self.cache.lock().unwrap();
+        for (cid, slot) in cids.iter().zip(results.iter_mut()) {
+            if slot.is_none() {
+                // The inner store returns one result per requested CID, in order
+                *slot = fetched.next().flatten();
+                if let Some(data) = slot {
+                    cache.insert(*cid, data.clone());
+                }
+            }
+        }
+        Ok(results)
+    }
+
+    async fn apply_commit(&self, commit: CommitData) -> Result<()> {
+        let deleted = commit.deleted_cids.clone();
+        let result = self.inner.apply_commit(commit).await;
+
+        // Invalidate even on failure, since some deletes may have gone through
+        let mut cache = self.cache.lock().unwrap();
+        for cid in &deleted {
+            cache.remove(cid);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mst::util::compute_cid;
+    use crate::storage::MemoryBlockStore;
+    use jacquard_common::types::string::Tid;
+    use std::collections::BTreeMap;
+    use std::sync::atomic::AtomicUsize;
+
+    /// Memory store that counts reads reaching it
+    #[derive(Clone, Default)]
+    struct CountingStore {
+        inner: MemoryBlockStore,
+        reads: Arc<AtomicUsize>,
+    }
+
+    impl CountingStore {
+        fn reads(&self) -> usize {
+            self.reads.load(Ordering::Relaxed)
+        }
+    }
+
+    impl BlockStore for CountingStore {
+        async fn get(&self, cid: &IpldCid) -> Result<Option<Bytes>> {
+            self.reads.fetch_add(1, Ordering::Relaxed);
+            self.inner.get(cid).await
+        }
+
+        async fn put(&self, data: &[u8]) -> Result<IpldCid> {
+            self.inner.put(data).await
+        }
+
+        async fn has(&self, cid: &IpldCid) -> Result<bool> {
+            self.reads.fetch_add(1, Ordering::Relaxed);
+            self.inner.has(cid).await
+        }
+
+        async fn put_many(
+            &self,
+            blocks: impl IntoIterator<Item = (IpldCid, Bytes)> + Send,
+        ) -> Result<()> {
+            self.inner.put_many(blocks).await
+        }
+
+        async fn get_many(&self, cids: &[IpldCid]) -> Result<Vec<Option<Bytes>>> {
+            self.reads.fetch_add(cids.len(), Ordering::Relaxed);
+            self.inner.get_many(cids).await
+        }
+
+        async fn apply_commit(&self, commit: CommitData) -> Result<()> {
+            self.inner.apply_commit(commit).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_second_get_is_cached() {
+        let inner = CountingStore::default();
+        let store = CachingBlockStore::new(inner.clone(), 1024);
+        let cid = store.put(b"hello").await.unwrap();
+
+        assert_eq!(
+            store.get(&cid).await.unwrap().as_deref(),
+            Some(&b"hello"[..])
+        );
+        assert_eq!(inner.reads(), 1);
+        assert_eq!((store.hits(), store.misses()), (0, 1));
+
+        assert_eq!(
+            store.get(&cid).await.unwrap().as_deref(),
+            Some(&b"hello"[..])
+        );
+        assert!(store.has(&cid).await.unwrap());
+        assert_eq!(inner.reads(), 1);
+        assert_eq!((store.hits(), store.misses()), (1, 1));
+
+        // Misses aren't cached
+        let missing = compute_cid(b"missing").unwrap();
+        assert_eq!(store.get(&missing).await.unwrap(), None);
+        assert_eq!(store.get(&missing).await.unwrap(), None);
+        assert_eq!(inner.reads(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_get_many_fetches_only_misses() {
+        let inner = CountingStore::default();
+        let store = CachingBlockStore::new(inner.clone(), 1024);
+        let a = store.put(b"a").await.unwrap();
+        let b = store.put(b"b").await.unwrap();
+        let missing = compute_cid(b"missing").unwrap();
+
+        store.get(&a).await.unwrap();
+        let results = store.get_many(&[b, a, missing]).await.unwrap();
+        assert_eq!(
+            results,
+            vec![
+                Some(Bytes::from_static(b"b")),
+                Some(Bytes::from_static(b"a")),
+                None
+            ]
+        );
+        // One read for the first get, then b and missing
+        assert_eq!(inner.reads(), 3);
+
+        store.get_many(&[a, b]).await.unwrap();
+        assert_eq!(inner.reads(), 3);
+        assert_eq!((store.hits(), store.misses()), (3, 3));
+    }
+
+    #[tokio::test]
+    async fn test_evicts_by_size() {
+        let store = CachingBlockStore::new(MemoryBlockStore::new(), 10);
+        let a = store.put(b"aaaa").await.unwrap();
+        let b = store.put(b"bbbb").await.unwrap();
+        let c = store.put(b"cccc").await.unwrap();
+        let big = store.put(b"this block is too big").await.unwrap();
+
+        store.get(&a).await.unwrap();
+        store.get(&b).await.unwrap();
+        store.get(&a).await.unwrap();
+        assert_eq!(store.cached_bytes(), 8);
+
+        // b is least recently used
+        store.get(&c).await.unwrap();
+        assert_eq!(store.cached_bytes(), 8);
+        store.get(&a).await.unwrap();
+        store.get(&b).await.unwrap();
+        assert_eq!((store.hits(), store.misses()), (2, 4));
+
+        store.get(&big).await.unwrap();
+        assert_eq!(store.cached_bytes(), 8);
+    }
+
+    #[tokio::test]
+    async fn test_apply_commit_invalidates_deleted() {
+        let store = CachingBlockStore::new(MemoryBlockStore::new(), 1024);
+        let old = store.put(b"old").await.unwrap();
+        store.get(&old).await.unwrap();
+        assert!(store.has(&old).await.unwrap());
+
+        let new = Bytes::from_static(b"new");
+        let new_cid = compute_cid(&new).unwrap();
+        store
+            .apply_commit(CommitData {
+                cid: new_cid,
+                rev: Tid::now_0(),
+                since: None,
+                prev: None,
+                data: new_cid,
+                prev_data: None,
+                blocks: BTreeMap::from([(new_cid, new)]),
+                relevant_blocks: BTreeMap::new(),
+                deleted_cids: vec![old],
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(store.cached_bytes(), 0);
+        assert!(!store.has(&old).await.unwrap());
+        assert_eq!(store.get(&old).await.unwrap(), None);
+    }
+}
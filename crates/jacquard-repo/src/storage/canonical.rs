@@ -0,0 +1,161 @@
+//! Strict ingestion of untrusted blocks
+//!
+//! A block's CID is only meaningful if every implementation derives the same bytes from the
+//! same data. DAG-CBOR requires a canonical encoding for exactly this reason, but a
+//! non-canonical block still has a self-consistent CID over its (non-standard) bytes, so
+//! checking the hash alone won't catch it.
+//!
+//! The helpers here decode each DAG-CBOR block, re-encode it and compare the bytes, rejecting
+//! mismatches with [`BlockError::NonCanonicalCbor`]. Blocks given with a CID are also rehashed,
+//! rejecting ones whose bytes don't match it with [`BlockError::CidMismatch`]. Use them in place of the plain
+//! [`BlockStore`] methods when importing CARs or commits from untrusted sources.
+
+use super::BlockStore;
+use crate::DAG_CBOR_CID_CODEC;
+use crate::car::reader::block_matches_cid;
+use crate::error::{BlockError, Result};
+use bytes::Bytes;
+use cid::Cid as IpldCid;
+use ipld_core::ipld::Ipld;
+
+/// Check that `data` is the canonical DAG-CBOR encoding of the value it decodes to
+///
+/// Blocks whose CID uses a codec other than DAG-CBOR (e.g. raw blobs) are accepted as-is.
+pub fn verify_canonical(cid: &IpldCid, data: &[u8]) -> std::result::Result<(), BlockError> {
+    if cid.codec() != DAG_CBOR_CID_CODEC {
+        return Ok(());
+    }
+
+    let value: Ipld =
+        serde_ipld_dagcbor::from_slice(data).map_err(|e| BlockError::InvalidCbor {
+            cid: *cid,
+            source: Box::new(e),
+        })?;
+    let canonical = serde_ipld_dagcbor::to_vec(&value).map_err(|e| BlockError::InvalidCbor {
+        cid: *cid,
+        source: Box::new(e),
+    })?;
+
+    if canonical != data {
+        return Err(BlockError::NonCanonicalCbor { cid: *cid });
+    }
+    Ok(())
+}
+
+/// Put a single block after verifying it is canonical DAG-CBOR
+pub async fn put_strict<S: BlockStore + Sync>(storage: &S, data: &[u8]) -> Result<IpldCid> {
+    let cid = crate::mst::util::compute_cid(data)?;
+    verify_canonical(&cid, data)?;
+    storage.put(data).await
+}
+
+/// Put many blocks after verifying each hashes to its CID and is canonical DAG-CBOR
+///
+/// All blocks are checked before any are written, so a rejected batch leaves the store
+/// untouched. A CID whose codec or hash function can't be checked is rejected too.
+pub async fn put_many_strict<S: BlockStore + Sync>(
+    storage: &S,
+    blocks: impl IntoIterator<Item = (IpldCid, Bytes)>,
+) -> Result<()> {
+    let blocks: Vec<(IpldCid, Bytes)> = blocks.into_iter().collect();
+    for (cid, data) in &blocks {
+        match block_matches_cid(cid, data) {
+            Some(true) => {}
+            Some(false) => return Err(BlockError::CidMismatch { cid: *cid }.into()),
+            None => return Err(BlockError::UnsupportedCid { cid: *cid }.into()),
+        }
+        verify_canonical(cid, data)?;
+    }
+    storage.put_many(blocks).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::RepoErrorKind;
+    use crate::storage::MemoryBlockStore;
+    use std::collections::BTreeMap;
+
+    fn canonical_block() -> Vec<u8> {
+        let mut map = BTreeMap::new();
+        map.insert("a".to_string(), Ipld::Integer(1));
+        map.insert("bb".to_string(), Ipld::String("x".into()));
+        serde_ipld_dagcbor::to_vec(&Ipld::Map(map)).unwrap()
+    }
+
+    #[test]
+    fn accepts_canonical_block() {
+        let data = canonical_block();
+        let cid = crate::mst::util::compute_cid(&data).unwrap();
+        verify_canonical(&cid, &data).unwrap();
+    }
+
+    #[test]
+    fn rejects_unsorted_map_keys() {
+        // {"bb": "x", "a": 1} - keys out of DAG-CBOR order (shorter keys sort first)
+        let data = [0xa2, 0x62, b'b', b'b', 0x61, b'x', 0x61, b'a', 0x01];
+        let cid = crate::mst::util::compute_cid(&data).unwrap();
+        assert!(matches!(
+            verify_canonical(&cid, &data),
+            Err(BlockError::NonCanonicalCbor { cid: c }) if c == cid
+        ));
+    }
+
+    #[test]
+    fn rejects_non_minimal_integer() {
+        // {"a": 1} with the integer encoded in a one-byte argument instead of inline
+        let data = [0xa1, 0x61, b'a', 0x18, 0x01];
+        let cid = crate::mst::util::compute_cid(&data).unwrap();
+        assert!(matches!(
+            verify_canonical(&cid, &data),
+            Err(BlockError::NonCanonicalCbor { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn put_many_strict_rejects_whole_batch() {
+        let storage = MemoryBlockStore::new();
+        let good = canonical_block();
+        let good_cid = crate::mst::util::compute_cid(&good).unwrap();
+        let bad = vec![0xa1, 0x61, b'a', 0x18, 0x01];
+        let bad_cid = crate::mst::util::compute_cid(&bad).unwrap();
+
+        let err = put_many_strict(
+            &storage,
+            [(good_cid, Bytes::from(good)), (bad_cid, Bytes::from(bad))],
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(*err.kind(), RepoErrorKind::Serialization);
+        assert!(!storage.has(&good_cid).await.unwrap());
+
+        let cid = put_strict(&storage, &canonical_block()).await.unwrap();
+        assert_eq!(cid, good_cid);
+    }
+
+    #[tokio::test]
+    async fn put_many_strict_rejects_cid_mismatch() {
+        let storage = MemoryBlockStore::new();
+        let good = canonical_block();
+        let good_cid = crate::mst::util::compute_cid(&good).unwrap();
+        // Canonical, but paired with another block's CID
+        let other = serde_ipld_dagcbor::to_vec(&Ipld::Integer(7)).unwrap();
+
+        let err = put_many_strict(
+            &storage,
+            [
+                (good_cid, Bytes::from(good.clone())),
+                (good_cid, Bytes::from(other)),
+            ],
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(*err.kind(), RepoErrorKind::CidMismatch);
+        assert!(!storage.has(&good_cid).await.unwrap());
+
+        put_many_strict(&storage, [(good_cid, Bytes::from(good))])
+            .await
+            .unwrap();
+        assert!(storage.has(&good_cid).await.unwrap());
+    }
+}
@@ -2,13 +2,17 @@
 
 use std::collections::BTreeMap;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
 
 use bytes::Bytes;
 use cid::Cid as IpldCid;
 
-use crate::error::Result;
-use crate::storage::BlockStore;
+use crate::error::{RepoError, Result};
+use crate::storage::{BlockStore, CollectableBlockStore};
+
+/// Distinguishes temporary files of concurrent flushes
+static FLUSH_COUNTER: AtomicU64 = AtomicU64::new(0);
 
 /// CAR file-backed block storage
 ///
@@ -16,6 +20,12 @@ use crate::storage::BlockStore;
 /// For very large CAR files, consider database-backed storage instead.
 ///
 /// Primarily useful for testing and simple file-based persistence.
+///
+/// Writes are cancellation-safe: `put` and friends only touch the in-memory map
+/// and never await, and `flush()` writes to a temporary file next to the CAR and
+/// renames it into place. Dropping a `flush()` future part-way through leaves the
+/// previous CAR file intact and the store still marked dirty, and the temporary
+/// file is removed once the blocking write it started has finished.
 #[derive(Debug, Clone)]
 pub struct FileBlockStore {
     path: PathBuf,
@@ -78,6 +88,10 @@ impl FileBlockStore {
     /// This is an async operation that writes the entire block store to the
     /// CAR file. Only writes if there have been changes since the last flush.
     ///
+    /// The CAR is written and synced to a temporary file in the same directory,
+    /// then atomically renamed over the target, so readers never observe a
+    /// partially-written file even if this future is dropped or the process dies.
+    ///
     /// # Errors
     ///
     /// Returns an error if the CAR file cannot be written.
@@ -88,7 +102,35 @@ impl FileBlockStore {
 
         let blocks = self.blocks.read().unwrap().clone();
         let roots = self.roots.read().unwrap().clone();
-        crate::car::write_car(&self.path, roots, blocks).await?;
+
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(format!(
+            ".{}.{}.tmp",
+            std::process::id(),
+            FLUSH_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let tmp_path = PathBuf::from(tmp_path);
+
+        let mut car = Vec::new();
+        crate::car::writer::write_car_to(&mut car, roots, blocks).await?;
+
+        // The guard goes into the blocking task and comes back with its result, so if this
+        // future is dropped first, the guard is dropped when the task ends and the file with it
+        let guard = TempFileGuard(Some(tmp_path.clone()));
+        let task_path = tmp_path.clone();
+        let mut guard = tokio::task::spawn_blocking(move || {
+            write_synced(&task_path, &car)?;
+            Ok::<_, RepoError>(guard)
+        })
+        .await
+        .map_err(|e| RepoError::io(e).with_context("writing CAR file"))??;
+        tokio::fs::rename(&tmp_path, &self.path)
+            .await
+            .map_err(|e| {
+                RepoError::io(e)
+                    .with_context(format!("replacing CAR file: {}", self.path.display()))
+            })?;
+        guard.0 = None;
 
         *self.dirty.write().unwrap() = false;
         Ok(())
@@ -105,6 +147,32 @@ impl FileBlockStore {
     }
 }
 
+/// Create `path` and write and sync `bytes` to it
+fn write_synced(path: &std::path::Path, bytes: &[u8]) -> Result<()> {
+    use std::io::Write;
+
+    let mut file = std::fs::File::create(path).map_err(|e| {
+        RepoError::io(e).with_context(format!("creating temporary CAR file: {}", path.display()))
+    })?;
+    file.write_all(bytes)
+        .map_err(|e| RepoError::io(e).with_context("writing CAR file"))?;
+    file.sync_all()
+        .map_err(|e| RepoError::io(e).with_context("syncing CAR file"))
+}
+
+/// Removes a temporary file when dropped, unless it has been taken
+///
+/// Covers both errors and a `flush()` future dropped before the rename.
+struct TempFileGuard(Option<PathBuf>);
+
+impl Drop for TempFileGuard {
+    fn drop(&mut self) {
+        if let Some(path) = self.0.take() {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
 impl BlockStore for FileBlockStore {
     async fn get(&self, cid: &IpldCid) -> Result<Option<Bytes>> {
         Ok(self.blocks.read().unwrap().get(cid).cloned())
@@ -163,6 +231,21 @@ impl BlockStore for FileBlockStore {
     }
 }
 
+impl CollectableBlockStore for FileBlockStore {
+    async fn list_cids(&self) -> Result<Vec<IpldCid>> {
+        Ok(self.blocks.read().unwrap().keys().copied().collect())
+    }
+
+    async fn delete_many(&self, cids: &[IpldCid]) -> Result<()> {
+        let mut store = self.blocks.write().unwrap();
+        for cid in cids {
+            store.remove(cid);
+        }
+        *self.dirty.write().unwrap() = true;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -290,4 +373,54 @@ mod tests {
         storage.flush().await.unwrap();
         assert!(!storage.is_dirty());
     }
+
+    #[test]
+    fn test_cancelled_flush_keeps_previous_file() {
+        let runtime = || {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap()
+        };
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("repo.car");
+
+        let storage = FileBlockStore::new(&path);
+        let (cid1, cid2) = runtime().block_on(async {
+            let cid1 = storage.put(b"test data 1").await.unwrap();
+            storage.set_roots(vec![cid1]);
+            storage.flush().await.unwrap();
+            (cid1, storage.put(b"test data 2").await.unwrap())
+        });
+        let original = std::fs::read(&path).unwrap();
+
+        // Start a flush and drop it at its first await point, while the file is being
+        // written on the blocking pool
+        let rt = runtime();
+        let polled = rt.block_on(n0_future::future::poll_once(storage.flush()));
+        assert!(polled.is_none(), "flush should still be pending");
+        // Shutting the runtime down waits for the blocking write to finish
+        drop(rt);
+
+        assert_eq!(std::fs::read(&path).unwrap(), original);
+        // The temporary file was cleaned up after the dropped future
+        let leftovers: Vec<_> = std::fs::read_dir(temp_dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .filter(|name| name != "repo.car")
+            .collect();
+        assert!(leftovers.is_empty(), "leftover files: {leftovers:?}");
+        assert!(storage.is_dirty());
+
+        runtime().block_on(async {
+            let reloaded = FileBlockStore::load(&path).await.unwrap();
+            assert_eq!(reloaded.roots(), vec![cid1]);
+            assert!(reloaded.has(&cid1).await.unwrap());
+
+            // A later flush still persists everything
+            storage.flush().await.unwrap();
+            let reloaded = FileBlockStore::load(&path).await.unwrap();
+            assert!(reloaded.has(&cid2).await.unwrap());
+        });
+    }
 }
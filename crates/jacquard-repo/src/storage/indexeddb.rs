@@ -0,0 +1,278 @@
+//! IndexedDB block storage for browsers
+//!
+//! Persists blocks in a single IndexedDB object store keyed by CID bytes, so a repo mirror
+//! survives page reloads. Only available on wasm targets with the `indexeddb` feature.
+
+use crate::error::{RepoError, Result};
+use crate::repo::CommitData;
+use crate::storage::BlockStore;
+use bytes::Bytes;
+use cid::Cid as IpldCid;
+use js_sys::{Promise, Uint8Array};
+use send_wrapper::SendWrapper;
+use std::fmt;
+use std::sync::Arc;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{IdbDatabase, IdbFactory, IdbRequest, IdbTransaction, IdbTransactionMode};
+
+/// Object store holding the blocks
+const STORE: &str = "blocks";
+
+/// Schema version; bump when the object store layout changes
+const DB_VERSION: u32 = 1;
+
+/// Error reported by the browser's IndexedDB implementation
+#[derive(Debug, thiserror::Error)]
+#[error("IndexedDB error: {0}")]
+pub struct IndexedDbError(String);
+
+impl From<JsValue> for IndexedDbError {
+    fn from(value: JsValue) -> Self {
+        let msg = if let Some(s) = value.as_string() {
+            s
+        } else if let Some(e) = value.dyn_ref::<js_sys::Error>() {
+            String::from(e.message())
+        } else {
+            format!("{value:?}")
+        };
+        Self(msg)
+    }
+}
+
+fn js_err(value: JsValue) -> RepoError {
+    RepoError::storage(IndexedDbError::from(value))
+}
+
+/// IndexedDB-backed block storage
+///
+/// Each block is stored as a `Uint8Array` under its binary CID. Batch operations
+/// (`put_many`, `get_many`, `apply_commit`) run in a single IndexedDB transaction, so a
+/// commit is applied atomically.
+///
+/// The browser's JS handles aren't `Send`, but wasm is single-threaded, so the database
+/// handle and operation futures are wrapped in [`SendWrapper`] to satisfy [`BlockStore`]'s
+/// `Send` bounds. Don't move the store to another thread (e.g. a web worker via shared
+/// memory) - `SendWrapper` panics if it's accessed off the thread that created it.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use jacquard_repo::storage::{BlockStore, IndexedDbBlockStore};
+///
+/// let storage = IndexedDbBlockStore::open("my-repo-mirror").await?;
+/// let cid = storage.put(b"hello world").await?;
+/// assert!(storage.has(&cid).await?);
+/// ```
+#[derive(Clone)]
+pub struct IndexedDbBlockStore {
+    db: Arc<SendWrapper<IdbDatabase>>,
+    name: String,
+}
+
+impl fmt::Debug for IndexedDbBlockStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IndexedDbBlockStore")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl IndexedDbBlockStore {
+    /// Open (or create) the named database
+    ///
+    /// Works in both window and worker contexts.
+    pub async fn open(name: impl Into<String>) -> Result<Self> {
+        let name = name.into();
+        SendWrapper::new(Self::open_inner(name)).await
+    }
+
+    async fn open_inner(name: String) -> Result<Self> {
+        let factory: IdbFactory = js_sys::Reflect::get(&js_sys::global(), &"indexedDB".into())
+            .map_err(js_err)?
+            .dyn_into()
+            .map_err(|_| js_err("IndexedDB is not available in this context".into()))?;
+
+        let request = factory.open_with_u32(&name, DB_VERSION).map_err(js_err)?;
+        let upgrade_request = request.clone();
+        let on_upgrade = Closure::<dyn FnMut(web_sys::Event)>::new(move |_event| {
+            if let Ok(db) = upgrade_request.result() {
+                let db: IdbDatabase = db.unchecked_into();
+                if !db.object_store_names().contains(STORE) {
+                    let _ = db.create_object_store(STORE);
+                }
+            }
+        });
+        request.set_onupgradeneeded(Some(on_upgrade.as_ref().unchecked_ref()));
+
+        let opened = request_done(&request).await;
+        request.set_onupgradeneeded(None);
+        drop(on_upgrade);
+
+        let db: IdbDatabase = opened?.dyn_into().map_err(js_err)?;
+        Ok(Self {
+            db: Arc::new(SendWrapper::new(db)),
+            name,
+        })
+    }
+
+    /// Name of the underlying database
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn transaction(&self, mode: IdbTransactionMode) -> Result<IdbTransaction> {
+        self.db
+            .transaction_with_str_and_mode(STORE, mode)
+            .map_err(js_err)
+    }
+
+    async fn get_inner(&self, cid: &IpldCid) -> Result<Option<Bytes>> {
+        let tx = self.transaction(IdbTransactionMode::Readonly)?;
+        let store = tx.object_store(STORE).map_err(js_err)?;
+        let request = store.get(&cid_key(cid)).map_err(js_err)?;
+        Ok(block_from_js(request_done(&request).await?))
+    }
+
+    async fn has_inner(&self, cid: &IpldCid) -> Result<bool> {
+        let tx = self.transaction(IdbTransactionMode::Readonly)?;
+        let store = tx.object_store(STORE).map_err(js_err)?;
+        let request = store.count_with_key(&cid_key(cid)).map_err(js_err)?;
+        let count = request_done(&request).await?;
+        Ok(count.as_f64().unwrap_or(0.0) > 0.0)
+    }
+
+    async fn get_many_inner(&self, cids: &[IpldCid]) -> Result<Vec<Option<Bytes>>> {
+        let tx = self.transaction(IdbTransactionMode::Readonly)?;
+        let store = tx.object_store(STORE).map_err(js_err)?;
+
+        // Issue every request before awaiting any: the transaction auto-commits once
+        // control returns to the event loop with nothing pending.
+        let pending = cids
+            .iter()
+            .map(|cid| {
+                let request = store.get(&cid_key(cid)).map_err(js_err)?;
+                Ok((request_future(&request), request))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut results = Vec::with_capacity(pending.len());
+        for (done, request) in pending {
+            done.await.map_err(|_| request_error(&request))?;
+            results.push(block_from_js(request.result().map_err(js_err)?));
+        }
+        Ok(results)
+    }
+
+    /// Write `blocks` and delete `deletes` in one readwrite transaction
+    async fn write_inner(
+        &self,
+        blocks: Vec<(IpldCid, Bytes)>,
+        deletes: Vec<IpldCid>,
+    ) -> Result<()> {
+        let tx = self.transaction(IdbTransactionMode::Readwrite)?;
+        let done = transaction_future(&tx);
+        let store = tx.object_store(STORE).map_err(js_err)?;
+
+        for (cid, data) in &blocks {
+            let value = Uint8Array::from(data.as_ref());
+            store.put_with_key(&value, &cid_key(cid)).map_err(js_err)?;
+        }
+        for cid in &deletes {
+            store.delete(&cid_key(cid)).map_err(js_err)?;
+        }
+
+        done.await.map_err(|e| match tx.error() {
+            Some(err) => js_err(err.into()),
+            None => js_err(e),
+        })?;
+        Ok(())
+    }
+}
+
+impl BlockStore for IndexedDbBlockStore {
+    async fn get(&self, cid: &IpldCid) -> Result<Option<Bytes>> {
+        SendWrapper::new(self.get_inner(cid)).await
+    }
+
+    async fn put(&self, data: &[u8]) -> Result<IpldCid> {
+        let cid = crate::mst::util::compute_cid(data)?;
+        let blocks = vec![(cid, Bytes::copy_from_slice(data))];
+        SendWrapper::new(self.write_inner(blocks, Vec::new())).await?;
+        Ok(cid)
+    }
+
+    async fn has(&self, cid: &IpldCid) -> Result<bool> {
+        SendWrapper::new(self.has_inner(cid)).await
+    }
+
+    async fn put_many(
+        &self,
+        blocks: impl IntoIterator<Item = (IpldCid, Bytes)> + Send,
+    ) -> Result<()> {
+        let blocks: Vec<_> = blocks.into_iter().collect();
+        if blocks.is_empty() {
+            return Ok(());
+        }
+        SendWrapper::new(self.write_inner(blocks, Vec::new())).await
+    }
+
+    async fn get_many(&self, cids: &[IpldCid]) -> Result<Vec<Option<Bytes>>> {
+        if cids.is_empty() {
+            return Ok(Vec::new());
+        }
+        SendWrapper::new(self.get_many_inner(cids)).await
+    }
+
+    async fn apply_commit(&self, commit: CommitData) -> Result<()> {
+        let blocks = commit.blocks.into_iter().collect();
+        SendWrapper::new(self.write_inner(blocks, commit.deleted_cids)).await
+    }
+}
+
+fn cid_key(cid: &IpldCid) -> JsValue {
+    Uint8Array::from(cid.to_bytes().as_slice()).into()
+}
+
+fn block_from_js(value: JsValue) -> Option<Bytes> {
+    if value.is_undefined() || value.is_null() {
+        None
+    } else {
+        Some(Bytes::from(Uint8Array::new(&value).to_vec()))
+    }
+}
+
+/// Future resolving when `request` succeeds
+///
+/// Handlers are attached immediately, so create this before yielding to the event loop.
+fn request_future(request: &IdbRequest) -> JsFuture {
+    JsFuture::from(Promise::new(&mut |resolve, reject| {
+        request.set_onsuccess(Some(&resolve));
+        request.set_onerror(Some(&reject));
+    }))
+}
+
+/// Await `request` and return its result
+async fn request_done(request: &IdbRequest) -> Result<JsValue> {
+    request_future(request)
+        .await
+        .map_err(|_| request_error(request))?;
+    request.result().map_err(js_err)
+}
+
+fn request_error(request: &IdbRequest) -> RepoError {
+    match request.error() {
+        Ok(Some(err)) => js_err(err.into()),
+        _ => js_err("IndexedDB request failed".into()),
+    }
+}
+
+/// Future resolving when `tx` commits, rejecting if it errors or aborts
+fn transaction_future(tx: &IdbTransaction) -> JsFuture {
+    JsFuture::from(Promise::new(&mut |resolve, reject| {
+        tx.set_oncomplete(Some(&resolve));
+        tx.set_onerror(Some(&reject));
+        tx.set_onabort(Some(&reject));
+    }))
+}
@@ -5,15 +5,19 @@
 //!
 //! This is used for firehose validation to avoid copying the entire previous MST tree.
 
-use crate::error::Result;
-use crate::storage::BlockStore;
+use crate::commit::Commit;
+use crate::error::{RepoError, Result};
+use crate::mst::Mst;
+use crate::storage::{BlockStore, CollectableBlockStore};
 use bytes::Bytes;
 use cid::Cid as IpldCid;
+use std::collections::HashSet;
+use std::sync::Arc;
 
 /// Layered block storage with a writable overlay and read-only base
 ///
 /// Reads check the writable layer first, then fall back to the base layer.
-/// All writes go to the writable layer only.
+/// All writes go to the writable layer only, until [`flush`](Self::flush) moves them down.
 ///
 /// # Use Case
 ///
@@ -45,7 +49,7 @@ use cid::Cid as IpldCid;
 #[derive(Clone)]
 pub struct LayeredBlockStore<W: BlockStore, B: BlockStore> {
     writable: W,
-    base: std::sync::Arc<B>,
+    base: Arc<B>,
 }
 
 impl<W: BlockStore, B: BlockStore> LayeredBlockStore<W, B> {
@@ -53,7 +57,7 @@ impl<W: BlockStore, B: BlockStore> LayeredBlockStore<W, B> {
     ///
     /// - `writable`: Top layer receiving all writes
     /// - `base`: Bottom layer for fallback reads (read-only, Arc-wrapped to avoid cloning)
-    pub fn new(writable: W, base: std::sync::Arc<B>) -> Self {
+    pub fn new(writable: W, base: Arc<B>) -> Self {
         Self { writable, base }
     }
 
@@ -63,11 +67,94 @@ impl<W: BlockStore, B: BlockStore> LayeredBlockStore<W, B> {
     }
 
     /// Get reference to the base layer
-    pub fn base(&self) -> &std::sync::Arc<B> {
+    pub fn base(&self) -> &Arc<B> {
         &self.base
     }
 }
 
+impl<W, B> LayeredBlockStore<W, B>
+where
+    W: BlockStore + Sync + 'static,
+    B: CollectableBlockStore + Sync + 'static,
+{
+    /// Delete base-layer blocks that no live root can reach
+    ///
+    /// Each of `live_roots` is either a commit or an MST root node. A live commit keeps itself,
+    /// its MST nodes and the record blocks its leaves point to. Its `prev` commit is not
+    /// followed, so older commits, and nodes and records only they reference, are collected
+    /// unless another live root points at them.
+    ///
+    /// Reachable blocks are read through both layers, but only the base layer is swept. If a
+    /// live root or one of its MST nodes can't be loaded, this fails before deleting anything.
+    ///
+    /// Returns the number of blocks removed.
+    pub async fn gc(&self, live_roots: &[IpldCid]) -> Result<usize> {
+        let storage = Arc::new(self.clone());
+        let mut live = HashSet::new();
+
+        for root in live_roots {
+            let bytes = self.get(root).await?.ok_or_else(|| {
+                RepoError::not_found("block", root)
+                    .with_context("loading live root for garbage collection")
+            })?;
+            let mst_root = match Commit::from_cbor_versioned(&bytes) {
+                Ok(commit) => {
+                    live.insert(*root);
+                    *commit.data()
+                }
+                // Not a commit, so it should be an MST node
+                Err(_) => *root,
+            };
+
+            let mst = Mst::load(storage.clone(), mst_root, None);
+            live.extend(mst.collect_node_cids().await?);
+            live.extend(mst.leaves().await?.into_iter().map(|(_key, cid)| cid));
+        }
+
+        let garbage: Vec<IpldCid> = self
+            .base
+            .list_cids()
+            .await?
+            .into_iter()
+            .filter(|cid| !live.contains(cid))
+            .collect();
+        self.base.delete_many(&garbage).await?;
+        Ok(garbage.len())
+    }
+}
+
+impl<W, B> LayeredBlockStore<W, B>
+where
+    W: CollectableBlockStore + Sync + 'static,
+    B: BlockStore + Sync + 'static,
+{
+    /// Move the writable layer's blocks down into the base layer
+    ///
+    /// Blocks the base doesn't have yet are written to it in one `put_many` batch, then the
+    /// writable layer is emptied. Use this once a validated commit should be kept, instead of
+    /// discarding the overlay.
+    ///
+    /// Returns the number of blocks copied to the base layer. Blocks already present in the
+    /// base are dropped from the writable layer without being counted.
+    pub async fn flush(&self) -> Result<usize> {
+        let cids = self.writable.list_cids().await?;
+        let blocks = self.writable.get_many(&cids).await?;
+
+        let mut upper_only = Vec::new();
+        for (cid, data) in cids.iter().zip(blocks) {
+            let Some(data) = data else { continue };
+            if !self.base.has(cid).await? {
+                upper_only.push((*cid, data));
+            }
+        }
+
+        let copied = upper_only.len();
+        self.base.put_many(upper_only).await?;
+        self.writable.delete_many(&cids).await?;
+        Ok(copied)
+    }
+}
+
 impl<W: BlockStore + Sync + 'static, B: BlockStore + Sync + 'static> BlockStore
     for LayeredBlockStore<W, B>
 {
@@ -203,6 +290,115 @@ mod tests {
         assert!(!base.has(&cid).await.unwrap());
     }
 
+    #[tokio::test]
+    async fn test_gc_collects_unreachable_base_blocks() {
+        use jacquard_common::types::string::Did;
+        use jacquard_common::types::tid::Ticker;
+
+        let base = Arc::new(MemoryBlockStore::new());
+        let did = Did::new("did:plc:test").unwrap();
+        let mut ticker = Ticker::new();
+        let record = |text: &str| serde_ipld_dagcbor::to_vec(&text).unwrap();
+
+        // First commit: 50 records
+        let mut records = Vec::new();
+        let mut mst1 = Mst::new(base.clone());
+        for n in 0..50 {
+            let cid = base.put(&record(&format!("record {}", n))).await.unwrap();
+            mst1 = mst1
+                .add(&format!("com.example.test/{:04}", n), cid)
+                .await
+                .unwrap();
+            records.push(cid);
+        }
+        let root1 = mst1.persist().await.unwrap();
+        let commit1 = Commit::new_unsigned(did.clone(), root1, ticker.next(None), None);
+        let commit1_cid = base.put(&commit1.to_cbor().unwrap()).await.unwrap();
+
+        // Second commit overwrites one record
+        let replacement = base.put(&record("replacement")).await.unwrap();
+        let mst2 = mst1
+            .update("com.example.test/0007", replacement)
+            .await
+            .unwrap();
+        let root2 = mst2.persist().await.unwrap();
+        let commit2 = Commit::new_unsigned(did, root2, ticker.next(None), Some(commit1_cid));
+        let commit2_cid = base.put(&commit2.to_cbor().unwrap()).await.unwrap();
+
+        // A block only in the writable layer is never touched
+        let writable = MemoryBlockStore::new();
+        let scratch = writable.put(b"scratch").await.unwrap();
+        let layered = LayeredBlockStore::new(writable.clone(), base.clone());
+
+        // Both commits live: nothing to collect
+        assert_eq!(layered.gc(&[commit2_cid, commit1_cid]).await.unwrap(), 0);
+
+        let old_nodes: HashSet<_> = mst1
+            .collect_node_cids()
+            .await
+            .unwrap()
+            .into_iter()
+            .collect();
+        let new_nodes: HashSet<_> = mst2
+            .collect_node_cids()
+            .await
+            .unwrap()
+            .into_iter()
+            .collect();
+        let orphaned_nodes = old_nodes.difference(&new_nodes).count();
+        assert!(orphaned_nodes > 0);
+
+        let before = base.len();
+        let removed = layered.gc(&[commit2_cid]).await.unwrap();
+        // The old commit, the overwritten record and the nodes on its old path
+        assert_eq!(removed, 2 + orphaned_nodes);
+        assert_eq!(base.len(), before - removed);
+
+        assert!(!base.has(&commit1_cid).await.unwrap());
+        assert!(!base.has(&root1).await.unwrap());
+        assert!(!base.has(&records[7]).await.unwrap());
+        for cid in old_nodes.difference(&new_nodes) {
+            assert!(!base.has(cid).await.unwrap());
+        }
+
+        assert!(base.has(&commit2_cid).await.unwrap());
+        assert!(base.has(&replacement).await.unwrap());
+        for cid in &new_nodes {
+            assert!(base.has(cid).await.unwrap());
+        }
+        for (n, cid) in records.iter().enumerate() {
+            assert_eq!(base.has(cid).await.unwrap(), n != 7);
+        }
+        assert!(writable.has(&scratch).await.unwrap());
+
+        // A missing live root is an error, not an empty live set
+        let missing = crate::mst::util::compute_cid(b"missing").unwrap();
+        assert!(layered.gc(&[missing]).await.is_err());
+        assert!(base.has(&commit2_cid).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_flush_moves_writable_blocks_to_base() {
+        let base = Arc::new(MemoryBlockStore::new());
+        let writable = MemoryBlockStore::new();
+
+        let shared = base.put(b"already in base").await.unwrap();
+        let layered = LayeredBlockStore::new(writable.clone(), base.clone());
+        let cid = layered.put(b"new data").await.unwrap();
+        layered.put(b"already in base").await.unwrap();
+        assert!(!base.has(&cid).await.unwrap());
+
+        assert_eq!(layered.flush().await.unwrap(), 1);
+
+        assert_eq!(&*base.get(&cid).await.unwrap().unwrap(), b"new data");
+        assert!(base.has(&shared).await.unwrap());
+        assert!(writable.is_empty());
+
+        // Still readable through the layers, now from the base
+        assert_eq!(&*layered.get(&cid).await.unwrap().unwrap(), b"new data");
+        assert_eq!(layered.flush().await.unwrap(), 0);
+    }
+
     #[tokio::test]
     async fn test_layered_has_checks_both_layers() {
         let base = Arc::new(MemoryBlockStore::new());
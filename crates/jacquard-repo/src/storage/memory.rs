@@ -1,7 +1,7 @@
 //! In-memory block storage implementation
 
 use crate::error::Result;
-use crate::storage::BlockStore;
+use crate::storage::{BlockStore, CollectableBlockStore};
 use bytes::Bytes;
 use cid::Cid as IpldCid;
 use std::collections::BTreeMap;
@@ -137,6 +137,20 @@ impl BlockStore for MemoryBlockStore {
     }
 }
 
+impl CollectableBlockStore for MemoryBlockStore {
+    async fn list_cids(&self) -> Result<Vec<IpldCid>> {
+        Ok(self.blocks.read().unwrap().keys().copied().collect())
+    }
+
+    async fn delete_many(&self, cids: &[IpldCid]) -> Result<()> {
+        let mut store = self.blocks.write().unwrap();
+        for cid in cids {
+            store.remove(cid);
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -152,6 +166,20 @@ mod tests {
         assert_eq!(retrieved.as_deref(), Some(&data[..]));
     }
 
+    #[tokio::test]
+    async fn test_cid_helpers_match_put() {
+        let store = MemoryBlockStore::new();
+        let record = serde_ipld_dagcbor::to_vec(&("app.bsky.feed.post", 42)).unwrap();
+
+        let cid = store.put(&record).await.unwrap();
+        assert_eq!(crate::cid_for_dag_cbor(&record).unwrap(), cid);
+
+        let blob = crate::cid_for_raw(&record).unwrap();
+        assert_eq!(blob.codec(), crate::RAW_CID_CODEC);
+        assert_eq!(blob.hash(), cid.hash());
+        assert_ne!(blob, cid);
+    }
+
     #[tokio::test]
     async fn test_has() {
         let store = MemoryBlockStore::new();
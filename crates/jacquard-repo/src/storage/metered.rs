@@ -0,0 +1,201 @@
+//! Block store access metrics
+//!
+//! Wraps any block store with counters for calls and bytes moved, for exporting to a
+//! metrics system (e.g. as Prometheus counters) without touching the store itself.
+
+use crate::error::Result;
+use crate::repo::CommitData;
+use crate::storage::BlockStore;
+use bytes::Bytes;
+use cid::Cid as IpldCid;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Point-in-time copy of a [`MeteredBlockStore`]'s counters
+///
+/// Every counter only ever increases, so rates can be computed from the difference between
+/// two snapshots.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StorageMetrics {
+    /// Single-block `get` calls
+    pub gets: u64,
+    /// Single-block `put` calls
+    pub puts: u64,
+    /// `has` calls
+    pub has_calls: u64,
+    /// Batch calls: `get_many`, `put_many` and `apply_commit`
+    pub batch_calls: u64,
+    /// Total size of the blocks returned by reads
+    pub bytes_read: u64,
+    /// Total size of the blocks handed to writes
+    pub bytes_written: u64,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    gets: AtomicU64,
+    puts: AtomicU64,
+    has_calls: AtomicU64,
+    batch_calls: AtomicU64,
+    bytes_read: AtomicU64,
+    bytes_written: AtomicU64,
+}
+
+impl Counters {
+    fn bump(counter: &AtomicU64, by: usize) {
+        counter.fetch_add(by as u64, Ordering::Relaxed);
+    }
+}
+
+/// Block store wrapper that counts calls and bytes
+///
+/// Every [`BlockStore`] method delegates to the inner store. Calls are counted whether or not
+/// they succeed; bytes are counted for blocks actually read, and for blocks passed to a write
+/// that succeeded. Batch methods count as one batch call each, with `get_many` and
+/// `put_many` adding the size of every block they move, and `apply_commit` the size of the
+/// commit's new blocks.
+///
+/// Clones share the counters.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use jacquard_repo::storage::{MemoryBlockStore, MeteredBlockStore};
+///
+/// let storage = MeteredBlockStore::new(MemoryBlockStore::new());
+/// let mst = Mst::new(Arc::new(storage.clone()));
+/// // ... build the tree ...
+/// let metrics = storage.snapshot();
+/// println!("{} bytes written in {} puts", metrics.bytes_written, metrics.puts);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MeteredBlockStore<S> {
+    inner: S,
+    counters: Arc<Counters>,
+}
+
+impl<S> MeteredBlockStore<S> {
+    /// Wrap `inner`, with all counters starting at zero
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            counters: Arc::new(Counters::default()),
+        }
+    }
+
+    /// The wrapped store
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Current value of every counter
+    pub fn snapshot(&self) -> StorageMetrics {
+        let c = &self.counters;
+        StorageMetrics {
+            gets: c.gets.load(Ordering::Relaxed),
+            puts: c.puts.load(Ordering::Relaxed),
+            has_calls: c.has_calls.load(Ordering::Relaxed),
+            batch_calls: c.batch_calls.load(Ordering::Relaxed),
+            bytes_read: c.bytes_read.load(Ordering::Relaxed),
+            bytes_written: c.bytes_written.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl<S: BlockStore + Sync + 'static> BlockStore for MeteredBlockStore<S> {
+    async fn get(&self, cid: &IpldCid) -> Result<Option<Bytes>> {
+        Counters::bump(&self.counters.gets, 1);
+        let data = self.inner.get(cid).await?;
+        if let Some(data) = &data {
+            Counters::bump(&self.counters.bytes_read, data.len());
+        }
+        Ok(data)
+    }
+
+    async fn put(&self, data: &[u8]) -> Result<IpldCid> {
+        Counters::bump(&self.counters.puts, 1);
+        let cid = self.inner.put(data).await?;
+        Counters::bump(&self.counters.bytes_written, data.len());
+        Ok(cid)
+    }
+
+    async fn has(&self, cid: &IpldCid) -> Result<bool> {
+        Counters::bump(&self.counters.has_calls, 1);
+        self.inner.has(cid).await
+    }
+
+    async fn put_many(
+        &self,
+        blocks: impl IntoIterator<Item = (IpldCid, Bytes)> + Send,
+    ) -> Result<()> {
+        Counters::bump(&self.counters.batch_calls, 1);
+        let blocks: Vec<(IpldCid, Bytes)> = blocks.into_iter().collect();
+        let size = blocks.iter().map(|(_, data)| data.len()).sum();
+        self.inner.put_many(blocks).await?;
+        Counters::bump(&self.counters.bytes_written, size);
+        Ok(())
+    }
+
+    async fn get_many(&self, cids: &[IpldCid]) -> Result<Vec<Option<Bytes>>> {
+        Counters::bump(&self.counters.batch_calls, 1);
+        let results = self.inner.get_many(cids).await?;
+        let size = results.iter().flatten().map(Bytes::len).sum();
+        Counters::bump(&self.counters.bytes_read, size);
+        Ok(results)
+    }
+
+    async fn apply_commit(&self, commit: CommitData) -> Result<()> {
+        Counters::bump(&self.counters.batch_calls, 1);
+        let size = commit.blocks.values().map(Bytes::len).sum();
+        self.inner.apply_commit(commit).await?;
+        Counters::bump(&self.counters.bytes_written, size);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mst::util::compute_cid;
+    use crate::storage::MemoryBlockStore;
+
+    #[tokio::test]
+    async fn test_snapshot_counts_operations() {
+        let store = MeteredBlockStore::new(MemoryBlockStore::new());
+        assert_eq!(store.snapshot(), StorageMetrics::default());
+
+        let a = store.put(b"aaaa").await.unwrap();
+        let b = compute_cid(b"bbbbbb").unwrap();
+        let c = compute_cid(b"cc").unwrap();
+        store
+            .put_many(vec![
+                (b, Bytes::from_static(b"bbbbbb")),
+                (c, Bytes::from_static(b"cc")),
+            ])
+            .await
+            .unwrap();
+
+        store.get(&a).await.unwrap();
+        let missing = compute_cid(b"missing").unwrap();
+        assert_eq!(store.get(&missing).await.unwrap(), None);
+        assert!(store.has(&b).await.unwrap());
+
+        let results = store.get_many(&[a, b, c, missing]).await.unwrap();
+        assert_eq!(results.iter().flatten().count(), 3);
+
+        // Clones share the counters
+        store.clone().has(&missing).await.unwrap();
+
+        assert_eq!(
+            store.snapshot(),
+            StorageMetrics {
+                gets: 2,
+                puts: 1,
+                has_calls: 2,
+                batch_calls: 2,
+                bytes_read: 4 + (4 + 6 + 2),
+                bytes_written: 4 + (6 + 2),
+            }
+        );
+    }
+}
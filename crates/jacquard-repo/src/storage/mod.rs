@@ -9,16 +9,21 @@ use cid::Cid as IpldCid;
 /// Provides CID-keyed block storage for MST nodes, commits, and record data.
 /// Implementations might use:
 /// - In-memory HashMap ([`MemoryBlockStore`])
-/// - CAR file ([`FileBlockStore`])
-/// - SQLite/RocksDB (user-provided)
+/// - CAR file ([`FileBlockStore`], native only)
+/// - IndexedDB (`IndexedDbBlockStore`, browser only, `indexeddb` feature)
+/// - Amazon S3 (`S3BlockStore`, native only, `s3` feature)
+/// - SQLite (`SqliteBlockStore`, native only, `sqlite` feature)
+/// - RocksDB or other databases (user-provided)
 /// - Remote HTTP storage (user-provided)
 ///
 /// Clone is required so MST can share storage references across tree operations.
 ///
 /// # WASM Compatibility
 ///
-/// The trait uses `trait_variant` to conditionally require `Send` only on non-WASM targets,
-/// allowing it to work in browser environments where `Send` is not available.
+/// The trait's futures are `Send` on every target, since the MST boxes storage futures as
+/// `Send` for parallel traversal. Browser-backed stores hold JS handles that are not `Send`;
+/// because wasm is single-threaded they can wrap those handles and futures in
+/// [`send_wrapper`](https://docs.rs/send_wrapper), as `IndexedDbBlockStore` does.
 ///
 /// # Example
 ///
@@ -67,7 +72,8 @@ pub trait BlockStore: Clone {
     /// # Note
     ///
     /// The provided CIDs should match the data, but implementations may choose to
-    /// recalculate and validate them.
+    /// recalculate and validate them. Bytes are not checked for canonical DAG-CBOR
+    /// encoding; use [`put_many_strict`] for blocks from untrusted sources.
     async fn put_many(
         &self,
         blocks: impl IntoIterator<Item = (IpldCid, Bytes)> + Send,
@@ -93,10 +99,45 @@ pub trait BlockStore: Clone {
     async fn apply_commit(&self, commit: CommitData) -> Result<()>;
 }
 
+/// Block storage that can list and delete its blocks
+///
+/// Needed for garbage collection (see [`LayeredBlockStore::gc`]), which has to find the blocks
+/// nothing references any more and remove them outside of a commit, and for flushing a
+/// layered store's writable layer ([`LayeredBlockStore::flush`]). Implemented by the
+/// bundled native stores; `IndexedDbBlockStore` and `S3BlockStore` don't support it yet.
+#[trait_variant::make(Send)]
+pub trait CollectableBlockStore: BlockStore {
+    /// CIDs of every block in the store, in no particular order
+    async fn list_cids(&self) -> Result<Vec<IpldCid>>;
+
+    /// Delete the given blocks, ignoring any that aren't present
+    async fn delete_many(&self, cids: &[IpldCid]) -> Result<()>;
+}
+
+pub mod caching;
+pub mod canonical;
+#[cfg(not(target_family = "wasm"))]
 pub mod file;
+#[cfg(all(feature = "indexeddb", target_family = "wasm"))]
+pub mod indexeddb;
 pub mod layered;
 pub mod memory;
+pub mod metered;
+#[cfg(all(feature = "s3", not(target_family = "wasm")))]
+pub mod s3;
+#[cfg(all(feature = "sqlite", not(target_family = "wasm")))]
+pub mod sqlite;
 
+pub use caching::CachingBlockStore;
+pub use canonical::{put_many_strict, put_strict, verify_canonical};
+#[cfg(not(target_family = "wasm"))]
 pub use file::FileBlockStore;
+#[cfg(all(feature = "indexeddb", target_family = "wasm"))]
+pub use indexeddb::IndexedDbBlockStore;
 pub use layered::LayeredBlockStore;
 pub use memory::MemoryBlockStore;
+pub use metered::{MeteredBlockStore, StorageMetrics};
+#[cfg(all(feature = "s3", not(target_family = "wasm")))]
+pub use s3::S3BlockStore;
+#[cfg(all(feature = "sqlite", not(target_family = "wasm")))]
+pub use sqlite::SqliteBlockStore;
@@ -0,0 +1,222 @@
+//! Amazon S3 block storage
+//!
+//! Stores each block as one object named after its CID, so a repo can live on S3 or any
+//! S3-compatible object store. Native only, behind the `s3` feature.
+
+use crate::error::{RepoError, Result};
+use crate::repo::CommitData;
+use crate::storage::BlockStore;
+use aws_sdk_s3::Client;
+use aws_sdk_s3::primitives::ByteStream;
+use bytes::Bytes;
+use cid::Cid as IpldCid;
+use n0_future::try_join_all;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// Default number of S3 requests a store keeps in flight at once
+pub const DEFAULT_CONCURRENCY: usize = 32;
+
+/// S3-backed block storage
+///
+/// Each block is one object whose key is the block's CID in its default string form
+/// (base32 for CIDv1), after an optional prefix. The client, bucket and prefix are all
+/// caller-provided, so this works against S3 and S3-compatible services alike.
+///
+/// Batch operations (`put_many`, `get_many`, `apply_commit`) issue their requests
+/// concurrently. A semaphore shared by all clones of the store bounds how many are in flight;
+/// see [`with_concurrency`](Self::with_concurrency).
+///
+/// # Consistency
+///
+/// S3 has no transactions, so `apply_commit` is not atomic. Following the [`BlockStore`]
+/// contract, every block in the commit is uploaded before any of `deleted_cids` is removed.
+/// A failure part-way through can leave some new blocks written, or some deletions undone,
+/// but never removes a block before the blocks that replace it are stored. Leftover blocks
+/// are unreferenced garbage and safe to clean up later.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use jacquard_repo::storage::{BlockStore, S3BlockStore};
+///
+/// let config = aws_config::load_from_env().await;
+/// let storage = S3BlockStore::new(aws_sdk_s3::Client::new(&config), "my-bucket")
+///     .with_prefix("repos/did:plc:abc123/");
+/// let cid = storage.put(b"hello world").await?;
+/// assert!(storage.has(&cid).await?);
+/// ```
+#[derive(Debug, Clone)]
+pub struct S3BlockStore {
+    client: Client,
+    bucket: String,
+    prefix: String,
+    permits: Arc<Semaphore>,
+}
+
+impl S3BlockStore {
+    /// Store blocks in `bucket`, with no key prefix
+    pub fn new(client: Client, bucket: impl Into<String>) -> Self {
+        Self {
+            client,
+            bucket: bucket.into(),
+            prefix: String::new(),
+            permits: Arc::new(Semaphore::new(DEFAULT_CONCURRENCY)),
+        }
+    }
+
+    /// Prepend `prefix` to every object key
+    ///
+    /// The prefix is used verbatim; end it with `/` for a directory-like layout.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Limit batch operations to `limit` concurrent requests
+    ///
+    /// Defaults to [`DEFAULT_CONCURRENCY`]. A limit of zero is treated as one.
+    pub fn with_concurrency(mut self, limit: usize) -> Self {
+        self.permits = Arc::new(Semaphore::new(limit.max(1)));
+        self
+    }
+
+    /// The S3 client
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// The bucket holding the blocks
+    pub fn bucket(&self) -> &str {
+        &self.bucket
+    }
+
+    /// The prefix prepended to object keys
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Object key for a block
+    pub fn object_key(&self, cid: &IpldCid) -> String {
+        format!("{}{}", self.prefix, cid)
+    }
+
+    async fn get_object(&self, cid: &IpldCid) -> Result<Option<Bytes>> {
+        let output = match self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(self.object_key(cid))
+            .send()
+            .await
+        {
+            Ok(output) => output,
+            Err(e) if e.as_service_error().is_some_and(|e| e.is_no_such_key()) => {
+                return Ok(None);
+            }
+            Err(e) => {
+                return Err(
+                    RepoError::storage(e).with_context(format!("fetching block {} from S3", cid))
+                );
+            }
+        };
+        let body = output.body.collect().await.map_err(|e| {
+            RepoError::storage(e).with_context(format!("reading block {} from S3", cid))
+        })?;
+        Ok(Some(body.into_bytes()))
+    }
+
+    async fn put_object(&self, cid: &IpldCid, data: Bytes) -> Result<()> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(self.object_key(cid))
+            .body(ByteStream::from(data))
+            .send()
+            .await
+            .map_err(|e| {
+                RepoError::storage(e).with_context(format!("uploading block {} to S3", cid))
+            })?;
+        Ok(())
+    }
+
+    async fn delete_object(&self, cid: &IpldCid) -> Result<()> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(self.object_key(cid))
+            .send()
+            .await
+            .map_err(|e| {
+                RepoError::storage(e).with_context(format!("deleting block {} from S3", cid))
+            })?;
+        Ok(())
+    }
+
+    /// Hold a concurrency permit for the duration of a request
+    async fn permit(&self) -> tokio::sync::SemaphorePermit<'_> {
+        // The semaphore is never closed
+        self.permits.acquire().await.expect("semaphore closed")
+    }
+}
+
+impl BlockStore for S3BlockStore {
+    async fn get(&self, cid: &IpldCid) -> Result<Option<Bytes>> {
+        self.get_object(cid).await
+    }
+
+    async fn put(&self, data: &[u8]) -> Result<IpldCid> {
+        let cid = crate::mst::util::compute_cid(data)?;
+        self.put_object(&cid, Bytes::copy_from_slice(data)).await?;
+        Ok(cid)
+    }
+
+    async fn has(&self, cid: &IpldCid) -> Result<bool> {
+        match self
+            .client
+            .head_object()
+            .bucket(&self.bucket)
+            .key(self.object_key(cid))
+            .send()
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(e) if e.as_service_error().is_some_and(|e| e.is_not_found()) => Ok(false),
+            Err(e) => {
+                Err(RepoError::storage(e).with_context(format!("checking for block {} in S3", cid)))
+            }
+        }
+    }
+
+    async fn put_many(
+        &self,
+        blocks: impl IntoIterator<Item = (IpldCid, Bytes)> + Send,
+    ) -> Result<()> {
+        let uploads = blocks.into_iter().map(|(cid, data)| async move {
+            let _permit = self.permit().await;
+            self.put_object(&cid, data).await
+        });
+        try_join_all(uploads).await?;
+        Ok(())
+    }
+
+    async fn get_many(&self, cids: &[IpldCid]) -> Result<Vec<Option<Bytes>>> {
+        let fetches = cids.iter().map(|cid| async move {
+            let _permit = self.permit().await;
+            self.get_object(cid).await
+        });
+        try_join_all(fetches).await
+    }
+
+    async fn apply_commit(&self, commit: CommitData) -> Result<()> {
+        // No transactions: write everything first, so a failed delete only leaves garbage
+        self.put_many(commit.blocks).await?;
+
+        let deletes = commit.deleted_cids.iter().map(|cid| async move {
+            let _permit = self.permit().await;
+            self.delete_object(cid).await
+        });
+        try_join_all(deletes).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,310 @@
+//! SQLite block storage
+//!
+//! Keeps blocks in a single `blocks (cid, bytes)` table, so a repo persists locally without
+//! rewriting a whole CAR file on every flush. Native only, behind the `sqlite` feature.
+
+use crate::error::{RepoError, Result};
+use crate::repo::CommitData;
+use crate::storage::{BlockStore, CollectableBlockStore};
+use bytes::Bytes;
+use cid::Cid as IpldCid;
+use rusqlite::{Connection, params, params_from_iter};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Most CIDs bound into one `IN (...)` query (SQLite's default variable limit)
+const MAX_QUERY_PARAMS: usize = 32766;
+
+/// Blocks are content-addressed, so an existing row already holds the same bytes
+const INSERT_BLOCK: &str = "INSERT OR IGNORE INTO blocks (cid, bytes) VALUES (?1, ?2)";
+
+fn sql_err(e: rusqlite::Error) -> RepoError {
+    RepoError::storage(e)
+}
+
+/// SQLite-backed block storage
+///
+/// Blocks live in a `blocks` table keyed by binary CID, created on open if missing. Batch
+/// writes run in a single transaction: `put_many` is all-or-nothing, and `apply_commit`
+/// inserts the commit's blocks and removes `deleted_cids` atomically.
+///
+/// The connection sits behind a mutex and queries run on the calling task. That's fine for
+/// a local database file, but wrap calls in `spawn_blocking` if a slow disk would stall
+/// your runtime.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use jacquard_repo::storage::{BlockStore, SqliteBlockStore};
+///
+/// let storage = SqliteBlockStore::open("repo.sqlite")?;
+/// let cid = storage.put(b"hello world").await?;
+/// assert!(storage.has(&cid).await?);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SqliteBlockStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl SqliteBlockStore {
+    /// Open (or create) a database file
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let conn = Connection::open(path).map_err(|e| {
+            sql_err(e).with_context(format!("opening SQLite database {}", path.display()))
+        })?;
+        Self::from_connection(conn)
+    }
+
+    /// Open a private in-memory database
+    ///
+    /// Blocks are lost when the last clone of the store is dropped.
+    pub fn open_in_memory() -> Result<Self> {
+        Self::from_connection(Connection::open_in_memory().map_err(sql_err)?)
+    }
+
+    /// Use an existing connection, creating the `blocks` table if needed
+    pub fn from_connection(conn: Connection) -> Result<Self> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS blocks (cid BLOB PRIMARY KEY, bytes BLOB NOT NULL)",
+            [],
+        )
+        .map_err(|e| sql_err(e).with_context("creating blocks table"))?;
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+        })
+    }
+}
+
+impl BlockStore for SqliteBlockStore {
+    async fn get(&self, cid: &IpldCid) -> Result<Option<Bytes>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare_cached("SELECT bytes FROM blocks WHERE cid = ?1")
+            .map_err(sql_err)?;
+        let mut rows = stmt.query(params![cid.to_bytes()]).map_err(sql_err)?;
+        match rows.next().map_err(sql_err)? {
+            Some(row) => Ok(Some(Bytes::from(
+                row.get::<_, Vec<u8>>(0).map_err(sql_err)?,
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    async fn put(&self, data: &[u8]) -> Result<IpldCid> {
+        let cid = crate::mst::util::compute_cid(data)?;
+        self.conn
+            .lock()
+            .unwrap()
+            .prepare_cached(INSERT_BLOCK)
+            .and_then(|mut stmt| stmt.execute(params![cid.to_bytes(), data]))
+            .map_err(sql_err)?;
+        Ok(cid)
+    }
+
+    async fn has(&self, cid: &IpldCid) -> Result<bool> {
+        self.conn
+            .lock()
+            .unwrap()
+            .prepare_cached("SELECT 1 FROM blocks WHERE cid = ?1")
+            .and_then(|mut stmt| stmt.exists(params![cid.to_bytes()]))
+            .map_err(sql_err)
+    }
+
+    async fn put_many(
+        &self,
+        blocks: impl IntoIterator<Item = (IpldCid, Bytes)> + Send,
+    ) -> Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(sql_err)?;
+        insert_blocks(&tx, blocks)?;
+        tx.commit().map_err(sql_err)
+    }
+
+    async fn get_many(&self, cids: &[IpldCid]) -> Result<Vec<Option<Bytes>>> {
+        let conn = self.conn.lock().unwrap();
+        let keys: Vec<Vec<u8>> = cids.iter().map(|cid| cid.to_bytes()).collect();
+
+        let mut found = HashMap::with_capacity(keys.len());
+        for chunk in keys.chunks(MAX_QUERY_PARAMS) {
+            let placeholders = vec!["?"; chunk.len()].join(", ");
+            let mut stmt = conn
+                .prepare(&format!(
+                    "SELECT cid, bytes FROM blocks WHERE cid IN ({})",
+                    placeholders
+                ))
+                .map_err(sql_err)?;
+            let rows = stmt
+                .query_map(params_from_iter(chunk), |row| {
+                    Ok((row.get::<_, Vec<u8>>(0)?, row.get::<_, Vec<u8>>(1)?))
+                })
+                .map_err(sql_err)?;
+            for row in rows {
+                let (cid, bytes) = row.map_err(sql_err)?;
+                found.insert(cid, Bytes::from(bytes));
+            }
+        }
+
+        Ok(keys.iter().map(|key| found.get(key).cloned()).collect())
+    }
+
+    async fn apply_commit(&self, commit: CommitData) -> Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(sql_err)?;
+        insert_blocks(&tx, commit.blocks)?;
+        delete_blocks(&tx, &commit.deleted_cids)?;
+        tx.commit()
+            .map_err(|e| sql_err(e).with_context(format!("committing {}", commit.cid)))
+    }
+}
+
+impl CollectableBlockStore for SqliteBlockStore {
+    async fn list_cids(&self) -> Result<Vec<IpldCid>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT cid FROM blocks").map_err(sql_err)?;
+        let rows = stmt
+            .query_map([], |row| row.get::<_, Vec<u8>>(0))
+            .map_err(sql_err)?;
+        rows.map(|row| {
+            let bytes = row.map_err(sql_err)?;
+            IpldCid::try_from(bytes.as_slice())
+                .map_err(|e| RepoError::invalid_cid_conversion(e, "reading CID from SQLite"))
+        })
+        .collect()
+    }
+
+    async fn delete_many(&self, cids: &[IpldCid]) -> Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(sql_err)?;
+        delete_blocks(&tx, cids)?;
+        tx.commit().map_err(sql_err)
+    }
+}
+
+/// Insert blocks within an open transaction
+fn insert_blocks(
+    tx: &rusqlite::Transaction<'_>,
+    blocks: impl IntoIterator<Item = (IpldCid, Bytes)>,
+) -> Result<()> {
+    let mut stmt = tx.prepare_cached(INSERT_BLOCK).map_err(sql_err)?;
+    for (cid, data) in blocks {
+        stmt.execute(params![cid.to_bytes(), &data[..]])
+            .map_err(sql_err)?;
+    }
+    Ok(())
+}
+
+/// Delete blocks within an open transaction
+fn delete_blocks(tx: &rusqlite::Transaction<'_>, cids: &[IpldCid]) -> Result<()> {
+    let mut stmt = tx
+        .prepare_cached("DELETE FROM blocks WHERE cid = ?1")
+        .map_err(sql_err)?;
+    for cid in cids {
+        stmt.execute(params![cid.to_bytes()]).map_err(sql_err)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mst::util::compute_cid;
+    use jacquard_common::types::string::Tid;
+    use std::collections::BTreeMap;
+
+    fn block(data: &str) -> (IpldCid, Bytes) {
+        let data = Bytes::copy_from_slice(data.as_bytes());
+        (compute_cid(&data).unwrap(), data)
+    }
+
+    #[tokio::test]
+    async fn test_put_get_has() {
+        let store = SqliteBlockStore::open_in_memory().unwrap();
+        let cid = store.put(b"test data").await.unwrap();
+
+        assert!(store.has(&cid).await.unwrap());
+        assert_eq!(
+            store.get(&cid).await.unwrap().as_deref(),
+            Some(&b"test data"[..])
+        );
+
+        // Storing the same block again is a no-op
+        assert_eq!(store.put(b"test data").await.unwrap(), cid);
+
+        let (missing, _) = block("missing");
+        assert!(!store.has(&missing).await.unwrap());
+        assert_eq!(store.get(&missing).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_put_many_get_many() {
+        let store = SqliteBlockStore::open_in_memory().unwrap();
+        let blocks: Vec<_> = (0..10).map(|n| block(&format!("block {}", n))).collect();
+        store.put_many(blocks.clone()).await.unwrap();
+
+        // Requested order, with misses and duplicates
+        let (missing, _) = block("missing");
+        let cids = vec![blocks[3].0, missing, blocks[0].0, blocks[3].0];
+        let fetched = store.get_many(&cids).await.unwrap();
+        assert_eq!(
+            fetched,
+            vec![
+                Some(blocks[3].1.clone()),
+                None,
+                Some(blocks[0].1.clone()),
+                Some(blocks[3].1.clone()),
+            ]
+        );
+
+        assert!(store.get_many(&[]).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_apply_commit() {
+        let store = SqliteBlockStore::open_in_memory().unwrap();
+        let old: Vec<_> = (0..3).map(|n| block(&format!("old {}", n))).collect();
+        store.put_many(old.clone()).await.unwrap();
+
+        let new: BTreeMap<_, _> = (0..3).map(|n| block(&format!("new {}", n))).collect();
+        let commit_cid = *new.keys().next().unwrap();
+        store
+            .apply_commit(CommitData {
+                cid: commit_cid,
+                rev: Tid::now_0(),
+                since: None,
+                prev: None,
+                data: commit_cid,
+                prev_data: None,
+                blocks: new.clone(),
+                relevant_blocks: BTreeMap::new(),
+                deleted_cids: vec![old[0].0, old[2].0],
+            })
+            .await
+            .unwrap();
+
+        for cid in new.keys() {
+            assert!(store.has(cid).await.unwrap());
+        }
+        assert!(!store.has(&old[0].0).await.unwrap());
+        assert!(store.has(&old[1].0).await.unwrap());
+        assert!(!store.has(&old[2].0).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_reopen_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("blocks.sqlite");
+
+        let cid = {
+            let store = SqliteBlockStore::open(&path).unwrap();
+            store.put(b"persisted").await.unwrap()
+        };
+
+        let store = SqliteBlockStore::open(&path).unwrap();
+        assert_eq!(
+            store.get(&cid).await.unwrap().as_deref(),
+            Some(&b"persisted"[..])
+        );
+    }
+}
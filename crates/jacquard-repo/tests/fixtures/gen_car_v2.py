@@ -0,0 +1,95 @@
+#!/usr/bin/env python3
+
+"""
+Writes the minimal CARv2 fixtures used by the CAR reader tests:
+
+    car_v2_indexed.car    - CARv2 with a MultihashIndexSorted (0x0401) index
+    car_v2_no_index.car   - the same payload with no index
+
+Both wrap a CARv1 payload with one root and two dag-cbor blocks (the strings "hello" and
+"world"). Layout follows https://ipld.io/specs/transport/car/carv2/ and the index encoding
+of go-car.
+"""
+
+import hashlib
+import struct
+
+PRAGMA = bytes.fromhex("0aa16776657273696f6e02")
+
+
+def varint(n):
+    out = bytearray()
+    while True:
+        byte = n & 0x7F
+        n >>= 7
+        if n:
+            out.append(byte | 0x80)
+        else:
+            out.append(byte)
+            return bytes(out)
+
+
+def cbor_string(s):
+    # Short text strings only
+    assert len(s) < 24
+    return bytes([0x60 | len(s)]) + s.encode()
+
+
+def cid_for(data):
+    # CIDv1, dag-cbor, sha2-256
+    digest = hashlib.sha256(data).digest()
+    return bytes([0x01, 0x71, 0x12, 0x20]) + digest, digest
+
+
+blocks = [cbor_string("hello"), cbor_string("world")]
+cids = [cid_for(b) for b in blocks]
+root = cids[0][0]
+
+# {"roots": [root], "version": 1}, keys in dag-cbor order
+header = (
+    b"\xa2"
+    + cbor_string("roots")
+    + b"\x81\xd8\x2a\x58"
+    + bytes([len(root) + 1, 0x00])
+    + root
+    + cbor_string("version")
+    + b"\x01"
+)
+
+payload = bytearray(varint(len(header)) + header)
+offsets = []
+for (cid, _digest), data in zip(cids, blocks):
+    offsets.append(len(payload))
+    section = cid + data
+    payload += varint(len(section)) + section
+payload = bytes(payload)
+
+
+def index():
+    # MultihashIndexSorted: one sha2-256 bucket of (digest, offset) entries sorted by digest
+    entries = sorted((digest, offset) for (_cid, digest), offset in zip(cids, offsets))
+    width = 32 + 8
+    bucket = b"".join(digest + struct.pack("<Q", offset) for digest, offset in entries)
+    return (
+        varint(0x0401)
+        + struct.pack("<i", 1)  # number of multihash codes
+        + struct.pack("<Q", 0x12)  # sha2-256
+        + struct.pack("<i", 1)  # number of width buckets
+        + struct.pack("<I", width)
+        + struct.pack("<q", len(bucket))
+        + bucket
+    )
+
+
+def car_v2(with_index):
+    data_offset = len(PRAGMA) + 40
+    index_offset = data_offset + len(payload) if with_index else 0
+    characteristics = bytes(16)
+    v2_header = characteristics + struct.pack("<QQQ", data_offset, len(payload), index_offset)
+    return PRAGMA + v2_header + payload + (index() if with_index else b"")
+
+
+with open("car_v2_indexed.car", "wb") as f:
+    f.write(car_v2(True))
+with open("car_v2_no_index.car", "wb") as f:
+    f.write(car_v2(False))
@@ -308,6 +308,80 @@ async fn test_commit_proof_fixtures() {
     }
 }
 
+#[tokio::test]
+async fn test_node_prefix_compression_roundtrip() {
+    use jacquard_repo::mst::NodeData;
+    use jacquard_repo::mst::util::compute_cid;
+
+    let json = include_str!("fixtures/commit_proof.json");
+    let fixtures: Vec<CommitProofFixture> =
+        serde_ipld_dagjson::from_slice(json.as_bytes()).unwrap();
+
+    for fixture in fixtures {
+        let leaf_cid: cid::Cid = fixture.leaf_value.parse().unwrap();
+        let storage = Arc::new(MemoryBlockStore::new());
+        let mut mst = Mst::new(storage.clone());
+        for key in &fixture.keys {
+            mst = mst.add(key, leaf_cid).await.unwrap();
+        }
+
+        // Root matches the reference implementation, so every node block does too
+        let root = mst.persist().await.unwrap();
+        assert_eq!(root.to_string(), fixture.root_before_commit);
+
+        let mut keys = Vec::new();
+        for cid in mst.collect_node_cids().await.unwrap() {
+            let bytes = storage.get(&cid).await.unwrap().unwrap();
+            let node: NodeData = serde_ipld_dagcbor::from_slice(&bytes).unwrap();
+
+            let decoded = node.entries_decoded().unwrap();
+            keys.extend(decoded.iter().map(|(key, _, _)| key.to_string()));
+
+            let rebuilt = NodeData::from_entries(node.left, decoded).unwrap();
+            assert_eq!(rebuilt, node, "fixture: {}", fixture.comment);
+            let encoded = serde_ipld_dagcbor::to_vec(&rebuilt).unwrap();
+            assert_eq!(encoded, bytes.as_ref(), "fixture: {}", fixture.comment);
+            assert_eq!(compute_cid(&encoded).unwrap(), cid);
+        }
+
+        keys.sort();
+        let mut expected = fixture.keys.clone();
+        expected.sort();
+        assert_eq!(keys, expected, "fixture: {}", fixture.comment);
+    }
+}
+
+#[test]
+fn test_node_from_entries_rejects_unordered_keys() {
+    use jacquard_repo::mst::NodeData;
+
+    let cid: cid::Cid = "bafyreie5cvv4h45feadgeuwhbcutmh6t2ceseocckahdoe6uat64zmz454"
+        .parse()
+        .unwrap();
+    let ordered = NodeData::from_entries(
+        None,
+        [
+            ("com.example/b".into(), cid, None),
+            ("com.example/c".into(), cid, None),
+        ],
+    )
+    .unwrap();
+    assert_eq!(ordered.entries[0].prefix_len, 0);
+    assert_eq!(ordered.entries[1].prefix_len, 12);
+    assert_eq!(ordered.entries[1].key_suffix.as_ref(), b"c");
+
+    assert!(
+        NodeData::from_entries(
+            None,
+            [
+                ("com.example/c".into(), cid, None),
+                ("com.example/b".into(), cid, None)
+            ],
+        )
+        .is_err()
+    );
+}
+
 #[tokio::test]
 async fn test_commit_proof_using_batch() {
     // Same as above but using batch operations instead of individual add/delete
@@ -0,0 +1,153 @@
+//! S3BlockStore against a real bucket
+//!
+//! Skipped unless `JACQUARD_S3_TEST_BUCKET` is set. Credentials come from
+//! `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and optionally `AWS_SESSION_TOKEN`; the
+//! region from `AWS_REGION` (default `us-east-1`). Set `AWS_ENDPOINT_URL` to test against an
+//! S3-compatible service such as MinIO.
+//!
+//! ```sh
+//! JACQUARD_S3_TEST_BUCKET=my-bucket cargo test -p jacquard-repo --features s3 --test s3
+//! ```
+//!
+//! Objects are written under a fresh `jacquard-test/<timestamp>/` prefix and deleted again.
+
+#![cfg(all(feature = "s3", not(target_family = "wasm")))]
+
+use std::collections::BTreeMap;
+use std::env;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use aws_sdk_s3::config::{BehaviorVersion, Credentials, Region};
+use bytes::Bytes;
+use jacquard_common::types::string::Tid;
+use jacquard_repo::mst::Mst;
+use jacquard_repo::mst::util::compute_cid;
+use jacquard_repo::repo::CommitData;
+use jacquard_repo::storage::{BlockStore, S3BlockStore};
+
+fn test_store() -> Option<S3BlockStore> {
+    let Ok(bucket) = env::var("JACQUARD_S3_TEST_BUCKET") else {
+        eprintln!("JACQUARD_S3_TEST_BUCKET not set, skipping S3 test");
+        return None;
+    };
+
+    let credentials = Credentials::new(
+        env::var("AWS_ACCESS_KEY_ID").expect("AWS_ACCESS_KEY_ID"),
+        env::var("AWS_SECRET_ACCESS_KEY").expect("AWS_SECRET_ACCESS_KEY"),
+        env::var("AWS_SESSION_TOKEN").ok(),
+        None,
+        "jacquard-test",
+    );
+    let mut config = aws_sdk_s3::Config::builder()
+        .behavior_version(BehaviorVersion::latest())
+        .region(Region::new(
+            env::var("AWS_REGION").unwrap_or_else(|_| "us-east-1".to_string()),
+        ))
+        .credentials_provider(credentials);
+    if let Ok(endpoint) = env::var("AWS_ENDPOINT_URL") {
+        config = config.endpoint_url(endpoint).force_path_style(true);
+    }
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let client = aws_sdk_s3::Client::from_conf(config.build());
+    Some(
+        S3BlockStore::new(client, bucket)
+            .with_prefix(format!("jacquard-test/{}/", nanos))
+            .with_concurrency(8),
+    )
+}
+
+#[tokio::test]
+async fn test_s3_block_store() {
+    let Some(store) = test_store() else {
+        return;
+    };
+
+    // Single blocks
+    let cid = store.put(b"hello world").await.unwrap();
+    assert!(store.has(&cid).await.unwrap());
+    assert_eq!(
+        store.get(&cid).await.unwrap().as_deref(),
+        Some(&b"hello world"[..])
+    );
+
+    let missing = compute_cid(b"never stored").unwrap();
+    assert!(!store.has(&missing).await.unwrap());
+    assert_eq!(store.get(&missing).await.unwrap(), None);
+
+    // Batches
+    let blocks: BTreeMap<_, _> = (0..50u32)
+        .map(|n| {
+            let data = Bytes::from(format!("block {}", n));
+            (compute_cid(&data).unwrap(), data)
+        })
+        .collect();
+    store.put_many(blocks.clone()).await.unwrap();
+
+    let mut cids: Vec<_> = blocks.keys().copied().collect();
+    cids.push(missing);
+    let fetched = store.get_many(&cids).await.unwrap();
+    assert_eq!(fetched.len(), cids.len());
+    for (cid, data) in cids.iter().zip(&fetched) {
+        assert_eq!(data.as_ref(), blocks.get(cid));
+    }
+
+    // apply_commit writes the new blocks and deletes the old ones
+    let new_data = Bytes::from_static(b"new block");
+    let new_cid = compute_cid(&new_data).unwrap();
+    let mut deleted_cids: Vec<_> = blocks.keys().copied().collect();
+    deleted_cids.push(cid);
+    store
+        .apply_commit(CommitData {
+            cid: new_cid,
+            rev: Tid::now_0(),
+            since: None,
+            prev: None,
+            data: new_cid,
+            prev_data: None,
+            blocks: BTreeMap::from([(new_cid, new_data.clone())]),
+            relevant_blocks: BTreeMap::new(),
+            deleted_cids,
+        })
+        .await
+        .unwrap();
+    assert_eq!(store.get(&new_cid).await.unwrap(), Some(new_data));
+    assert!(!store.has(&cid).await.unwrap());
+    assert!(
+        store
+            .get_many(&cids)
+            .await
+            .unwrap()
+            .iter()
+            .all(Option::is_none)
+    );
+
+    // An MST round-trips through the bucket
+    let storage = Arc::new(store.clone());
+    let mut tree = Mst::new(storage.clone());
+    for n in 0..100u32 {
+        let key = format!("com.example.test/{:04}", n);
+        tree = tree.add(&key, new_cid).await.unwrap();
+    }
+    let root = tree.persist().await.unwrap();
+    let loaded = Mst::load(storage, root, None);
+    assert_eq!(loaded.leaves().await.unwrap(), tree.leaves().await.unwrap());
+
+    // Clean up
+    let mut leftovers = tree.collect_node_cids().await.unwrap();
+    leftovers.push(new_cid);
+    for cid in leftovers {
+        store
+            .client()
+            .delete_object()
+            .bucket(store.bucket())
+            .key(store.object_key(&cid))
+            .send()
+            .await
+            .unwrap();
+    }
+}
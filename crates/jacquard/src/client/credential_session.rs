@@ -627,7 +627,9 @@ where
             builder = builder.header(http::header::AUTHORIZATION, hv);
         }
 
-        if let Some(proxy) = &opts.atproto_proxy {
+        if let Some(proxy) =
+            opts.proxy_for(<Str::Request as jacquard_common::xrpc::XrpcRequest>::NSID)
+        {
             builder = builder.header("atproto-proxy", proxy.as_ref());
         }
         if let Some(labelers) = &opts.atproto_accept_labelers {
@@ -687,7 +689,9 @@ where
                 })?;
                 builder = builder.header(http::header::AUTHORIZATION, hv);
             }
-            if let Some(proxy) = &opts.atproto_proxy {
+            if let Some(proxy) =
+                opts.proxy_for(<Str::Request as jacquard_common::xrpc::XrpcRequest>::NSID)
+            {
                 builder = builder.header("atproto-proxy", proxy.as_ref());
             }
             if let Some(labelers) = &opts.atproto_accept_labelers {
@@ -4,12 +4,14 @@
 //! and detection of embed candidates (record and external embeds).
 
 #[cfg(feature = "api_bluesky")]
-use crate::api::app_bsky::richtext::facet::Facet;
+use crate::api::app_bsky::richtext::facet::{ByteSlice, Facet};
 #[cfg(feature = "api_bluesky")]
 use crate::api::com_atproto::repo::strong_ref::StrongRef;
 use crate::common::CowStr;
 #[cfg(feature = "api_bluesky")]
 use crate::types::aturi::AtUri;
+#[cfg(feature = "api_bluesky")]
+use crate::types::uri::Uri;
 use jacquard_common::IntoStatic;
 #[cfg(feature = "api_bluesky")]
 use jacquard_common::http_client::HttpClient;
@@ -17,6 +19,8 @@ use jacquard_common::types::did::{DID_REGEX, Did};
 use jacquard_common::types::handle::HANDLE_REGEX;
 use jacquard_common::types::string::AtStrError;
 use jacquard_common::types::uri::UriParseError;
+#[cfg(feature = "api_bluesky")]
+use jacquard_identity::IdentityWarning;
 use jacquard_identity::resolver::IdentityError;
 #[cfg(feature = "api_bluesky")]
 use jacquard_identity::resolver::IdentityResolver;
@@ -105,6 +109,104 @@ impl RichText<'static> {
     }
 }
 
+/// A run of text within a [`RichText`], optionally covered by a facet
+///
+/// Produced by [`RichText::segments`].
+#[derive(Debug, Clone, Copy)]
+#[cfg(feature = "api_bluesky")]
+pub struct RichTextSegment<'a> {
+    /// The text of this run
+    pub text: &'a str,
+    /// The facet covering this run, or `None` for plain text
+    pub facet: Option<&'a Facet<'a>>,
+}
+
+#[cfg(feature = "api_bluesky")]
+impl<'a> RichTextSegment<'a> {
+    /// Whether this run is covered by a facet
+    pub fn is_faceted(&self) -> bool {
+        self.facet.is_some()
+    }
+}
+
+#[cfg(feature = "api_bluesky")]
+impl<'a> RichText<'a> {
+    /// Walk the text as an ordered sequence of plain and faceted runs
+    ///
+    /// Facets are ordered by `byteStart` regardless of their order in `facets`.
+    /// Facet edges which fall inside a grapheme cluster are widened to cover the
+    /// whole cluster, so a multibyte character is never split across segments.
+    ///
+    /// Overlapping facets are resolved first-wins: once a facet has been emitted,
+    /// any later facet starting before its end is skipped. When two facets start
+    /// at the same byte, the one appearing first in `facets` wins. Facets with an
+    /// empty, negative or out-of-bounds byte range are skipped.
+    pub fn segments(&self) -> impl Iterator<Item = RichTextSegment<'_>> {
+        use unicode_segmentation::UnicodeSegmentation;
+
+        let text = self.text.as_ref();
+
+        // Grapheme cluster start offsets, plus the end of the text
+        let boundaries: Vec<usize> = text
+            .grapheme_indices(true)
+            .map(|(i, _)| i)
+            .chain(std::iter::once(text.len()))
+            .collect();
+        let floor = |i: usize| match boundaries.binary_search(&i) {
+            Ok(_) => i,
+            Err(n) => boundaries[n - 1],
+        };
+        let ceil = |i: usize| match boundaries.binary_search(&i) {
+            Ok(_) => i,
+            Err(n) => boundaries[n],
+        };
+
+        let mut spans: Vec<(usize, usize, &Facet<'_>)> = self
+            .facets
+            .iter()
+            .flatten()
+            .filter_map(|facet| {
+                let start = usize::try_from(facet.index.byte_start).ok()?;
+                let end = usize::try_from(facet.index.byte_end).ok()?;
+                if start >= end || end > text.len() {
+                    return None;
+                }
+                Some((floor(start), ceil(end), facet))
+            })
+            .collect();
+        // Stable sort, so ties keep their original order
+        spans.sort_by_key(|(start, _, _)| *start);
+
+        let mut segments = Vec::with_capacity(spans.len() * 2 + 1);
+        let mut pos = 0;
+        for (start, end, facet) in spans {
+            if start < pos {
+                // Overlaps a facet we've already emitted
+                continue;
+            }
+            if start > pos {
+                segments.push(RichTextSegment {
+                    text: &text[pos..start],
+                    facet: None,
+                });
+            }
+            segments.push(RichTextSegment {
+                text: &text[start..end],
+                facet: Some(facet),
+            });
+            pos = end;
+        }
+        if pos < text.len() {
+            segments.push(RichTextSegment {
+                text: &text[pos..],
+                facet: None,
+            });
+        }
+
+        segments.into_iter()
+    }
+}
+
 /// Detected embed candidate from URL or at-URI
 #[derive(Debug, Clone)]
 #[cfg(feature = "api_bluesky")]
@@ -340,6 +442,166 @@ pub fn parse_with_domains(text: impl AsRef<str>) -> RichTextBuilder<Unresolved>
     }
 }
 
+/// A facet found in raw text by [`detect_facets`]
+///
+/// Indices are UTF-8 byte offsets into the text that was scanned, ready to use as an
+/// `app.bsky.richtext.facet#byteSlice`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg(feature = "api_bluesky")]
+pub enum DetectedFacet<'a> {
+    /// `@handle` mention; resolve the handle to a DID before publishing
+    Mention {
+        /// Byte range, including the `@`
+        index: ByteSlice<'static>,
+        /// Handle as written, without the `@` (may also be a DID)
+        handle: &'a str,
+        /// The mentioned DID, once known
+        ///
+        /// Set up front for mentions written as a DID, and by [`resolve_mentions`] for
+        /// handles.
+        did: Option<Did<'static>>,
+    },
+    /// Bare URL, with `https://` prepended to scheme-less domains
+    Link {
+        /// Byte range of the URL as written
+        index: ByteSlice<'static>,
+        /// Normalized URL
+        uri: Uri<'static>,
+    },
+    /// `#hashtag`
+    Tag {
+        /// Byte range, including the `#`
+        index: ByteSlice<'static>,
+        /// Tag text, without the `#`
+        tag: &'a str,
+    },
+}
+
+#[cfg(feature = "api_bluesky")]
+impl<'a> DetectedFacet<'a> {
+    /// Byte range of this facet in the scanned text
+    pub fn index(&self) -> &ByteSlice<'static> {
+        match self {
+            DetectedFacet::Mention { index, .. }
+            | DetectedFacet::Link { index, .. }
+            | DetectedFacet::Tag { index, .. } => index,
+        }
+    }
+
+    /// Convert to a lexicon [`Facet`]
+    ///
+    /// Returns `None` for mentions whose DID isn't known yet; see [`resolve_mentions`].
+    pub fn into_facet(self) -> Option<Facet<'static>> {
+        use crate::api::app_bsky::richtext::facet::{FacetFeaturesItem, Link, Mention, Tag};
+        use std::collections::BTreeMap;
+
+        let (index, feature) = match self {
+            DetectedFacet::Mention { index, did, .. } => {
+                let feature = FacetFeaturesItem::Mention(Box::new(Mention {
+                    did: did?,
+                    extra_data: BTreeMap::new(),
+                }));
+                (index, feature)
+            }
+            DetectedFacet::Link { index, uri } => {
+                let feature = FacetFeaturesItem::Link(Box::new(Link {
+                    uri,
+                    extra_data: BTreeMap::new(),
+                }));
+                (index, feature)
+            }
+            DetectedFacet::Tag { index, tag } => {
+                let feature = FacetFeaturesItem::Tag(Box::new(Tag {
+                    tag: CowStr::copy_from_str(tag),
+                    extra_data: BTreeMap::new(),
+                }));
+                (index, feature)
+            }
+        };
+        Some(Facet {
+            index,
+            features: vec![feature],
+            extra_data: BTreeMap::new(),
+        })
+    }
+}
+
+/// Find mentions, bare URLs and hashtags in post text
+///
+/// Unlike [`parse`], the text is scanned exactly as given: it isn't sanitized and markdown
+/// links are left alone, so the returned byte ranges index into `text` itself. Facets are
+/// returned in order of their start offset, and never overlap.
+///
+/// ```
+/// use jacquard::richtext::{DetectedFacet, detect_facets};
+///
+/// let facets = detect_facets("👋 @alice.bsky.social");
+/// let DetectedFacet::Mention { index, handle, .. } = &facets[0] else { panic!() };
+/// assert_eq!(*handle, "alice.bsky.social");
+/// assert_eq!((index.byte_start, index.byte_end), (5, 23));
+/// ```
+#[cfg(feature = "api_bluesky")]
+pub fn detect_facets(text: &str) -> Vec<DetectedFacet<'_>> {
+    use std::collections::BTreeMap;
+
+    let mut candidates = detect_mentions(text);
+    candidates.extend(detect_urls(text));
+    candidates.extend(detect_tags(text));
+    candidates.sort_by_key(|fc| match fc {
+        FacetCandidate::MarkdownLink { display_range, .. } => display_range.start,
+        FacetCandidate::Mention { range, .. }
+        | FacetCandidate::Link { range }
+        | FacetCandidate::Tag { range } => range.start,
+    });
+
+    let mut facets = Vec::with_capacity(candidates.len());
+    let mut last_end = 0;
+    for candidate in candidates {
+        let range = match &candidate {
+            FacetCandidate::MarkdownLink { display_range, .. } => display_range.clone(),
+            FacetCandidate::Mention { range, .. }
+            | FacetCandidate::Link { range }
+            | FacetCandidate::Tag { range } => range.clone(),
+        };
+        if range.start < last_end {
+            continue;
+        }
+        let index = ByteSlice {
+            byte_start: range.start as i64,
+            byte_end: range.end as i64,
+            extra_data: BTreeMap::new(),
+        };
+        let written = &text[range.clone()];
+        let facet = match candidate {
+            FacetCandidate::Mention { did, .. } => DetectedFacet::Mention {
+                index,
+                handle: &written[1..],
+                did,
+            },
+            FacetCandidate::Link { .. } => {
+                let uri = if written.starts_with("http://") || written.starts_with("https://") {
+                    Uri::new_owned(written)
+                } else {
+                    Uri::new_owned(format!("https://{}", written))
+                };
+                let Ok(uri) = uri else { continue };
+                DetectedFacet::Link { index, uri }
+            }
+            FacetCandidate::Tag { .. } => DetectedFacet::Tag {
+                index,
+                tag: written
+                    .strip_prefix('#')
+                    .or_else(|| written.strip_prefix('＃'))
+                    .unwrap_or(written),
+            },
+            FacetCandidate::MarkdownLink { .. } => continue,
+        };
+        last_end = range.end;
+        facets.push(facet);
+    }
+    facets
+}
+
 impl RichTextBuilder<Resolved> {
     /// Entry point for manual richtext construction
     pub fn builder() -> Self {
@@ -596,14 +858,13 @@ fn detect_tags(text: &str) -> Vec<FacetCandidate> {
             continue;
         }
 
-        let hash_pos = cap.get(0).unwrap().start();
-        // Find the actual # character position
-        let hash_start = text[hash_pos..]
-            .chars()
-            .position(|c| c == '#' || c == '＃')
+        // The # (or ＃) sits right before the tag; both are byte offsets
+        let end = tag_match.start() + trimmed_len;
+        let start = text[..tag_match.start()]
+            .char_indices()
+            .next_back()
+            .map(|(i, _)| i)
             .unwrap();
-        let start = hash_pos + hash_start;
-        let end = start + 1 + trimmed_len; // # + tag length
 
         // Store range including # symbol - extract and process at build time
         facets.push(FacetCandidate::Tag { range: start..end });
@@ -694,6 +955,110 @@ fn extract_at_uri_from_url(url: &str, embed_domains: &[&str]) -> Option<AtUri<'s
     AtUri::new(&at_uri_str).ok().map(|u| u.into_static())
 }
 
+/// Resolve the handles of detected mentions to DIDs
+///
+/// Each distinct handle is resolved once, and all of them concurrently. Mentions that
+/// resolve get their `did` filled in. Mentions whose handle doesn't resolve are removed
+/// from `facets`, with one [`IdentityWarning::UnresolvedHandle`] per removed mention;
+/// resolution failures never fail the call, since a post can still go out without them.
+#[cfg(feature = "api_bluesky")]
+pub async fn resolve_mentions<R>(
+    facets: &mut Vec<DetectedFacet<'_>>,
+    resolver: &R,
+) -> Vec<IdentityWarning>
+where
+    R: IdentityResolver + Sync,
+{
+    use jacquard_common::types::handle::Handle;
+    use smol_str::{SmolStr, format_smolstr};
+    use std::collections::{BTreeMap, BTreeSet};
+
+    let pending: BTreeSet<&str> = facets
+        .iter()
+        .filter_map(|facet| match facet {
+            DetectedFacet::Mention {
+                handle, did: None, ..
+            } => Some(*handle),
+            _ => None,
+        })
+        .collect();
+
+    let resolved: BTreeMap<&str, Result<Did<'static>, SmolStr>> =
+        n0_future::join_all(pending.into_iter().map(|handle| async move {
+            let did = match Handle::new(handle) {
+                Ok(parsed) => resolver
+                    .resolve_handle(&parsed)
+                    .await
+                    .map_err(|e| format_smolstr!("{e}")),
+                Err(e) => Err(format_smolstr!("{e}")),
+            };
+            (handle, did)
+        }))
+        .await
+        .into_iter()
+        .collect();
+
+    let mut warnings = Vec::new();
+    facets.retain_mut(|facet| {
+        let DetectedFacet::Mention {
+            handle,
+            did: did @ None,
+            ..
+        } = facet
+        else {
+            return true;
+        };
+        match &resolved[*handle] {
+            Ok(resolved) => {
+                *did = Some(resolved.clone());
+                true
+            }
+            Err(reason) => {
+                warnings.push(IdentityWarning::UnresolvedHandle {
+                    handle: (*handle).into(),
+                    reason: reason.clone(),
+                });
+                false
+            }
+        }
+    });
+    warnings
+}
+
+/// Maximum length of a Bluesky post's text, in graphemes
+pub const MAX_POST_GRAPHEMES: usize = 300;
+
+/// Length of `text` in Unicode extended grapheme clusters
+///
+/// This is what Bluesky's post length limit counts: a family emoji or a letter with a
+/// combining accent is one grapheme, however many chars or bytes it takes.
+pub fn grapheme_len(text: &str) -> usize {
+    use unicode_segmentation::UnicodeSegmentation;
+    text.graphemes(true).count()
+}
+
+/// Check `text` against the post length limit ([`MAX_POST_GRAPHEMES`])
+pub fn validate_post_length(text: &str) -> Result<(), TooLong> {
+    let graphemes = grapheme_len(text);
+    if graphemes > MAX_POST_GRAPHEMES {
+        return Err(TooLong {
+            graphemes,
+            limit: MAX_POST_GRAPHEMES,
+        });
+    }
+    Ok(())
+}
+
+/// Post text is over the length limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error, miette::Diagnostic)]
+#[error("post is {graphemes} graphemes long, over the limit of {limit}")]
+pub struct TooLong {
+    /// Length of the text in graphemes
+    pub graphemes: usize,
+    /// The limit it exceeded
+    pub limit: usize,
+}
+
 /// Errors that can occur during richtext building
 #[derive(Debug, thiserror::Error, miette::Diagnostic)]
 pub enum RichTextError {
@@ -643,3 +643,316 @@ fn test_sanitize_newlines_with_emoji() {
 
     assert_eq!(builder.text, "Hello 🎉\n\nWorld 🌍");
 }
+
+// === Segment Iteration Tests ===
+
+#[cfg(feature = "api_bluesky")]
+fn link_facet(start: usize, end: usize) -> Facet<'static> {
+    use crate::api::app_bsky::richtext::facet::{ByteSlice, FacetFeaturesItem, Link};
+    use std::collections::BTreeMap;
+
+    Facet {
+        index: ByteSlice {
+            byte_start: start as i64,
+            byte_end: end as i64,
+            extra_data: BTreeMap::new(),
+        },
+        features: vec![FacetFeaturesItem::Link(Box::new(Link {
+            uri: crate::types::uri::Uri::new_owned("https://example.com").unwrap(),
+            extra_data: BTreeMap::new(),
+        }))],
+        extra_data: BTreeMap::new(),
+    }
+}
+
+#[test]
+#[cfg(feature = "api_bluesky")]
+fn test_segments_interleave_text_and_facets() {
+    let did = crate::types::did::Did::new_static("did:plc:z72i7hdynmk6r22z27h6tvur").unwrap();
+    let richtext = RichText::builder()
+        .text("Hey @alice.bsky.social see #rust!")
+        .mention(&did, 4..22)
+        .tag("rust", None)
+        .build()
+        .unwrap();
+
+    let segments: Vec<_> = richtext
+        .segments()
+        .map(|s| (s.text, s.is_faceted()))
+        .collect();
+
+    assert_eq!(
+        segments,
+        vec![
+            ("Hey ", false),
+            ("@alice.bsky.social", true),
+            (" see ", false),
+            ("#rust", true),
+            ("!", false),
+        ]
+    );
+}
+
+#[test]
+#[cfg(feature = "api_bluesky")]
+fn test_segments_without_facets() {
+    let richtext = RichText::builder().text("just text").build().unwrap();
+
+    let segments: Vec<_> = richtext.segments().collect();
+    assert_eq!(segments.len(), 1);
+    assert_eq!(segments[0].text, "just text");
+    assert!(segments[0].facet.is_none());
+
+    let empty = RichText::builder().build().unwrap();
+    assert_eq!(empty.segments().count(), 0);
+}
+
+#[test]
+#[cfg(feature = "api_bluesky")]
+fn test_segments_overlapping_facets_first_wins() {
+    let richtext = RichText {
+        text: CowStr::new_static("0123456789abcdef"),
+        // Unsorted, with the second facet overlapping the first
+        facets: Some(vec![
+            link_facet(12, 14),
+            link_facet(2, 8),
+            link_facet(6, 10),
+            link_facet(2, 4),
+        ]),
+    };
+
+    let segments: Vec<_> = richtext
+        .segments()
+        .map(|s| (s.text, s.is_faceted()))
+        .collect();
+
+    assert_eq!(
+        segments,
+        vec![
+            ("01", false),
+            ("234567", true),
+            ("89ab", false),
+            ("cd", true),
+            ("ef", false),
+        ]
+    );
+}
+
+#[test]
+#[cfg(feature = "api_bluesky")]
+fn test_segments_respect_grapheme_boundaries() {
+    // "👋🏽" is a hand (4 bytes) plus a skin tone modifier (4 bytes), one grapheme
+    let text = "hi 👋🏽 there";
+    let richtext = RichText {
+        text: CowStr::new_static(text),
+        // Starts between the hand and the modifier, ends mid-codepoint
+        facets: Some(vec![link_facet(7, 10)]),
+    };
+
+    let segments: Vec<_> = richtext
+        .segments()
+        .map(|s| (s.text, s.is_faceted()))
+        .collect();
+
+    assert_eq!(
+        segments,
+        vec![("hi ", false), ("👋🏽", true), (" there", false)]
+    );
+}
+
+#[test]
+#[cfg(feature = "api_bluesky")]
+fn test_segments_skip_invalid_ranges() {
+    let richtext = RichText {
+        text: CowStr::new_static("short"),
+        facets: Some(vec![link_facet(3, 100), link_facet(2, 2)]),
+    };
+
+    let segments: Vec<_> = richtext.segments().collect();
+    assert_eq!(segments.len(), 1);
+    assert_eq!(segments[0].text, "short");
+}
+
+#[test]
+#[cfg(feature = "api_bluesky")]
+fn test_detect_facets_url_trailing_punctuation() {
+    let text = "Read https://example.com/post/1. Then reply!";
+    let facets = detect_facets(text);
+
+    assert_eq!(facets.len(), 1);
+    let DetectedFacet::Link { index, uri } = &facets[0] else {
+        panic!("expected link, got {:?}", facets[0]);
+    };
+    assert_eq!(uri.as_str(), "https://example.com/post/1");
+    let range = index.byte_start as usize..index.byte_end as usize;
+    assert_eq!(&text[range], "https://example.com/post/1");
+}
+
+#[test]
+#[cfg(feature = "api_bluesky")]
+fn test_detect_facets_byte_offsets_after_emoji() {
+    // 🦋 is four bytes but one char, so char offsets would be three short
+    let text = "🦋🦋 hi @alice.bsky.social";
+    let facets = detect_facets(text);
+
+    assert_eq!(facets.len(), 1);
+    let DetectedFacet::Mention { index, handle, .. } = &facets[0] else {
+        panic!("expected mention, got {:?}", facets[0]);
+    };
+    assert_eq!(*handle, "alice.bsky.social");
+    assert_eq!(index.byte_start, 12);
+    assert_eq!(index.byte_end, 30);
+    assert_eq!(
+        &text[index.byte_start as usize..index.byte_end as usize],
+        "@alice.bsky.social"
+    );
+    // Handles need resolving before they can become a facet
+    assert!(facets[0].clone().into_facet().is_none());
+}
+
+#[test]
+#[cfg(feature = "api_bluesky")]
+fn test_detect_facets_tag_at_end_of_string() {
+    let text = "@did:plc:z72i7hdynmk6r22z27h6tvur example.com #rustlang";
+    let facets = detect_facets(text);
+
+    assert_eq!(facets.len(), 3);
+    assert!(
+        matches!(&facets[0], DetectedFacet::Mention { handle, .. } if handle.starts_with("did:plc:"))
+    );
+    assert!(
+        matches!(&facets[1], DetectedFacet::Link { uri, .. } if uri.as_str() == "https://example.com/")
+    );
+    let DetectedFacet::Tag { index, tag } = &facets[2] else {
+        panic!("expected tag, got {:?}", facets[2]);
+    };
+    assert_eq!(*tag, "rustlang");
+    assert_eq!(index.byte_end as usize, text.len());
+    assert_eq!(index.byte_start as usize, text.len() - "#rustlang".len());
+
+    // DID mentions, links and tags all convert straight to lexicon facets
+    assert!(facets.into_iter().all(|f| f.into_facet().is_some()));
+}
+
+#[test]
+fn test_fullwidth_hash_tag_byte_range() {
+    let text = "日本語　＃タグ";
+    let builder = RichText::parse(text);
+
+    let tags: Vec<_> = builder
+        .facet_candidates
+        .iter()
+        .filter_map(|fc| match fc {
+            FacetCandidate::Tag { range } => Some(&builder.text[range.clone()]),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(tags, vec!["＃タグ"]);
+}
+
+#[test]
+fn test_grapheme_len_combining_and_zwj() {
+    // e + combining acute: two chars, one grapheme
+    let combining = "cafe\u{301}";
+    assert_eq!(combining.chars().count(), 5);
+    assert_eq!(grapheme_len(combining), 4);
+
+    // Family emoji: four people joined by three ZWJs
+    let family = "👨\u{200D}👩\u{200D}👧\u{200D}👦";
+    assert_eq!(family.chars().count(), 7);
+    assert_eq!(grapheme_len(family), 1);
+
+    // Flag: two regional indicators
+    assert_eq!(grapheme_len("🇯🇵"), 1);
+}
+
+#[test]
+fn test_validate_post_length() {
+    // 300 families is 2100 chars but exactly at the limit
+    let at_limit = "👨\u{200D}👩\u{200D}👧\u{200D}👦".repeat(MAX_POST_GRAPHEMES);
+    assert!(at_limit.chars().count() > MAX_POST_GRAPHEMES);
+    assert_eq!(validate_post_length(&at_limit), Ok(()));
+
+    let over = format!("{}e\u{301}", "a".repeat(MAX_POST_GRAPHEMES));
+    let err = validate_post_length(&over).unwrap_err();
+    assert_eq!(
+        err,
+        TooLong {
+            graphemes: 301,
+            limit: 300
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "post is 301 graphemes long, over the limit of 300"
+    );
+}
+
+/// Resolver that knows one handle and counts lookups
+#[cfg(feature = "api_bluesky")]
+#[derive(Default)]
+struct CountingResolver {
+    options: jacquard_identity::resolver::ResolverOptions,
+    lookups: std::sync::atomic::AtomicUsize,
+}
+
+#[cfg(feature = "api_bluesky")]
+impl IdentityResolver for CountingResolver {
+    fn options(&self) -> &jacquard_identity::resolver::ResolverOptions {
+        &self.options
+    }
+
+    async fn resolve_handle(
+        &self,
+        handle: &jacquard_common::types::handle::Handle<'_>,
+    ) -> Result<Did<'static>, IdentityError> {
+        self.lookups
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        match handle.as_str() {
+            "alice.bsky.social" => Ok(Did::new_static("did:plc:alice").unwrap()),
+            _ => Err(IdentityError::invalid_well_known()),
+        }
+    }
+
+    async fn resolve_did_doc(
+        &self,
+        _did: &Did<'_>,
+    ) -> Result<jacquard_identity::resolver::DidDocResponse, IdentityError> {
+        // Mentions only resolve handles
+        Err(IdentityError::invalid_well_known())
+    }
+}
+
+#[tokio::test]
+#[cfg(feature = "api_bluesky")]
+async fn test_resolve_mentions_dedupes_handles() {
+    let text = "@alice.bsky.social meet @bob.example.com, cc @alice.bsky.social #intro";
+    let mut facets = detect_facets(text);
+    let resolver = CountingResolver::default();
+
+    let warnings = resolve_mentions(&mut facets, &resolver).await;
+
+    // alice once, bob once
+    assert_eq!(
+        resolver.lookups.load(std::sync::atomic::Ordering::SeqCst),
+        2
+    );
+
+    // bob is dropped with a warning; both alice mentions and the tag remain
+    assert_eq!(warnings.len(), 1);
+    assert!(matches!(
+        &warnings[0],
+        IdentityWarning::UnresolvedHandle { handle, .. } if handle == "bob.example.com"
+    ));
+    assert_eq!(facets.len(), 3);
+    let dids: Vec<_> = facets
+        .iter()
+        .filter_map(|facet| match facet {
+            DetectedFacet::Mention { did, .. } => Some(did.as_ref().unwrap().as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(dids, vec!["did:plc:alice", "did:plc:alice"]);
+    assert!(facets.into_iter().all(|f| f.into_facet().is_some()));
+}
@@ -1,3 +1,4 @@
 pub mod blob;
+pub mod firehose;
 pub mod repo;
 pub mod video;
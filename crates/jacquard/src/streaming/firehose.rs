@@ -0,0 +1,1045 @@
+//! Resumable `com.atproto.sync.subscribeRepos` consumption
+//!
+//! [`CheckpointedStream`] wraps a firehose message stream and persists the sequence number of
+//! the last event the consumer has *finished* processing to a [`CheckpointStore`]. Writes are
+//! batched: the cursor is saved at most once every [`CheckpointConfig::max_events`]
+//! acknowledgements or [`CheckpointConfig::max_interval`], whichever comes first.
+//!
+//! [`subscribe_checkpointed`] loads the saved cursor and passes it to the subscription, so
+//! after a crash the consumer resumes from its last checkpoint. Delivery is at-least-once:
+//! events acknowledged since the last flush are replayed, but none are skipped.
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! use jacquard::streaming::firehose::{
+//!     CheckpointConfig, MemoryCheckpointStore, message_seq, subscribe_checkpointed,
+//! };
+//! use jacquard_common::xrpc::TungsteniteSubscriptionClient;
+//! use n0_future::StreamExt;
+//!
+//! let client = TungsteniteSubscriptionClient::from_base_uri("wss://bsky.network".parse()?);
+//! let store = MemoryCheckpointStore::default();
+//! let (_sink, mut events) =
+//!     subscribe_checkpointed(&client, store, CheckpointConfig::default()).await?;
+//!
+//! while let Some(msg) = events.next().await {
+//!     let msg = msg?;
+//!     // ... process the event ...
+//!     if let Some(seq) = message_seq(&msg) {
+//!         events.ack(seq).await?;
+//!     }
+//! }
+//! events.flush().await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`ResilientSubscription`] keeps a subscription alive across dropped connections,
+//! reconnecting with backoff from the last sequence number it yielded.
+//!
+//! [`to_ndjson`] turns a message stream into line-delimited JSON for tailing the firehose
+//! from the command line and piping it to `jq`.
+
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use jacquard_api::com_atproto::sync::subscribe_repos::{SubscribeRepos, SubscribeReposMessage};
+use jacquard_common::error::{BoxError, DecodeError};
+use jacquard_common::retry::RetryConfig;
+use jacquard_common::smol_str::SmolStr;
+use jacquard_common::types::cid::IpldCid;
+use jacquard_common::types::crypto::decode_uvarint;
+use jacquard_common::types::value::Data;
+use jacquard_common::xrpc::SubscriptionClient;
+use jacquard_common::{StreamError, StreamErrorKind, WsSink};
+use n0_future::boxed::BoxStream;
+use n0_future::time::Instant;
+use n0_future::{Stream, StreamExt};
+use serde_json::{Value, json};
+
+/// Persistent storage for a firehose cursor
+#[cfg_attr(not(target_arch = "wasm32"), trait_variant::make(Send))]
+pub trait CheckpointStore {
+    /// Error returned by the backing store
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Load the last saved sequence number, if any
+    fn load(&self) -> impl Future<Output = Result<Option<i64>, Self::Error>>;
+
+    /// Persist `seq` as the sequence number to resume after
+    fn save(&self, seq: i64) -> impl Future<Output = Result<(), Self::Error>>;
+}
+
+/// In-memory checkpoint store
+///
+/// Doesn't survive a restart; useful for tests, or for resuming after a reconnect within
+/// one process. Clones share the same cursor.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryCheckpointStore(Arc<Mutex<Option<i64>>>);
+
+impl MemoryCheckpointStore {
+    /// Create a store pre-loaded with a cursor
+    pub fn with_cursor(seq: i64) -> Self {
+        Self(Arc::new(Mutex::new(Some(seq))))
+    }
+
+    /// The currently saved cursor
+    pub fn cursor(&self) -> Option<i64> {
+        *self.0.lock().unwrap()
+    }
+}
+
+impl CheckpointStore for MemoryCheckpointStore {
+    type Error = Infallible;
+
+    async fn load(&self) -> Result<Option<i64>, Self::Error> {
+        Ok(self.cursor())
+    }
+
+    async fn save(&self, seq: i64) -> Result<(), Self::Error> {
+        *self.0.lock().unwrap() = Some(seq);
+        Ok(())
+    }
+}
+
+/// How often [`CheckpointedStream`] flushes to its store
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckpointConfig {
+    /// Flush after this many acknowledged events
+    pub max_events: usize,
+    /// Flush on the first acknowledgement at least this long after the previous flush
+    pub max_interval: Duration,
+}
+
+impl Default for CheckpointConfig {
+    /// Every 1000 events or 5 seconds
+    fn default() -> Self {
+        Self {
+            max_events: 1000,
+            max_interval: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Errors from checkpointed firehose consumption
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+pub enum CheckpointError {
+    /// Loading the saved cursor failed
+    #[error("failed to load firehose checkpoint")]
+    Load(#[source] BoxError),
+    /// Saving the cursor failed
+    #[error("failed to save firehose checkpoint at seq {seq}")]
+    Save {
+        /// Sequence number that was being saved
+        seq: i64,
+        /// Underlying store error
+        #[source]
+        source: BoxError,
+    },
+    /// Connecting to the subscription failed
+    #[error("failed to subscribe to firehose")]
+    Subscribe(#[source] BoxError),
+}
+
+/// Sequence number of a firehose message, if it has one
+///
+/// `#info` and unrecognized messages aren't sequenced.
+pub fn message_seq(msg: &SubscribeReposMessage<'_>) -> Option<i64> {
+    match msg {
+        SubscribeReposMessage::Commit(commit) => Some(commit.seq),
+        SubscribeReposMessage::Sync(sync) => Some(sync.seq),
+        SubscribeReposMessage::Identity(identity) => Some(identity.seq),
+        SubscribeReposMessage::Account(account) => Some(account.seq),
+        _ => None,
+    }
+}
+
+/// Firehose stream adapter with batched cursor checkpointing
+///
+/// Yields the inner stream's messages unchanged. Call [`ack`](Self::ack) with a message's
+/// sequence number once it has been fully processed; only acknowledged sequence numbers are
+/// ever saved. Acknowledge in stream order - acking a later event before an earlier one
+/// finishes can checkpoint past work that hasn't been done.
+///
+/// Pending acknowledgements are not saved on drop; call [`flush`](Self::flush) before
+/// shutting down.
+pub struct CheckpointedStream<St, C> {
+    inner: St,
+    store: C,
+    config: CheckpointConfig,
+    acked: Option<i64>,
+    saved: Option<i64>,
+    unsaved: usize,
+    last_flush: Instant,
+}
+
+impl<St, C: CheckpointStore> CheckpointedStream<St, C> {
+    /// Wrap a message stream
+    ///
+    /// The stream should already be positioned at the store's cursor; see
+    /// [`subscribe_checkpointed`].
+    pub fn new(inner: St, store: C, config: CheckpointConfig) -> Self {
+        Self {
+            inner,
+            store,
+            config,
+            acked: None,
+            saved: None,
+            unsaved: 0,
+            last_flush: Instant::now(),
+        }
+    }
+
+    /// Mark `seq` as processed, flushing to the store if a threshold has been reached
+    pub async fn ack(&mut self, seq: i64) -> Result<(), CheckpointError> {
+        self.acked = Some(seq);
+        self.unsaved += 1;
+        if self.unsaved >= self.config.max_events
+            || self.last_flush.elapsed() >= self.config.max_interval
+        {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    /// Save the latest acknowledged sequence number now, if it hasn't been saved yet
+    pub async fn flush(&mut self) -> Result<(), CheckpointError> {
+        if let Some(seq) = self.acked.filter(|seq| self.saved != Some(*seq)) {
+            self.store
+                .save(seq)
+                .await
+                .map_err(|e| CheckpointError::Save {
+                    seq,
+                    source: Box::new(e),
+                })?;
+            self.saved = Some(seq);
+        }
+        self.unsaved = 0;
+        self.last_flush = Instant::now();
+        Ok(())
+    }
+
+    /// Latest acknowledged sequence number
+    pub fn acked(&self) -> Option<i64> {
+        self.acked
+    }
+
+    /// Latest sequence number written to the store
+    pub fn checkpointed(&self) -> Option<i64> {
+        self.saved
+    }
+
+    /// The checkpoint store
+    pub fn store(&self) -> &C {
+        &self.store
+    }
+
+    /// Unwrap into the inner stream and store, discarding unflushed acknowledgements
+    pub fn into_parts(self) -> (St, C) {
+        (self.inner, self.store)
+    }
+}
+
+impl<St: Stream + Unpin, C: Unpin> Stream for CheckpointedStream<St, C> {
+    type Item = St::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.inner).poll_next(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Subscribe to `com.atproto.sync.subscribeRepos`, resuming from the store's cursor
+///
+/// Returns the connection's sink alongside the checkpointed message stream; dropping the
+/// sink may close the connection.
+pub async fn subscribe_checkpointed<Client, C>(
+    client: &Client,
+    store: C,
+    config: CheckpointConfig,
+) -> Result<
+    (
+        WsSink,
+        CheckpointedStream<
+            impl Stream<Item = Result<SubscribeReposMessage<'static>, StreamError>> + Unpin,
+            C,
+        >,
+    ),
+    CheckpointError,
+>
+where
+    Client: SubscriptionClient + Sync,
+    C: CheckpointStore,
+{
+    let cursor = store
+        .load()
+        .await
+        .map_err(|e| CheckpointError::Load(Box::new(e)))?;
+    let params = SubscribeRepos { cursor };
+    let (sink, messages) = client
+        .subscribe(&params)
+        .await
+        .map_err(|e| CheckpointError::Subscribe(Box::new(e)))?
+        .into_stream();
+
+    let mut stream = CheckpointedStream::new(messages, store, config);
+    stream.acked = cursor;
+    stream.saved = cursor;
+    Ok((sink, stream))
+}
+
+/// Connection state reported by [`ResilientSubscription`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// Subscribed, resuming after `cursor` if set
+    Connected {
+        /// Cursor passed to the subscription
+        cursor: Option<i64>,
+    },
+    /// The connection dropped or couldn't be opened; retrying after `delay`
+    Reconnecting {
+        /// Consecutive failures so far, starting at 1
+        attempt: u32,
+        /// How long until the next attempt
+        delay: Duration,
+    },
+    /// Gave up; no more messages will be yielded
+    Failed,
+}
+
+/// Errors from [`ResilientSubscription`]
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+pub enum ResilientError {
+    /// The cursor is ahead of the server's latest event
+    ///
+    /// Usually means the consumer's checkpoint came from a different relay, or the relay lost
+    /// data. Fatal: retrying with the same cursor would fail the same way.
+    #[error("firehose cursor {cursor:?} is ahead of the server")]
+    FutureCursor {
+        /// Cursor the subscription was resumed with
+        cursor: Option<i64>,
+        /// Detail from the server, if any
+        message: Option<String>,
+    },
+    /// The cursor is older than the server's retention window
+    ///
+    /// Events between the cursor and the oldest event the server still has were skipped and
+    /// need to be backfilled some other way. Not fatal: the subscription carries on from the
+    /// oldest available event.
+    #[error("firehose cursor {cursor:?} is older than the server retains; events were skipped")]
+    OutdatedCursor {
+        /// Cursor the subscription was resumed with
+        cursor: Option<i64>,
+        /// Detail from the server, if any
+        message: Option<String>,
+    },
+    /// A message couldn't be decoded; the connection is kept open
+    #[error(transparent)]
+    Stream(#[from] StreamError),
+    /// Reconnecting failed too many times in a row
+    #[error("failed to reconnect to firehose after {attempts} attempts")]
+    Reconnect {
+        /// Consecutive failures before giving up
+        attempts: u32,
+        /// The last failure
+        #[source]
+        source: BoxError,
+    },
+}
+
+/// Error name and message of a subscription error frame
+fn error_frame(err: &StreamError) -> Option<(SmolStr, Option<String>)> {
+    match err.source()?.downcast_ref::<DecodeError>()? {
+        DecodeError::ErrorFrame { error, message } => Some((error.clone(), message.clone())),
+        _ => None,
+    }
+}
+
+/// `com.atproto.sync.subscribeRepos` subscription that reconnects on its own
+///
+/// Tracks the sequence number of the last message yielded and, when the connection drops,
+/// reconnects from there with exponential backoff per the [`RetryConfig`]. A message that
+/// fails to decode is yielded as [`ResilientError::Stream`] without dropping the connection.
+///
+/// Cursor problems are reported rather than papered over: `FutureCursor` ends the
+/// subscription with [`ResilientError::FutureCursor`], and an `OutdatedCursor` info message
+/// is turned into [`ResilientError::OutdatedCursor`] so the gap isn't missed.
+///
+/// Delivery is at-least-once around reconnects. The cursor only advances as messages are
+/// yielded, so combine with [`CheckpointedStream`] (via [`into_stream`](Self::into_stream))
+/// to survive restarts as well.
+///
+/// ```no_run
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use jacquard::streaming::firehose::{ResilientError, ResilientSubscription};
+/// use jacquard_common::xrpc::TungsteniteSubscriptionClient;
+///
+/// let client = TungsteniteSubscriptionClient::from_base_uri("wss://bsky.network".parse()?);
+/// let mut firehose = ResilientSubscription::new(client, None)
+///     .on_state_change(|state| println!("firehose: {:?}", state));
+///
+/// while let Some(msg) = firehose.next().await {
+///     match msg {
+///         Ok(msg) => { /* ... process the event ... */ }
+///         Err(ResilientError::OutdatedCursor { .. }) => { /* ... schedule a backfill ... */ }
+///         Err(ResilientError::Stream(e)) => eprintln!("skipping bad message: {}", e),
+///         Err(e) => return Err(e.into()),
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ResilientSubscription<Client> {
+    client: Client,
+    retry: RetryConfig,
+    on_state: Option<Box<dyn FnMut(&ConnectionState) + Send>>,
+    cursor: Option<i64>,
+    connection: Option<(
+        WsSink,
+        BoxStream<Result<SubscribeReposMessage<'static>, StreamError>>,
+    )>,
+    failures: u32,
+    last_error: Option<BoxError>,
+    failed: bool,
+}
+
+impl<Client> ResilientSubscription<Client>
+where
+    Client: SubscriptionClient + Sync,
+{
+    /// Create a subscription that starts at `cursor`
+    ///
+    /// Nothing connects until the first call to [`next`](Self::next). By default it retries
+    /// forever, backing off from 1 second up to 1 minute.
+    pub fn new(client: Client, cursor: Option<i64>) -> Self {
+        Self {
+            client,
+            retry: RetryConfig {
+                max_attempts: u32::MAX,
+                base_delay: Duration::from_secs(1),
+                max_delay: Duration::from_secs(60),
+                jitter: 0.5,
+            },
+            on_state: None,
+            cursor,
+            connection: None,
+            failures: 0,
+            last_error: None,
+            failed: false,
+        }
+    }
+
+    /// Set the reconnect backoff
+    ///
+    /// `max_attempts` bounds consecutive failures; it resets whenever a message arrives.
+    pub fn with_retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Call `f` on every connection state change
+    pub fn on_state_change(mut self, f: impl FnMut(&ConnectionState) + Send + 'static) -> Self {
+        self.on_state = Some(Box::new(f));
+        self
+    }
+
+    /// Sequence number of the last message yielded, or the starting cursor
+    pub fn cursor(&self) -> Option<i64> {
+        self.cursor
+    }
+
+    /// Next message, reconnecting as needed
+    ///
+    /// Returns `None` once the subscription has failed for good; the error that caused it
+    /// is yielded first.
+    pub async fn next(&mut self) -> Option<Result<SubscribeReposMessage<'static>, ResilientError>> {
+        loop {
+            if self.failed {
+                return None;
+            }
+            let Some((_, messages)) = &mut self.connection else {
+                if let Err(e) = self.connect().await {
+                    return Some(Err(e));
+                }
+                continue;
+            };
+
+            let err = match messages.next().await {
+                Some(Ok(SubscribeReposMessage::Info(info))) if info.name == "OutdatedCursor" => {
+                    return Some(Err(ResilientError::OutdatedCursor {
+                        cursor: self.cursor,
+                        message: info.message.map(|m| m.to_string()),
+                    }));
+                }
+                Some(Ok(msg)) => {
+                    if let Some(seq) = message_seq(&msg) {
+                        self.cursor = Some(seq);
+                    }
+                    self.failures = 0;
+                    return Some(Ok(msg));
+                }
+                Some(Err(e)) => e,
+                None => StreamError::closed(),
+            };
+
+            match error_frame(&err) {
+                Some((error, message)) if error == "FutureCursor" => {
+                    self.fail();
+                    return Some(Err(ResilientError::FutureCursor {
+                        cursor: self.cursor,
+                        message,
+                    }));
+                }
+                // Other error frames (e.g. `ConsumerTooSlow`) come just before a close
+                Some(_) => {}
+                None if matches!(
+                    err.kind(),
+                    StreamErrorKind::Decode | StreamErrorKind::WrongMessageFormat
+                ) =>
+                {
+                    return Some(Err(err.into()));
+                }
+                None => {}
+            }
+
+            #[cfg(feature = "tracing")]
+            tracing::debug!(error = %err, cursor = ?self.cursor, "firehose connection dropped");
+            self.connection = None;
+            self.failures += 1;
+            self.last_error = Some(Box::new(err));
+        }
+    }
+
+    /// Turn into a stream of messages, ending once the subscription fails for good
+    pub fn into_stream(
+        self,
+    ) -> impl Stream<Item = Result<SubscribeReposMessage<'static>, ResilientError>> {
+        n0_future::stream::unfold(self, |mut sub| async move {
+            let item = sub.next().await?;
+            Some((item, sub))
+        })
+    }
+
+    async fn connect(&mut self) -> Result<(), ResilientError> {
+        loop {
+            if self.failures > 0 {
+                if self.failures >= self.retry.max_attempts.max(1) {
+                    self.fail();
+                    return Err(ResilientError::Reconnect {
+                        attempts: self.failures,
+                        source: self
+                            .last_error
+                            .take()
+                            .unwrap_or_else(|| Box::new(StreamError::closed())),
+                    });
+                }
+                let delay = self.retry.delay_for(self.failures);
+                self.set_state(ConnectionState::Reconnecting {
+                    attempt: self.failures,
+                    delay,
+                });
+                n0_future::time::sleep(delay).await;
+            }
+
+            let params = SubscribeRepos {
+                cursor: self.cursor,
+            };
+            match self.client.subscribe(&params).await {
+                Ok(stream) => {
+                    self.connection = Some(stream.into_stream());
+                    self.set_state(ConnectionState::Connected {
+                        cursor: self.cursor,
+                    });
+                    return Ok(());
+                }
+                Err(e) => {
+                    self.failures += 1;
+                    self.last_error = Some(Box::new(e));
+                }
+            }
+        }
+    }
+
+    fn fail(&mut self) {
+        self.connection = None;
+        self.failed = true;
+        self.set_state(ConnectionState::Failed);
+    }
+
+    fn set_state(&mut self, state: ConnectionState) {
+        if let Some(f) = &mut self.on_state {
+            f(&state);
+        }
+    }
+}
+
+/// Errors from [`to_ndjson`]
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+pub enum NdjsonError {
+    /// The underlying subscription failed
+    #[error(transparent)]
+    Stream(#[from] StreamError),
+    /// A message couldn't be encoded as JSON
+    #[error("failed to encode firehose message as JSON")]
+    Json(#[source] serde_json::Error),
+}
+
+/// Convert a firehose message stream into newline-delimited JSON
+///
+/// Each item is one message rendered by [`message_to_json`], without a trailing newline.
+/// Stream errors are passed through and don't end the stream.
+///
+/// ```no_run
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use jacquard::streaming::firehose::to_ndjson;
+/// use jacquard_api::com_atproto::sync::subscribe_repos::SubscribeRepos;
+/// use jacquard_common::xrpc::{SubscriptionClient, TungsteniteSubscriptionClient};
+/// use n0_future::StreamExt;
+///
+/// let client = TungsteniteSubscriptionClient::from_base_uri("wss://bsky.network".parse()?);
+/// let (_sink, messages) = client
+///     .subscribe(&SubscribeRepos { cursor: None })
+///     .await?
+///     .into_stream();
+/// let mut lines = to_ndjson(messages);
+/// while let Some(line) = lines.next().await {
+///     println!("{}", line?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_ndjson<'a, St>(messages: St) -> impl Stream<Item = Result<String, NdjsonError>>
+where
+    St: Stream<Item = Result<SubscribeReposMessage<'a>, StreamError>>,
+{
+    messages.map(|msg| -> Result<String, NdjsonError> {
+        let value = message_to_json(&msg?).map_err(NdjsonError::Json)?;
+        serde_json::to_string(&value).map_err(NdjsonError::Json)
+    })
+}
+
+/// Render a firehose message as JSON for display
+///
+/// Messages serialize as their lexicon JSON form, with two changes for `#commit` and
+/// `#sync` events:
+///
+/// - `blocks` is replaced by a summary of the CAR diff: its size in bytes, root CIDs,
+///   and the CID of every block, instead of the raw bytes.
+/// - each commit op whose record block is included gets a `record` field with the
+///   decoded record.
+///
+/// A `blocks` section that isn't valid CAR data is summarized by size with an `error`.
+pub fn message_to_json(msg: &SubscribeReposMessage<'_>) -> Result<Value, serde_json::Error> {
+    let mut value = serde_json::to_value(msg)?;
+    let (blocks, ops) = match msg {
+        SubscribeReposMessage::Commit(commit) => (&commit.blocks, Some(&commit.ops)),
+        SubscribeReposMessage::Sync(sync) => (&sync.blocks, None),
+        _ => return Ok(value),
+    };
+
+    let car = parse_car(blocks);
+    value["blocks"] = match &car {
+        Some((roots, sections)) => json!({
+            "size": blocks.len(),
+            "roots": roots.iter().map(IpldCid::to_string).collect::<Vec<_>>(),
+            "count": sections.len(),
+            "cids": sections.iter().map(|(cid, _)| cid.to_string()).collect::<Vec<_>>(),
+        }),
+        None => json!({ "size": blocks.len(), "error": "malformed CAR data" }),
+    };
+
+    if let (Some(ops), Some((_, sections))) = (ops, &car) {
+        for (i, op) in ops.iter().enumerate() {
+            let Ok(cid) = op.cid.to_ipld() else { continue };
+            let Some((_, block)) = sections.iter().find(|(c, _)| *c == cid) else {
+                continue;
+            };
+            if let Ok(record) = serde_ipld_dagcbor::from_slice::<Data<'_>>(block) {
+                value["ops"][i]["record"] = serde_json::to_value(&record)?;
+            }
+        }
+    }
+    Ok(value)
+}
+
+/// Split in-memory CARv1 data into its root CIDs and `(cid, block)` sections
+fn parse_car(data: &[u8]) -> Option<(Vec<IpldCid>, Vec<(IpldCid, &[u8])>)> {
+    #[derive(serde::Deserialize)]
+    struct Header {
+        roots: Vec<IpldCid>,
+    }
+
+    fn section(data: &[u8]) -> Option<(&[u8], &[u8])> {
+        let (len, prefix) = decode_uvarint(data)?;
+        let end = prefix.checked_add(usize::try_from(len).ok()?)?;
+        (end <= data.len()).then(|| (&data[prefix..end], &data[end..]))
+    }
+
+    let (header, mut rest) = section(data)?;
+    let header: Header = serde_ipld_dagcbor::from_slice(header).ok()?;
+    let mut sections = Vec::new();
+    while !rest.is_empty() {
+        let (mut body, next) = section(rest)?;
+        let cid = IpldCid::read_bytes(&mut body).ok()?;
+        sections.push((cid, body));
+        rest = next;
+    }
+    Some((header.roots, sections))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::SinkExt;
+    use jacquard_api::com_atproto::sync::subscribe_repos::Identity;
+    use jacquard_common::types::string::{Datetime, Did};
+    use jacquard_common::url::Url;
+    use jacquard_common::xrpc::BasicSubscriptionClient;
+    use jacquard_common::{WebSocketClient, WebSocketConnection, WsMessage, WsStream};
+    use std::collections::VecDeque;
+
+    fn stream(
+        store: MemoryCheckpointStore,
+        config: CheckpointConfig,
+    ) -> CheckpointedStream<n0_future::stream::Empty<()>, MemoryCheckpointStore> {
+        CheckpointedStream::new(n0_future::stream::empty(), store, config)
+    }
+
+    #[tokio::test]
+    async fn flushes_every_n_acks() {
+        let store = MemoryCheckpointStore::default();
+        let mut events = stream(
+            store.clone(),
+            CheckpointConfig {
+                max_events: 3,
+                max_interval: Duration::from_secs(3600),
+            },
+        );
+
+        events.ack(10).await.unwrap();
+        events.ack(11).await.unwrap();
+        assert_eq!(store.cursor(), None);
+        assert_eq!(events.acked(), Some(11));
+
+        events.ack(12).await.unwrap();
+        assert_eq!(store.cursor(), Some(12));
+
+        events.ack(13).await.unwrap();
+        assert_eq!(store.cursor(), Some(12));
+        events.flush().await.unwrap();
+        assert_eq!(store.cursor(), Some(13));
+        assert_eq!(events.checkpointed(), Some(13));
+    }
+
+    #[tokio::test]
+    async fn flushes_after_interval() {
+        let store = MemoryCheckpointStore::with_cursor(5);
+        let mut events = stream(
+            store.clone(),
+            CheckpointConfig {
+                max_events: 1000,
+                max_interval: Duration::ZERO,
+            },
+        );
+        events.ack(6).await.unwrap();
+        assert_eq!(store.cursor(), Some(6));
+    }
+
+    #[test]
+    fn sequenced_messages() {
+        let identity = Identity::builder()
+            .did(Did::new_static("did:plc:abc").unwrap())
+            .seq(42)
+            .time(Datetime::now())
+            .build();
+        let msg = SubscribeReposMessage::Identity(Box::new(identity));
+        assert_eq!(message_seq(&msg), Some(42));
+    }
+
+    #[test]
+    fn error_frames_are_recognized() {
+        let mut frame = serde_ipld_dagcbor::to_vec(&json!({ "op": -1 })).unwrap();
+        frame.extend(
+            serde_ipld_dagcbor::to_vec(&json!({
+                "error": "FutureCursor",
+                "message": "Cursor in the future.",
+            }))
+            .unwrap(),
+        );
+        let err = SubscribeReposMessage::decode_framed(&frame).unwrap_err();
+        let err = StreamError::decode(err);
+        assert_eq!(
+            error_frame(&err),
+            Some((
+                SmolStr::new_static("FutureCursor"),
+                Some("Cursor in the future.".to_string())
+            ))
+        );
+
+        assert_eq!(error_frame(&StreamError::closed()), None);
+        let err = StreamError::decode(DecodeError::UnknownEventType("#nope".into()));
+        assert_eq!(error_frame(&err), None);
+    }
+
+    /// WebSocket client replaying scripted connections and recording the URLs it was asked for
+    ///
+    /// Each entry is the frames one connection delivers before it drops, or `None` for a
+    /// connection attempt that fails. Once the script runs out every attempt fails.
+    #[derive(Clone, Default)]
+    struct MockSocket {
+        script: Arc<Mutex<VecDeque<Option<Vec<Vec<u8>>>>>>,
+        urls: Arc<Mutex<Vec<Url>>>,
+    }
+
+    impl MockSocket {
+        fn new(script: impl IntoIterator<Item = Option<Vec<Vec<u8>>>>) -> Self {
+            Self {
+                script: Arc::new(Mutex::new(script.into_iter().collect())),
+                urls: Arc::default(),
+            }
+        }
+
+        /// `cursor` query parameter of each connection attempt
+        fn cursors(&self) -> Vec<Option<i64>> {
+            self.urls
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|url| {
+                    url.query_pairs()
+                        .find(|(key, _)| key == "cursor")
+                        .map(|(_, value)| value.parse().unwrap())
+                })
+                .collect()
+        }
+    }
+
+    impl WebSocketClient for MockSocket {
+        type Error = StreamError;
+
+        async fn connect(&self, url: Url) -> Result<WebSocketConnection, StreamError> {
+            self.urls.lock().unwrap().push(url);
+            let Some(Some(frames)) = self.script.lock().unwrap().pop_front() else {
+                return Err(StreamError::closed());
+            };
+            let rx = n0_future::stream::iter(
+                frames
+                    .into_iter()
+                    .map(|frame| Ok(WsMessage::Binary(frame.into()))),
+            );
+            let tx = futures::sink::drain().sink_map_err(|never: Infallible| match never {});
+            Ok(WebSocketConnection::new(WsSink::new(tx), WsStream::new(rx)))
+        }
+    }
+
+    fn frame(t: &str, body: Value) -> Vec<u8> {
+        let mut frame = serde_ipld_dagcbor::to_vec(&json!({ "op": 1, "t": t })).unwrap();
+        frame.extend(serde_ipld_dagcbor::to_vec(&body).unwrap());
+        frame
+    }
+
+    fn identity_frame(seq: i64) -> Vec<u8> {
+        frame(
+            "#identity",
+            json!({ "did": "did:plc:abc", "seq": seq, "time": "2024-01-01T00:00:00.000Z" }),
+        )
+    }
+
+    fn resilient(
+        socket: &MockSocket,
+        cursor: Option<i64>,
+    ) -> (
+        ResilientSubscription<BasicSubscriptionClient<MockSocket>>,
+        Arc<Mutex<Vec<ConnectionState>>>,
+    ) {
+        let client =
+            BasicSubscriptionClient::new(socket.clone(), "wss://relay.test".parse().unwrap());
+        let states = Arc::new(Mutex::new(Vec::new()));
+        let seen = states.clone();
+        let delay = Duration::from_millis(1);
+        let firehose = ResilientSubscription::new(client, cursor)
+            .with_retry(RetryConfig {
+                max_attempts: 3,
+                base_delay: delay,
+                max_delay: delay,
+                jitter: 0.0,
+            })
+            .on_state_change(move |state| seen.lock().unwrap().push(state.clone()));
+        (firehose, states)
+    }
+
+    #[tokio::test]
+    async fn resumes_from_last_seq_after_drop() {
+        let socket = MockSocket::new([
+            Some(vec![identity_frame(1), identity_frame(2)]),
+            None,
+            Some(vec![identity_frame(3)]),
+        ]);
+        let (mut firehose, states) = resilient(&socket, None);
+
+        let mut seqs = Vec::new();
+        let err = loop {
+            match firehose.next().await.unwrap() {
+                Ok(msg) => seqs.push(message_seq(&msg).unwrap()),
+                Err(e) => break e,
+            }
+        };
+        assert_eq!(seqs, [1, 2, 3]);
+        assert!(matches!(err, ResilientError::Reconnect { attempts: 3, .. }));
+        assert!(firehose.next().await.is_none());
+        assert_eq!(firehose.cursor(), Some(3));
+
+        // Every reconnect asks for the events after the last one yielded
+        assert_eq!(socket.cursors(), [None, Some(2), Some(2), Some(3), Some(3)]);
+
+        let delay = Duration::from_millis(1);
+        assert_eq!(
+            *states.lock().unwrap(),
+            [
+                ConnectionState::Connected { cursor: None },
+                ConnectionState::Reconnecting { attempt: 1, delay },
+                ConnectionState::Reconnecting { attempt: 2, delay },
+                ConnectionState::Connected { cursor: Some(2) },
+                // A message arrived, so the failure count started over
+                ConnectionState::Reconnecting { attempt: 1, delay },
+                ConnectionState::Reconnecting { attempt: 2, delay },
+                ConnectionState::Failed,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn future_cursor_ends_subscription() {
+        let mut error = serde_ipld_dagcbor::to_vec(&json!({ "op": -1 })).unwrap();
+        error.extend(serde_ipld_dagcbor::to_vec(&json!({ "error": "FutureCursor" })).unwrap());
+        let socket = MockSocket::new([Some(vec![error]), Some(vec![identity_frame(101)])]);
+        let (mut firehose, states) = resilient(&socket, Some(100));
+
+        let err = firehose.next().await.unwrap().unwrap_err();
+        assert!(matches!(
+            err,
+            ResilientError::FutureCursor {
+                cursor: Some(100),
+                ..
+            }
+        ));
+        assert!(firehose.next().await.is_none());
+
+        assert_eq!(socket.cursors(), [Some(100)]);
+        assert_eq!(
+            *states.lock().unwrap(),
+            [
+                ConnectionState::Connected { cursor: Some(100) },
+                ConnectionState::Failed,
+            ]
+        );
+    }
+
+    fn car(roots: &[IpldCid], blocks: &[(IpldCid, Vec<u8>)]) -> Vec<u8> {
+        fn push_section(out: &mut Vec<u8>, body: &[u8]) {
+            out.extend(jacquard_common::types::crypto::encode_uvarint(
+                body.len() as u64
+            ));
+            out.extend_from_slice(body);
+        }
+
+        #[derive(serde::Serialize)]
+        struct Header<'a> {
+            roots: &'a [IpldCid],
+            version: u64,
+        }
+        let mut out = Vec::new();
+        let header = serde_ipld_dagcbor::to_vec(&Header { roots, version: 1 }).unwrap();
+        push_section(&mut out, &header);
+        for (cid, block) in blocks {
+            push_section(&mut out, &[cid.to_bytes(), block.clone()].concat());
+        }
+        out
+    }
+
+    fn cid(n: u8) -> IpldCid {
+        let mut bytes = vec![0x01, 0x71, 0x12, 0x20];
+        bytes.extend([n; 32]);
+        IpldCid::try_from(&bytes[..]).unwrap()
+    }
+
+    #[test]
+    fn commit_json_summarizes_blocks_and_inlines_records() {
+        use jacquard_api::com_atproto::sync::subscribe_repos::{Commit, RepoOp};
+        use jacquard_common::types::cid::CidLink;
+        use jacquard_common::types::string::Tid;
+
+        let (commit_cid, record_cid) = (cid(1), cid(2));
+        let record = serde_ipld_dagcbor::to_vec(&json!({
+            "$type": "app.bsky.feed.post",
+            "text": "hello",
+        }))
+        .unwrap();
+        let blocks = car(
+            &[commit_cid],
+            &[(commit_cid, vec![0xa0]), (record_cid, record)],
+        );
+        let rev = Tid::now_0();
+        let commit = Commit::builder()
+            .blobs(Vec::new())
+            .blocks(blocks.clone().into())
+            .commit(CidLink::ipld(commit_cid))
+            .ops(vec![
+                RepoOp::builder()
+                    .action("create")
+                    .cid(CidLink::ipld(record_cid))
+                    .path("app.bsky.feed.post/3kabc")
+                    .build(),
+            ])
+            .rebase(false)
+            .repo(Did::new_static("did:plc:abc").unwrap())
+            .rev(rev.clone())
+            .seq(7)
+            .since(rev)
+            .time(Datetime::now())
+            .too_big(false)
+            .build();
+        let msg = SubscribeReposMessage::Commit(Box::new(commit));
+
+        let value = message_to_json(&msg).unwrap();
+        assert_eq!(value["seq"], 7);
+        assert_eq!(value["blocks"]["size"], blocks.len());
+        assert_eq!(value["blocks"]["count"], 2);
+        assert_eq!(value["blocks"]["roots"][0], commit_cid.to_string());
+        assert_eq!(value["blocks"]["cids"][1], record_cid.to_string());
+        assert_eq!(value["ops"][0]["record"]["text"], "hello");
+
+        let mut broken = msg.clone();
+        if let SubscribeReposMessage::Commit(commit) = &mut broken {
+            commit.blocks = blocks[..blocks.len() - 3].to_vec().into();
+        }
+        let value = message_to_json(&broken).unwrap();
+        assert!(value["blocks"]["error"].is_string());
+        assert!(value["ops"][0].get("record").is_none());
+    }
+
+    #[tokio::test]
+    async fn ndjson_emits_one_line_per_message() {
+        let identity = Identity::builder()
+            .did(Did::new_static("did:plc:abc").unwrap())
+            .seq(1)
+            .time(Datetime::now())
+            .build();
+        let messages = n0_future::stream::iter(vec![Ok(SubscribeReposMessage::Identity(
+            Box::new(identity),
+        ))]);
+        let lines: Vec<_> = to_ndjson(messages).collect().await;
+        let line = lines[0].as_ref().unwrap();
+        assert!(!line.contains('\n'));
+        let value: Value = serde_json::from_str(line).unwrap();
+        assert_eq!(value["did"], "did:plc:abc");
+        assert_eq!(value["seq"], 1);
+    }
+}
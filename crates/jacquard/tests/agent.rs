@@ -59,7 +59,7 @@ impl IdentityResolver for MockClient {
         let doc = serde_json::json!({
             "id": "did:plc:alice",
             "service": [{
-                "id": "#pds",
+                "id": "#atproto_pds",
                 "type": "AtprotoPersonalDataServer",
                 "serviceEndpoint": "https://pds"
             }]
@@ -7,13 +7,18 @@ use jacquard::IntoStatic;
 use jacquard::client::Agent;
 use jacquard::types::did::Did;
 use jacquard::xrpc::XrpcClient;
+use jacquard_common::AuthorizationToken;
 use jacquard_common::http_client::HttpClient;
+use jacquard_common::session::SessionStoreError;
 use jacquard_oauth::atproto::AtprotoClientMetadata;
+use jacquard_oauth::authstore::ClientAuthStore;
 use jacquard_oauth::client::OAuthSession;
+use jacquard_oauth::error::OAuthError;
+use jacquard_oauth::request::RequestErrorKind;
 use jacquard_oauth::resolver::OAuthResolver;
 use jacquard_oauth::scopes::Scope;
 use jacquard_oauth::session::SessionRegistry;
-use jacquard_oauth::session::{ClientData, ClientSessionData, DpopClientData};
+use jacquard_oauth::session::{AuthRequestData, ClientData, ClientSessionData, DpopClientData};
 use jacquard_oauth::types::{OAuthAuthorizationServerMetadata, OAuthTokenType, TokenSet};
 use tokio::sync::Mutex;
 
@@ -68,7 +73,7 @@ impl jacquard::identity::resolver::IdentityResolver for MockClient {
         let doc = serde_json::json!({
             "id": "did:plc:alice",
             "service": [{
-                "id": "#pds",
+                "id": "#atproto_pds",
                 "type": "AtprotoPersonalDataServer",
                 "serviceEndpoint": "https://pds"
             }]
@@ -386,3 +391,152 @@ async fn oauth_xrpc_invalid_token_body_triggers_refresh_and_retries() {
     // Cleanup temp file
     let _ = std::fs::remove_file(&path);
 }
+
+/// Store that records the refresh token of every session written to it
+struct RecordingStore {
+    inner: jacquard_oauth::authstore::MemoryAuthStore,
+    upserts: std::sync::Mutex<Vec<Option<String>>>,
+}
+
+impl ClientAuthStore for RecordingStore {
+    async fn get_session(
+        &self,
+        did: &Did<'_>,
+        session_id: &str,
+    ) -> Result<Option<ClientSessionData<'_>>, SessionStoreError> {
+        self.inner.get_session(did, session_id).await
+    }
+
+    async fn upsert_session(
+        &self,
+        session: ClientSessionData<'_>,
+    ) -> Result<(), SessionStoreError> {
+        self.upserts.lock().unwrap().push(
+            session
+                .token_set
+                .refresh_token
+                .as_ref()
+                .map(|t| t.to_string()),
+        );
+        self.inner.upsert_session(session).await
+    }
+
+    async fn delete_session(
+        &self,
+        did: &Did<'_>,
+        session_id: &str,
+    ) -> Result<(), SessionStoreError> {
+        self.inner.delete_session(did, session_id).await
+    }
+
+    async fn get_auth_req_info(
+        &self,
+        state: &str,
+    ) -> Result<Option<AuthRequestData<'_>>, SessionStoreError> {
+        self.inner.get_auth_req_info(state).await
+    }
+
+    async fn save_auth_req_info(
+        &self,
+        auth_req_info: &AuthRequestData<'_>,
+    ) -> Result<(), SessionStoreError> {
+        self.inner.save_auth_req_info(auth_req_info).await
+    }
+
+    async fn delete_auth_req_info(&self, state: &str) -> Result<(), SessionStoreError> {
+        self.inner.delete_auth_req_info(state).await
+    }
+}
+
+/// Session holding access token `atk1` and refresh token `rt1`, stored in a fresh registry
+async fn recorded_session(
+    client: Arc<MockClient>,
+) -> (
+    Arc<SessionRegistry<MockClient, RecordingStore>>,
+    OAuthSession<MockClient, RecordingStore>,
+) {
+    let client_data = ClientData {
+        keyset: None,
+        config: AtprotoClientMetadata::new_localhost(None, Some(vec![Scope::Atproto])),
+    };
+    let session_data = ClientSessionData {
+        account_did: Did::new_static("did:plc:alice").unwrap(),
+        session_id: jacquard::CowStr::from("state"),
+        host_url: url::Url::parse("https://pds").unwrap(),
+        authserver_url: url::Url::parse("https://issuer").unwrap(),
+        authserver_token_endpoint: jacquard::CowStr::from("https://issuer/token"),
+        authserver_revocation_endpoint: None,
+        scopes: vec![Scope::Atproto],
+        dpop_data: DpopClientData {
+            dpop_key: jacquard_oauth::utils::generate_key(&[jacquard::CowStr::from("ES256")])
+                .unwrap(),
+            dpop_authserver_nonce: jacquard::CowStr::from(""),
+            dpop_host_nonce: jacquard::CowStr::from(""),
+        },
+        token_set: TokenSet {
+            iss: jacquard::CowStr::from("https://issuer"),
+            sub: Did::new_static("did:plc:alice").unwrap(),
+            aud: jacquard::CowStr::from("https://pds"),
+            scope: None,
+            refresh_token: Some(jacquard::CowStr::from("rt1")),
+            access_token: jacquard::CowStr::from("atk1"),
+            token_type: OAuthTokenType::DPoP,
+            expires_at: None,
+        },
+    }
+    .into_static();
+    let store = RecordingStore {
+        inner: jacquard_oauth::authstore::MemoryAuthStore::new(),
+        upserts: Default::default(),
+    };
+    let registry = Arc::new(SessionRegistry::new(store, client.clone(), client_data));
+    registry.set(session_data.clone()).await.unwrap();
+    registry.store.upserts.lock().unwrap().clear();
+    let session = OAuthSession::new(registry.clone(), client, session_data);
+    (registry, session)
+}
+
+#[tokio::test]
+async fn refresh_persists_rotated_refresh_token_once() {
+    let client = Arc::new(MockClient::default());
+    client.push(token_refresh_ok()).await;
+    let (registry, session) = recorded_session(client).await;
+
+    let token = session.refresh().await.expect("refresh succeeds");
+    assert!(matches!(token, AuthorizationToken::Dpop(ref t) if *t == "newacc"));
+    assert_eq!(
+        *registry.store.upserts.lock().unwrap(),
+        vec![Some("newref".to_string())]
+    );
+    assert!(matches!(
+        session.refresh_token().await,
+        Some(AuthorizationToken::Dpop(ref t)) if *t == "newref"
+    ));
+}
+
+#[tokio::test]
+async fn refresh_with_rejected_grant_reports_expiry() {
+    let client = Arc::new(MockClient::default());
+    client
+        .push(
+            HttpResponse::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .header(http::header::CONTENT_TYPE, "application/json")
+                .body(serde_json::to_vec(&serde_json::json!({"error":"invalid_grant"})).unwrap())
+                .unwrap(),
+        )
+        .await;
+    let (registry, session) = recorded_session(client).await;
+
+    let err = session.refresh().await.unwrap_err();
+    assert!(matches!(
+        err,
+        OAuthError::Session(jacquard_oauth::session::Error::ServerAgent(ref e))
+            if matches!(e.kind(), RequestErrorKind::RefreshTokenExpired)
+    ));
+    assert!(registry.store.upserts.lock().unwrap().is_empty());
+    assert!(matches!(
+        session.refresh_token().await,
+        Some(AuthorizationToken::Dpop(ref t)) if *t == "rt1"
+    ));
+}
@@ -64,7 +64,7 @@ impl jacquard::identity::resolver::IdentityResolver for MockClient {
         let doc = serde_json::json!({
             "id": "did:plc:alice",
             "service": [{
-                "id": "#pds",
+                "id": "#atproto_pds",
                 "type": "AtprotoPersonalDataServer",
                 "serviceEndpoint": "https://pds"
             }]
@@ -102,7 +102,7 @@ impl OAuthResolver for MockClient {
         let doc = serde_json::json!({
             "id": "did:plc:alice",
             "service": [{
-                "id": "#pds",
+                "id": "#atproto_pds",
                 "type": "AtprotoPersonalDataServer",
                 "serviceEndpoint": "https://pds"
             }]
@@ -58,7 +58,7 @@ impl IdentityResolver for MockResolver {
         let doc = serde_json::json!({
             "id": "did:plc:alice",
             "service": [{
-                "id": "#pds",
+                "id": "#atproto_pds",
                 "type": "AtprotoPersonalDataServer",
                 "serviceEndpoint": "https://pds-resolved"
             }]
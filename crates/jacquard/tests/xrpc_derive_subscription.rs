@@ -0,0 +1,122 @@
+#![cfg(feature = "websocket")]
+
+use jacquard::xrpc::{MessageEncoding, SubscriptionEndpoint, SubscriptionResp, XrpcSubscription};
+use jacquard::{CowStr, IntoStatic};
+use jacquard_derive::XrpcRequest;
+use serde::{Deserialize, Serialize};
+
+// Test message union; frame headers name variants in short form, JSON in full
+#[derive(Serialize, Deserialize, IntoStatic)]
+#[serde(tag = "$type")]
+pub enum ThingMessage<'a> {
+    #[serde(rename = "#created", alias = "com.example.subscribeThings#created")]
+    Created {
+        #[serde(borrow)]
+        id: CowStr<'a>,
+    },
+    #[serde(rename = "#deleted", alias = "com.example.subscribeThings#deleted")]
+    Deleted {
+        #[serde(borrow)]
+        id: CowStr<'a>,
+    },
+}
+
+#[derive(Serialize, Deserialize, IntoStatic, XrpcRequest)]
+#[xrpc(
+    nsid = "com.example.subscribeThings",
+    method = Subscription,
+    output = ThingMessage,
+    server
+)]
+pub struct SubscribeThings {
+    pub cursor: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, XrpcRequest)]
+#[xrpc(
+    nsid = "com.example.subscribeThingsJson",
+    method = Subscription,
+    output = ThingMessage,
+    encoding = Json,
+    path = "/subscribe"
+)]
+pub struct SubscribeThingsJson<'a> {
+    #[serde(borrow)]
+    pub collection: Option<CowStr<'a>>,
+}
+
+// Compiles only if `T` doesn't implement `XrpcRequest`: otherwise both impls apply and the
+// type parameter of `AmbiguousIfRequest` can't be inferred
+trait AmbiguousIfRequest<A> {
+    fn check() {}
+}
+impl<T: ?Sized> AmbiguousIfRequest<()> for T {}
+struct IsRequest;
+impl<T: ?Sized + jacquard::xrpc::XrpcRequest> AmbiguousIfRequest<IsRequest> for T {}
+
+const _: fn() = || {
+    <SubscribeThings as AmbiguousIfRequest<_>>::check();
+    <SubscribeThingsJson<'static> as AmbiguousIfRequest<_>>::check();
+};
+
+fn assert_subscription<S: XrpcSubscription>() {}
+
+#[test]
+fn test_xrpc_subscription_impl() {
+    assert_subscription::<SubscribeThings>();
+    assert_subscription::<SubscribeThingsJson<'_>>();
+
+    assert_eq!(SubscribeThings::NSID, "com.example.subscribeThings");
+    assert_eq!(SubscribeThings::ENCODING, MessageEncoding::DagCbor);
+    assert_eq!(SubscribeThings::CUSTOM_PATH, None);
+
+    assert_eq!(SubscribeThingsJson::ENCODING, MessageEncoding::Json);
+    assert_eq!(SubscribeThingsJson::CUSTOM_PATH, Some("/subscribe"));
+}
+
+#[test]
+fn test_generated_stream_marker() {
+    assert_eq!(SubscribeThingsStream::NSID, "com.example.subscribeThings");
+
+    let message = SubscribeThingsJsonStream::decode_message(
+        br#"{"$type":"com.example.subscribeThings#deleted","id":"abc"}"#,
+    )
+    .unwrap();
+    assert!(matches!(message, ThingMessage::Deleted { id } if id == "abc"));
+}
+
+#[derive(Serialize)]
+struct Header<'a> {
+    op: i64,
+    t: &'a str,
+}
+
+#[derive(Serialize)]
+struct Body<'a> {
+    id: &'a str,
+}
+
+fn frame(op: i64, t: &str, id: &str) -> Vec<u8> {
+    let mut bytes = serde_ipld_dagcbor::to_vec(&Header { op, t }).unwrap();
+    bytes.extend(serde_ipld_dagcbor::to_vec(&Body { id }).unwrap());
+    bytes
+}
+
+#[test]
+fn test_generated_stream_decodes_frames() {
+    let bytes = frame(1, "#created", "abc");
+    let message = SubscribeThingsStream::decode_message(&bytes).unwrap();
+    assert!(matches!(message, ThingMessage::Created { id } if id == "abc"));
+
+    let bytes = frame(1, "#renamed", "abc");
+    assert!(SubscribeThingsStream::decode_message(&bytes).is_err());
+}
+
+#[test]
+fn test_subscription_endpoint_impl() {
+    assert_eq!(
+        SubscribeThingsEndpoint::PATH,
+        "/xrpc/com.example.subscribeThings"
+    );
+    assert_eq!(SubscribeThingsEndpoint::ENCODING, MessageEncoding::DagCbor);
+}
@@ -0,0 +1,79 @@
+//! Example: Serve a `com.atproto.sync.subscribeRepos` firehose from Axum
+//!
+//! Streams a fixed sequence of commit events to each client, starting after the
+//! `cursor` the client asked for.
+//!
+//! Usage:
+//!   cargo run -p jacquard-axum --example axum_subscription
+//!   cargo run --example subscribe_repos -- ws://127.0.0.1:3000 --cursor 2
+
+use axum::{Router, response::Response, routing::get};
+use jacquard::api::com_atproto::sync::subscribe_repos::{Commit, RepoOp, SubscribeReposEndpoint};
+use jacquard_axum::subscription::SubscriptionUpgrade;
+use jacquard_common::types::cid::CidLink;
+use jacquard_common::types::string::{Datetime, Did, Tid};
+use jacquard_common::xrpc::SubscriptionEndpoint;
+use miette::{IntoDiagnostic, Result};
+
+/// Highest sequence number this server has to offer
+const LAST_SEQ: i64 = 5;
+
+const RECORD_CID: &str = "bafyreie5737gdxlw5i64vzichcalba3z2v5n6icifvx5xytvske7mr3hpm";
+
+/// The commit event with sequence number `seq`
+fn commit(seq: i64) -> Commit<'static> {
+    let rev = Tid::now_0();
+    Commit::builder()
+        .seq(seq)
+        .repo(Did::new_static("did:plc:ewvi7nxzyoun6zhxrhs64oiz").unwrap())
+        .rev(rev.clone())
+        .since(rev)
+        .commit(CidLink::new_static(RECORD_CID))
+        .ops(vec![
+            RepoOp::builder()
+                .action("create")
+                .path(format!("app.bsky.feed.post/{}", Tid::now_0()))
+                .cid(CidLink::new_static(RECORD_CID))
+                .build(),
+        ])
+        .blobs(vec![])
+        .blocks(bytes::Bytes::new())
+        .rebase(false)
+        .too_big(false)
+        .time(Datetime::now())
+        .build()
+}
+
+async fn subscribe_repos(upgrade: SubscriptionUpgrade<SubscribeReposEndpoint>) -> Response {
+    let cursor = upgrade.cursor();
+    upgrade.on_upgrade(move |mut sink| async move {
+        if cursor.is_some_and(|cursor| cursor > LAST_SEQ) {
+            let _ = sink
+                .send_error("FutureCursor", Some("cursor is ahead of the stream"))
+                .await;
+            return;
+        }
+        // Backfill everything after the cursor; without one, a real server would only
+        // send live events from here on
+        let first = cursor.map_or(1, |cursor| cursor + 1);
+        for seq in first..=LAST_SEQ {
+            if let Err(e) = sink.send_frame("#commit", &commit(seq)).await {
+                eprintln!("client went away: {e}");
+                return;
+            }
+        }
+        let _ = sink.close().await;
+    })
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let app = Router::new().route(SubscribeReposEndpoint::PATH, get(subscribe_repos));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .into_diagnostic()?;
+    println!("serving subscribeRepos on ws://127.0.0.1:3000");
+    axum::serve(listener, app).await.into_diagnostic()?;
+    Ok(())
+}
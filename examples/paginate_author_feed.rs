@@ -0,0 +1,48 @@
+//! Example: page through an actor's whole feed
+//!
+//! `XrpcPaginator` threads the `cursor` from each `getAuthorFeed` response into the
+//! next request until the appview stops returning one.
+//!
+//! Usage:
+//!   cargo run --example paginate_author_feed -- pfrazee.com --max 200
+
+use clap::Parser;
+use jacquard::api::app_bsky::feed::get_author_feed::GetAuthorFeed;
+use jacquard::client::BasicClient;
+use jacquard::types::ident::AtIdentifier;
+use jacquard::xrpc::XrpcPaginator;
+use n0_future::StreamExt;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Page through an actor's feed")]
+struct Args {
+    /// Handle or DID of the actor
+    #[arg(default_value = "pfrazee.com")]
+    actor: String,
+
+    /// Stop after this many posts
+    #[arg(short, long, default_value_t = 200)]
+    max: usize,
+}
+
+#[tokio::main]
+async fn main() -> miette::Result<()> {
+    let args = Args::parse();
+    let client = BasicClient::unauthenticated();
+
+    let request = GetAuthorFeed::new()
+        .actor(AtIdentifier::new(&args.actor)?)
+        .limit(100)
+        .build();
+
+    let mut feed = std::pin::pin!(XrpcPaginator::new(&client, request).items().take(args.max));
+    let mut count = 0;
+    while let Some(item) = feed.next().await {
+        let item = item?;
+        count += 1;
+        println!("{}. {}", count, item.post.uri);
+    }
+    println!("\n{} posts", count);
+
+    Ok(())
+}